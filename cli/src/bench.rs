@@ -0,0 +1,71 @@
+//! `boggle bench`: times every solver on a board and dictionary of the
+//! user's choosing, in this process. The `#[bench]` benchmarks only run on
+//! nightly and only on the bundled board.
+
+use std::time::{Duration, Instant};
+
+use boggle::{alloc_audit, strategy, Board, Dictionary, Solver};
+
+/// How one solver did, averaged over the iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub solver: &'static str,
+    pub words: usize,
+    pub per_solve: Duration,
+    pub allocations: usize,
+}
+
+/// Solves `board` `iterations` times with each of [`strategy::ALL`], one
+/// strategy after another.
+pub fn run(board: &Board, dictionary: &Dictionary, iterations: u32) -> Vec<Timing> {
+    let iterations = iterations.max(1);
+    strategy::ALL.iter()
+        .map(|strategy| {
+            let mut found = 0;
+            let start = Instant::now();
+            let (_, allocations) = alloc_audit::count(|| {
+                for _ in 0..iterations {
+                    found = strategy.solve(board, dictionary).len();
+                }
+            });
+            Timing { solver: strategy.name(), words: found, per_solve: start.elapsed() / iterations, allocations: allocations / iterations as usize }
+        })
+        .collect()
+}
+
+/// Compiles `words` `iterations` times as [`Solver::new`] does, then as
+/// [`Solver::build_parallel`] does; `per_solve` is the time per build.
+pub fn builds(words: &str, iterations: u32) -> Vec<Timing> {
+    let iterations = iterations.max(1);
+    let builders = [("compile", Solver::new as fn(&str) -> Solver), ("compile parallel", Solver::build_parallel)];
+    builders.iter()
+        .map(|&(solver, build)| {
+            let mut found = 0;
+            let start = Instant::now();
+            let (_, allocations) = alloc_audit::count(|| {
+                for _ in 0..iterations {
+                    found = build(words).dictionary().words().count();
+                }
+            });
+            Timing { solver, words: found, per_solve: start.elapsed() / iterations, allocations: allocations / iterations as usize }
+        })
+        .collect()
+}
+
+/// How many times faster the parallel build in `builds` was than the
+/// sequential one.
+pub fn speedup(builds: &[Timing]) -> f64 {
+    builds[0].per_solve.as_secs_f64() / builds[1].per_solve.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+/// The timings as a table, one solver per row, with a header.
+pub fn table(timings: &[Timing]) -> String {
+    let mut table = format!("{:<16} {:>6} {:>12} {:>10} {:>12}\n", "solver", "words", "per solve", "solves/s", "allocations");
+    for timing in timings {
+        let seconds = timing.per_solve.as_secs_f64();
+        let throughput = if seconds > 0.0 { 1.0 / seconds } else { f64::INFINITY };
+        table.push_str(&format!("{:<16} {:>6} {:>10.3}ms {:>10.1} {:>12}\n",
+            timing.solver, timing.words, seconds * 1000.0, throughput, timing.allocations));
+    }
+    table
+}
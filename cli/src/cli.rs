@@ -0,0 +1,1061 @@
+use std::ops::RangeInclusive;
+
+use boggle::alphabet::Alphabet;
+use boggle::board::Format;
+use boggle::compiled::Metadata;
+use boggle::score::Tier;
+use boggle::{strategy, Error, Topology};
+use boggle_server::Preset;
+
+use crate::daily::Date;
+use crate::peer::{self, Peer};
+
+/// What to do, parsed from the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `boggle [solve] ...`: list every word on the board.
+    Solve(Solve),
+    /// `boggle score ...`: just the total score.
+    Score(Solve),
+    /// `boggle generate ...`: roll a random board, or draw its letters by
+    /// frequency, again and again if it's to have a number of words from
+    /// `dictionary` or none from `banned`.
+    Generate {
+        size: usize,
+        seed: Option<u64>,
+        letters: bool,
+        vowels: Option<RangeInclusive<usize>>,
+        target_words: Option<RangeInclusive<usize>>,
+        dictionary: Option<String>,
+        banned: Option<String>,
+    },
+    /// `boggle optimize ...`: search for a high-scoring roll of the dice.
+    Optimize { dictionary: String, size: usize, steps: usize, seed: Option<u64> },
+    /// `boggle compile-dict words out`: compile a word list for fast loading,
+    /// recording its license and where it's from.
+    CompileDict { words: String, out: String, metadata: Metadata },
+    /// `boggle build-index words out`: like `compile-dict`, for big lists,
+    /// with progress, resuming and a check of the result.
+    BuildIndex { words: String, out: String, metadata: Metadata },
+    /// `boggle dict-info dictionary`: how many words a dictionary has and
+    /// what its index records about the list's license.
+    DictInfo { dictionary: String, output: Output },
+    /// `boggle index-diff old new`: the words added and removed between two
+    /// compiled dictionaries, and how their tries' sizes compare.
+    IndexDiff { old: String, new: String },
+    /// `boggle similar board dir`: list the boards in a directory closest to
+    /// one board, ignoring rotations and reflections.
+    Similar { board: String, dir: String, top: usize },
+    /// `boggle check dictionary board word...`: whether each word is allowed
+    /// on the board, and where it lies if so, for settling disputes.
+    Check { dictionary: String, board: String, words: Vec<String>, min_length: usize },
+    /// `boggle show dictionary board word`: the board with the word's path
+    /// picked out, for showing new players how a word winds through it.
+    Show { dictionary: String, board: String, word: String, color: Color },
+    /// `boggle analyze dictionary board`: how many words go through each
+    /// cell, as a heatmap, and other statistics for tuning a board.
+    Analyze { dictionary: String, board: String, min_length: Option<usize> },
+    /// `boggle strings dictionary board`: the longest word, and the highest
+    /// scoring words that can be traced without sharing a tile.
+    Strings { dictionary: String, board: String, min_length: Option<usize> },
+    /// `boggle trim dictionary board`: write just the dictionary's words
+    /// that are on the board, or with `pruned` those the search's quick
+    /// checks don't rule out, to `out` or stdout.
+    Trim { dictionary: String, board: String, out: Option<String>, pruned: bool, min_length: Option<usize> },
+    /// `boggle duel dictionary board words words`: score two players' word
+    /// lists for the same board, crossing off the words both found.
+    Duel { dictionary: String, board: String, players: [String; 2], min_length: Option<usize>, top: usize },
+    /// `boggle duel --host|--join ... dictionary`: play a duel against
+    /// another terminal over TCP.
+    DuelPeer { dictionary: String, peer: Peer, top: usize },
+    /// `boggle daily [--date DATE] dictionary`: play the day's board for
+    /// `seconds`, then print a result to share, or the board of `date`,
+    /// keeping the result in `data` if given.
+    Daily { dictionary: String, date: Option<Date>, seconds: u64, data: Option<String> },
+    /// `boggle daily --archive DAYS ...`: list the days up to `date`, or
+    /// today, with their boards and the results kept in `data`.
+    DailyArchive { days: usize, date: Option<Date>, data: Option<String> },
+    /// `boggle words --anagram|--pattern ... dictionary`: look words up in
+    /// the dictionary, without a board.
+    Words { dictionary: String, query: Query },
+    /// `boggle submit ...`: check a community board before accepting it,
+    /// printing a JSON report.
+    Submit { board: String, banned: Option<String>, boards_dir: Option<String> },
+    /// `boggle validate [--fix] board...`: list every problem with each
+    /// board, or print it with them left out.
+    Validate { boards: Vec<String>, fix: bool },
+    /// `boggle serve ...`: answer `POST /solve` requests over HTTP, with the
+    /// dictionaries loaded once. Either named dictionaries and the presets
+    /// requests may pick from, or a config file, whose port `port` overrides.
+    Serve { dictionaries: Vec<(String, String)>, presets: Vec<Preset>, config: Option<String>, port: Option<u16> },
+    /// `boggle tui dictionary [board]`: edit a board in the terminal, seeing
+    /// its words as it changes.
+    Tui { dictionary: String, board: Option<String> },
+    /// `boggle debug trie ...`: print part of the dictionary's trie as a
+    /// Graphviz graph.
+    DebugTrie { dictionary: String, prefix: String, depth: usize },
+    /// `boggle debug trace ...`: print how the search explores a board, as
+    /// JSON or a Graphviz graph.
+    DebugTrace { dictionary: String, board: String, limit: usize, dot: bool },
+    /// `boggle debug profile ...`: time the per-word solver on each word and
+    /// list the slowest.
+    DebugProfile { dictionary: String, board: String, top: usize },
+    /// `boggle bench [--iterations N] dictionary board`: time each solver on
+    /// the board and compare them.
+    Bench { dictionary: String, board: String, iterations: u32 },
+    SelfTest,
+    Help,
+}
+
+/// What `words` looks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Words spelled with some or all of these letters, `?` being a blank.
+    Anagram(String),
+    /// Words matching a pattern of letters, `?` and `*`.
+    Pattern(String),
+}
+
+/// The order `solve` lists words in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    Alpha,
+    /// Longest first.
+    Length,
+    /// Highest first.
+    Score,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solve {
+    pub dictionary: String,
+    /// More dictionaries searched along with `dictionary` without merging
+    /// them, each word listed with the ones it's in.
+    pub also: Vec<String>,
+    /// Word lists whose words are taken out of every dictionary before
+    /// solving, e.g. profanity.
+    pub exclude: Vec<String>,
+    /// Boards written out on the command line with `--board`, solved
+    /// before `boards`.
+    pub inline: Vec<String>,
+    /// Solved in order, all against the same dictionary.
+    pub boards: Vec<String>,
+    /// How every board is written, or `None` to tell from each one.
+    pub format: Option<Format>,
+    /// A directory of boards to solve after `boards`, in file name order.
+    pub boards_dir: Option<String>,
+    pub low_power: bool,
+    pub threads: Option<usize>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub sort: Sort,
+    pub output: Output,
+    /// Include each word's path in JSON output.
+    pub paths: bool,
+    /// List text output under a heading for each first letter.
+    pub group: bool,
+    /// A frequency list to score rare words higher with, and to show each
+    /// word's tier.
+    pub rarity: Option<String>,
+    /// With `rarity`, leave out words rarer than this.
+    pub max_obscurity: Option<Tier>,
+    /// A glossary to show each word's definition from.
+    pub define: Option<String>,
+    /// Which cells are adjacent on every board.
+    pub topology: Topology,
+    /// The letters boards and word lists may have besides `a..=z`.
+    pub alphabet: Alphabet,
+    /// Check each board's words against a slow search too, reporting where
+    /// they differ.
+    pub verify: bool,
+    /// List only the words a person could plausibly find, leaving out those
+    /// with winding paths and, with `rarity`, rare ones.
+    pub findable: bool,
+    /// Take apostrophes, hyphens and accents out of dictionary words rather
+    /// than skipping them.
+    pub normalize: bool,
+    /// Draw a progress bar over the boards on stderr.
+    pub progress: bool,
+    /// Report each solve's work and timings on stderr.
+    pub stats: bool,
+    /// Solve with the one of [`strategy::ALL`] with this name instead, for
+    /// comparing them.
+    pub strategy: Option<&'static str>,
+}
+
+/// Whether `show` colors its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// When stdout is a terminal and `NO_COLOR` isn't set.
+    Auto,
+    Always,
+    Never,
+}
+
+/// How `solve` and `score` print their results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    Text,
+    /// A single line of JSON, for scripts and web frontends.
+    Json,
+}
+
+/// Parses the arguments after the program name. A bare `dictionary board` is
+/// still accepted as `solve`.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, Error> {
+    let mut args = args.into_iter().peekable();
+    match args.peek().map(String::as_str) {
+        Some("selftest") => Ok(Command::SelfTest),
+        Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some("generate") => {
+            args.next();
+            generate(args)
+        }
+        Some("optimize") => {
+            args.next();
+            optimize(args)
+        }
+        Some("compile-dict") => {
+            args.next();
+            let (words, out, metadata) = compile(args)?;
+            Ok(Command::CompileDict { words, out, metadata })
+        }
+        Some("build-index") => {
+            args.next();
+            let (words, out, metadata) = compile(args)?;
+            Ok(Command::BuildIndex { words, out, metadata })
+        }
+        Some("dict-info") => {
+            args.next();
+            dict_info(args)
+        }
+        Some("index-diff") => {
+            args.next();
+            match (args.next(), args.next(), args.next()) {
+                (Some(old), Some(new), None) if !old.starts_with("--") && !new.starts_with("--") => Ok(Command::IndexDiff { old, new }),
+                _ => Err(Error::Usage),
+            }
+        }
+        Some("similar") => {
+            args.next();
+            similar(args)
+        }
+        Some("check") => {
+            args.next();
+            check(args)
+        }
+        Some("show") => {
+            args.next();
+            show(args)
+        }
+        Some("analyze") => {
+            args.next();
+            analyze(args)
+        }
+        Some("strings") => {
+            args.next();
+            strings(args)
+        }
+        Some("trim") => {
+            args.next();
+            trim(args)
+        }
+        Some("duel") => {
+            args.next();
+            duel(args)
+        }
+        Some("daily") => {
+            args.next();
+            daily(args)
+        }
+        Some("words") => {
+            args.next();
+            words(args)
+        }
+        Some("submit") => {
+            args.next();
+            submit(args)
+        }
+        Some("validate") => {
+            args.next();
+            validate(args)
+        }
+        Some("serve") => {
+            args.next();
+            serve(args)
+        }
+        Some("tui") => {
+            args.next();
+            match (args.next(), args.next(), args.next()) {
+                (Some(dictionary), board, None) if !dictionary.starts_with("--") => Ok(Command::Tui { dictionary, board }),
+                _ => Err(Error::Usage),
+            }
+        }
+        Some("debug") => {
+            args.next();
+            match args.next().as_deref() {
+                Some("trie") => debug_trie(args),
+                Some("trace") => debug_trace(args),
+                Some("profile") => debug_profile(args),
+                _ => Err(Error::Usage),
+            }
+        }
+        Some("bench") => {
+            args.next();
+            bench(args)
+        }
+        Some("score") => {
+            args.next();
+            solve(args).map(Command::Score)
+        }
+        Some("solve") => {
+            args.next();
+            solve(args).map(Command::Solve)
+        }
+        _ => solve(args).map(Command::Solve),
+    }
+}
+
+fn value<T: std::str::FromStr, I: Iterator<Item = String>>(args: &mut I) -> Result<T, Error> {
+    args.next().and_then(|v| v.parse().ok()).ok_or(Error::Usage)
+}
+
+// `MIN..MAX`, both included
+fn range<I: Iterator<Item = String>>(args: &mut I) -> Result<RangeInclusive<usize>, Error> {
+    let arg = args.next().ok_or(Error::Usage)?;
+    let (min, max) = arg.split_once("..").ok_or(Error::Usage)?;
+    match (min.parse(), max.parse()) {
+        (Ok(min), Ok(max)) if min <= max => Ok(min..=max),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn generate<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut size = 4;
+    let mut seed = None;
+    let mut letters = false;
+    let mut vowels = None;
+    let mut target_words = None;
+    let mut dictionary = None;
+    let mut banned = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = value(&mut args)?,
+            "--big" => size = 5,
+            "--seed" => seed = Some(value(&mut args)?),
+            "--letters" => letters = true,
+            "--vowels" => vowels = Some(range(&mut args)?),
+            "--target-words" => target_words = Some(range(&mut args)?),
+            "--banned" => banned = Some(args.next().ok_or(Error::Usage)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ if dictionary.is_none() => dictionary = Some(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    // the word count needs a dictionary to count from, and only drawn letters have vowels to constrain
+    if size != 4 && size != 5 || target_words.is_some() != dictionary.is_some() || vowels.is_some() && !letters {
+        return Err(Error::Usage);
+    }
+    Ok(Command::Generate { size, seed, letters, vowels, target_words, dictionary, banned })
+}
+
+fn optimize<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut size = 4;
+    let mut steps = 2000;
+    let mut seed = None;
+    let mut dictionary = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = value(&mut args)?,
+            "--big" => size = 5,
+            "--steps" => steps = value(&mut args)?,
+            "--seed" => seed = Some(value(&mut args)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ if dictionary.is_none() => dictionary = Some(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    if size != 4 && size != 5 {
+        return Err(Error::Usage);
+    }
+    Ok(Command::Optimize { dictionary: dictionary.ok_or(Error::Usage)?, size, steps, seed })
+}
+
+// `words out` and the metadata to record, for `compile-dict` and `build-index`
+fn compile<I: Iterator<Item = String>>(mut args: I) -> Result<(String, String, Metadata), Error> {
+    let mut metadata = Metadata::default();
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        let field = match arg.as_str() {
+            "--license" => &mut metadata.license,
+            "--attribution" => &mut metadata.attribution,
+            "--source" => &mut metadata.source,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => {
+                paths.push(arg);
+                continue;
+            }
+        };
+        // each is stored as a single line
+        *field = Some(args.next().filter(|v| !v.contains(['\n', '\r'])).ok_or(Error::Usage)?);
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(words), Some(out), None) => Ok((words, out, metadata)),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn dict_info<I: Iterator<Item = String>>(args: I) -> Result<Command, Error> {
+    let mut output = Output::Text;
+    let mut dictionary = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => output = Output::Json,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ if dictionary.is_none() => dictionary = Some(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    Ok(Command::DictInfo { dictionary: dictionary.ok_or(Error::Usage)?, output })
+}
+
+fn similar<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut top = 5;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => top = value(&mut args)?,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(board), Some(dir), None) => Ok(Command::Similar { board, dir, top }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn check<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut min_length = 3;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-length" => min_length = value(&mut args)?,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    match (positional.next(), positional.next()) {
+        (Some(dictionary), Some(board)) if positional.len() > 0 => {
+            Ok(Command::Check { dictionary, board, words: positional.collect(), min_length })
+        }
+        _ => Err(Error::Usage),
+    }
+}
+
+fn show<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut color = Color::Auto;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--color" => {
+                color = match args.next().as_deref() {
+                    Some("auto") => Color::Auto,
+                    Some("always") => Color::Always,
+                    Some("never") => Color::Never,
+                    _ => return Err(Error::Usage),
+                }
+            }
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    match (positional.next(), positional.next(), positional.next(), positional.next()) {
+        (Some(dictionary), Some(board), Some(word), None) => Ok(Command::Show { dictionary, board, word, color }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn analyze<I: Iterator<Item = String>>(args: I) -> Result<Command, Error> {
+    let (dictionary, board, min_length) = dictionary_and_board(args)?;
+    Ok(Command::Analyze { dictionary, board, min_length })
+}
+
+fn strings<I: Iterator<Item = String>>(args: I) -> Result<Command, Error> {
+    let (dictionary, board, min_length) = dictionary_and_board(args)?;
+    Ok(Command::Strings { dictionary, board, min_length })
+}
+
+fn trim<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut min_length = None;
+    let mut out = None;
+    let mut pruned = false;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-length" => min_length = Some(value(&mut args)?),
+            "-o" => out = Some(args.next().ok_or(Error::Usage)?),
+            "--pruned" => pruned = true,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(dictionary), Some(board), None) => Ok(Command::Trim { dictionary, board, out, pruned, min_length }),
+        _ => Err(Error::Usage),
+    }
+}
+
+// `[--min-length N] dictionary board`
+fn dictionary_and_board<I: Iterator<Item = String>>(mut args: I) -> Result<(String, String, Option<usize>), Error> {
+    let mut min_length = None;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-length" => min_length = Some(value(&mut args)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(dictionary), Some(board), None) => Ok((dictionary, board, min_length)),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn duel<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut min_length = None;
+    let mut top = 10;
+    let (mut host, mut join) = (false, None);
+    let (mut port, mut seed, mut seconds) = (None, None, None);
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-length" => min_length = Some(value(&mut args)?),
+            "--top" => top = value(&mut args)?,
+            "--host" => host = true,
+            "--join" => join = Some(args.next().ok_or(Error::Usage)?),
+            "--port" => port = Some(value(&mut args)?),
+            "--seed" => seed = Some(value(&mut args)?),
+            "--seconds" => seconds = Some(value(&mut args)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => positional.push(arg),
+        }
+    }
+    // only the host picks the round
+    let hosting = port.is_some() || seed.is_some() || seconds.is_some();
+    let mut positional = positional.into_iter();
+    match (host, join, positional.next(), positional.next(), positional.next(), positional.next(), positional.next()) {
+        (false, None, Some(dictionary), Some(board), Some(first), Some(second), None) if !hosting => {
+            Ok(Command::Duel { dictionary, board, players: [first, second], min_length, top })
+        }
+        (true, None, Some(dictionary), None, ..) => {
+            let peer = Peer::Host {
+                port: port.unwrap_or(peer::DEFAULT_PORT),
+                seed,
+                seconds: seconds.unwrap_or(180),
+                min_length: min_length.unwrap_or(3),
+            };
+            Ok(Command::DuelPeer { dictionary, peer, top })
+        }
+        (false, Some(address), Some(dictionary), None, ..) if !hosting && min_length.is_none() => {
+            Ok(Command::DuelPeer { dictionary, peer: Peer::Join(address), top })
+        }
+        _ => Err(Error::Usage),
+    }
+}
+
+fn daily<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut date = None;
+    let mut seconds = None;
+    let mut data = None;
+    let mut archive = None;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--date" => date = Some(value(&mut args)?),
+            "--seconds" => seconds = Some(value(&mut args)?),
+            "--data" => data = Some(args.next().ok_or(Error::Usage)?),
+            "--archive" => archive = Some(value(&mut args)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (archive, paths.next(), paths.next()) {
+        (None, Some(dictionary), None) => Ok(Command::Daily { dictionary, date, seconds: seconds.unwrap_or(180), data }),
+        (Some(days), None, None) if seconds.is_none() => Ok(Command::DailyArchive { days, date, data }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn words<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut query = None;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        let found = match arg.as_str() {
+            "--anagram" => Query::Anagram(args.next().ok_or(Error::Usage)?),
+            "--pattern" => Query::Pattern(args.next().ok_or(Error::Usage)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => {
+                paths.push(arg);
+                continue;
+            }
+        };
+        if query.replace(found).is_some() {
+            return Err(Error::Usage);
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (query, paths.next(), paths.next()) {
+        (Some(query), Some(dictionary), None) => Ok(Command::Words { dictionary, query }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn submit<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut banned = None;
+    let mut boards_dir = None;
+    let mut board = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--banned" => banned = Some(args.next().ok_or(Error::Usage)?),
+            "--boards-dir" => boards_dir = Some(args.next().ok_or(Error::Usage)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ if board.is_none() => board = Some(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    Ok(Command::Submit { board: board.ok_or(Error::Usage)?, banned, boards_dir })
+}
+
+fn validate<I: Iterator<Item = String>>(args: I) -> Result<Command, Error> {
+    let mut fix = false;
+    let mut boards = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--fix" => fix = true,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => boards.push(arg),
+        }
+    }
+    if boards.is_empty() {
+        return Err(Error::Usage);
+    }
+    Ok(Command::Validate { boards, fix })
+}
+
+fn serve<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut port = None;
+    let mut config = None;
+    let mut presets = Vec::new();
+    let mut dictionaries = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => port = Some(value(&mut args)?),
+            "--config" => config = Some(args.next().ok_or(Error::Usage)?),
+            "--preset" => presets.push(value(&mut args)?),
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            // `name=path`, or a bare path for a dictionary named "default"
+            _ => match arg.split_once('=') {
+                Some((name, path)) if !name.is_empty() && !path.is_empty() => dictionaries.push((name.to_string(), path.to_string())),
+                Some(_) => return Err(Error::Usage),
+                None => dictionaries.push(("default".to_string(), arg)),
+            },
+        }
+    }
+    if dictionaries.is_empty() == config.is_none() || config.is_some() && !presets.is_empty() {
+        return Err(Error::Usage);
+    }
+    Ok(Command::Serve { dictionaries, presets, config, port })
+}
+
+fn debug_trie<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut prefix = String::new();
+    let mut depth = 3;
+    let mut dictionary = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--prefix" => prefix = args.next().ok_or(Error::Usage)?,
+            "--depth" => depth = value(&mut args)?,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ if dictionary.is_none() => dictionary = Some(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    Ok(Command::DebugTrie { dictionary: dictionary.ok_or(Error::Usage)?, prefix, depth })
+}
+
+fn debug_trace<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut limit = 1000;
+    let mut dot = false;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" => limit = value(&mut args)?,
+            "--dot" => dot = true,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(dictionary), Some(board), None) => Ok(Command::DebugTrace { dictionary, board, limit, dot }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn debug_profile<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut top = 20;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => top = value(&mut args)?,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(dictionary), Some(board), None) => Ok(Command::DebugProfile { dictionary, board, top }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn bench<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut iterations = 10;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => iterations = value(&mut args)?,
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => paths.push(arg),
+        }
+    }
+    let mut paths = paths.into_iter();
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(dictionary), Some(board), None) => Ok(Command::Bench { dictionary, board, iterations }),
+        _ => Err(Error::Usage),
+    }
+}
+
+fn solve<I: Iterator<Item = String>>(mut args: I) -> Result<Solve, Error> {
+    let mut low_power = false;
+    let mut threads = None;
+    let mut min_length = None;
+    let mut max_length = None;
+    let mut sort = Sort::Alpha;
+    let mut output = Output::Text;
+    let mut paths = false;
+    let mut group = false;
+    let mut verify = false;
+    let mut findable = false;
+    let mut normalize = false;
+    let mut progress = false;
+    let mut stats = false;
+    let mut strategy = None;
+    let mut rarity = None;
+    let mut max_obscurity = None;
+    let mut define = None;
+    let mut boards_dir = None;
+    let mut inline = Vec::new();
+    let mut format = None;
+    let mut topology = Topology::default();
+    let mut also = Vec::new();
+    let mut exclude = Vec::new();
+    let mut alphabet = Alphabet::english();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--low-power" => low_power = true,
+            "--threads" => threads = Some(value(&mut args).ok().filter(|&n| n > 0).ok_or(Error::Usage)?),
+            "--min-length" => min_length = Some(value(&mut args)?),
+            "--max-length" => max_length = Some(value(&mut args)?),
+            "--sort" => {
+                sort = match args.next().as_deref() {
+                    Some("alpha") => Sort::Alpha,
+                    Some("length") => Sort::Length,
+                    Some("score") => Sort::Score,
+                    _ => return Err(Error::Usage),
+                }
+            }
+            "--output" => {
+                output = match args.next().as_deref() {
+                    Some("text") => Output::Text,
+                    Some("json") => Output::Json,
+                    _ => return Err(Error::Usage),
+                }
+            }
+            "--json" => output = Output::Json,
+            "--paths" => paths = true,
+            "--group" => group = true,
+            "--verify" => verify = true,
+            "--findable" => findable = true,
+            "--normalize" => normalize = true,
+            "--progress" => progress = true,
+            "--stats" => stats = true,
+            "--strategy" => strategy = Some(args.next().as_deref().and_then(strategy::find).ok_or(Error::Usage)?.name()),
+            "--rarity" => rarity = Some(args.next().ok_or(Error::Usage)?),
+            "--max-obscurity" => {
+                max_obscurity = match args.next().as_deref() {
+                    Some("common") => Some(Tier::Common),
+                    Some("uncommon") => Some(Tier::Uncommon),
+                    Some("rare") => Some(Tier::Rare),
+                    _ => return Err(Error::Usage),
+                }
+            }
+            "--define" => define = Some(args.next().ok_or(Error::Usage)?),
+            "--boards-dir" => boards_dir = Some(args.next().ok_or(Error::Usage)?),
+            "--board" => inline.push(args.next().ok_or(Error::Usage)?),
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("grid") => Some(Format::Grid),
+                    Some("inline") => Some(Format::Inline),
+                    Some("csv") => Some(Format::Csv),
+                    Some("json") => Some(Format::Json),
+                    _ => return Err(Error::Usage),
+                }
+            }
+            "--also" => also.push(args.next().ok_or(Error::Usage)?),
+            "--exclude" => exclude.push(args.next().ok_or(Error::Usage)?),
+            "--no-diagonals" => topology.diagonals = false,
+            "--wrap" => topology.wrap = true,
+            "--alphabet" => {
+                let letters = args.next().ok_or(Error::Usage)?;
+                alphabet = Alphabet::for_language(&letters).or_else(|| Alphabet::new(&letters)).ok_or(Error::Usage)?;
+            }
+            // `-` alone is stdin, not a flag
+            flag if flag.starts_with("--") => return Err(Error::Usage),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let dictionary = positional.next().ok_or(Error::Usage)?;
+    let boards: Vec<_> = positional.collect();
+    // tiers come from the frequency list
+    if boards.is_empty() && inline.is_empty() && boards_dir.is_none() || max_obscurity.is_some() && rarity.is_none() {
+        return Err(Error::Usage);
+    }
+    // the strategies find and score every word the same way, on one thread or all of them
+    let tuned = low_power || threads.is_some() || min_length.is_some() || max_length.is_some() || rarity.is_some() || stats;
+    if strategy.is_some() && tuned {
+        return Err(Error::Usage);
+    }
+    Ok(Solve { dictionary, also, exclude, inline, boards, format, boards_dir, low_power, threads, min_length, max_length, sort, output, paths, group, rarity, max_obscurity, define, topology, alphabet, verify, findable, normalize, progress, stats, strategy })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &str) -> Result<Command, Error> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn commands() {
+        let plain = Solve {
+            dictionary: "dict".to_string(),
+            also: Vec::new(),
+            exclude: Vec::new(),
+            inline: Vec::new(),
+            boards: vec!["board".to_string()],
+            format: None,
+            boards_dir: None,
+            low_power: false,
+            threads: None,
+            min_length: None,
+            max_length: None,
+            sort: Sort::Alpha,
+            output: Output::Text,
+            paths: false,
+            group: false,
+            rarity: None,
+            max_obscurity: None,
+            define: None,
+            topology: Topology::default(),
+            alphabet: Alphabet::english(),
+            verify: false,
+            findable: false,
+            normalize: false,
+            progress: false,
+            stats: false,
+            strategy: None,
+        };
+        assert_eq!(args("dict board").unwrap(), Command::Solve(plain.clone()));
+        assert_eq!(args("--alphabet es dict board").unwrap(), Command::Solve(Solve { alphabet: Alphabet::spanish(), ..plain.clone() }));
+        assert_eq!(args("--alphabet åäö dict board").unwrap(), Command::Solve(Solve { alphabet: Alphabet::new("åäö").unwrap(), ..plain.clone() }));
+        assert_eq!(args("solve dict board").unwrap(), Command::Solve(plain.clone()));
+        assert_eq!(args("dict board --verify").unwrap(), Command::Solve(Solve { verify: true, ..plain.clone() }));
+        assert_eq!(args("--findable dict board").unwrap(), Command::Solve(Solve { findable: true, ..plain.clone() }));
+        assert_eq!(args("--normalize dict board").unwrap(), Command::Solve(Solve { normalize: true, ..plain.clone() }));
+        assert_eq!(args("--progress dict board").unwrap(), Command::Solve(Solve { progress: true, ..plain.clone() }));
+        assert_eq!(args("--stats dict board").unwrap(), Command::Solve(Solve { stats: true, ..plain.clone() }));
+        assert_eq!(args("--strategy dawg dict board").unwrap(), Command::Solve(Solve { strategy: Some("dawg"), ..plain.clone() }));
+        assert!(args("--strategy fastest dict board").is_err() && args("dict board --strategy").is_err());
+        for option in &["--min-length 4", "--max-length 6", "--rarity freq", "--low-power", "--threads 2", "--stats"] {
+            assert!(args(&format!("--strategy trie {} dict board", option)).is_err(), "{}", option);
+        }
+        assert!(args("--strategy trie --wrap --verify --findable dict board").is_ok());
+        assert_eq!(
+            args("--rarity freq --max-obscurity uncommon dict board").unwrap(),
+            Command::Solve(Solve { rarity: Some("freq".to_string()), max_obscurity: Some(Tier::Uncommon), ..plain.clone() })
+        );
+        assert_eq!(args("--define wordnet dict board").unwrap(), Command::Solve(Solve { define: Some("wordnet".to_string()), ..plain.clone() }));
+        assert_eq!(args("score dict board").unwrap(), Command::Score(plain.clone()));
+        assert_eq!(
+            args("solve --threads 2 --min-length 4 --max-length 8 dict --sort score --json board").unwrap(),
+            Command::Solve(Solve { threads: Some(2), min_length: Some(4), max_length: Some(8), sort: Sort::Score, output: Output::Json, ..plain.clone() })
+        );
+        assert_eq!(
+            args("--output json --paths --group --rarity freq dict board").unwrap(),
+            Command::Solve(Solve { output: Output::Json, paths: true, group: true, rarity: Some("freq".to_string()), ..plain.clone() })
+        );
+        assert_eq!(
+            args("dict b1 b2 --boards-dir boards").unwrap(),
+            Command::Solve(Solve {
+                boards: vec!["b1".to_string(), "b2".to_string()],
+                boards_dir: Some("boards".to_string()),
+                ..plain.clone()
+            })
+        );
+        assert_eq!(
+            args("--wrap --no-diagonals dict board").unwrap(),
+            Command::Solve(Solve { topology: Topology { diagonals: false, wrap: true }, ..plain.clone() })
+        );
+        assert_eq!(
+            args("--also slang --also medical dict board").unwrap(),
+            Command::Solve(Solve { also: vec!["slang".to_string(), "medical".to_string()], ..plain.clone() })
+        );
+        assert_eq!(
+            args("--exclude profanity dict board --exclude names").unwrap(),
+            Command::Solve(Solve { exclude: vec!["profanity".to_string(), "names".to_string()], ..plain.clone() })
+        );
+        assert_eq!(
+            args("--board abcd,efgh,ijkl,mnop --format inline dict --board ab,cd").unwrap(),
+            Command::Solve(Solve {
+                inline: vec!["abcd,efgh,ijkl,mnop".to_string(), "ab,cd".to_string()],
+                boards: Vec::new(),
+                format: Some(Format::Inline),
+                ..plain.clone()
+            })
+        );
+        assert_eq!(
+            args("dict --boards-dir boards").unwrap(),
+            Command::Solve(Solve { boards: Vec::new(), boards_dir: Some("boards".to_string()), ..plain })
+        );
+        assert_eq!(args("generate").unwrap(),
+                   Command::Generate { size: 4, seed: None, letters: false, vowels: None, target_words: None, dictionary: None, banned: None });
+        assert_eq!(args("generate --size 5 --seed 9").unwrap(),
+                   Command::Generate { size: 5, seed: Some(9), letters: false, vowels: None, target_words: None, dictionary: None, banned: None });
+        assert_eq!(args("generate --banned bad").unwrap(),
+                   Command::Generate { size: 4, seed: None, letters: false, vowels: None, target_words: None, dictionary: None, banned: Some("bad".to_string()) });
+        assert_eq!(
+            args("generate --letters --vowels 4..7 --target-words 80..150 dict").unwrap(),
+            Command::Generate {
+                size: 4,
+                seed: None,
+                letters: true,
+                vowels: Some(4..=7),
+                target_words: Some(80..=150),
+                dictionary: Some("dict".to_string()),
+                banned: None,
+            }
+        );
+        assert_eq!(args("compile-dict words out").unwrap(),
+                   Command::CompileDict { words: "words".to_string(), out: "out".to_string(), metadata: Metadata::default() });
+        assert_eq!(args("compile-dict --license CC0-1.0 words --source example.com out").unwrap(),
+                   Command::CompileDict {
+                       words: "words".to_string(),
+                       out: "out".to_string(),
+                       metadata: Metadata { license: Some("CC0-1.0".to_string()), source: Some("example.com".to_string()), ..Metadata::default() },
+                   });
+        assert_eq!(args("dict-info dict").unwrap(), Command::DictInfo { dictionary: "dict".to_string(), output: Output::Text });
+        assert_eq!(args("dict-info --json dict").unwrap(), Command::DictInfo { dictionary: "dict".to_string(), output: Output::Json });
+        assert_eq!(args("tui dict").unwrap(), Command::Tui { dictionary: "dict".to_string(), board: None });
+        assert_eq!(args("tui dict board").unwrap(), Command::Tui { dictionary: "dict".to_string(), board: Some("board".to_string()) });
+        assert_eq!(args("build-index words out").unwrap(),
+                   Command::BuildIndex { words: "words".to_string(), out: "out".to_string(), metadata: Metadata::default() });
+        assert_eq!(args("index-diff old.idx new.idx").unwrap(),
+                   Command::IndexDiff { old: "old.idx".to_string(), new: "new.idx".to_string() });
+        assert_eq!(args("optimize --big --steps 10 --seed 3 dict").unwrap(),
+                   Command::Optimize { dictionary: "dict".to_string(), size: 5, steps: 10, seed: Some(3) });
+        assert_eq!(args("similar --top 2 board boards").unwrap(),
+                   Command::Similar { board: "board".to_string(), dir: "boards".to_string(), top: 2 });
+        assert_eq!(args("analyze --min-length 4 dict board").unwrap(),
+                   Command::Analyze { dictionary: "dict".to_string(), board: "board".to_string(), min_length: Some(4) });
+        assert_eq!(args("strings dict board").unwrap(),
+                   Command::Strings { dictionary: "dict".to_string(), board: "board".to_string(), min_length: None });
+        assert_eq!(args("trim --pruned dict board -o small.txt").unwrap(), Command::Trim {
+            dictionary: "dict".to_string(), board: "board".to_string(), out: Some("small.txt".to_string()), pruned: true, min_length: None,
+        });
+        assert_eq!(args("daily dict").unwrap(), Command::Daily { dictionary: "dict".to_string(), date: None, seconds: 180, data: None });
+        assert_eq!(args("daily --date 2026-10-14 --seconds 60 --data saved dict").unwrap(),
+                   Command::Daily { dictionary: "dict".to_string(), date: "2026-10-14".parse().ok(), seconds: 60, data: Some("saved".to_string()) });
+        assert_eq!(args("daily --archive 7 --data saved").unwrap(), Command::DailyArchive { days: 7, date: None, data: Some("saved".to_string()) });
+        assert_eq!(args("duel --top 3 dict board one two").unwrap(),
+                   Command::Duel { dictionary: "dict".to_string(), board: "board".to_string(), players: ["one".to_string(), "two".to_string()], min_length: None, top: 3 });
+        assert_eq!(args("duel --host --seconds 60 --min-length 4 dict").unwrap(), Command::DuelPeer {
+            dictionary: "dict".to_string(),
+            peer: Peer::Host { port: peer::DEFAULT_PORT, seed: None, seconds: 60, min_length: 4 },
+            top: 10,
+        });
+        assert_eq!(args("duel --join host:7878 dict").unwrap(),
+                   Command::DuelPeer { dictionary: "dict".to_string(), peer: Peer::Join("host:7878".to_string()), top: 10 });
+        assert_eq!(args("check --min-length 4 dict board cat Dogs").unwrap(),
+                   Command::Check { dictionary: "dict".to_string(), board: "board".to_string(), words: vec!["cat".to_string(), "Dogs".to_string()], min_length: 4 });
+        assert_eq!(args("show --color never dict board cat").unwrap(),
+                   Command::Show { dictionary: "dict".to_string(), board: "board".to_string(), word: "cat".to_string(), color: Color::Never });
+        assert_eq!(args("words --anagram retains dict").unwrap(),
+                   Command::Words { dictionary: "dict".to_string(), query: Query::Anagram("retains".to_string()) });
+        assert_eq!(args("words dict --pattern c?t*").unwrap(),
+                   Command::Words { dictionary: "dict".to_string(), query: Query::Pattern("c?t*".to_string()) });
+        assert_eq!(args("submit --banned words --boards-dir boards board").unwrap(),
+                   Command::Submit { board: "board".to_string(), banned: Some("words".to_string()), boards_dir: Some("boards".to_string()) });
+        assert_eq!(args("validate a --fix -").unwrap(), Command::Validate { boards: vec!["a".to_string(), "-".to_string()], fix: true });
+        assert_eq!(args("serve --port 8080 dict").unwrap(),
+                   Command::Serve { dictionaries: vec![("default".to_string(), "dict".to_string())], presets: Vec::new(), config: None, port: Some(8080) });
+        assert_eq!(args("serve --preset big casual=common.txt --preset classic tournament=sowpods.dict").unwrap(),
+                   Command::Serve {
+                       dictionaries: vec![("casual".to_string(), "common.txt".to_string()), ("tournament".to_string(), "sowpods.dict".to_string())],
+                       presets: vec![Preset::Big, Preset::Classic],
+                       config: None,
+                       port: None,
+                   });
+        assert_eq!(args("serve --config server.toml").unwrap(),
+                   Command::Serve { dictionaries: Vec::new(), presets: Vec::new(), config: Some("server.toml".to_string()), port: None });
+        assert_eq!(args("debug trie --prefix qu dict").unwrap(),
+                   Command::DebugTrie { dictionary: "dict".to_string(), prefix: "qu".to_string(), depth: 3 });
+        assert_eq!(args("debug trie dict --depth 1").unwrap(),
+                   Command::DebugTrie { dictionary: "dict".to_string(), prefix: String::new(), depth: 1 });
+        assert_eq!(args("debug trace --dot dict board").unwrap(),
+                   Command::DebugTrace { dictionary: "dict".to_string(), board: "board".to_string(), limit: 1000, dot: true });
+        assert_eq!(args("debug trace dict board --limit 5").unwrap(),
+                   Command::DebugTrace { dictionary: "dict".to_string(), board: "board".to_string(), limit: 5, dot: false });
+        assert_eq!(args("debug profile --top 3 dict board").unwrap(),
+                   Command::DebugProfile { dictionary: "dict".to_string(), board: "board".to_string(), top: 3 });
+        assert_eq!(args("bench --iterations 3 dict board").unwrap(),
+                   Command::Bench { dictionary: "dict".to_string(), board: "board".to_string(), iterations: 3 });
+        assert!(args("bench dict").is_err());
+        assert_eq!(args("selftest").unwrap(), Command::SelfTest);
+        assert_eq!(args("--help").unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn usage() {
+        for bad in &["", "dict", "dict --boards-dir", "dict --board", "--format xml dict board", "--threads 0 dict board", "--sort random dict board",
+                     "--what dict board", "--max-length x dict board", "dict board --rarity", "dict board --define", "--max-obscurity rare dict board", "--rarity freq --max-obscurity weird dict board", "dict board --also", "dict board --exclude", "--alphabet fr dict board", "--alphabet dict board", "--output xml dict board", "generate --size 6", "generate --vowels 4..7", "generate --target-words 80..150", "generate dict", "generate --letters --target-words 150..80 dict", "generate --letters --vowels 4 ", "generate --banned", "show dict board", "show dict board cat dog", "show --color red dict board cat", "generate --seed",
+                     "compile-dict words", "compile-dict words out extra", "compile-dict --license words out", "compile-dict --author x words out", "build-index words", "dict-info", "dict-info a b", "dict-info --text a", "index-diff a", "index-diff a b c", "index-diff --json a b", "tui", "tui a b c", "debug dict", "similar board", "check dict board", "check --min-length x dict board cat", "check --paths dict board cat", "analyze dict", "analyze --min-length dict board", "analyze dict board cat", "strings dict", "strings --min-length dict board", "trim dict", "trim dict board -o", "daily", "daily dict board", "daily --date 2026-02-30 dict", "daily --seconds dict", "daily --archive 7 dict", "daily --archive 7 --seconds 60", "daily --archive", "daily --data", "trim --out x dict board", "duel dict board one", "duel dict board one two three", "duel --top x dict board one two", "duel --host dict board", "duel --host --join a dict", "duel --join a --seconds 9 dict", "duel --seed 1 dict board one two", "words dict", "words --anagram", "words --anagram abc", "words --anagram abc --pattern a* dict", "words --pattern a* a b", "submit", "submit --banned", "submit a b", "validate", "validate --fix", "validate --fixed a", "optimize", "optimize --size 3 dict", "optimize a b", "serve", "serve --config c dict", "serve --port 70000 dict", "serve --preset speed dict", "serve --preset big --config c", "serve =dict", "serve en=", "similar --top board dir", "debug trie",
+                     "debug trie --prefix", "debug trie a b", "debug trace dict", "debug trace --limit x dict board",
+                     "debug trace a b c", "debug profile dict", "debug profile --top dict board"] {
+            assert!(matches!(args(bad), Err(Error::Usage)), "{:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! `boggle daily`: one board a day, the same for everyone, played against
+//! the clock and summed up in a few lines that can be shared without giving
+//! the board away. Each day's result can be kept in [`Storage`] under
+//! `daily/YYYY-MM-DD`, for the archive of past days to show.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use boggle::duel::Player;
+use boggle::storage::Storage;
+use boggle::{dice, Board, Error, Solutions};
+use serde::{Deserialize, Serialize};
+
+/// A day, as the number of days since 1970-01-01.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date(i64);
+
+impl Date {
+    /// Today, in UTC, so players in different time zones share a board for
+    /// most of the day.
+    pub fn today() -> Date {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        Date((secs / 86_400) as i64)
+    }
+
+    fn from_civil(year: i64, month: i64, day: i64) -> Date {
+        // counting years from March, so the leap day falls at the end of the year
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Date(era * 146_097 + day_of_era - 719_468)
+    }
+
+    fn civil(self) -> (i64, i64, i64) {
+        let days = self.0 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted + 2) / 5 + 1;
+        let month = if shifted < 10 { shifted + 3 } else { shifted - 9 };
+        (year_of_era + era * 400 + (month <= 2) as i64, month, day)
+    }
+
+    /// The seed the day's board is rolled with.
+    pub fn seed(self) -> u64 {
+        self.0 as u64
+    }
+
+    /// The day's board, as `Board::parse` reads it.
+    pub fn board(self) -> String {
+        dice::roll(&dice::CLASSIC, self.seed())
+    }
+}
+
+/// Reads `YYYY-MM-DD`.
+impl FromStr for Date {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Date, ()> {
+        let mut parts = s.splitn(3, '-');
+        let mut part = |digits: usize| match parts.next() {
+            Some(part) if part.len() == digits && part.bytes().all(|b| b.is_ascii_digit()) => part.parse().map_err(|_| ()),
+            _ => Err(()),
+        };
+        let (year, month, day) = (part(4)?, part(2)?, part(2)?);
+        let date = Date::from_civil(year, month, day);
+        // days past the end of the month roll over into the next, so they don't come back the same
+        if date.civil() == (year, month, day) { Ok(date) } else { Err(()) }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.civil();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// How a day's round went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub score: u32,
+    /// What every word on the board is worth together.
+    pub total: u32,
+    pub found: usize,
+    pub words: usize,
+}
+
+impl Record {
+    pub fn new(player: &Player, missed: &Solutions) -> Record {
+        Record {
+            score: player.score,
+            total: player.score + missed.iter().map(|s| s.score).sum::<u32>(),
+            found: player.scored.len(),
+            words: player.scored.len() + missed.len(),
+        }
+    }
+}
+
+fn key(date: Date) -> String {
+    format!("daily/{}", date)
+}
+
+/// Keeps `record` as the result for `date`, replacing any earlier one.
+pub fn save(storage: &dyn Storage, date: Date, record: &Record) -> Result<(), Error> {
+    storage.put(&key(date), &serde_json::to_vec(record).expect("records serialize"))
+}
+
+/// The result kept for `date`, if it was played; one that can't be read
+/// counts as not played.
+pub fn load(storage: &dyn Storage, date: Date) -> Result<Option<Record>, Error> {
+    Ok(storage.get(&key(date))?.and_then(|value| serde_json::from_slice(&value).ok()))
+}
+
+/// The `days` days up to and including `until`, latest first, each with its
+/// board as `Board::parse` reads it and its result, if there is one.
+pub fn archive(storage: &dyn Storage, until: Date, days: usize) -> Result<Vec<(Date, String, Option<Record>)>, Error> {
+    (0..days as i64)
+        .map(|back| Date(until.0 - back))
+        .map(|date| Ok((date, date.board(), load(storage, date)?)))
+        .collect()
+}
+
+/// The result to share: the date, points and words out of those on the
+/// board, and the board with the cells the player's words cover in green.
+pub fn share(date: Date, board: &Board, player: &Player, missed: &Solutions) -> String {
+    let record = Record::new(player, missed);
+    let mut used = vec![vec![false; board.len()]; board.len()];
+    for (word, _) in &player.scored {
+        for (x, y) in board.find_word(word).unwrap_or_default() {
+            used[x][y] = true;
+        }
+    }
+
+    let mut share = format!("Boggle {} {}/{} ({}/{})", date, record.score, record.total, record.found, record.words);
+    for row in used {
+        share.push('\n');
+        share.extend(row.into_iter().map(|used| if used { '🟩' } else { '⬜' }));
+    }
+    share
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use boggle::duel::{Game, PlayerSubmission};
+    use boggle::storage::MemoryStorage;
+    use boggle::SolveOptions;
+
+    #[test]
+    fn dates() {
+        assert_eq!("1970-01-01".parse(), Ok(Date(0)));
+        assert_eq!("2000-03-01".parse(), Ok(Date(11_017)));
+        assert_eq!("1969-12-31".parse(), Ok(Date(-1)));
+        for raw in ["2024-02-29", "2000-02-29", "2026-10-14", "1999-12-31", "0001-01-01"] {
+            assert_eq!(raw.parse::<Date>().unwrap().to_string(), raw);
+        }
+        for raw in ["2023-02-29", "1900-02-29", "2026-13-01", "2026-00-10", "2026-04-31", "2026-4-01", "26-04-01",
+                    "2026-04-01x", "2026/04/01", "", "+026-04-01"] {
+            assert_eq!(raw.parse::<Date>(), Err(()), "{:?}", raw);
+        }
+        // consecutive days follow on, across months and years
+        let eve: Date = "2025-12-31".parse().unwrap();
+        assert_eq!(Date(eve.0 + 1).to_string(), "2026-01-01");
+        assert!(Date::today() > eve);
+
+        let day: Date = "2026-10-14".parse().unwrap();
+        assert_eq!(day.board(), day.board());
+        assert_ne!(day.board(), Date(day.0 + 1).board());
+        assert!(Board::parse(&day.board()).is_ok());
+    }
+
+    #[test]
+    fn shared() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxb").unwrap();
+        let game = Game::new("cat\ncats\nact\ndog", SolveOptions::default());
+        let result = game.adjudicate(&board, &[PlayerSubmission { words: &["cat", "dog", "act"] }]);
+        let date = "2026-10-14".parse().unwrap();
+        assert_eq!(share(date, &board, &result.players[0], &result.missed),
+                   "Boggle 2026-10-14 1/2 (1/2)\n🟩🟩🟩⬜\n⬜⬜⬜⬜\n⬜⬜⬜⬜\n⬜⬜⬜⬜");
+
+        let result = game.adjudicate(&board, &[PlayerSubmission { words: &[] }]);
+        assert!(share(date, &board, &result.players[0], &result.missed).starts_with("Boggle 2026-10-14 0/2 (0/2)\n⬜"));
+    }
+
+    #[test]
+    fn archived() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxb").unwrap();
+        let game = Game::new("cat\ncats\nact\ndog", SolveOptions::default());
+        let result = game.adjudicate(&board, &[PlayerSubmission { words: &["cat", "dog"] }]);
+        let record = Record::new(&result.players[0], &result.missed);
+        assert_eq!(record, Record { score: 1, total: 2, found: 1, words: 2 });
+
+        let storage = MemoryStorage::new();
+        let date: Date = "2026-10-14".parse().unwrap();
+        save(&storage, Date(date.0 - 2), &record).unwrap();
+        assert_eq!(storage.list("").unwrap(), vec!["daily/2026-10-12"]);
+        let archive = archive(&storage, date, 3).unwrap();
+        assert_eq!(archive.iter().map(|(date, _, record)| (date.to_string(), *record)).collect::<Vec<_>>(),
+                   vec![("2026-10-14".to_string(), None), ("2026-10-13".to_string(), None), ("2026-10-12".to_string(), Some(record))]);
+        assert_eq!(archive[0].1, date.board());
+
+        // played again, the newer result replaces the older; a garbled one is as good as none
+        save(&storage, Date(date.0 - 2), &Record { score: 2, ..record }).unwrap();
+        assert_eq!(load(&storage, Date(date.0 - 2)).unwrap().map(|record| record.score), Some(2));
+        storage.put("daily/2026-10-14", b"{").unwrap();
+        assert_eq!(load(&storage, date).unwrap(), None);
+    }
+}
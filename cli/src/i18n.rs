@@ -0,0 +1,591 @@
+use std::env;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use boggle::compiled::Metadata;
+use boggle::{Error, SolveStats};
+use boggle::provenance::Provenance;
+use boggle::verify::Discrepancy;
+
+use crate::daily::{Date, Record};
+
+const USAGE_EN: &str = "USAGE: boggle [solve] [OPTIONS] dictionary board...
+       boggle score [OPTIONS] dictionary board...
+       boggle generate [--size 4|5] [--seed N] [--letters [--vowels MIN..MAX]]
+                       [--target-words MIN..MAX dictionary] [--banned FILE]
+       boggle compile-dict [METADATA] words compiled
+       boggle build-index [METADATA] words compiled
+       boggle dict-info [--json] dictionary
+       boggle index-diff old new
+       boggle optimize [--size 4|5] [--steps N] [--seed N] dictionary
+       boggle similar [--top N] board dir
+       boggle check [--min-length N] dictionary board word...
+       boggle show [--color auto|always|never] dictionary board word
+       boggle analyze [--min-length N] dictionary board
+       boggle strings [--min-length N] dictionary board
+       boggle trim [--min-length N] [--pruned] [-o FILE] dictionary board
+       boggle duel [--min-length N] [--top N] dictionary board words words
+       boggle duel --host [--port N] [--seconds N] [--seed N] [--min-length N]
+                   [--top N] dictionary
+       boggle duel --join ADDRESS [--top N] dictionary
+       boggle daily [--date YYYY-MM-DD] [--seconds N] [--data DIR] dictionary
+       boggle daily --archive DAYS [--date YYYY-MM-DD] [--data DIR]
+       boggle words --anagram LETTERS|--pattern PATTERN dictionary
+       boggle submit [--banned FILE] [--boards-dir DIR] board
+       boggle validate [--fix] board...
+       boggle serve [--port N] [--preset NAME]... [name=]dictionary...
+       boggle serve [--port N] --config FILE
+       boggle tui dictionary [board]
+       boggle debug trie [--prefix LETTERS] [--depth N] dictionary
+       boggle debug trace [--limit N] [--dot] dictionary board
+       boggle debug profile [--top N] dictionary board
+       boggle bench [--iterations N] dictionary board
+       boggle selftest
+
+A path of - reads stdin. A board is one row per line, or a CSV file with a
+tile per cell, which is how to write tiles such as th, or JSON: an array of
+rows, each a string or an array of tiles, or an object with them under \"rows\"
+and optionally \"diagonals\" and \"wrap\". generate rolls the same board for the
+same --seed on any machine; without one it picks a seed and prints it to
+stderr, so the board can be rolled again. With --letters it draws each letter
+by how often it appears in English instead, with between MIN and MAX vowels if
+given, and with --target-words it draws boards until one has between MIN and
+MAX words from the dictionary. With --banned it draws again whenever a word
+from FILE could be traced on the board. The dictionary may be a word list or
+one compiled with compile-dict, which loads much faster. Either may be
+gzipped, and a list may have a tab and a count after each word, as frequency
+lists do; --rarity then ranks the words by their counts. build-index does the
+same for big lists, showing its progress and resuming where an interrupted
+build stopped. METADATA is any of --license TEXT, --attribution TEXT and
+--source TEXT, recorded in the index for dict-info to print along with the
+word count. index-diff lists the words added to and removed from a compiled
+dictionary, marked + and -, without the word lists it was compiled from.
+Several boards are solved one after another against the same dictionary. check
+says whether each word counts on the board and numbers the cells of its path.
+show draws the board with one word's path picked out, in color on a terminal,
+and numbers its cells. analyze shows how many words can be traced through each
+cell, which cells no word uses and how the words score by length. strings
+prints the longest word and the highest scoring words that can be traced
+without two sharing a tile, numbering each cell by the word that uses it. trim
+writes the dictionary's words that are on the board, one per line, to the -o
+file or stdout, for shipping a word list with a single puzzle; with --pruned
+it keeps every word passing the search's quick checks instead, which may be
+more but needs no solving. words lists the dictionary's words of 3 letters or
+more spelled with some or all of --anagram's letters, ? being a blank, or
+matching --pattern, where ? is any letter and * any run of letters. duel
+scores two players' word lists, one word per line, for one board: words both
+found are crossed off, and the best words neither found are listed. With
+--host it rolls a board instead and waits on port 7878 for another player to
+--join it; both type words for the same board for --seconds, 180 by default,
+and see the same result. daily rolls the day's board, the same for everyone,
+or that of --date, and times a round as duel does; afterwards it prints a
+result to share, with the score and words found out of those on the board, and
+which cells they cover. With --data it keeps each result under DIR, and with
+--archive it lists the DAYS days up to --date or today, latest first, with
+their boards and the results kept under DIR. validate lists everything wrong
+with each board, such as characters that aren't letters or rows of the wrong
+length, rather than stopping at the first; with --fix it prints each board
+with the bad characters and tiles left out instead, listing what it left out
+on stderr. serve answers POST /solve on 127.0.0.1, port 8080 by default; each
+request may name one of its dictionaries and one of the presets (classic, big,
+word_hunt) given with --preset, the first of each being the default. It also
+keeps a leaderboard per board: POST /scores checks a player's words against
+the board and keeps their best score, and POST /leaderboard lists the top
+scores, kept in the config's [storage]. POST /games starts a timed game that
+players report their words to with POST /games/found, and that spectators
+follow with POST /games/watch, seeing how many words each player has found but
+not which. tui edits a board in the terminal, listing its words as it changes.
+Tab swaps in a freshly rolled board, solved in the background while the board
+on screen is edited. bench times each solver on the board, averaged over
+--iterations solves, 10 by default, with the allocations each solve makes. It
+also times compiling the dictionary on one thread and on every core, and
+counts the dictionary words left to search for once those the board hasn't the
+letters for are pruned. Setting BOGGLE_LOG to debug logs what a long run is
+doing to stderr.
+
+OPTIONS:
+    --boards-dir DIR  also solve every board in DIR
+    --board ROWS      also solve the board whose rows are ROWS, separated by
+                      commas, e.g. abcd,efgh,ijkl,mnop; may be given more
+                      than once
+    --format FORMAT   read every board as grid, inline, csv or json, rather
+                      than telling from each one
+    --also DICT       search DICT too, listing which dictionaries each word
+                      is in; may be given more than once
+    --exclude FILE    leave the words in FILE out of every dictionary, e.g.
+                      profanity; may be given more than once
+    --low-power       solve on a single thread
+    --threads N       solve on N threads
+    --min-length N    skip words shorter than N letters, 3 by default
+    --max-length N    skip words longer than N letters
+    --sort ORDER      list words by alpha, length or score
+    --output FORMAT   print the results as text or json
+    --json            same as --output json
+    --paths           include each word's path in JSON output
+    --group           list words under their first letter
+    --no-diagonals    only cells sharing an edge are adjacent
+    --wrap            the board wraps around at its edges
+    --alphabet ABC    letters boards and dictionaries may have besides a-z:
+                      es, de, or the letters themselves, e.g. åäö
+    --normalize       take apostrophes, hyphens and accents out of dictionary
+                      words rather than skipping them
+    --rarity FILE     score rare words higher, ranked by the words in FILE
+                      from most to least common, and show each word's tier
+    --max-obscurity TIER
+                      with --rarity, leave out words rarer than TIER: common,
+                      uncommon or rare
+    --define FILE     show each word's definition from FILE, a word and its
+                      definition per line separated by a tab, or a WordNet
+                      data file; it may be gzipped
+    --verify          check each board's words against a slow but simple
+                      search, reporting any word they disagree on
+    --strategy NAME   solve with one plain solver instead: single-threaded,
+                      trie, parallel, recursive or dawg; these find every
+                      word, so can't be given --min-length, --max-length,
+                      --rarity, --low-power, --threads or --stats
+    --progress        draw a progress bar over the boards on stderr
+    --stats           print how much work each solve did, and how long each
+                      phase took, to stderr
+    --findable        only list words a person could plausibly find: paths
+                      with few turns and, with --rarity, words that aren't
+                      rare";
+
+const USAGE_ES: &str = "USO: boggle [solve] [OPCIONES] diccionario tablero...
+     boggle score [OPCIONES] diccionario tablero...
+     boggle generate [--size 4|5] [--seed N] [--letters [--vowels MIN..MAX]]
+                     [--target-words MIN..MAX diccionario] [--banned ARCHIVO]
+     boggle compile-dict [METADATOS] palabras compilado
+     boggle build-index [METADATOS] palabras compilado
+     boggle dict-info [--json] diccionario
+     boggle index-diff anterior nuevo
+     boggle optimize [--size 4|5] [--steps N] [--seed N] diccionario
+     boggle similar [--top N] tablero directorio
+     boggle check [--min-length N] diccionario tablero palabra...
+     boggle show [--color auto|always|never] diccionario tablero palabra
+     boggle analyze [--min-length N] diccionario tablero
+     boggle strings [--min-length N] diccionario tablero
+     boggle trim [--min-length N] [--pruned] [-o ARCHIVO] diccionario tablero
+     boggle duel [--min-length N] [--top N] diccionario tablero lista lista
+     boggle duel --host [--port N] [--seconds N] [--seed N] [--min-length N]
+                 [--top N] diccionario
+     boggle duel --join DIRECCIÓN [--top N] diccionario
+     boggle daily [--date AAAA-MM-DD] [--seconds N] [--data DIR] diccionario
+     boggle daily --archive DÍAS [--date AAAA-MM-DD] [--data DIR]
+     boggle words --anagram LETRAS|--pattern PATRÓN diccionario
+     boggle submit [--banned ARCHIVO] [--boards-dir DIR] tablero
+     boggle validate [--fix] tablero...
+     boggle serve [--port N] [--preset NOMBRE]... [nombre=]diccionario...
+     boggle serve [--port N] --config ARCHIVO
+     boggle tui diccionario [tablero]
+     boggle debug trie [--prefix LETRAS] [--depth N] diccionario
+     boggle debug trace [--limit N] [--dot] diccionario tablero
+     boggle debug profile [--top N] diccionario tablero
+     boggle bench [--iterations N] diccionario tablero
+     boggle selftest
+
+Una ruta - lee la entrada estándar. Un tablero es una fila por línea, o un CSV
+con una ficha por casilla, que es como se escriben fichas como th, o JSON: una
+lista de filas, cada una un texto o una lista de fichas, o un objeto con ellas
+en \"rows\" y, si se quiere, \"diagonals\" y \"wrap\". generate tira el mismo
+tablero con la misma --seed en cualquier máquina; sin ella elige una semilla y
+la muestra en la salida de errores, para poder volver a tirar el tablero. Con
+--letters saca cada letra según su frecuencia en inglés, con entre MIN y MAX
+vocales si se indican, y con --target-words saca tableros hasta que uno tenga
+entre MIN y MAX palabras del diccionario. Con --banned vuelve a sacar cada vez
+que una palabra de ARCHIVO pueda trazarse en el tablero. El diccionario puede
+ser una lista de palabras o uno compilado con compile-dict, que carga mucho
+más rápido. Cualquiera de los dos puede estar comprimido con gzip, y una lista
+puede llevar un tabulador y un número tras cada palabra, como las listas de
+frecuencias; --rarity ordena entonces las palabras por ese número. build-index
+hace lo mismo con listas grandes, mostrando su progreso y continuando donde se
+interrumpió. METADATOS es cualquiera de --license TEXTO, --attribution TEXTO y
+--source TEXTO, que se guardan en el índice para que dict-info los muestre
+junto al número de palabras. index-diff lista las palabras añadidas a un
+diccionario compilado y las quitadas, marcadas con + y -, sin las listas de
+las que se compiló. Varios tableros se resuelven uno tras otro con el mismo
+diccionario. check indica si cada palabra vale en el tablero y numera las
+casillas de su recorrido. show dibuja el tablero resaltando el recorrido de
+una palabra, en color en una terminal, y numera sus casillas. analyze muestra
+cuántas palabras pasan por cada casilla, qué casillas no usa ninguna palabra y
+cómo puntúan las palabras según su longitud. strings muestra la palabra más
+larga y las palabras que más puntúan sin que dos compartan casilla, numerando
+cada casilla según la palabra que la usa. trim escribe las palabras del
+diccionario que están en el tablero, una por línea, en el archivo de -o o en
+la salida estándar, para acompañar un solo tablero con su lista de palabras;
+con --pruned conserva en cambio todas las que pasan las comprobaciones rápidas
+de la búsqueda, que pueden ser más pero no hace falta resolver el tablero.
+words lista las palabras del diccionario de 3 letras o más que se forman con
+algunas o todas las letras de --anagram, siendo ? un comodín, o que encajan
+con --pattern, donde ? es cualquier letra y * cualquier serie de letras. duel
+puntúa las listas de palabras de dos jugadores, una por línea, en un mismo
+tablero: se tachan las que encontraron los dos y se listan las mejores que no
+encontró ninguno. Con --host tira un tablero y espera en el puerto 7878 a que
+otro jugador se una con --join; los dos escriben palabras para el mismo
+tablero durante --seconds, 180 por defecto, y ven el mismo resultado. daily
+tira el tablero del día, el mismo para todos, o el de --date, y cronometra una
+partida como duel; al terminar muestra un resultado para compartir, con la
+puntuación y las palabras encontradas de las que tiene el tablero, y qué
+casillas cubren. Con --data guarda cada resultado en DIR, y con --archive
+lista los DÍAS días hasta --date u hoy, empezando por el último, con sus
+tableros y los resultados guardados en DIR. validate lista todo lo que está
+mal en cada tablero, como caracteres que no son letras o filas de longitud
+incorrecta, en lugar de detenerse en lo primero; con --fix muestra en cambio
+cada tablero sin los caracteres y las fichas no válidos, indicando en la
+salida de errores lo que quitó. serve responde a POST /solve en 127.0.0.1, por
+defecto en el puerto 8080; cada petición puede nombrar uno de sus diccionarios
+y una de las reglas (classic, big, word_hunt) dadas con --preset, siendo la
+primera de cada una la predeterminada. También lleva una clasificación por
+tablero: POST /scores comprueba las palabras de un jugador en el tablero y
+guarda su mejor puntuación, y POST /leaderboard lista las mejores, guardadas
+en el [storage] de la configuración. POST /games empieza una partida con
+tiempo a la que los jugadores mandan sus palabras con POST /games/found y que
+los espectadores siguen con POST /games/watch, viendo cuántas palabras ha
+encontrado cada jugador pero no cuáles. tui edita un tablero en la terminal,
+listando sus palabras a medida que cambia. Tab pone un tablero recién tirado,
+resuelto en segundo plano mientras se edita el de la pantalla. bench
+cronometra cada solucionador en el tablero, con la media de --iterations
+resoluciones, 10 por defecto, y las reservas de memoria de cada una. También
+cronometra la compilación del diccionario en un hilo y en todos los núcleos, y
+cuenta las palabras del diccionario que quedan por buscar tras descartar
+aquellas para las que el tablero no tiene las letras. Con BOGGLE_LOG=debug se
+registra en la salida de errores lo que va haciendo una ejecución larga.
+
+OPCIONES:
+    --boards-dir DIR  resolver también cada tablero de DIR
+    --board FILAS     resolver también el tablero de FILAS, separadas por
+                      comas, p. ej. abcd,efgh,ijkl,mnop; se puede repetir
+    --format FORMATO  leer cada tablero como grid, inline, csv o json, en vez
+                      de deducirlo de cada uno
+    --also DICC       buscar también en DICC, indicando en qué diccionarios
+                      está cada palabra; se puede repetir
+    --exclude LISTA   quitar de cada diccionario las palabras de LISTA, p. ej.
+                      palabrotas; se puede repetir
+    --low-power       resolver en un solo hilo
+    --threads N       resolver en N hilos
+    --min-length N    omitir palabras de menos de N letras, 3 por defecto
+    --max-length N    omitir palabras de más de N letras
+    --sort ORDEN      listar palabras por alpha, length o score
+    --output FORMATO  imprimir los resultados como text o json
+    --json            igual que --output json
+    --paths           incluir el recorrido de cada palabra en JSON
+    --group           agrupar las palabras por su primera letra
+    --no-diagonals    solo las casillas con un lado en común son adyacentes
+    --wrap            el tablero continúa por el borde opuesto
+    --alphabet ABC    letras que pueden tener tableros y diccionarios además
+                      de a-z: es, de o las propias letras, p. ej. åäö
+    --normalize       quitar apóstrofos, guiones y acentos de las palabras del
+                      diccionario en lugar de omitirlas
+    --rarity ARCHIVO  puntuar más las palabras raras, según el orden de ARCHIVO
+                      de más a menos común, y mostrar el nivel de cada una
+    --max-obscurity NIVEL
+                      con --rarity, omitir las palabras más raras que NIVEL:
+                      common, uncommon o rare
+    --define ARCHIVO  mostrar la definición de cada palabra según ARCHIVO, con
+                      una palabra y su definición por línea separadas por un
+                      tabulador, o un archivo de datos de WordNet; puede estar
+                      comprimido con gzip
+    --verify          comprobar las palabras de cada tablero con una búsqueda
+                      lenta pero sencilla, indicando en cuáles no coinciden
+    --strategy NOMBRE
+                      resolver con un solo buscador sencillo: single-threaded,
+                      trie, parallel, recursive o dawg; estos encuentran todas
+                      las palabras, así que no admiten --min-length,
+                      --max-length, --rarity, --low-power, --threads ni
+                      --stats
+    --progress        dibujar una barra de progreso de los tableros en la
+                      salida de errores
+    --stats           mostrar en la salida de errores cuánto trabajo hizo
+                      cada resolución y cuánto tardó cada fase
+    --findable        listar solo las palabras que una persona podría
+                      encontrar: caminos con pocos giros y, con --rarity,
+                      palabras que no son raras";
+
+/// The languages the CLI can talk in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Es,
+}
+
+impl Locale {
+    /// Picks the locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, in that order
+    /// like gettext, falling back to English.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|tag| !tag.is_empty())
+            .and_then(|tag| Locale::parse(&tag))
+            .unwrap_or(Locale::En)
+    }
+
+    /// Parses POSIX-style tags like `es`, `es_MX` or `es_ES.UTF-8`.
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let lang = tag.split(['_', '-', '.']).next().unwrap_or("");
+        match lang {
+            "en" | "C" | "POSIX" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+}
+
+/// Everything the solver prints for a person to read. `selftest` and `bench`
+/// are support tools, and their output stays in English.
+pub enum Message<'a> {
+    Found(usize),
+    TotalScore(u32),
+    SkippedTooLong(usize),
+    SkippedInvalid(usize),
+    Normalized(usize),
+    Excluded(usize),
+    /// The seed `generate` picked, for rolling the same board again.
+    Seed(u64),
+    /// How many words the board `generate` settled on has, and how many it drew to find it.
+    Generated { words: usize, attempts: usize },
+    NoBoard(usize),
+    /// How many boards `generate --banned` drew, and whether they were also to have a number of words.
+    NoCleanBoard { attempts: usize, target: bool },
+    /// How many boards `validate` found problems with.
+    BoardProblems(usize),
+    /// How many boards `validate --fix` couldn't fix.
+    Unfixable(usize),
+    Progress { done: usize, total: usize },
+    /// `--stats`' report on one solve.
+    SolveStats(&'a SolveStats),
+    SolvedBy(&'a Provenance),
+    Listening(SocketAddr),
+    /// Words read so far, per second, and the time left if the input's size is known.
+    Indexed { words: usize, rate: u64, eta: Option<Duration> },
+    ResumedShards(usize),
+    IndexVerified(usize),
+    /// `check`'s verdicts on a word.
+    OnBoard { word: &'a str, score: u32 },
+    TooShort { word: &'a str, min: usize },
+    NotInDictionary(&'a str),
+    NotOnBoard(&'a str),
+    /// `duel`'s verdicts beyond `check`'s, and its headings.
+    Disallowed(&'a str),
+    Cancelled(&'a str),
+    PlayerScore { player: usize, score: u32 },
+    Missed,
+    /// `analyze`'s statistics after its heatmap.
+    Longest(&'a str),
+    ByLength { letters: usize, words: usize, score: u32 },
+    DeadCells(&'a [(usize, usize)]),
+    /// `strings`' summary: how many words share no tiles, their score, and
+    /// whether the search proved no other set scores more.
+    WordStrings { words: usize, score: u32, exact: bool },
+    /// How many of the dictionary's words `trim` kept.
+    Trimmed { kept: usize, words: usize },
+    /// `--verify`'s report of a word on a board, and of how many there were.
+    Discrepancy { board: &'a str, discrepancy: &'a Discrepancy<'a> },
+    Disagreements(usize),
+    WaitingForPlayer(SocketAddr),
+    DuelStart { player: usize, seconds: u64 },
+    DailyStart { date: Date, seconds: u64 },
+    /// A line of `daily --archive`: the day, its board with rows separated by "/", and how it went.
+    ArchiveDay { date: Date, board: &'a str, record: Option<Record> },
+    TimeUp,
+    /// A dictionary's word count, whether it's compiled, and what it records about the list.
+    DictInfo { words: usize, compiled: bool, metadata: &'a Metadata },
+    /// `index-diff`'s summary: words added and removed, and the node counts before and after.
+    IndexDiff { added: usize, removed: usize, nodes: (usize, usize) },
+    TuiHelp,
+    Error(&'a Error),
+}
+
+impl<'a> Message<'a> {
+    pub fn localize(&self, locale: Locale) -> String {
+        use self::Locale::*;
+        use self::Message::*;
+        match (locale, self) {
+            (En, Found(n)) => format!("Found {} words in board", n),
+            (Es, Found(n)) => format!("Se encontraron {} palabras en el tablero", n),
+            (En, TotalScore(n)) => format!("Total score: {}", n),
+            (Es, TotalScore(n)) => format!("Puntuación total: {}", n),
+            (En, SkippedTooLong(n)) => format!("Skipped {} dictionary words too long for the board", n),
+            (Es, SkippedTooLong(n)) => format!("Se omitieron {} palabras del diccionario demasiado largas para el tablero", n),
+            (En, SkippedInvalid(n)) => format!("Skipped {} dictionary words with characters other than letters", n),
+            (Es, SkippedInvalid(n)) => format!("Se omitieron {} palabras del diccionario con caracteres que no son letras", n),
+            (En, Normalized(n)) => format!("Took apostrophes, hyphens or accents out of {} dictionary words", n),
+            (Es, Normalized(n)) => format!("Se quitaron apóstrofos, guiones o acentos de {} palabras del diccionario", n),
+            (En, Excluded(n)) => format!("Excluded {} dictionary words", n),
+            (Es, Excluded(n)) => format!("Se excluyeron {} palabras del diccionario", n),
+            (En, SolvedBy(p)) => format!("Solved by {}", p),
+            (Es, SolvedBy(p)) => format!("Resuelto con {}", p),
+            (En, IndexDiff { added, removed, nodes: (before, after) }) => {
+                format!("{} words added, {} removed\nTrie nodes: {} before, {} after", added, removed, before, after)
+            }
+            (Es, IndexDiff { added, removed, nodes: (before, after) }) => {
+                format!("{} palabras añadidas, {} quitadas\nNodos del trie: {} antes, {} después", added, removed, before, after)
+            }
+            (En, TuiHelp) => "Arrows move, letters change a tile, Tab rolls a new board, Esc quits".to_string(),
+            (Es, TuiHelp) => "Las flechas mueven, las letras cambian una casilla, Tab tira otro tablero, Esc sale".to_string(),
+            (En, Seed(seed)) => format!("Seed {}; roll the board again with --seed {}", seed, seed),
+            (Es, Seed(seed)) => format!("Semilla {}; se vuelve a tirar con --seed {}", seed, seed),
+            (En, Generated { words, attempts }) => format!("{} words, found in {} tries", words, attempts),
+            (Es, Generated { words, attempts }) => format!("{} palabras, encontrado en {} intentos", words, attempts),
+            (En, NoBoard(attempts)) => format!("None of {} boards had that many words", attempts),
+            (Es, NoBoard(attempts)) => format!("Ninguno de {} tableros tenía ese número de palabras", attempts),
+            (En, NoCleanBoard { attempts, target: true }) => format!("None of {} boards had that many words and no banned ones", attempts),
+            (Es, NoCleanBoard { attempts, target: true }) => format!("Ninguno de {} tableros tenía ese número de palabras y ninguna prohibida", attempts),
+            (En, NoCleanBoard { attempts, target: false }) => format!("None of {} boards was free of banned words", attempts),
+            (Es, NoCleanBoard { attempts, target: false }) => format!("Ninguno de {} tableros estaba libre de palabras prohibidas", attempts),
+            (En, BoardProblems(n)) => format!("{} boards have problems", n),
+            (Es, BoardProblems(n)) => format!("{} tableros tienen problemas", n),
+            (En, Unfixable(n)) => format!("{} boards couldn't be fixed", n),
+            (Es, Unfixable(n)) => format!("{} tableros no se pudieron arreglar", n),
+            (En, Progress { done, total }) => format!("{}/{} boards", done, total),
+            (Es, Progress { done, total }) => format!("{}/{} tableros", done, total),
+            (En, Trimmed { kept, words }) => format!("Kept {} of {} dictionary words", kept, words),
+            (Es, Trimmed { kept, words }) => format!("Se conservaron {} de {} palabras del diccionario", kept, words),
+            (En, SolveStats(stats)) => format!(
+                "Loaded {} words into {} trie nodes, pruning {} the board can't spell\nSearched {} cells\nTook {:.3}ms loading, {:.3}ms searching and {:.3}ms finishing",
+                stats.load.loaded, stats.trie_nodes, stats.pruned, stats.expanded,
+                millis(stats.load_time), millis(stats.search_time), millis(stats.finish_time)
+            ),
+            (Es, SolveStats(stats)) => format!(
+                "Se cargaron {} palabras en {} nodos del trie, descartando {} que el tablero no puede formar\nSe recorrieron {} casillas\nSe tardó {:.3}ms en cargar, {:.3}ms en buscar y {:.3}ms en terminar",
+                stats.load.loaded, stats.trie_nodes, stats.pruned, stats.expanded,
+                millis(stats.load_time), millis(stats.search_time), millis(stats.finish_time)
+            ),
+            (En, Listening(addr)) => format!("Listening on {}", addr),
+            (Es, Listening(addr)) => format!("Escuchando en {}", addr),
+            (En, Indexed { words, rate, eta: Some(eta) }) => {
+                format!("Indexed {} words, {} words/s, about {}s left", words, rate, eta.as_secs())
+            }
+            (En, Indexed { words, rate, eta: None }) => format!("Indexed {} words, {} words/s", words, rate),
+            (Es, Indexed { words, rate, eta: Some(eta) }) => {
+                format!("Indexadas {} palabras, {} palabras/s, quedan unos {}s", words, rate, eta.as_secs())
+            }
+            (Es, Indexed { words, rate, eta: None }) => format!("Indexadas {} palabras, {} palabras/s", words, rate),
+            (En, ResumedShards(n)) => format!("Reused {} shards from an interrupted build", n),
+            (Es, ResumedShards(n)) => format!("Se reutilizaron {} fragmentos de una compilación interrumpida", n),
+            (En, IndexVerified(n)) => format!("Checked the index against the word list on {} boards", n),
+            (Es, IndexVerified(n)) => format!("Se comprobó el índice con la lista de palabras en {} tableros", n),
+            (En, OnBoard { word, score }) => format!("{}: on the board, score {}", word, score),
+            (Es, OnBoard { word, score }) => format!("{}: está en el tablero, puntuación {}", word, score),
+            (En, TooShort { word, min }) => format!("{}: shorter than {} letters", word, min),
+            (Es, TooShort { word, min }) => format!("{}: tiene menos de {} letras", word, min),
+            (En, NotInDictionary(word)) => format!("{}: not in the dictionary", word),
+            (Es, NotInDictionary(word)) => format!("{}: no está en el diccionario", word),
+            (En, NotOnBoard(word)) => format!("{}: in the dictionary but not on the board", word),
+            (Es, NotOnBoard(word)) => format!("{}: está en el diccionario pero no en el tablero", word),
+            (En, Disallowed(word)) => format!("{}: not allowed by the rules", word),
+            (Es, Disallowed(word)) => format!("{}: no lo permiten las reglas", word),
+            (En, Cancelled(word)) => format!("{}: found by both players, crossed off", word),
+            (Es, Cancelled(word)) => format!("{}: la encontraron los dos jugadores, tachada", word),
+            (En, PlayerScore { player, score }) => format!("Player {}: score {}", player, score),
+            (Es, PlayerScore { player, score }) => format!("Jugador {}: puntuación {}", player, score),
+            (En, Missed) => "Best words neither player found:".to_string(),
+            (Es, Missed) => "Mejores palabras que no encontró ningún jugador:".to_string(),
+            (En, Longest(word)) => format!("Longest word: {}", word),
+            (Es, Longest(word)) => format!("Palabra más larga: {}", word),
+            (En, ByLength { letters, words, score }) => format!("{} letters: {} words, score {}", letters, words, score),
+            (Es, ByLength { letters, words, score }) => format!("{} letras: {} palabras, puntuación {}", letters, words, score),
+            (En, WordStrings { words, score, exact }) => format!("{} words sharing no tiles, score {}{}", words, score, if *exact { "" } else { " (maybe not the best)" }),
+            (Es, WordStrings { words, score, exact }) => format!("{} palabras sin casillas en común, puntuación {}{}", words, score, if *exact { "" } else { " (quizá no la mejor)" }),
+            (En, DeadCells([])) => "No dead cells".to_string(),
+            (Es, DeadCells([])) => "Ninguna casilla sin usar".to_string(),
+            (En, DeadCells(cells)) => format!("Dead cells, which no word uses: {}", cells_list(cells)),
+            (Es, DeadCells(cells)) => format!("Casillas que no usa ninguna palabra: {}", cells_list(cells)),
+            (En, Discrepancy { board, discrepancy }) => format!("{}: {}", board, discrepancy),
+            (Es, Discrepancy { board, discrepancy }) => match discrepancy {
+                boggle::verify::Discrepancy::Missed(word) => format!("{}: {} está en el tablero pero no se encontró", board, word),
+                boggle::verify::Discrepancy::Spurious(word) => format!("{}: {} se encontró pero no está en el tablero", board, word),
+                boggle::verify::Discrepancy::BadPath(solution) => {
+                    format!("{}: {} se encontró en {:?}, que no la forma", board, solution.word, solution.path)
+                }
+                boggle::verify::Discrepancy::Duplicate(word) => format!("{}: {} se encontró más de una vez", board, word),
+            },
+            (En, Disagreements(n)) => format!("the solver and the slow search disagree on {} words", n),
+            (Es, Disagreements(n)) => format!("el solucionador y la búsqueda lenta no coinciden en {} palabras", n),
+            (En, WaitingForPlayer(addr)) => format!("Waiting for the other player on {}", addr),
+            (Es, WaitingForPlayer(addr)) => format!("Esperando al otro jugador en {}", addr),
+            (En, DuelStart { player, seconds }) => {
+                format!("You're player {}: type words, one per line, for {} seconds", player, seconds)
+            }
+            (Es, DuelStart { player, seconds }) => {
+                format!("Eres el jugador {}: escribe palabras, una por línea, durante {} segundos", player, seconds)
+            }
+            (En, DailyStart { date, seconds }) => {
+                format!("The board for {}: type words, one per line, for {} seconds", date, seconds)
+            }
+            (Es, DailyStart { date, seconds }) => {
+                format!("El tablero del {}: escribe palabras, una por línea, durante {} segundos", date, seconds)
+            }
+            (En, ArchiveDay { date, board, record: Some(record) }) => {
+                format!("{} {} {}/{} points, {}/{} words", date, board, record.score, record.total, record.found, record.words)
+            }
+            (Es, ArchiveDay { date, board, record: Some(record) }) => {
+                format!("{} {} {}/{} puntos, {}/{} palabras", date, board, record.score, record.total, record.found, record.words)
+            }
+            (En, ArchiveDay { date, board, record: None }) => format!("{} {} not played", date, board),
+            (Es, ArchiveDay { date, board, record: None }) => format!("{} {} sin jugar", date, board),
+            (En, TimeUp) => "Time's up".to_string(),
+            (Es, TimeUp) => "Se acabó el tiempo".to_string(),
+            (En, DictInfo { words, compiled, metadata }) => {
+                let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "not recorded".to_string());
+                format!("{} words, {}\nLicense: {}\nAttribution: {}\nSource: {}",
+                        words, if *compiled { "compiled" } else { "word list" },
+                        field(&metadata.license), field(&metadata.attribution), field(&metadata.source))
+            }
+            (Es, DictInfo { words, compiled, metadata }) => {
+                let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "sin datos".to_string());
+                format!("{} palabras, {}\nLicencia: {}\nAtribución: {}\nOrigen: {}",
+                        words, if *compiled { "compilado" } else { "lista de palabras" },
+                        field(&metadata.license), field(&metadata.attribution), field(&metadata.source))
+            }
+            (En, Error(boggle::Error::Usage)) => USAGE_EN.to_string(),
+            (En, Error(err)) => err.to_string(),
+            (Es, Error(err)) => match err {
+                boggle::Error::Usage => USAGE_ES.to_string(),
+                boggle::Error::Io(err) => format!("error de E/S: {}", err),
+                boggle::Error::EmptyBoard => "el tablero está vacío".to_string(),
+                boggle::Error::BoardRowLength { row, expected, got } => {
+                    format!("la fila {} tiene {} casillas, se esperaban {} para coincidir con el número de filas", row, got, expected)
+                }
+                boggle::Error::InvalidCharacter { row, col, ch } => {
+                    format!("carácter no válido {:?} en la fila {}, columna {}", ch, row, col)
+                }
+                boggle::Error::InvalidTile { row, col, tile } => {
+                    format!("ficha desconocida {:?} en la fila {}, columna {}", tile, row, col)
+                }
+                boggle::Error::CubeLayer { layer, expected, got } => {
+                    format!("la capa {} tiene {} casillas de ancho, se esperaban {} para coincidir con el número de capas", layer, got, expected)
+                }
+                boggle::Error::InvalidJson { offset } => format!("JSON no válido en el byte {}", offset),
+                boggle::Error::NotABoard => {
+                    "el JSON no es un tablero: se esperaba una lista de filas, cada una un texto o una lista de fichas".to_string()
+                }
+                boggle::Error::CorruptDictionary => "diccionario compilado dañado".to_string(),
+                boggle::Error::CorruptGzip => "archivo gzip dañado".to_string(),
+                boggle::Error::InvalidCount { line } => {
+                    format!("la línea {} de la lista de palabras no tiene palabra, o no tiene número tras el tabulador", line)
+                }
+                boggle::Error::CorruptAnswers => "respuestas dañadas, o de otro tablero o diccionario".to_string(),
+                boggle::Error::SelfTest(err) => format!("la autoprueba falló: {}", err),
+            },
+        }
+    }
+}
+
+// `(row, col)` cells as `row,col` separated by spaces
+fn cells_list(cells: &[(usize, usize)]) -> String {
+    cells.iter().map(|(row, col)| format!("{},{}", row, col)).collect::<Vec<_>>().join(" ")
+}
+
+fn millis(time: Duration) -> f64 {
+    time.as_secs_f64() * 1000.0
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Locale::parse("es_ES.UTF-8"), Some(Locale::Es));
+    assert_eq!(Locale::parse("es"), Some(Locale::Es));
+    assert_eq!(Locale::parse("en_GB"), Some(Locale::En));
+    assert_eq!(Locale::parse("C.UTF-8"), Some(Locale::En));
+    assert_eq!(Locale::parse("fr_FR"), None);
+    assert_eq!(Message::Found(3).localize(Locale::Es), "Se encontraron 3 palabras en el tablero");
+}
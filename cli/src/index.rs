@@ -0,0 +1,128 @@
+//! `boggle build-index`: `compile-dict` for word lists big enough that it's
+//! worth watching. The list is read a shard at a time and each shard cleaned
+//! on its own thread, then saved beside the output, so an interrupted build
+//! picks up from the last shard finished. The compiled index is checked by
+//! solving a few boards with it and with the word list before it's kept.
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use boggle::compiled::{self, CompiledDictionary, Metadata};
+use boggle::{dice, Board, Dictionary, Error, SolveOptions};
+
+use crate::i18n::{Locale, Message};
+
+// lines per shard, a few megabytes of a typical list
+const SHARD: usize = 100_000;
+
+// the boards the index is checked on, rolled from these seeds
+const SAMPLE_SEEDS: [u64; 3] = [1, 2, 3];
+
+// where a shard is saved once it's cleaned; until then it's written beside it as `.tmp`
+fn shard_path(dir: &Path, shard: usize) -> PathBuf {
+    dir.join(format!("{:06}", shard))
+}
+
+// reads up to `SHARD` lines, returning them and how many bytes they took
+fn read_shard<R: BufRead>(input: &mut R) -> Result<(String, u64), Error> {
+    let mut text = String::new();
+    let mut bytes = 0;
+    for _ in 0..SHARD {
+        let read = input.read_line(&mut text)?;
+        if read == 0 {
+            break;
+        }
+        bytes += read as u64;
+    }
+    Ok((text, bytes))
+}
+
+/// Builds the index of `words` at `out`, recording `metadata` in it and
+/// reporting progress on stderr.
+pub fn build(words: &str, out: &str, metadata: &Metadata, locale: Locale) -> Result<(), Error> {
+    let (input, total): (Box<dyn Read>, Option<u64>) = if words == "-" {
+        (Box::new(io::stdin()), None)
+    } else {
+        let file = File::open(words)?;
+        let len = file.metadata()?.len();
+        (Box::new(file), Some(len))
+    };
+    let mut input = BufReader::new(input);
+    let dir = PathBuf::from(format!("{}.part", out));
+    fs::create_dir_all(&dir)?;
+
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let start = Instant::now();
+    let (mut shards, mut resumed, mut lines, mut read) = (0, 0, 0, 0);
+    loop {
+        let mut batch = Vec::new();
+        for _ in 0..threads {
+            let (text, bytes) = read_shard(&mut input)?;
+            if bytes == 0 {
+                break;
+            }
+            read += bytes;
+            lines += text.lines().count();
+            batch.push((shards, text));
+            shards += 1;
+        }
+        if batch.is_empty() {
+            break;
+        }
+
+        let (done, todo): (Vec<_>, Vec<_>) = batch.into_iter().partition(|(shard, _)| shard_path(&dir, *shard).exists());
+        resumed += done.len();
+        thread::scope(|scope| {
+            let handles: Vec<_> = todo.iter().map(|(shard, text)| {
+                let path = shard_path(&dir, *shard);
+                scope.spawn(move || -> io::Result<()> {
+                    let tmp = path.with_extension("tmp");
+                    fs::write(&tmp, Dictionary::parse(text).as_str())?;
+                    fs::rename(&tmp, &path)
+                })
+            }).collect();
+            handles.into_iter().try_for_each(|handle| handle.join().expect("shard thread panicked"))
+        })?;
+
+        let elapsed = start.elapsed().as_secs_f64().max(1e-3);
+        let eta = total.map(|total| Duration::from_secs_f64(total.saturating_sub(read) as f64 * elapsed / read as f64));
+        eprintln!("{}", Message::Indexed { words: lines, rate: (lines as f64 / elapsed) as u64, eta }.localize(locale));
+    }
+    if resumed > 0 {
+        eprintln!("{}", Message::ResumedShards(resumed).localize(locale));
+    }
+
+    let mut text = String::new();
+    for shard in 0..shards {
+        text.push_str(&fs::read_to_string(shard_path(&dir, shard))?);
+    }
+    let bytes = compiled::compile_with_metadata(&text, metadata);
+    verify(&text, &CompiledDictionary::from_bytes(&bytes)?)?;
+    eprintln!("{}", Message::IndexVerified(SAMPLE_SEEDS.len()).localize(locale));
+
+    let tmp = format!("{}.tmp", out);
+    fs::write(&tmp, &bytes)?;
+    fs::rename(&tmp, out)?;
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+// solves the sample boards with the index and with the words it was compiled from
+fn verify(words: &str, index: &CompiledDictionary) -> Result<(), Error> {
+    let options = SolveOptions::default();
+    for &seed in SAMPLE_SEEDS.iter() {
+        let raw = dice::roll(&dice::CLASSIC, seed);
+        let board = Board::parse(&raw)?;
+        let expected: BTreeSet<_> = board.solve(words, &options).into_iter().map(|s| s.word).collect();
+        let found: BTreeSet<_> = board.solve_compiled(index, &options).into_iter().map(|s| s.word).collect();
+        if found != expected {
+            let message = format!("the index found {} words on sample board {}, the word list {}", found.len(), seed, expected.len());
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message).into());
+        }
+    }
+    Ok(())
+}
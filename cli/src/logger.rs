@@ -0,0 +1,34 @@
+//! The library's debug logs on stderr, for seeing what a long run is doing.
+//! `BOGGLE_LOG` picks the most detailed level shown: `error`, `warn`,
+//! `info`, `debug` or `trace`. Unset, nothing is logged.
+
+use std::env;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct Stderr;
+
+impl Log for Stderr {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level().as_str().to_lowercase(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Logs to stderr at the level `BOGGLE_LOG` names, if any.
+pub fn init() {
+    let level = match env::var("BOGGLE_LOG").ok().and_then(|level| level.parse().ok()) {
+        Some(level) if level != LevelFilter::Off => level,
+        _ => return,
+    };
+    if log::set_logger(&Stderr).is_ok() {
+        log::set_max_level(level);
+    }
+}
@@ -0,0 +1,1003 @@
+mod bench;
+mod cli;
+mod daily;
+mod i18n;
+mod index;
+mod logger;
+mod peer;
+mod selftest;
+mod tui;
+
+use std::fs::{self, File};
+use std::borrow::Cow;
+use std::io::{self, IsTerminal, Read, Write};
+use std::str;
+use std::sync::Arc;
+
+use boggle::{board, dice, rng, Board, Dictionary, Error, LoadStats, Scorer, Solution, SolveOptions, Solutions, Topology, Trie};
+use boggle::alphabet::Alphabet;
+use boggle::annotate::{Annotations, Definitions, Glossary, WordAnnotator};
+use boggle::compiled::{self, CompiledDictionary, Metadata};
+use boggle::dictionary;
+use boggle::duel::{self, Duel, Game, Player, PlayerSubmission, Rejection};
+use boggle::federation::Federation;
+use boggle::findable::Plausible;
+use boggle::generate::{Letters, Target};
+use boggle::optimize::{self, Annealing};
+use boggle::provenance::Provenance;
+use boggle::rng::SplitMix64;
+use boggle::rules::CommonWords;
+use boggle::submission::{self, Checks, Report};
+use boggle::score::{Classic, Rarity};
+use boggle::strategy;
+use boggle::storage::{FileStorage, MemoryStorage, Storage};
+use boggle::strings;
+use boggle::trace::{Trace, TraceNode};
+use boggle::verify;
+use boggle_server::{Server, ServerConfig};
+use serde::{Serialize, Serializer};
+
+use cli::{Color, Command, Output, Query, Solve, Sort};
+use daily::Date;
+use i18n::{Locale, Message};
+
+// so `bench` can count allocations; one relaxed increment each costs the other commands nothing noticeable
+#[global_allocator]
+static ALLOCATOR: boggle::alloc_audit::CountingAllocator = boggle::alloc_audit::CountingAllocator;
+
+// `-` reads stdin, e.g. `boggle generate | boggle dictionary -`
+fn read(path: &str) -> Result<String, Error> {
+    String::from_utf8(read_bytes(path)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into())
+}
+
+// a dictionary file as a word list or compiled dictionary, decompressed and without any counts
+fn read_dictionary(path: &str) -> Result<Vec<u8>, Error> {
+    Ok(dictionary::decode(read_bytes(path)?)?.bytes)
+}
+
+// a dictionary file's words, one per line
+fn read_words(path: &str) -> Result<String, Error> {
+    Ok(dictionary::decode(read_bytes(path)?)?.words()?.into_owned())
+}
+
+// a ranking for `--rarity`: a list with counts's words most used first, or any other list in order
+fn read_ranked(path: &str) -> Result<String, Error> {
+    let file = dictionary::decode(read_bytes(path)?)?;
+    match file.ranked {
+        Some(ranked) => Ok(ranked),
+        None => Ok(file.words()?.into_owned()),
+    }
+}
+
+// a board in any of the formats `parse_any` tells apart
+fn read_board(path: &str) -> Result<Board, Error> {
+    board::parse_any(&read(path)?, None, &Alphabet::english())
+}
+
+fn read_bytes(path: &str) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    if path == "-" {
+        io::stdin().read_to_end(&mut buf)?;
+    } else {
+        File::open(path)?.read_to_end(&mut buf)?;
+    }
+    Ok(buf)
+}
+
+// the files in `dir`, in name order
+fn files(dir: &str) -> Result<Vec<String>, Error> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path.to_string_lossy().into_owned());
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// a dictionary as a compiled one, compiling a word list in memory, less the words in `blocklist`
+fn compiled_bytes(raw: Vec<u8>, blocklist: &str, alphabet: &Alphabet, normalize: bool) -> Result<Vec<u8>, Error> {
+    if compiled::is_compiled(&raw) && blocklist.is_empty() {
+        return Ok(raw);
+    }
+    let mut dictionary = parse_dictionary(&raw, alphabet, normalize)?;
+    dictionary.exclude(blocklist);
+    Ok(compiled::compile_in(dictionary.as_str(), alphabet))
+}
+
+// a word list or compiled dictionary's words as a `Dictionary`, with `alphabet`'s extra letters
+fn parse_dictionary(raw: &[u8], alphabet: &Alphabet, normalize: bool) -> Result<Dictionary, Error> {
+    if compiled::is_compiled(raw) {
+        return Ok(Dictionary::parse_in(CompiledDictionary::from_bytes(raw)?.as_str(), alphabet));
+    }
+    let raw = str::from_utf8(raw).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(if normalize { Dictionary::parse_normalized(raw, alphabet) } else { Dictionary::parse_in(raw, alphabet) })
+}
+
+// where the words come from; several boards share one trie rather than each building its own
+enum Words<'a> {
+    Compiled(CompiledDictionary<'a>),
+    Federated(Federation<'a, 'a>),
+    Shared(Trie<'a>),
+    Text(&'a str),
+}
+
+fn solve(args: &Solve, locale: Locale, score_only: bool) -> Result<(), Error> {
+    let raw_dict = read_dictionary(&args.dictionary)?;
+    let mut paths = args.boards.clone();
+    if let Some(ref dir) = args.boards_dir {
+        paths.extend(files(dir)?);
+    }
+    // boards given with `--board` are labelled with their text
+    let boards: Vec<_> = args.inline.iter().map(|text| (text, true)).chain(paths.iter().map(|path| (path, false))).collect();
+
+    let mut options = if args.low_power { SolveOptions::low_power() } else { SolveOptions::default() };
+    if args.threads.is_some() {
+        options.threads = args.threads;
+    }
+    options.min_len = args.min_length;
+    options.max_len = args.max_length;
+    let ranked = match args.rarity {
+        Some(ref path) => Some(read_ranked(path)?),
+        None => None,
+    };
+    let rarity = ranked.as_ref().map(|ranked| Arc::new(Rarity::new(Classic, ranked)));
+    if let Some(ref rarity) = rarity {
+        options.scorer = Box::new(rarity.clone());
+    }
+    let definitions = match args.define {
+        Some(ref path) => Some(Definitions(Glossary::load(path)?)),
+        None => None,
+    };
+
+    let mut blocklist = String::new();
+    for path in &args.exclude {
+        blocklist.push_str(&read(path)?);
+        blocklist.push('\n');
+    }
+
+    let names: Vec<_> = std::iter::once(&args.dictionary).chain(&args.also).collect();
+    let mut indexes = Vec::new();
+    if !args.also.is_empty() {
+        indexes.push(compiled_bytes(raw_dict.clone(), &blocklist, &args.alphabet, args.normalize)?);
+        for path in &args.also {
+            indexes.push(compiled_bytes(read_dictionary(path)?, &blocklist, &args.alphabet, args.normalize)?);
+        }
+    }
+
+    let mut dictionary;
+    let mut text = String::new();
+    let (words, provenance, searched) = if !indexes.is_empty() {
+        let mut federation = Federation::new();
+        for (name, bytes) in names.iter().zip(&indexes) {
+            let index = CompiledDictionary::from_bytes(bytes)?;
+            text.push_str(index.as_str());
+            federation.add(name, index);
+        }
+        (Words::Federated(federation), Provenance::new(&text), &text[..])
+    } else if compiled::is_compiled(&raw_dict) && args.exclude.is_empty() {
+        let compiled = CompiledDictionary::from_bytes(&raw_dict)?;
+        (Words::Compiled(compiled), Provenance::new(compiled.as_str()), compiled.as_str())
+    } else {
+        dictionary = parse_dictionary(&raw_dict, &args.alphabet, args.normalize)?;
+        if dictionary.normalized() > 0 {
+            eprintln!("{}", Message::Normalized(dictionary.normalized()).localize(locale));
+        }
+        if dictionary.invalid() > 0 {
+            eprintln!("{}", Message::SkippedInvalid(dictionary.invalid()).localize(locale));
+        }
+        if !args.exclude.is_empty() {
+            dictionary.exclude(&blocklist);
+            eprintln!("{}", Message::Excluded(dictionary.excluded()).localize(locale));
+        }
+        // the words actually searched, once any are excluded
+        let provenance = Provenance::new(dictionary.as_str());
+        // `--stats` measures building each board's own trie, so one isn't shared
+        let words = if boards.len() > 1 && !args.stats {
+            Words::Shared(Trie::from_dictionary_in(dictionary.as_str(), &args.alphabet))
+        } else {
+            Words::Text(dictionary.as_str())
+        };
+        (words, provenance, dictionary.as_str())
+    };
+
+    let federation = match words {
+        Words::Federated(ref federation) => Some(federation),
+        _ => None,
+    };
+    // the words searched as a `Dictionary` for `--strategy`, decoded first so parsing keeps the extra letters
+    let chosen = args.strategy.map(|name| {
+        (strategy::find(name).expect("checked when parsed"), Dictionary::parse_in(&args.alphabet.decode(searched), &args.alphabet))
+    });
+    let mut disagreements = 0;
+    let mut annotators: Vec<&dyn WordAnnotator> = Vec::new();
+    if let Some(ref rarity) = rarity {
+        annotators.push(rarity);
+    }
+    if let Some(ref definitions) = definitions {
+        annotators.push(definitions);
+    }
+    let annotator: &dyn WordAnnotator = &annotators;
+    // with a frequency list, rare words are too obscure to count as findable
+    let findable = Plausible {
+        common: rarity.as_ref().zip(ranked.as_ref()).map(|(rarity, ranked)| CommonWords::new(ranked, rarity.uncommon)),
+        ..Plausible::default()
+    };
+    let provenance = provenance.with_rules(rules(args));
+    let output = Printer { args, locale, score_only, annotator, federation, provenance, several: boards.len() > 1 };
+    for (done, &(path, inline)) in boards.iter().enumerate() {
+        if args.progress {
+            progress(done, boards.len(), locale);
+        }
+        let raw_board = if inline { path.clone() } else { read(path)? };
+        let board = board::parse_any(&raw_board, args.format, &args.alphabet)?;
+        // the flags change a JSON board's own topology rather than replacing it
+        let topology = Topology { diagonals: board.topology().diagonals && args.topology.diagonals, wrap: board.topology().wrap || args.topology.wrap };
+        let board = board.with_topology(topology);
+        let (solutions, stats) = match (&chosen, &words) {
+            (Some((strategy, dictionary)), _) => (strategy.solve(&board, dictionary).0, LoadStats::default()),
+            (None, Words::Compiled(compiled)) => (board.solve_compiled(compiled, &options), LoadStats::default()),
+            (None, Words::Federated(federation)) => (board.solve_federated(federation, &options), LoadStats::default()),
+            (None, Words::Shared(trie)) => (board.solve_with_trie(trie, &options), LoadStats::default()),
+            (None, Words::Text(words)) if args.stats => {
+                let (solutions, stats) = board.solve_measured(words, &options);
+                eprintln!("{}", Message::SolveStats(&stats).localize(locale));
+                (solutions, stats.load)
+            }
+            (None, Words::Text(words)) => board.solve_with_stats(words, &options),
+        };
+        // `--strategy`'s own words are checked; otherwise the trie solver stands in for the rest
+        let discrepancies = match chosen {
+            Some(_) if args.verify => verify::check(&board, searched, &solutions),
+            None if args.verify => verify::verify(&board, searched),
+            _ => Vec::new(),
+        };
+        if stats.too_long > 0 {
+            eprintln!("{}", Message::SkippedTooLong(stats.too_long).localize(locale));
+        }
+        let mut solutions = Solutions::from(solutions);
+        if args.findable {
+            solutions = solutions.findable(&board, &findable);
+        }
+        if let (Some(max), Some(rarity)) = (args.max_obscurity, &rarity) {
+            solutions = solutions.no_rarer_than(rarity, max);
+        }
+        output.print(path, &board, solutions);
+        for discrepancy in &discrepancies {
+            eprintln!("{}", Message::Discrepancy { board: path, discrepancy }.localize(locale));
+            disagreements += 1;
+        }
+    }
+    if args.progress {
+        progress(boards.len(), boards.len(), locale);
+    }
+    if disagreements > 0 {
+        return Err(Error::SelfTest(Message::Disagreements(disagreements).localize(locale)));
+    }
+    Ok(())
+}
+
+// the flags that change which words are reported or how they score, for `Provenance`
+fn rules(args: &Solve) -> String {
+    let mut rules = Vec::new();
+    if let Some(n) = args.min_length {
+        rules.push(format!("min-length {}", n));
+    }
+    if let Some(n) = args.max_length {
+        rules.push(format!("max-length {}", n));
+    }
+    if let Some(ref path) = args.rarity {
+        rules.push(format!("rarity {}", path));
+    }
+    if let Some(tier) = args.max_obscurity {
+        rules.push(format!("max-obscurity {:?}", tier).to_lowercase());
+    }
+    if args.findable {
+        rules.push("findable".to_string());
+    }
+    if !args.topology.diagonals {
+        rules.push("no-diagonals".to_string());
+    }
+    if args.topology.wrap {
+        rules.push("wrap".to_string());
+    }
+    rules.join(", ")
+}
+
+// redraws a bar of `done` out of `total` boards in place on stderr, ending the line once they're all done
+fn progress(done: usize, total: usize, locale: Locale) {
+    const WIDTH: usize = 30;
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(WIDTH);
+    eprint!("\r[{}{}] {}", "#".repeat(filled), "-".repeat(WIDTH - filled), Message::Progress { done, total }.localize(locale));
+    if done == total {
+        eprintln!();
+    }
+}
+
+// prints each board's results; with several boards, each is labelled with its path
+struct Printer<'a> {
+    args: &'a Solve,
+    locale: Locale,
+    score_only: bool,
+    annotator: &'a dyn WordAnnotator,
+    // with `--also`, what says which dictionaries each word is in
+    federation: Option<&'a Federation<'a, 'a>>,
+    provenance: Provenance,
+    several: bool,
+}
+
+impl<'a> Printer<'a> {
+    fn print(&self, path: &str, board: &Board, mut solutions: Solutions) {
+        let (args, locale) = (self.args, self.locale);
+        match args.sort {
+            Sort::Alpha => solutions.sort_alphabetically(),
+            Sort::Length => solutions.sort_by_length(),
+            Sort::Score => solutions.sort_by_score(),
+        }
+        let score = solutions.total_score();
+
+        if args.output == Output::Json {
+            let found = solutions.len();
+            let identified = if self.score_only { Vec::new() } else { solutions.identify(board) };
+            let words = identified.iter()
+                .map(|identified| {
+                    let s = &identified.solution;
+                    WordJson {
+                        word: args.alphabet.decode(s.word),
+                        score: s.score,
+                        notes: Notes(self.annotator.annotate(s)),
+                        sources: self.federation.map(|federation| federation.sources(s.word)),
+                        path: if args.paths { Some(&s.path) } else { None },
+                        id: identified.id,
+                    }
+                })
+                .collect();
+            let solved = SolvedJson {
+                board: if self.several { Some(path) } else { None },
+                found,
+                score,
+                words,
+                solved_by: self.provenance.to_string(),
+            };
+            println!("{}", serde_json::to_string(&solved).expect("solutions serialize"));
+        } else if self.score_only {
+            if self.several {
+                println!("{} {}", path, score);
+            } else {
+                println!("{}", score);
+            }
+        } else {
+            if self.several {
+                println!("== {} ==", path);
+            }
+            println!("{}", Message::Found(solutions.len()).localize(locale));
+            println!("{}", Message::TotalScore(score).localize(locale));
+            if args.group {
+                for (letter, group) in solutions.by_first_letter() {
+                    println!("{}:", args.alphabet.decode(&letter.to_string()));
+                    for solution in group {
+                        println!("  {}", self.word_line(solution));
+                    }
+                }
+            } else {
+                for solution in solutions.iter() {
+                    println!("{}", self.word_line(solution));
+                }
+            }
+            println!("{}", Message::SolvedBy(&self.provenance).localize(locale));
+        }
+    }
+
+    fn word_line(&self, solution: &Solution) -> String {
+        let mut line = format!("{} {}", self.args.alphabet.decode(solution.word), solution.score);
+        for (_, value) in self.annotator.annotate(solution) {
+            line.push(' ');
+            line.push_str(&value);
+        }
+        match self.federation {
+            Some(federation) => format!("{} ({})", line, federation.sources(solution.word).join(", ")),
+            None => line,
+        }
+    }
+}
+
+// each player's score and words, then the best words neither found
+fn print_duel(duel: &Duel, min: usize, locale: Locale) {
+    for (i, player) in duel.players.iter().enumerate() {
+        println!("{}", Message::PlayerScore { player: i + 1, score: player.score }.localize(locale));
+        print_verdicts(player, min, locale);
+        println!();
+    }
+    println!("{}", Message::Missed.localize(locale));
+    for solution in duel.missed.iter() {
+        println!("  {} {}", solution.word, solution.score);
+    }
+}
+
+// each of a player's words with its score, or why it doesn't count
+fn print_verdicts(player: &Player, min: usize, locale: Locale) {
+    for (word, score) in &player.scored {
+        println!("  {} {}", word, score);
+    }
+    for word in &player.cancelled {
+        println!("  {}", Message::Cancelled(word).localize(locale));
+    }
+    for (word, rejection) in &player.rejected {
+        let verdict = match rejection {
+            Rejection::TooShort => Message::TooShort { word, min },
+            Rejection::NotInDictionary => Message::NotInDictionary(word),
+            Rejection::NotOnBoard => Message::NotOnBoard(word),
+            Rejection::Disallowed => Message::Disallowed(word),
+        };
+        println!("  {}", verdict.localize(locale));
+    }
+}
+
+// a dictionary's words for looking single words up in, compiled or not
+fn word_list(raw: &[u8]) -> Result<Cow<'_, str>, Error> {
+    if compiled::is_compiled(raw) {
+        return Ok(Cow::Borrowed(CompiledDictionary::from_bytes(raw)?.as_str()));
+    }
+    let text = str::from_utf8(raw).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(Cow::Owned(Dictionary::parse(text).as_str().to_string()))
+}
+
+// where `word` lies on the board, or why it doesn't count
+fn find<'w>(board: &Board, list: &str, word: &'w str, min_length: usize) -> Result<Vec<(usize, usize)>, Message<'w>> {
+    if word.len() < min_length {
+        Err(Message::TooShort { word, min: min_length })
+    } else if !list.lines().any(|w| w == word) {
+        Err(Message::NotInDictionary(word))
+    } else {
+        board.find_word(word).ok_or(Message::NotOnBoard(word))
+    }
+}
+
+// the board's tiles with the cells of `path` picked out, in reverse video with `ansi` and in brackets without
+fn highlight(board: &Board, path: &[(usize, usize)], ansi: bool) -> String {
+    let width = (0..board.len()).flat_map(|x| (0..board.len()).map(move |y| board.tile((x, y)).len())).max().unwrap_or(1);
+    let mut grid = String::new();
+    for x in 0..board.len() {
+        for y in 0..board.len() {
+            let tile = format!("{:<1$}", str::from_utf8(board.tile((x, y))).expect("tiles are ascii").to_uppercase(), width);
+            let on_path = path.contains(&(x, y));
+            grid.push_str(&match (on_path, ansi) {
+                (true, true) => format!("\x1b[1;7m {} \x1b[0m", tile),
+                (true, false) => format!("[{}]", tile),
+                (false, _) => format!(" {} ", tile),
+            });
+        }
+        grid.push('\n');
+    }
+    grid
+}
+
+// the board with each cell of `path` numbered by its place in it, and the rest dotted
+fn path_grid(board: &Board, path: &[(usize, usize)]) -> String {
+    let mut grid = String::new();
+    for x in 0..board.len() {
+        let row: Vec<_> = (0..board.len())
+            .map(|y| path.iter().position(|&cell| cell == (x, y)).map_or(" .".to_string(), |step| format!("{:>2}", step + 1)))
+            .collect();
+        grid.push_str(&row.join(" "));
+        grid.push('\n');
+    }
+    grid
+}
+
+// each cell numbered by the word using it
+fn strings_grid(board: &Board, words: &[Solution]) -> String {
+    let mut grid = String::new();
+    for x in 0..board.len() {
+        let row: Vec<_> = (0..board.len())
+            .map(|y| words.iter().position(|s| s.path.contains(&(x, y))).map_or(" .".to_string(), |i| format!("{:>2}", i + 1)))
+            .collect();
+        grid.push_str(&row.join(" "));
+        grid.push('\n');
+    }
+    grid
+}
+
+// each cell's count, lined up in columns
+fn heatmap(counts: &[Vec<usize>]) -> String {
+    let width = counts.iter().flatten().max().map_or(1, |n| n.to_string().len());
+    let mut grid = String::new();
+    for row in counts {
+        let row: Vec<_> = row.iter().map(|n| format!("{:>1$}", n, width)).collect();
+        grid.push_str(&row.join(" "));
+        grid.push('\n');
+    }
+    grid
+}
+
+// `solve --output json`'s line for a board
+#[derive(Serialize)]
+struct SolvedJson<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    board: Option<&'a str>,
+    found: usize,
+    score: u32,
+    words: Vec<WordJson<'a>>,
+    solved_by: String,
+}
+
+#[derive(Serialize)]
+struct WordJson<'a> {
+    word: Cow<'a, str>,
+    score: u32,
+    #[serde(flatten)]
+    notes: Notes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sources: Option<Vec<&'a str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a Vec<(usize, usize)>>,
+    #[serde(serialize_with = "hex")]
+    id: u64,
+}
+
+// an id as `Identified` writes one
+fn hex<S: Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{:016x}", id))
+}
+
+// a word's annotations as fields of its own, in the order the annotator gave them
+struct Notes(Annotations);
+
+impl Serialize for Notes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+    }
+}
+
+fn trace_json(trace: &Trace) -> String {
+    #[derive(Serialize)]
+    struct TraceJson<'a> {
+        truncated: bool,
+        nodes: &'a [TraceNode<'a>],
+    }
+
+    serde_json::to_string(&TraceJson { truncated: trace.truncated, nodes: &trace.nodes }).expect("traces serialize")
+}
+
+fn report_json(report: &Report, paths: &[String]) -> String {
+    #[derive(Serialize)]
+    struct ReportJson<'a> {
+        accepted: bool,
+        legal_roll: Option<bool>,
+        banned: &'a [&'a str],
+        nearest: Option<Nearest<'a>>,
+    }
+
+    #[derive(Serialize)]
+    struct Nearest<'a> {
+        board: &'a str,
+        distance: usize,
+    }
+
+    let nearest = report.nearest.map(|(i, distance)| Nearest { board: &paths[i], distance });
+    let json = ReportJson { accepted: report.accepted, legal_roll: report.legal_roll, banned: &report.banned, nearest };
+    serde_json::to_string(&json).expect("reports serialize")
+}
+
+fn boggle_main(locale: Locale) -> Result<(), Error> {
+    match cli::parse(std::env::args().skip(1))? {
+        Command::Solve(args) => solve(&args, locale, false),
+        Command::Score(args) => solve(&args, locale, true),
+        Command::Generate { size, seed, letters, vowels, target_words, dictionary, banned } => {
+            let dice = if size == 5 { &dice::BIG[..] } else { &dice::CLASSIC[..] };
+            let seed = seed.unwrap_or_else(|| {
+                let seed = rng::entropy();
+                eprintln!("{}", Message::Seed(seed).localize(locale));
+                seed
+            });
+            let frequencies = Letters { vowels: vowels.unwrap_or(0..=usize::MAX), ..Letters::default() };
+            let draw = |rng: &mut SplitMix64| if letters {
+                frequencies.draw(size, rng)
+            } else {
+                dice::roll_with(dice, rng)
+            };
+            let mut rng = SplitMix64::new(seed);
+            if target_words.is_none() && banned.is_none() {
+                print!("{}", draw(&mut rng));
+                return Ok(());
+            }
+            // without a target any number of words will do, so long as none is banned
+            let dictionary = match dictionary {
+                Some(ref path) => Dictionary::parse(&read_words(path)?),
+                None => Dictionary::parse(""),
+            };
+            let banned_words = match banned {
+                Some(ref path) => Dictionary::parse(&read_words(path)?.to_lowercase()),
+                None => Dictionary::parse(""),
+            };
+            let (trie, banned_trie) = (Trie::from_dictionary(dictionary.as_str()), Trie::from_dictionary(banned_words.as_str()));
+            let target = Target::new(target_words.clone().unwrap_or(0..=usize::MAX));
+            match target.generate_avoiding(&trie, &banned_trie, &mut rng, draw) {
+                Some(generated) => {
+                    print!("{}", generated.board);
+                    if target_words.is_some() {
+                        eprintln!("{}", Message::Generated { words: generated.words, attempts: generated.attempts }.localize(locale));
+                    }
+                }
+                None => {
+                    let message = match banned {
+                        Some(_) => Message::NoCleanBoard { attempts: target.attempts, target: target_words.is_some() },
+                        None => Message::NoBoard(target.attempts),
+                    };
+                    eprintln!("{}", message.localize(locale));
+                    std::process::exit(1);
+                }
+            }
+            Ok(())
+        }
+        Command::CompileDict { words, out, metadata } => {
+            let dictionary = Dictionary::parse(&read_words(&words)?);
+            fs::write(out, compiled::compile_with_metadata(dictionary.as_str(), &metadata))?;
+            Ok(())
+        }
+        Command::BuildIndex { words, out, metadata } => index::build(&words, &out, &metadata, locale),
+        Command::DictInfo { dictionary, output } => {
+            let raw = read_dictionary(&dictionary)?;
+            let compiled = compiled::is_compiled(&raw);
+            let (words, metadata) = if compiled {
+                let dictionary = CompiledDictionary::from_bytes(&raw)?;
+                (dictionary.words().count(), dictionary.metadata())
+            } else {
+                let raw = str::from_utf8(&raw).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                (Dictionary::parse(raw).as_str().lines().count(), Metadata::default())
+            };
+            match output {
+                Output::Text => println!("{}", Message::DictInfo { words, compiled, metadata: &metadata }.localize(locale)),
+                Output::Json => {
+                    #[derive(Serialize)]
+                    struct DictInfoJson<'a> {
+                        words: usize,
+                        compiled: bool,
+                        #[serde(flatten)]
+                        metadata: &'a Metadata,
+                    }
+
+                    println!("{}", serde_json::to_string(&DictInfoJson { words, compiled, metadata: &metadata }).expect("metadata serializes"));
+                }
+            }
+            Ok(())
+        }
+        Command::IndexDiff { old, new } => {
+            let english = Alphabet::english();
+            let (old, new) = (compiled_bytes(read_dictionary(&old)?, "", &english, false)?, compiled_bytes(read_dictionary(&new)?, "", &english, false)?);
+            let (old, new) = (CompiledDictionary::from_bytes(&old)?, CompiledDictionary::from_bytes(&new)?);
+            let diff = compiled::diff(&old, &new);
+            let mut lines: Vec<_> = diff.removed.iter().map(|w| (w, '-')).chain(diff.added.iter().map(|w| (w, '+'))).collect();
+            lines.sort();
+            for (word, sign) in lines {
+                println!("{}{}", sign, word);
+            }
+            let nodes = (old.node_count(), new.node_count());
+            println!("{}", Message::IndexDiff { added: diff.added.len(), removed: diff.removed.len(), nodes }.localize(locale));
+            Ok(())
+        }
+        Command::Optimize { dictionary, size, steps, seed } => {
+            let raw = read_words(&dictionary)?;
+            let dictionary = Dictionary::parse(&raw);
+            let trie = Trie::from_dictionary(dictionary.as_str());
+            let dice = if size == 5 { &dice::BIG[..] } else { &dice::CLASSIC[..] };
+            let annealing = Annealing { steps, ..Annealing::default() };
+            let best = optimize::optimize(dice, &trie, &annealing, &mut SplitMix64::new(seed.unwrap_or_else(rng::entropy)));
+            print!("{}", best.board);
+            eprintln!("{}", Message::TotalScore(best.score).localize(locale));
+            Ok(())
+        }
+        Command::Similar { board, dir, top } => {
+            let board = read_board(&board)?;
+            let mut nearest = Vec::new();
+            for path in files(&dir)? {
+                let distance = board.distance(&read_board(&path)?);
+                nearest.push((distance, path));
+            }
+            nearest.sort();
+            for (distance, path) in nearest.into_iter().take(top) {
+                println!("{} {}", distance, path);
+            }
+            Ok(())
+        }
+        Command::Check { dictionary, board, words, min_length } => {
+            let raw = read_dictionary(&dictionary)?;
+            let list = word_list(&raw)?;
+            let board = read_board(&board)?;
+            for (i, word) in words.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let word = word.trim().to_ascii_lowercase();
+                match find(&board, &list, &word, min_length) {
+                    Ok(path) => {
+                        let score = Classic.score(word.as_bytes(), &path, &board);
+                        println!("{}", Message::OnBoard { word: &word, score }.localize(locale));
+                        print!("{}", path_grid(&board, &path));
+                    }
+                    Err(verdict) => println!("{}", verdict.localize(locale)),
+                }
+            }
+            Ok(())
+        }
+        Command::Show { dictionary, board, word, color } => {
+            let raw = read_dictionary(&dictionary)?;
+            let list = word_list(&raw)?;
+            let board = read_board(&board)?;
+            let word = word.trim().to_ascii_lowercase();
+            match find(&board, &list, &word, 3) {
+                Ok(path) => {
+                    let ansi = match color {
+                        Color::Always => true,
+                        Color::Never => false,
+                        Color::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+                    };
+                    print!("{}", highlight(&board, &path, ansi));
+                    println!();
+                    print!("{}", path_grid(&board, &path));
+                }
+                Err(verdict) => println!("{}", verdict.localize(locale)),
+            }
+            Ok(())
+        }
+        Command::Analyze { dictionary, board, min_length } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let options = SolveOptions { min_len: min_length, ..SolveOptions::default() };
+            let analysis = read_board(&board)?.analyze(dictionary.as_str(), &options);
+            print!("{}", heatmap(&analysis.through));
+            println!("{}", Message::Found(analysis.found).localize(locale));
+            println!("{}", Message::TotalScore(analysis.score).localize(locale));
+            if let Some(ref longest) = analysis.longest {
+                println!("{}", Message::Longest(longest.word).localize(locale));
+            }
+            for (letters, &(words, score)) in analysis.by_length.iter().enumerate().filter(|(_, &(words, _))| words > 0) {
+                println!("{}", Message::ByLength { letters, words, score }.localize(locale));
+            }
+            println!("{}", Message::DeadCells(&analysis.dead).localize(locale));
+            Ok(())
+        }
+        Command::Strings { dictionary, board, min_length } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let board = read_board(&board)?;
+            let options = SolveOptions { min_len: min_length, ..SolveOptions::default() };
+            let mut solutions = Solutions(board.solve(dictionary.as_str(), &options));
+            solutions.sort_by_length();
+            if let Some(longest) = solutions.first() {
+                println!("{}", Message::Longest(longest.word).localize(locale));
+            }
+            let strings = strings::word_strings(&board, &solutions);
+            println!("{}", Message::WordStrings { words: strings.words.len(), score: strings.score, exact: strings.exact }.localize(locale));
+            print!("{}", strings_grid(&board, &strings.words));
+            for (i, solution) in strings.words.iter().enumerate() {
+                println!("{:>2} {} {}", i + 1, solution.word, solution.score);
+            }
+            Ok(())
+        }
+        Command::Trim { dictionary, board, out, pruned, min_length } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let board = read_board(&board)?;
+            let min_len = min_length.unwrap_or(3);
+            let mut words: Vec<_> = if pruned {
+                board.spellable(dictionary.as_str()).into_iter().filter(|w| w.len() >= min_len).collect()
+            } else {
+                let options = SolveOptions { min_len: min_length, ..SolveOptions::default() };
+                board.solve(dictionary.as_str(), &options).into_iter().map(|s| s.word).collect()
+            };
+            words.sort_unstable();
+            let mut text = words.join("\n");
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            match out {
+                Some(path) => fs::write(path, text)?,
+                None => print!("{}", text),
+            }
+            eprintln!("{}", Message::Trimmed { kept: words.len(), words: dictionary.as_str().lines().count() }.localize(locale));
+            Ok(())
+        }
+        Command::Duel { dictionary, board, players, min_length, top } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let board = read_board(&board)?;
+            let lists = [read(&players[0])?, read(&players[1])?];
+            let words: Vec<Vec<&str>> = lists.iter().map(|list| list.lines().collect()).collect();
+            let options = SolveOptions { min_len: min_length, ..SolveOptions::default() };
+            let duel = duel::judge(&board, dictionary.as_str(), &options, [&words[0], &words[1]], top);
+            print_duel(&duel, min_length.unwrap_or(3), locale);
+            Ok(())
+        }
+        Command::DuelPeer { dictionary, peer, top } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let provenance = Provenance::new(dictionary.as_str()).to_string();
+            let (raw_board, round, words) = peer::run(&peer, &provenance, locale)?;
+            let board = Board::parse(&raw_board)?;
+            let words: Vec<Vec<&str>> = words.iter().map(|list| list.iter().map(String::as_str).collect()).collect();
+            let options = SolveOptions { min_len: Some(round.min_length), ..SolveOptions::default() };
+            println!();
+            print_duel(&duel::judge(&board, dictionary.as_str(), &options, [&words[0], &words[1]], top), round.min_length, locale);
+            Ok(())
+        }
+        Command::Daily { dictionary, date, seconds, data } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let date = date.unwrap_or_else(Date::today);
+            let raw = date.board();
+            print!("{}", raw.to_uppercase());
+            println!("{}", Message::DailyStart { date, seconds }.localize(locale));
+            io::stdout().flush()?;
+            let words = peer::play(seconds, locale);
+            let words: Vec<&str> = words.iter().map(String::as_str).collect();
+            let board = Board::parse(&raw)?;
+            let result = Game::new(dictionary.as_str(), SolveOptions::default()).adjudicate(&board, &[PlayerSubmission { words: &words }]);
+            println!();
+            print_verdicts(&result.players[0], 3, locale);
+            println!();
+            println!("{}", daily::share(date, &board, &result.players[0], &result.missed));
+            if let Some(data) = data {
+                daily::save(&FileStorage::new(data), date, &daily::Record::new(&result.players[0], &result.missed))?;
+            }
+            Ok(())
+        }
+        Command::DailyArchive { days, date, data } => {
+            let storage: Box<dyn Storage> = match data {
+                Some(data) => Box::new(FileStorage::new(data)),
+                None => Box::new(MemoryStorage::new()),
+            };
+            for (date, board, record) in daily::archive(storage.as_ref(), date.unwrap_or_else(Date::today), days)? {
+                let board = board.trim_end().replace('\n', "/").to_uppercase();
+                println!("{}", Message::ArchiveDay { date, board: &board, record }.localize(locale));
+            }
+            Ok(())
+        }
+        Command::Words { dictionary, query } => {
+            let dictionary = parse_dictionary(&read_dictionary(&dictionary)?, &Alphabet::english(), false)?;
+            let trie = Trie::from_dictionary(dictionary.as_str());
+            let words = match query {
+                Query::Anagram(rack) => trie.anagrams(rack.to_ascii_lowercase().as_bytes()),
+                Query::Pattern(pattern) => trie.matching(pattern.to_ascii_lowercase().as_bytes()),
+            };
+            for word in words {
+                println!("{}", word);
+            }
+            Ok(())
+        }
+        Command::Submit { board, banned, boards_dir } => {
+            let board = read_board(&board)?;
+            let banned = match banned {
+                Some(path) => read(&path)?,
+                None => String::new(),
+            };
+            let paths = match boards_dir {
+                Some(dir) => files(&dir)?,
+                None => Vec::new(),
+            };
+            let existing = paths.iter().map(|path| read_board(path)).collect::<Result<Vec<_>, _>>()?;
+            let dice = match board.len() {
+                4 => Some(&dice::CLASSIC[..]),
+                5 => Some(&dice::BIG[..]),
+                _ => None,
+            };
+            let checks = Checks { dice, banned: &banned, existing: &existing, ..Checks::default() };
+            println!("{}", report_json(&submission::review(&board, &checks), &paths));
+            Ok(())
+        }
+        Command::Validate { boards, fix } => {
+            let mut bad = 0;
+            for path in &boards {
+                // bytes that aren't UTF-8 are just more characters that aren't letters
+                let (board, problems) = Board::parse_lossy(&String::from_utf8_lossy(&read_bytes(path)?));
+                for problem in &problems {
+                    let line = format!("{}: {}", path, Message::Error(problem).localize(locale));
+                    if fix { eprintln!("{}", line) } else { println!("{}", line) }
+                }
+                match board {
+                    Some(board) if fix => {
+                        if boards.len() > 1 {
+                            println!("== {} ==", path);
+                        }
+                        println!("{}", board.text());
+                    }
+                    None if fix => bad += 1,
+                    _ if !problems.is_empty() => bad += 1,
+                    _ => {}
+                }
+            }
+            if bad > 0 {
+                eprintln!("{}", if fix { Message::Unfixable(bad) } else { Message::BoardProblems(bad) }.localize(locale));
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Command::Serve { dictionaries, presets, config, port } => {
+            let mut config = match config {
+                Some(path) => ServerConfig::from_toml(&read(&path)?),
+                None => {
+                    let mut builder = ServerConfig::builder();
+                    for (name, path) in dictionaries {
+                        builder = builder.dictionary(name, path);
+                    }
+                    if !presets.is_empty() {
+                        builder = builder.presets(presets);
+                    }
+                    builder.build()
+                }
+            }.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            if let Some(port) = port {
+                config.set_port(port);
+            }
+            let server = Server::bind(&config)?;
+            eprintln!("{}", Message::Listening(server.local_addr()?).localize(locale));
+            server.run()
+        }
+        Command::Tui { dictionary, board } => {
+            let raw = read_dictionary(&dictionary)?;
+            let dictionary;
+            let words = if compiled::is_compiled(&raw) {
+                CompiledDictionary::from_bytes(&raw)?.as_str()
+            } else {
+                dictionary = Dictionary::parse(str::from_utf8(&raw).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?);
+                dictionary.as_str()
+            };
+            let board = board.as_deref().map(read_board).transpose()?;
+            tui::run(board.as_ref(), &Trie::from_dictionary(words), locale)
+        }
+        Command::DebugTrie { dictionary, prefix, depth } => {
+            let raw = read_words(&dictionary)?;
+            let dictionary = Dictionary::parse(&raw);
+            let trie = Trie::from_dictionary(dictionary.as_str());
+            let prefix = prefix.to_ascii_lowercase();
+            match trie.step(Trie::ROOT, prefix.as_bytes()) {
+                Some(node) => print!("{}", trie.to_dot_from(node, depth)),
+                None => print!("digraph trie {{\n}}\n"),
+            }
+            Ok(())
+        }
+        Command::DebugTrace { dictionary, board, limit, dot } => {
+            let raw = read_words(&dictionary)?;
+            let dictionary = Dictionary::parse(&raw);
+            let trace = read_board(&board)?.trace(dictionary.as_str(), &SolveOptions::default(), limit);
+            if dot {
+                print!("{}", trace.to_dot());
+            } else {
+                println!("{}", trace_json(&trace));
+            }
+            Ok(())
+        }
+        Command::DebugProfile { dictionary, board, top } => {
+            let raw = read_words(&dictionary)?;
+            let dictionary = Dictionary::parse(&raw);
+            let (_, slowest) = read_board(&board)?.profile_single_threaded(dictionary.as_str(), top);
+            for profile in slowest {
+                println!("{} {}us {}", profile.word, profile.time.as_micros(), profile.expansions);
+            }
+            Ok(())
+        }
+        Command::Bench { dictionary, board, iterations } => {
+            let raw = read_words(&dictionary)?;
+            let dictionary = Dictionary::parse(&raw);
+            let board = read_board(&board)?;
+            let mut timings = bench::run(&board, &dictionary, iterations);
+            let builds = bench::builds(dictionary.as_str(), iterations);
+            timings.extend_from_slice(&builds);
+            print!("{}", bench::table(&timings));
+            println!("parallel compile: {:.2}x as fast", bench::speedup(&builds));
+            let pruning = board.pruning(dictionary.as_str());
+            println!("pruning: {} of {} words searched for, {} going by letters alone", pruning.kept, pruning.words, pruning.letters);
+            Ok(())
+        }
+        Command::SelfTest => selftest::run(),
+        Command::Help => {
+            println!("{}", Message::Error(&Error::Usage).localize(locale));
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    let locale = Locale::from_env();
+    logger::init();
+    if let Err(err) = boggle_main(locale) {
+        eprintln!("{}", Message::Error(&err).localize(locale));
+        std::process::exit(1);
+    }
+}
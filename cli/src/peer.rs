@@ -0,0 +1,168 @@
+//! `boggle duel --host` and `--join`: a duel between two terminals. The host
+//! waits for the other player and sends them the round: the seed of the
+//! board, how long it lasts, the minimum word length and which dictionary
+//! words are judged with. Both players then type words until time runs out
+//! or their input ends, swap lists, and are judged the same way `boggle
+//! duel` judges two files.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use boggle::{dice, rng, Error};
+
+use crate::i18n::{Locale, Message};
+
+/// The port `--host` listens on unless told otherwise.
+pub const DEFAULT_PORT: u16 = 7878;
+
+// the first line the host sends, so joining something else fails early
+const HELLO: &str = "boggle-duel 1";
+
+/// What the host and the joining player agree on before playing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub seed: u64,
+    pub seconds: u64,
+    pub min_length: usize,
+    /// The provenance of the dictionary the host judges with, which the
+    /// joining player's must match.
+    pub dictionary: String,
+}
+
+/// Which end of the connection this player is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Peer {
+    /// Wait on `port` and send the round to whoever joins.
+    Host { port: u16, seed: Option<u64>, seconds: u64, min_length: usize },
+    /// Join the host at this address.
+    Join(String),
+}
+
+/// The board played, as `Board::parse` reads it, the round, and both
+/// players' words, the host's first.
+pub type Played = (String, Round, [Vec<String>; 2]);
+
+fn invalid(message: String) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+fn send_round<W: Write>(out: &mut W, round: &Round) -> io::Result<()> {
+    writeln!(out, "{}\n{}\n{}\n{}\n{}", HELLO, round.seed, round.seconds, round.min_length, round.dictionary)
+}
+
+fn read_round<R: BufRead>(input: &mut R) -> Result<Round, Error> {
+    let mut lines = Vec::new();
+    for _ in 0..5 {
+        let mut line = String::new();
+        input.read_line(&mut line)?;
+        lines.push(line.trim_end().to_string());
+    }
+    if lines[0] != HELLO {
+        return Err(invalid("the other end isn't hosting a boggle duel".to_string()));
+    }
+    let number = |line: &str| line.parse().map_err(|_| invalid(format!("bad round from the host: {:?}", line)));
+    Ok(Round { seed: number(&lines[1])?, seconds: number(&lines[2])?, min_length: number(&lines[3])? as usize, dictionary: lines[4].clone() })
+}
+
+// sends our words, then reads theirs until they're done sending
+fn swap<R: BufRead>(out: &mut TcpStream, input: R, words: &[String]) -> io::Result<Vec<String>> {
+    for word in words {
+        writeln!(out, "{}", word.trim())?;
+    }
+    out.shutdown(Shutdown::Write)?;
+    input.lines().collect()
+}
+
+/// The lines typed on stdin until `seconds` pass or stdin ends.
+pub fn play(seconds: u64, locale: Locale) -> Vec<String> {
+    let (lines, typed) = mpsc::channel();
+    // left blocked on stdin once time is up, which is fine since the process is about to end
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if lines.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(seconds);
+    let mut words = Vec::new();
+    loop {
+        match typed.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(word) => words.push(word),
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                eprintln!("{}", Message::TimeUp.localize(locale));
+                break;
+            }
+        }
+    }
+    words
+}
+
+/// Plays one round against the other player. `dictionary` is the
+/// provenance of this player's dictionary.
+pub fn run(peer: &Peer, dictionary: &str, locale: Locale) -> Result<Played, Error> {
+    let (mut stream, sent) = match *peer {
+        Peer::Host { port, seed, seconds, min_length } => {
+            let listener = TcpListener::bind(("0.0.0.0", port))?;
+            eprintln!("{}", Message::WaitingForPlayer(listener.local_addr()?).localize(locale));
+            let (mut stream, _) = listener.accept()?;
+            let round = Round { seed: seed.unwrap_or_else(rng::entropy), seconds, min_length, dictionary: dictionary.to_string() };
+            send_round(&mut stream, &round)?;
+            (stream, Some(round))
+        }
+        Peer::Join(ref address) => (TcpStream::connect(address)?, None),
+    };
+    let mut input = BufReader::new(stream.try_clone()?);
+    let host = sent.is_some();
+    let round = match sent {
+        Some(round) => round,
+        None => read_round(&mut input)?,
+    };
+    if round.dictionary != dictionary {
+        return Err(invalid(format!("the host judges words with {}, not {}", round.dictionary, dictionary)));
+    }
+
+    let raw = dice::roll(&dice::CLASSIC, round.seed);
+    print!("{}", raw.to_uppercase());
+    println!("{}", Message::DuelStart { player: if host { 1 } else { 2 }, seconds: round.seconds }.localize(locale));
+    io::stdout().flush()?;
+    let mine = play(round.seconds, locale);
+    let theirs = swap(&mut stream, input, &mine)?;
+    let words = if host { [mine, theirs] } else { [theirs, mine] };
+    Ok((raw, round, words))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn protocol() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let round = Round { seed: 42, seconds: 90, min_length: 4, dictionary: "boggle 0.1.0 (abc)".to_string() };
+        let host = {
+            let round = round.clone();
+            thread::spawn(move || {
+                let (mut stream, _) = listener.accept().unwrap();
+                send_round(&mut stream, &round).unwrap();
+                let input = BufReader::new(stream.try_clone().unwrap());
+                swap(&mut stream, input, &["cat".to_string(), " dog ".to_string()]).unwrap()
+            })
+        };
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        let mut input = BufReader::new(stream.try_clone().unwrap());
+        assert_eq!(read_round(&mut input).unwrap(), round);
+        assert_eq!(swap(&mut stream, input, &["tax".to_string()]).unwrap(), ["cat", "dog"]);
+        assert_eq!(host.join().unwrap(), ["tax"]);
+
+        let stray = b"HTTP/1.1 200 OK\r\n\r\n\n\n\n".to_vec();
+        assert!(read_round(&mut &stray[..]).is_err());
+    }
+}
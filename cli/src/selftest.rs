@@ -0,0 +1,41 @@
+use std::collections::BTreeSet;
+
+use boggle::{strategy, Board, Dictionary, Error, Solution, SolveOptions};
+
+const DICTIONARY: &str = include_str!("../../test/dictionary");
+const BOARD: &str = include_str!("../../test/board1");
+const EXPECTED: usize = 126;
+
+/// Runs the bundled board and dictionary through every solver and checks they
+/// agree on the known answer, printing what was checked along the way.
+pub fn run() -> Result<(), Error> {
+    println!("boggle {} on {} {}, {} threads available",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+
+    let board = Board::parse(BOARD)?;
+    let dictionary = Dictionary::parse(DICTIONARY);
+    let mut expected = None;
+    for strategy in strategy::ALL {
+        check(strategy.name(), &strategy.solve(&board, &dictionary), &mut expected)?;
+    }
+    for &(name, threads) in &[("solve, 1 thread", 1), ("solve, 4 threads", 4)] {
+        check(name, &board.solve(DICTIONARY, &SolveOptions { threads: Some(threads), ..SolveOptions::default() }), &mut expected)?;
+    }
+    Ok(())
+}
+
+// checks one solver's words against the known count and the first solver's words
+fn check<'a>(name: &str, solutions: &[Solution<'a>], expected: &mut Option<BTreeSet<&'a str>>) -> Result<(), Error> {
+    let words: BTreeSet<_> = solutions.iter().map(|s| s.word).collect();
+    if words.len() != EXPECTED {
+        return Err(Error::SelfTest(format!("{} found {} words, expected {}", name, words.len(), EXPECTED)));
+    }
+    if expected.get_or_insert_with(|| words.clone()) != &words {
+        return Err(Error::SelfTest(format!("{} found different words than {}", name, strategy::ALL[0].name())));
+    }
+    println!("{}: {} words ok", name, words.len());
+    Ok(())
+}
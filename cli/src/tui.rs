@@ -0,0 +1,296 @@
+//! `boggle tui`: a board editor for building puzzles. The grid is on the
+//! left with a cursor to move around and type letters into; every edit
+//! re-solves the board, and its words are listed on the right, best first.
+//! A key pressed while a big board is still solving cuts the solve short.
+//! Tab swaps in a freshly rolled board, which is solved on another thread
+//! while the editor waits for keys so its words are ready at once.
+
+use std::io::{self, Write};
+use std::thread::{self, Scope, ScopedJoinHandle};
+use std::time::Duration;
+
+use boggle::{dice, rng, Board, CancelToken, Error, SolveOptions, Solutions, Trie};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, queue, terminal};
+
+use crate::i18n::{Locale, Message};
+
+// columns between the grid and the word list
+const GUTTER: u16 = 4;
+
+/// The board being edited and where the cursor is on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Editor {
+    // each cell as `Board` stores it
+    tiles: Vec<Vec<u8>>,
+    cursor: (usize, usize),
+}
+
+impl Editor {
+    pub fn new(board: &Board) -> Editor {
+        let tiles = (0..board.len())
+            .map(|i| (0..board.len()).map(|j| board[(i, j)]).collect())
+            .collect();
+        Editor { tiles, cursor: (0, 0) }
+    }
+
+    /// The board as `Board::parse` reads it, with the cells of each row
+    /// separated by spaces if there are tiles other than "Qu".
+    pub fn text(&self) -> String {
+        let delimited = self.tiles.iter().flatten().any(|&c| c != Board::QU && c.is_ascii_uppercase());
+        self.tiles.iter()
+            .map(|row| row.iter().map(|&c| letters(c)).collect::<Vec<_>>().join(if delimited { " " } else { "" }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Applies a key, returning whether the board changed. Arrows move the
+    /// cursor, stopping at the edges; a letter replaces the tile under it,
+    /// `q` being the "Qu" tile, and moves on to the next cell.
+    pub fn key(&mut self, key: KeyCode) -> bool {
+        let n = self.tiles.len();
+        let (x, y) = self.cursor;
+        match key {
+            KeyCode::Up => self.cursor = (x.saturating_sub(1), y),
+            KeyCode::Down => self.cursor = ((x + 1).min(n - 1), y),
+            KeyCode::Left => self.cursor = (x, y.saturating_sub(1)),
+            KeyCode::Right => self.cursor = (x, (y + 1).min(n - 1)),
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                let c = c.to_ascii_lowercase() as u8;
+                self.tiles[x][y] = if c == b'q' { Board::QU } else { c };
+                let next = x * n + y + 1;
+                if next < n * n {
+                    self.cursor = (next / n, next % n);
+                }
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+// the letters a cell spells
+fn letters(c: u8) -> String {
+    match Board::DIGRAPHS.iter().find(|&&(_, cell)| cell == c) {
+        Some(&(tile, _)) => tile.to_string(),
+        None => (c as char).to_string(),
+    }
+}
+
+// the word list, one "word score" per line, as many as fit in `height` rows
+fn panel(solutions: &Solutions, height: usize) -> Vec<String> {
+    solutions.iter().take(height).map(|s| format!("{:<16} {:>3}", s.word, s.score)).collect()
+}
+
+// the board's words, best first, and whether `cancel` cut the solve short
+fn solve<'a>(editor: &Editor, trie: &Trie<'a>, cancel: &CancelToken) -> Result<(Solutions<'a>, bool), Error> {
+    let text = editor.text();
+    let board = Board::parse(&text)?;
+    let (solutions, stopped) = board.solve_with_cancel(trie, &SolveOptions::default(), cancel);
+    let mut solutions = Solutions(solutions);
+    solutions.sort_by_score();
+    Ok((solutions, stopped))
+}
+
+// solves on another thread while watching for keys; one pressed meanwhile cancels the solve, and comes back to
+// be handled along with the words found by then
+fn solve_watching<'a>(editor: &Editor, trie: &Trie<'a>) -> Result<(Solutions<'a>, Option<KeyEvent>), Error> {
+    let cancel = CancelToken::new();
+    thread::scope(|scope| {
+        let solving = scope.spawn(|| solve(editor, trie, &cancel));
+        let mut pressed = None;
+        while pressed.is_none() && !solving.is_finished() {
+            if event::poll(Duration::from_millis(10))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Release {
+                        cancel.cancel();
+                        pressed = Some(key);
+                    }
+                }
+            }
+        }
+        let (solutions, _) = solving.join().expect("solving doesn't panic")?;
+        Ok((solutions, pressed))
+    })
+}
+
+// the board Tab brings up next, solved in the background while the editor is idle
+struct Upcoming<'scope, 'a> {
+    editor: Editor,
+    cancel: CancelToken,
+    solving: Option<ScopedJoinHandle<'scope, Result<(Solutions<'a>, bool), Error>>>,
+    solved: Option<Solutions<'a>>,
+}
+
+impl<'scope, 'a: 'scope> Upcoming<'scope, 'a> {
+    fn roll() -> Result<Upcoming<'scope, 'a>, Error> {
+        let board = Board::parse(&dice::roll(&dice::CLASSIC, rng::entropy()))?;
+        Ok(Upcoming { editor: Editor::new(&board), cancel: CancelToken::new(), solving: None, solved: None })
+    }
+
+    // starts solving, unless it's solved already or solving
+    fn resume<'env>(&mut self, scope: &'scope Scope<'scope, 'env>, trie: &'env Trie<'a>) {
+        if self.solved.is_none() && self.solving.is_none() {
+            let (editor, cancel) = (self.editor.clone(), CancelToken::new());
+            self.cancel = cancel.clone();
+            self.solving = Some(scope.spawn(move || solve(&editor, trie, &cancel)));
+        }
+    }
+
+    // stops solving so an edit's solve has the machine to itself, keeping the words if it had finished
+    fn pause(&mut self) -> Result<(), Error> {
+        if let Some(solving) = self.solving.take() {
+            self.cancel.cancel();
+            let (solutions, stopped) = solving.join().expect("solving doesn't panic")?;
+            if !stopped {
+                self.solved = Some(solutions);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn draw<W: Write>(out: &mut W, editor: &Editor, solutions: &Solutions, locale: Locale) -> io::Result<()> {
+    let (_, rows) = terminal::size()?;
+    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+    for (x, row) in editor.tiles.iter().enumerate() {
+        for (y, &c) in row.iter().enumerate() {
+            let tile = letters(c);
+            let tile = tile[..1].to_ascii_uppercase() + &tile[1..];
+            queue!(out, cursor::MoveTo(y as u16 * 3, x as u16))?;
+            if (x, y) == editor.cursor {
+                queue!(out, SetAttribute(Attribute::Reverse), Print(format!("{:<2}", tile)), SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(out, Print(tile))?;
+            }
+        }
+    }
+
+    let below = editor.tiles.len() as u16 + 1;
+    let lines = [
+        Message::Found(solutions.len()).localize(locale),
+        Message::TotalScore(solutions.total_score()).localize(locale),
+        String::new(),
+        Message::TuiHelp.localize(locale),
+    ];
+    for (i, line) in lines.iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, below + i as u16), Print(line))?;
+    }
+
+    let left = editor.tiles.len() as u16 * 3 + GUTTER;
+    for (i, line) in panel(solutions, rows as usize).iter().enumerate() {
+        queue!(out, cursor::MoveTo(left, i as u16), Print(line))?;
+    }
+    out.flush()
+}
+
+/// Edits `board`, or a freshly rolled one, until Esc or Ctrl-C, solving it
+/// against `trie` after every change. Tab swaps in the next rolled board,
+/// whose solve is put on hold while an edit's runs.
+pub fn run(board: Option<&Board>, trie: &Trie, locale: Locale) -> Result<(), Error> {
+    let mut editor = match board {
+        Some(board) => Editor::new(board),
+        None => Upcoming::roll()?.editor,
+    };
+
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = thread::scope(|scope| -> Result<(), Error> {
+        let (mut solutions, mut pending) = solve_watching(&editor, trie)?;
+        let mut upcoming = Upcoming::roll()?;
+        upcoming.resume(scope, trie);
+        loop {
+            draw(&mut out, &editor, &solutions, locale)?;
+            // a key that cut the last solve short has it solved again, whether or not it changes the board
+            let interrupted = pending.is_some();
+            let key = match pending.take() {
+                Some(key) => key,
+                None => match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                    _ => continue,
+                },
+            };
+            if key.code == KeyCode::Esc || key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                return upcoming.pause();
+            }
+            if key.code == KeyCode::Tab {
+                upcoming.pause()?;
+                let next = std::mem::replace(&mut upcoming, Upcoming::roll()?);
+                editor = next.editor;
+                (solutions, pending) = match next.solved {
+                    Some(solved) => (solved, None),
+                    None => solve_watching(&editor, trie)?,
+                };
+                upcoming.resume(scope, trie);
+            } else if editor.key(key.code) || interrupted {
+                upcoming.pause()?;
+                (solutions, pending) = solve_watching(&editor, trie)?;
+                upcoming.resume(scope, trie);
+            }
+        }
+    });
+    // put the terminal back whatever happened, so an error is readable
+    queue!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    out.flush()?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edit() {
+        let board = Board::parse("cat\nxxx\nxxx").unwrap();
+        let mut editor = Editor::new(&board);
+        assert!(!editor.key(KeyCode::Left) && !editor.key(KeyCode::Up));
+        assert_eq!(editor.cursor, (0, 0));
+        editor.key(KeyCode::Right);
+        editor.key(KeyCode::Right);
+        assert!(editor.key(KeyCode::Char('Q')));
+        assert_eq!(editor.cursor, (1, 0));
+        editor.key(KeyCode::Down);
+        editor.key(KeyCode::Down);
+        assert_eq!(editor.cursor, (2, 0));
+        assert_eq!(editor.text(), "caqu\nxxx\nxxx");
+        assert!(!editor.key(KeyCode::Char('1')));
+
+        let board = Board::parse("th a\nqu x").unwrap();
+        assert_eq!(Editor::new(&board).text(), "th a\nqu x");
+    }
+
+    #[test]
+    fn solved() {
+        let trie = Trie::from_dictionary("cat\ncats\ntax\nzzz\n");
+        let editor = Editor::new(&Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap());
+        let (solutions, stopped) = solve(&editor, &trie, &CancelToken::new()).unwrap();
+        assert_eq!(panel(&solutions, 2), vec!["cat                1", "cats               1"]);
+        assert!(!stopped);
+        let cancelled = CancelToken::new();
+        cancelled.cancel();
+        let (solutions, stopped) = solve(&editor, &trie, &cancelled).unwrap();
+        assert!(solutions.is_empty() && stopped);
+    }
+
+    #[test]
+    fn upcoming() {
+        let trie = Trie::from_dictionary("cat\ncats\ntax\nzzz\n");
+        let editor = Editor::new(&Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap());
+        thread::scope(|scope| {
+            let mut upcoming = Upcoming { editor: editor.clone(), ..Upcoming::roll().unwrap() };
+            upcoming.resume(scope, &trie);
+            while !upcoming.solving.as_ref().unwrap().is_finished() {
+                thread::yield_now();
+            }
+            // finished before the pause, so its words are kept and there's nothing left to solve
+            upcoming.pause().unwrap();
+            assert_eq!(upcoming.solved, Some(solve(&editor, &trie, &CancelToken::new()).unwrap().0));
+            upcoming.resume(scope, &trie);
+            assert!(upcoming.solving.is_none());
+        });
+    }
+}
@@ -0,0 +1,901 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn boggle(args: &[&str]) -> Output {
+    boggle_in("C", args)
+}
+
+fn boggle_in(lang: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_boggle"))
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("BOGGLE_LOG")
+        .env("LANG", lang)
+        .output()
+        .expect("failed to run boggle")
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn solve() {
+    let output = boggle(&["test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let mut lines = stdout(&output).lines();
+    assert_eq!(lines.next(), Some("Found 126 words in board"));
+    assert_eq!(lines.next(), Some("Total score: 165"));
+    let words: Vec<_> = lines.by_ref().take(126).collect();
+    assert!(words.contains(&"amity 2"));
+    assert!(words.windows(2).all(|w| w[0] < w[1]));
+    let total: u32 = words.iter().map(|w| w.rsplit(' ').next().unwrap().parse::<u32>().unwrap()).sum();
+    assert_eq!(total, 165);
+    assert!(lines.next().unwrap().starts_with("Solved by boggle "));
+    assert_eq!(lines.next(), None);
+
+    let output = boggle(&["solve", "--min-length", "4", "--wrap", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).lines().last().unwrap().ends_with(" rules min-length 4, wrap"));
+}
+
+#[test]
+fn flags() {
+    let default = boggle(&["test/dictionary", "test/board1"]);
+    for args in &[&["--threads", "1"][..], &["--threads", "4"], &["--low-power"]] {
+        let args: Vec<_> = args.iter().chain(&["test/dictionary", "test/board1"]).cloned().collect();
+        let output = boggle(&args);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), stdout(&default));
+    }
+}
+
+#[test]
+fn subcommands() {
+    let solve = boggle(&["solve", "test/dictionary", "test/board1"]);
+    assert!(solve.status.success());
+    assert_eq!(stdout(&solve), stdout(&boggle(&["test/dictionary", "test/board1"])));
+
+    let score = boggle(&["score", "test/dictionary", "test/board1"]);
+    assert!(score.status.success());
+    assert_eq!(stdout(&score), "165\n");
+
+    let help = boggle(&["--help"]);
+    assert!(help.status.success());
+    assert!(stdout(&help).starts_with("USAGE: boggle"));
+    assert!(stdout(&help).contains("--min-length"));
+}
+
+#[test]
+fn solve_flags() {
+    let output = boggle(&["solve", "--min-length", "6", "--sort", "score", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let lines: Vec<_> = stdout(&output).lines().collect();
+    assert_eq!(lines[0], "Found 7 words in board");
+    assert_eq!(lines[2], "fermate 5");
+    let words = &lines[2..lines.len() - 1];
+    assert!(words.iter().all(|w| w.split(' ').next().unwrap().len() >= 6));
+
+    let output = boggle(&["score", "--no-diagonals", "test/dictionary", "test/board1"]);
+    let orthogonal: u32 = stdout(&output).trim().parse().unwrap();
+    let output = boggle(&["score", "--wrap", "test/dictionary", "test/board1"]);
+    let wrapped: u32 = stdout(&output).trim().parse().unwrap();
+    assert!(orthogonal < 165 && wrapped > 165, "{} {}", orthogonal, wrapped);
+
+    let output = boggle(&["solve", "--min-length", "4", "--max-length", "4", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let lines: Vec<_> = stdout(&output).lines().collect();
+    assert!(lines[2..lines.len() - 1].iter().all(|w| w.split(' ').next().unwrap().len() == 4));
+
+    let output = boggle(&["solve", "--group", "--sort", "length", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let lines: Vec<_> = stdout(&output).lines().collect();
+    assert_eq!(&lines[2..5], &["a:", "  amity 2", "  aero 1"]);
+    assert!(lines.contains(&"f:"));
+
+    let output = boggle(&["solve", "--json", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let json = stdout(&output);
+    assert!(json.starts_with("{\"found\":126,\"score\":165,\"words\":[{\"word\":\"aero\",\"score\":1,\"id\":\""));
+    assert!(json.contains("\"solved_by\":\"boggle "));
+    assert_eq!(json.lines().count(), 1);
+
+    let output = boggle(&["solve", "--output", "json", "--paths", "--min-length", "7", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("{\"word\":\"fermate\",\"score\":5,\"path\":[[1,0],[2,0],[2,1],[2,2],[3,3],[2,3],[3,2]],\"id\":\"2d5f154b5356c54c\"}"));
+    // a word's ID is the same from every solver
+    let output = boggle(&["solve", "--output", "json", "--strategy", "dawg", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).contains("{\"word\":\"fermate\",\"score\":5,\"id\":\"2d5f154b5356c54c\"}"));
+
+    let output = boggle(&["score", "--verify", "--wrap", "--min-length", "5", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn strategy() {
+    let plain = boggle(&["solve", "test/dictionary", "test/board1"]);
+    let plain = stdout(&plain);
+    assert!(plain.starts_with("Found 126 words in board\nTotal score: 165\n"));
+    for strategy in &["single-threaded", "trie", "parallel", "recursive", "dawg"] {
+        let output = boggle(&["solve", "--strategy", strategy, "--verify", "test/dictionary", "test/board1"]);
+        assert!(output.status.success(), "{}", strategy);
+        assert_eq!((stdout(&output), stderr(&output)), (plain, ""), "{}", strategy);
+    }
+
+    // the strategies find every word, so options saying otherwise are refused
+    let output = boggle(&["solve", "--strategy", "trie", "--min-length", "7", "test/dictionary", "test/board1"]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert!(!boggle(&["solve", "--strategy", "fastest", "test/dictionary", "test/board1"]).status.success());
+}
+
+#[test]
+fn rarity() {
+    let output = boggle(&["solve", "--rarity", "test/frequency", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("\nate 1 common\n"));
+    assert!(out.contains("\namity 6 rare\n"));
+
+    let output = boggle(&["solve", "--json", "--rarity", "test/frequency", "--min-length", "7", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).contains("{\"word\":\"fermate\",\"score\":15,\"tier\":\"rare\",\"id\":\""));
+
+    let output = boggle(&["solve", "--rarity", "test/frequency", "--max-obscurity", "uncommon", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Found 2 words in board\nTotal score: 2\nate 1 common\nmat 1 common\n"));
+}
+
+#[test]
+fn validate() {
+    let dir = env!("CARGO_TARGET_TMPDIR");
+    let (good, typo, bad) = (format!("{}/good", dir), format!("{}/typo", dir), format!("{}/bad", dir));
+    std::fs::write(&good, "cat\nQuxx\nxxx\n").unwrap();
+    std::fs::write(&typo, b"ca1t\nxx\xffx\nxxx\n").unwrap();
+    std::fs::write(&bad, "ab\ncd\nef\n").unwrap();
+
+    let output = boggle(&["validate", &good]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    let output = boggle(&["validate", &good, &typo, &bad]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), format!(concat!("{typo}: invalid character '1' in row 0, column 2\n",
+                                                "{typo}: invalid character '\u{fffd}' in row 1, column 2\n",
+                                                "{bad}: row 0 has 2 cells, expected 3 to match the number of rows\n",
+                                                "{bad}: row 1 has 2 cells, expected 3 to match the number of rows\n",
+                                                "{bad}: row 2 has 2 cells, expected 3 to match the number of rows\n"), typo = typo, bad = bad));
+    assert_eq!(stderr(&output), "2 boards have problems\n");
+
+    // a board that can be fixed is printed without its problems, which go to stderr
+    let output = boggle(&["validate", "--fix", &typo]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "cat\nxxx\nxxx\n");
+    assert_eq!(stderr(&output).lines().count(), 2);
+    let output = boggle(&["validate", "--fix", &good, &bad]);
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), format!("== {} ==\ncat\nquxx\nxxx\n", good));
+    assert!(stderr(&output).ends_with("1 boards couldn't be fixed\n"));
+}
+
+#[test]
+fn define() {
+    let glossary = format!("{}/glossary", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&glossary, "amity\tfriendly relations\nate\tpast of eat\naero\ta \"rug\\\" \u{1}\n").unwrap();
+    let output = boggle(&["solve", "--define", &glossary, "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("\namity 2 friendly relations\n"));
+    assert!(out.contains("\nmat 1\n"));
+
+    let output = boggle(&["solve", "--json", "--rarity", "test/frequency", "--define", &glossary, "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).contains("{\"word\":\"ate\",\"score\":1,\"tier\":\"common\",\"definition\":\"past of eat\",\"id\":\""));
+    // escaped as JSON strings
+    assert!(stdout(&output).contains(r#""definition":"a \"rug\\\" \u0001","id":""#));
+
+    let output = boggle(&["solve", "--define", "test/missing", "test/dictionary", "test/board1"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn dictionary_formats() {
+    let solved = |args: &[&str]| {
+        let output = boggle(args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output).lines().filter(|l| !l.starts_with("Solved by")).collect::<Vec<_>>().join("\n")
+    };
+    // a gzipped list finds what the list does unzipped
+    let plain = format!("{}/words", env!("CARGO_TARGET_TMPDIR"));
+    let dictionary = std::fs::read_to_string("../test/dictionary").unwrap();
+    std::fs::write(&plain, dictionary.lines().take(5_000).flat_map(|w| [w, "\n"]).collect::<String>()).unwrap();
+    assert_eq!(solved(&["test/words.gz", "test/board1"]), solved(&[&plain, "test/board1"]));
+
+    // counts rank the words for --rarity as a list from most to least common does
+    let counts = format!("{}/counts", env!("CARGO_TARGET_TMPDIR"));
+    let frequency = std::fs::read_to_string("../test/frequency").unwrap();
+    let ranked: Vec<_> = frequency.lines().filter(|w| !w.trim().is_empty()).collect();
+    let lines: String = ranked.iter().enumerate().rev().map(|(rank, word)| format!("{}\t{}\n", word, ranked.len() - rank)).collect();
+    std::fs::write(&counts, lines).unwrap();
+    assert_eq!(solved(&["--rarity", &counts, "test/dictionary", "test/board1"]),
+               solved(&["--rarity", "test/frequency", "test/dictionary", "test/board1"]));
+    // and are left off when it's the dictionary
+    assert!(solved(&[&counts, "test/board1"]).starts_with("Found "));
+
+    std::fs::write(&counts, "cat\t1\ndog\n").unwrap();
+    assert_eq!(stderr(&boggle(&[&counts, "test/board1"])), "line 1 of the word list has no word, or no count after its tab\n");
+}
+
+#[test]
+fn findable() {
+    let output = boggle(&["solve", "--findable", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("Found 106 words in board\nTotal score: 121\n"));
+    assert!(out.contains("\namir 1\n"));
+    // its path turns four times, twice doubling back
+    assert!(!out.contains("fermate"));
+
+    let output = boggle(&["solve", "--findable", "--rarity", "test/frequency", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).starts_with("Found 2 words in board\nTotal score: 2\nate 1 common\nmat 1 common\n"));
+}
+
+#[test]
+fn also() {
+    let slang = format!("{}/slang", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&slang, "amity\nyvf\n").unwrap();
+    let output = boggle(&["--also", &slang, "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("Found 127 words in board\n"));
+    assert!(out.contains(&format!("\namity 2 (test/dictionary, {})\n", slang)));
+    assert!(out.contains(&format!("\nyvf 1 ({})\n", slang)));
+
+    let output = boggle(&["--json", "--also", &slang, "--min-length", "5", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).contains(&format!("{{\"word\":\"amity\",\"score\":2,\"sources\":[\"test/dictionary\",\"{}\"],\"id\":\"", slang)));
+}
+
+#[test]
+fn exclude() {
+    let (blocked, slang) = (format!("{}/blocked", env!("CARGO_TARGET_TMPDIR")), format!("{}/slang-blocked", env!("CARGO_TARGET_TMPDIR")));
+    std::fs::write(&blocked, "WAVE\nvary\nyvf\n").unwrap();
+    std::fs::write(&slang, "amity\nyvf\n").unwrap();
+    let compiled = format!("{}/exclude.compiled", env!("CARGO_TARGET_TMPDIR"));
+    assert!(boggle(&["compile-dict", "test/dictionary", &compiled]).status.success());
+
+    for dictionary in &["test/dictionary", &compiled] {
+        let output = boggle(&["--exclude", &blocked, dictionary, "test/board1"]);
+        assert!(output.status.success());
+        assert!(stdout(&output).starts_with("Found 124 words in board\n"), "{}", stdout(&output));
+        assert!(!stdout(&output).contains("\nwave 1\n"));
+        assert_eq!(stderr(&output), "Excluded 2 dictionary words\n");
+    }
+    let output = boggle(&["--exclude", &blocked, "--also", &slang, "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).starts_with("Found 124 words in board\n"), "{}", stdout(&output));
+    assert!(!stdout(&output).contains("yvf"));
+}
+
+#[test]
+fn normalize() {
+    let words = format!("{}/punctuated", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&words, "don't\nmitered\nmit-ered\nfermaté\n").unwrap();
+    let output = boggle(&[&words, "test/board1"]);
+    assert!(stdout(&output).starts_with("Found 1 words in board\n"));
+    assert_eq!(stderr(&output), "Skipped 3 dictionary words with characters other than letters\n");
+
+    let output = boggle(&["--normalize", &words, "test/board1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Found 2 words in board\nTotal score: 10\nfermate 5\nmitered 5\n"));
+    assert_eq!(stderr(&output), "Took apostrophes, hyphens or accents out of 3 dictionary words\n");
+}
+
+#[test]
+fn alphabet() {
+    let (words, board) = (format!("{}/spanish", env!("CARGO_TARGET_TMPDIR")), format!("{}/spanish-board", env!("CARGO_TARGET_TMPDIR")));
+    std::fs::write(&words, "año\nniño\nanos\n").unwrap();
+    std::fs::write(&board, "AÑO\nnix\nsxx\n").unwrap();
+    let output = boggle(&["--alphabet", "es", "--json", &words, &board]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("{\"found\":2,\"score\":2,\"words\":[{\"word\":\"año\",\"score\":1,\"id\":\"a6f8e3b48ece965c\"},{\"word\":\"niño\",\"score\":1,\"id\":\"d45d0da2d5320048\"}]"), "{}", stdout(&output));
+    let output = boggle(&[&words, &board]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("'Ñ'"), "{}", stderr(&output));
+}
+
+#[test]
+fn usage() {
+    for args in &[&[][..], &["test/dictionary"], &["--threads", "0", "test/dictionary", "test/board1"], &["--bogus"]] {
+        let output = boggle(args);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stdout(&output).is_empty());
+        assert!(stderr(&output).starts_with("USAGE: boggle"));
+    }
+}
+
+#[test]
+fn io_error() {
+    let output = boggle(&["test/dictionary", "test/does-not-exist"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!stderr(&output).is_empty());
+    assert!(!stderr(&output).starts_with("USAGE"));
+}
+
+#[test]
+fn board_size() {
+    let output = boggle(&["test/dictionary", "test/board_uneven"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "row 1 has 2 cells, expected 3 to match the number of rows\n");
+
+    let output = boggle(&["test/dictionary", "test/board_empty"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "empty board\n");
+}
+
+#[test]
+fn spanish() {
+    let output = boggle_in("es_ES.UTF-8", &["test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Se encontraron 126 palabras en el tablero\nPuntuación total: 165\n"));
+
+    let output = boggle_in("es_ES.UTF-8", &["test/dictionary"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("USO: boggle"));
+}
+
+#[test]
+fn generate() {
+    let output = boggle(&["generate", "--seed", "42"]);
+    assert!(output.status.success());
+    let board = stdout(&output);
+    assert_eq!(board.lines().count(), 4);
+    assert_eq!(stdout(&boggle(&["generate", "--seed", "42"])), board);
+    assert_eq!(stderr(&output), "");
+
+    // without a seed, the one picked is printed so the board can be rolled again
+    let output = boggle(&["generate"]);
+    let seed = stderr(&output).split_whitespace().nth(1).unwrap().trim_end_matches(';').to_string();
+    assert_eq!(stdout(&boggle(&["generate", "--seed", &seed])), stdout(&output));
+
+    let output = boggle(&["generate", "--size", "5", "--seed", "42"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).lines().count(), 5);
+
+    // drawn by letter frequency until the board has 20 to 40 words
+    let output = boggle(&["generate", "--letters", "--vowels", "5..6", "--target-words", "20..40", "--seed", "1", "test/dictionary"]);
+    assert!(output.status.success());
+    let words: usize = stderr(&output).split_whitespace().next().unwrap().parse().unwrap();
+    assert!((20..=40).contains(&words));
+    let path = format!("{}/generated-board", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, stdout(&output)).unwrap();
+    assert!(stdout(&boggle(&["test/dictionary", &path])).starts_with(&format!("Found {} words in board\n", words)));
+
+    let output = boggle(&["generate", "--target-words", "9000..9999", "--seed", "1", "test/dictionary"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "None of 1000 boards had that many words\n");
+
+    // "the" can be traced on the board for --seed 42, so another is drawn
+    let banned = format!("{}/banned-words", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&banned, "THE\n").unwrap();
+    let output = boggle(&["generate", "--seed", "42", "--banned", &banned]);
+    assert!(output.status.success());
+    assert_ne!(stdout(&output), board);
+    assert_eq!(stderr(&output), "");
+    std::fs::write(&path, stdout(&output)).unwrap();
+    std::fs::write(&banned, "the\n").unwrap();
+    assert!(stdout(&boggle(&[&banned, &path])).starts_with("Found 0 words in board\n"));
+    std::fs::write(&path, board).unwrap();
+    assert!(stdout(&boggle(&[&banned, &path])).starts_with("Found 1 words in board\n"));
+
+    let output = boggle(&["generate", "--target-words", "9000..9999", "--seed", "1", "--banned", &banned, "test/dictionary"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "None of 1000 boards had that many words and no banned ones\n");
+
+    let output = boggle(&["generate", "--seed"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("USAGE: boggle"));
+}
+
+#[test]
+fn compile_dict() {
+    let out = format!("{}/dictionary.compiled", env!("CARGO_TARGET_TMPDIR"));
+    let output = boggle(&["compile-dict", "test/dictionary", &out]);
+    assert!(output.status.success());
+
+    // the same words, so the same provenance too
+    let compiled = boggle(&["--sort", "score", &out, "test/board1"]);
+    assert!(compiled.status.success());
+    assert_eq!(stdout(&compiled), stdout(&boggle(&["--sort", "score", "test/dictionary", "test/board1"])));
+    assert_eq!(stdout(&boggle(&["score", &out, "test/board1"])), "165\n");
+
+    let truncated = format!("{}/truncated.compiled", env!("CARGO_TARGET_TMPDIR"));
+    let bytes = std::fs::read(&out).unwrap();
+    std::fs::write(&truncated, &bytes[..bytes.len() - 1]).unwrap();
+    let output = boggle(&[&truncated, "test/board1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "corrupt compiled dictionary\n");
+}
+
+#[test]
+fn index_diff() {
+    let (old, new) = (format!("{}/old.compiled", env!("CARGO_TARGET_TMPDIR")), format!("{}/new.words", env!("CARGO_TARGET_TMPDIR")));
+    std::fs::write(&new, "cat\ncats\nzebra\n").unwrap();
+    std::fs::write(format!("{}.words", old), "dog\ncat\nzebra\n").unwrap();
+    assert!(boggle(&["compile-dict", &format!("{}.words", old), &old]).status.success());
+    let output = boggle(&["index-diff", &old, &new]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "+cats\n-dog\n1 words added, 1 removed\nTrie nodes: 12 before, 10 after\n");
+    assert!(stdout(&boggle(&["index-diff", &old, &old])).starts_with("0 words added, 0 removed\n"));
+}
+
+#[test]
+fn build_index() {
+    let (compiled, out) = (format!("{}/built.compiled", env!("CARGO_TARGET_TMPDIR")), format!("{}/built.idx", env!("CARGO_TARGET_TMPDIR")));
+    assert!(boggle(&["compile-dict", "test/dictionary", &compiled]).status.success());
+    let output = boggle(&["build-index", "test/dictionary", &out]);
+    assert!(output.status.success());
+    assert!(stderr(&output).contains("Indexed 178590 words, "), "{}", stderr(&output));
+    assert!(stderr(&output).ends_with("Checked the index against the word list on 3 boards\n"));
+    assert_eq!(std::fs::read(&out).unwrap(), std::fs::read(&compiled).unwrap());
+    assert!(!std::path::Path::new(&format!("{}.part", out)).exists());
+
+    // shards left by an interrupted build are used as they are
+    std::fs::create_dir_all(format!("{}.part", out)).unwrap();
+    std::fs::write(format!("{}.part/000000", out), "cats\n").unwrap();
+    std::fs::write(format!("{}.part/000001", out), "").unwrap();
+    let output = boggle(&["build-index", "test/dictionary", &out]);
+    assert!(stderr(&output).contains("Reused 2 shards from an interrupted build\n"), "{}", stderr(&output));
+    let board = format!("{}/cats.board", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&board, "cats\nxxxx\nxxxx\nxxxx\n").unwrap();
+    assert_eq!(stdout(&boggle(&["score", &out, &board])), "1\n");
+}
+
+#[test]
+fn dict_info() {
+    let out = format!("{}/licensed.compiled", env!("CARGO_TARGET_TMPDIR"));
+    let output = boggle(&["compile-dict", "--license", "CC0-1.0", "--attribution", "Word \"list\" authors", "test/dictionary", &out]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&boggle(&["dict-info", &out])),
+               "178590 words, compiled\nLicense: CC0-1.0\nAttribution: Word \"list\" authors\nSource: not recorded\n");
+    assert_eq!(stdout(&boggle(&["dict-info", "--json", &out])),
+               "{\"words\":178590,\"compiled\":true,\"license\":\"CC0-1.0\",\"attribution\":\"Word \\\"list\\\" authors\",\"source\":null}\n");
+    assert_eq!(stdout(&boggle(&["dict-info", "--json", "test/dictionary"])),
+               "{\"words\":178590,\"compiled\":false,\"license\":null,\"attribution\":null,\"source\":null}\n");
+}
+
+#[test]
+fn check() {
+    let output = boggle(&["check", "test/dictionary", "test/board1", "WAVE", "wait", "ma", "zzzq"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "wave: on the board, score 1\n 1  2  .  .\n .  3  .  .\n 4  .  .  .\n .  .  .  .\n\n\
+                                 wait: in the dictionary but not on the board\n\n\
+                                 ma: shorter than 3 letters\n\n\
+                                 zzzq: not in the dictionary\n");
+    let output = boggle_in("es_ES.UTF-8", &["check", "--min-length", "5", "test/dictionary", "test/board1", "wave"]);
+    assert_eq!(stdout(&output), "wave: tiene menos de 5 letras\n");
+}
+
+#[test]
+fn analyze() {
+    let output = boggle(&["analyze", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "13 27 22  1\n18 18 27 23\n47 63 57 38\n35 30 56 28\n\
+                                 Found 126 words in board\nTotal score: 165\nLongest word: fermate\n\
+                                 3 letters: 47 words, score 47\n4 letters: 51 words, score 51\n5 letters: 21 words, score 42\n\
+                                 6 letters: 5 words, score 15\n7 letters: 2 words, score 10\nNo dead cells\n");
+    let output = boggle(&["analyze", "--min-length", "6", "test/dictionary", "test/board1"]);
+    assert!(stdout(&output).ends_with("Dead cells, which no word uses: 0,2 0,3\n"), "{}", stdout(&output));
+}
+
+#[test]
+fn strings() {
+    let output = boggle(&["strings", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Longest word: fermate\n2 words sharing no tiles, score 6\n 2  2  2  .\n 1  .  .  .\n 1  1  1  1\n .  .  1  1\n 1 fermate 5\n 2 raw 1\n");
+    let output = boggle_in("es_ES.UTF-8", &["strings", "--min-length", "8", "test/dictionary", "test/board1"]);
+    assert_eq!(stdout(&output), "0 palabras sin casillas en común, puntuación 0\n .  .  .  .\n .  .  .  .\n .  .  .  .\n .  .  .  .\n");
+}
+
+#[test]
+fn trim() {
+    let out = format!("{}/trimmed", env!("CARGO_TARGET_TMPDIR"));
+    let output = boggle(&["trim", "test/dictionary", "test/board1", "-o", &out]);
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "Kept 126 of 178590 dictionary words\n");
+    let trimmed = std::fs::read_to_string(&out).unwrap();
+    assert_eq!(trimmed.lines().count(), 126);
+    assert!(trimmed.lines().any(|w| w == "fermate") && trimmed.ends_with('\n'));
+    // the trimmed list finds the same words, though it says it's another dictionary
+    let solved = |dictionary: &str| {
+        let output = boggle(&[dictionary, "test/board1"]);
+        stdout(&output).lines().filter(|l| !l.starts_with("Solved by")).collect::<Vec<_>>().join("\n")
+    };
+    assert_eq!(solved(&out), solved("test/dictionary"));
+
+    let output = boggle(&["trim", "--pruned", "--min-length", "4", "test/dictionary", "test/board1"]);
+    let pruned: Vec<_> = stdout(&output).lines().map(str::to_string).collect();
+    assert!(pruned.len() > 100 && pruned.iter().all(|w| w.len() >= 4));
+    assert!(pruned.windows(2).all(|w| w[0] < w[1]));
+    assert!(trimmed.lines().filter(|w| w.len() >= 4).all(|w| pruned.iter().any(|p| p == w)));
+}
+
+#[test]
+fn words() {
+    let output = boggle(&["words", "--anagram", "TAC", "test/dictionary"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "act\ncat\n");
+    let output = boggle(&["words", "--anagram", "tac?", "test/dictionary"]);
+    assert!(stdout(&output).lines().any(|w| w == "tact") && stdout(&output).lines().all(|w| w.len() <= 4));
+    assert_eq!(stdout(&boggle(&["words", "--pattern", "c?t", "test/dictionary"])), "cat\ncot\ncut\n");
+}
+
+#[test]
+fn duel() {
+    let (one, two) = (format!("{}/duel-one", env!("CARGO_TARGET_TMPDIR")), format!("{}/duel-two", env!("CARGO_TARGET_TMPDIR")));
+    std::fs::write(&one, "wave\namity\nwait\n").unwrap();
+    std::fs::write(&two, "WAVE\nma\n").unwrap();
+    let output = boggle(&["duel", "--top", "2", "test/dictionary", "test/board1", &one, &two]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "Player 1: score 2\n  amity 2\n  wave: found by both players, crossed off\n  \
+                                 wait: in the dictionary but not on the board\n\n\
+                                 Player 2: score 0\n  wave: found by both players, crossed off\n  ma: shorter than 3 letters\n\n\
+                                 Best words neither player found:\n  fermate 5\n  mitered 5\n");
+}
+
+#[test]
+fn daily() {
+    let play = |args: &[&str], words: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_boggle"))
+            .args(["daily", "--seconds", "60"])
+            .args(args)
+            .arg("test/dictionary")
+            .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+            .env("LANG", "C")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // closing stdin ends the round early
+        child.stdin.take().unwrap().write_all(words.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let played = play(&["--date", "2026-10-14"], "abed\nABET\nab\n");
+    assert_eq!(played, "BAEE\nNEEA\nMTDS\nTRVH\nThe board for 2026-10-14: type words, one per line, for 60 seconds\n\n  \
+                        abed 1\n  abet 1\n  ab: shorter than 3 letters\n\n\
+                        Boggle 2026-10-14 2/102 (2/84)\n🟩🟩⬜⬜\n⬜🟩⬜⬜\n⬜🟩🟩⬜\n⬜⬜⬜⬜\n");
+    // the next day has another board, and today's is the same each time it's played
+    assert!(!play(&["--date", "2026-10-15"], "").starts_with("BAEE"));
+    assert_eq!(play(&[], "")[..20], play(&[], "")[..20]);
+    assert!(!boggle(&["daily", "--date", "2026-02-30", "test/dictionary"]).status.success());
+
+    // with --data the result is kept, for the archive to show alongside the days not played
+    let data = format!("{}/daily-{}", env!("CARGO_TARGET_TMPDIR"), std::process::id());
+    play(&["--date", "2026-10-14", "--data", &data], "abed\n");
+    let output = boggle(&["daily", "--archive", "2", "--date", "2026-10-15", "--data", &data]);
+    assert!(output.status.success());
+    let archive = stdout(&output);
+    let lines: Vec<_> = archive.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("2026-10-15 ") && lines[0].ends_with(" not played"), "{}", archive);
+    assert_eq!(lines[1], "2026-10-14 BAEE/NEEA/MTDS/TRVH 1/102 points, 1/84 words");
+    std::fs::remove_dir_all(data).unwrap();
+}
+
+#[test]
+fn duel_over_tcp() {
+    use std::io::{BufRead, BufReader};
+
+    let spawn = |args: &[&str], words: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_boggle"))
+            .args(args)
+            .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+            .env("LANG", "C")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // closing stdin ends that player's round early
+        child.stdin.take().unwrap().write_all(words.as_bytes()).unwrap();
+        child
+    };
+    let mut host = spawn(&["duel", "--host", "--port", "0", "--seed", "1", "test/dictionary"], "zzzz\n");
+    let mut waiting = String::new();
+    BufReader::new(host.stderr.take().unwrap()).read_line(&mut waiting).unwrap();
+    let port = waiting.trim().rsplit(':').next().unwrap().to_string();
+    let join = spawn(&["duel", "--join", &format!("127.0.0.1:{}", port), "test/dictionary"], "qqqq\nzzzz\n");
+
+    let join = join.wait_with_output().unwrap();
+    let host = host.wait_with_output().unwrap();
+    assert!(host.status.success() && join.status.success(), "{}", stderr(&join));
+    let generated = stdout(&boggle(&["generate", "--seed", "1"])).to_uppercase();
+    for (output, player) in [(&host, 1), (&join, 2)] {
+        let start = format!("{}You're player {}: type words, one per line, for 180 seconds\n\n", generated, player);
+        assert!(stdout(output).starts_with(&start), "{}", stdout(output));
+    }
+    let result = |output: &Output| stdout(output).split("\n\n").skip(1).collect::<Vec<_>>().join("\n\n");
+    assert_eq!(result(&host), result(&join));
+    assert!(result(&host).starts_with("Player 1: score 0\n  zzzz: not in the dictionary\n\n\
+                                       Player 2: score 0\n  qqqq: not in the dictionary\n  zzzz: not in the dictionary\n\n"), "{}", result(&host));
+}
+
+#[test]
+fn several_boards() {
+    let one = |board| stdout(&boggle(&["test/dictionary", board])).to_string();
+    let output = boggle(&["test/dictionary", "test/board1", "test/board2"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("== test/board1 ==\n{}== test/board2 ==\n{}", one("test/board1"), one("test/board2")));
+
+    let dir = format!("{}/boards", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(concat!(env!("CARGO_MANIFEST_DIR"), "/../test/board1"), format!("{}/b", dir)).unwrap();
+    std::fs::copy(concat!(env!("CARGO_MANIFEST_DIR"), "/../test/board2"), format!("{}/a", dir)).unwrap();
+    let output = boggle(&["score", "--boards-dir", &dir, "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let lines: Vec<_> = stdout(&output).lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "test/board1 165");
+    assert!(lines[1].starts_with(&format!("{}/a ", dir)));
+    assert_eq!(lines[2], format!("{}/b 165", dir));
+
+    let output = boggle(&["--json", "test/dictionary", "test/board1", "test/board1"]);
+    assert!(stdout(&output).lines().all(|l| l.starts_with(r#"{"board":"test/board1","found":126,"#)));
+}
+
+#[test]
+fn optimize() {
+    let output = boggle(&["optimize", "--steps", "50", "--seed", "4", "test/dictionary"]);
+    assert!(output.status.success());
+    let board = stdout(&output).to_string();
+    assert_eq!(board.lines().count(), 4);
+    let score = String::from_utf8_lossy(&output.stderr).trim().trim_start_matches("Total score: ").to_string();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_boggle"))
+        .args(["score", "test/dictionary", "-"])
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(board.as_bytes()).unwrap();
+    let scored = child.wait_with_output().unwrap();
+    assert_eq!(stdout(&scored).trim(), score);
+}
+
+#[test]
+fn similar() {
+    let dir = format!("{}/similar", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(concat!(env!("CARGO_MANIFEST_DIR"), "/../test/board2"), format!("{}/other", dir)).unwrap();
+    // board1 turned a quarter turn, with one cell changed
+    let board1 = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../test/board1")).unwrap();
+    let rows: Vec<Vec<char>> = board1.lines().map(|l| l.chars().collect()).collect();
+    let mut turned: Vec<String> = (0..4).map(|j| (0..4).rev().map(|i| rows[i][j]).collect()).collect();
+    turned[0].replace_range(0..1, "z");
+    std::fs::write(format!("{}/turned", dir), turned.join("\n")).unwrap();
+
+    let output = boggle(&["similar", "--top", "1", "test/board1", &dir]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("1 {}/turned\n", dir));
+    let output = boggle(&["similar", "test/board1", &dir]);
+    assert_eq!(stdout(&output).lines().count(), 2);
+}
+
+#[test]
+fn submit() {
+    let dir = format!("{}/submit", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::create_dir_all(format!("{}/accepted", dir)).unwrap();
+    std::fs::write(format!("{}/accepted/old", dir), "zoon\ntaxe\nrbsi\nvyce\n").unwrap();
+    std::fs::write(format!("{}/board", dir), "zoon\ntaxe\nrbsi\nvycm\n").unwrap();
+    std::fs::write(format!("{}/banned", dir), "zoo\n").unwrap();
+
+    let output = boggle(&["submit", &format!("{}/board", dir)]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "{\"accepted\":true,\"legal_roll\":true,\"banned\":[],\"nearest\":null}\n");
+
+    let output = boggle(&["submit", "--banned", &format!("{}/banned", dir), "--boards-dir", &format!("{}/accepted", dir),
+                          &format!("{}/board", dir)]);
+    assert_eq!(stdout(&output), format!("{{\"accepted\":false,\"legal_roll\":true,\"banned\":[\"zoo\"],\"nearest\":{{\"board\":\"{}/accepted/old\",\"distance\":1}}}}\n", dir));
+}
+
+#[test]
+fn serve() {
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpStream;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_boggle"))
+        .args(["serve", "--port", "0", "--preset", "classic", "--preset", "big", "en=test/dictionary", "tiny=test/board1"])
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("LANG", "C")
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut listening = String::new();
+    BufReader::new(child.stderr.take().unwrap()).read_line(&mut listening).unwrap();
+    let addr = listening.trim().trim_start_matches("Listening on ").to_string();
+
+    let post = |body: &str| {
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        write!(stream, "POST /solve HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n\r\n{}", addr, body.len(), body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+    let response = post(r#"{"board": "cats\nxxxx\nxxxx\nxxxx"}"#);
+    let big = post(r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "dictionary": "en", "preset": "big"}"#);
+    // the board file read as a word list: "wari", "fvyi", "ermt" and "doea"
+    let tiny = post(r#"{"board": "wari\nxxxx\nxxxx\nxxxx", "dictionary": "tiny"}"#);
+    let refused = post(r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "preset": "word_hunt"}"#);
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.ends_with("\r\n\r\n{\"found\":3,\"score\":3,\"words\":[{\"word\":\"cat\",\"score\":1,\"path\":[[0,0],[0,1],[0,2]],\"id\":\"bd7469e353d1d57f\"},\
+                                {\"word\":\"cats\",\"score\":1,\"path\":[[0,0],[0,1],[0,2],[0,3]],\"id\":\"12b92392971786b0\"},\
+                                {\"word\":\"tax\",\"score\":1,\"path\":[[0,2],[0,1],[1,0]],\"id\":\"5ed2bf29f1c5d639\"}]}"), "{}", response);
+    assert!(big.ends_with("\r\n\r\n{\"found\":1,\"score\":1,\"words\":[{\"word\":\"cats\",\"score\":1,\"path\":[[0,0],[0,1],[0,2],[0,3]],\"id\":\"12b92392971786b0\"}]}"), "{}", big);
+    assert!(tiny.contains("{\"found\":1,"), "{}", tiny);
+    assert!(refused.starts_with("HTTP/1.1 400 "), "{}", refused);
+}
+
+#[test]
+fn debug_trie() {
+    let output = boggle(&["debug", "trie", "--prefix", "zyg", "--depth", "2", "test/dictionary"]);
+    assert!(output.status.success());
+    let dot = stdout(&output);
+    assert!(dot.starts_with("digraph trie {\n"));
+    assert!(dot.contains("label=\"zyg\""));
+    assert!(dot.lines().filter(|l| !l.contains(" -> ") && l.contains("label=")).all(|l| l.contains("label=\"zyg")));
+    assert!(dot.ends_with("}\n"));
+
+    let output = boggle(&["debug", "trie", "--prefix", "qqq", "test/dictionary"]);
+    assert_eq!(stdout(&output), "digraph trie {\n}\n");
+}
+
+#[test]
+fn debug_trace() {
+    let output = boggle(&["debug", "trace", "--limit", "3", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let json = stdout(&output);
+    assert!(json.starts_with(r#"{"truncated":true,"nodes":[{"parent":null,"cell":[0,0],"prefix":"#), "{}", json);
+    assert_eq!(json.matches(r#""step":"#).count(), 3);
+
+    let output = boggle(&["debug", "trace", "--dot", "--limit", "3", "test/dictionary", "test/board1"]);
+    let dot = stdout(&output);
+    assert!(dot.starts_with("digraph search {\n"));
+    assert_eq!(dot.matches(" [label=").count(), 3);
+}
+
+#[test]
+fn debug_profile() {
+    let output = boggle(&["debug", "profile", "--top", "4", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let lines: Vec<_> = stdout(&output).lines().map(|l| l.split(' ').collect::<Vec<_>>()).collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines.iter().all(|l| l.len() == 3 && l[1].ends_with("us") && l[2].parse::<usize>().is_ok()));
+}
+
+#[test]
+fn stdin_board() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_boggle"))
+        .args(["test/dictionary", "-"])
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("LANG", "C")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run boggle");
+    child.stdin.take().unwrap().write_all(include_bytes!("../../test/board1")).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Found 126 words in board\n"));
+}
+
+#[test]
+fn board_formats() {
+    let grid = boggle(&["test/dictionary", "test/board1"]);
+    for args in [&["test/dictionary", "--board", "wari,fvyi,ermt,doea"][..], &["test/dictionary", "test/board1.csv"],
+                 &["test/dictionary", "test/board1.json"], &["--format", "grid", "test/dictionary", "test/board1"]] {
+        let output = boggle(args);
+        assert!(output.status.success(), "{:?}", args);
+        assert_eq!(stdout(&output), stdout(&grid), "{:?}", args);
+    }
+    assert_eq!(stdout(&boggle(&["analyze", "test/dictionary", "test/board1.csv"])), stdout(&boggle(&["analyze", "test/dictionary", "test/board1"])));
+
+    let output = boggle(&["--format", "json", "test/dictionary", "test/board1"]);
+    assert!(!output.status.success());
+    assert_eq!(stderr(&output), "invalid JSON at byte 0\n");
+    let output = boggle(&["--board", "ab,c1", "test/dictionary"]);
+    assert_eq!(stderr(&output), "invalid character '1' in row 1, column 1\n");
+}
+
+#[test]
+fn show() {
+    let output = boggle(&["show", "test/dictionary", "test/board1", "Amity"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    let (board, path) = out.split_once("\n\n").unwrap();
+    assert_eq!(board.lines().count(), 4);
+    assert_eq!(board.matches('[').count(), 5);
+    assert!(!board.contains('\x1b'));
+    assert_eq!(path, stdout(&boggle(&["check", "test/dictionary", "test/board1", "amity"])).split_once('\n').unwrap().1);
+
+    let output = boggle(&["show", "--color", "always", "test/dictionary", "test/board1", "amity"]);
+    assert_eq!(stdout(&output).matches("\x1b[1;7m").count(), 5);
+    let output = boggle(&["show", "test/dictionary", "test/board1", "xyzzy"]);
+    assert_eq!(stdout(&output), "xyzzy: not in the dictionary\n");
+}
+
+#[test]
+fn stats() {
+    let output = boggle(&["--stats", "test/dictionary", "test/board1", "test/board1"]);
+    assert!(output.status.success());
+    let log = stderr(&output);
+    let lines: Vec<_> = log.lines().collect();
+    assert_eq!(lines.len(), 6, "{}", log);
+    assert_eq!(lines[0], "Loaded 395 words into 666 trie nodes, pruning 178195 the board can't spell");
+    assert_eq!(lines[1], "Searched 259 cells");
+    assert!(lines[2].starts_with("Took ") && lines[2].ends_with("ms finishing"), "{}", log);
+    // the same work for the same board, though not the same timings
+    assert_eq!(lines[..2], lines[3..5]);
+    assert_eq!(stdout(&output).matches("Found 126 words in board").count(), 2);
+}
+
+#[test]
+fn progress() {
+    let output = boggle(&["--progress", "test/dictionary", "test/board1", "test/board1"]);
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), format!("\r[{}] 0/2 boards\r[{}{}] 1/2 boards\r[{}] 2/2 boards\n",
+                                        "-".repeat(30), "#".repeat(15), "-".repeat(15), "#".repeat(30)));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_boggle"))
+        .args(["optimize", "--steps", "10", "--seed", "1", "test/dictionary"])
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("LANG", "C")
+        .env("BOGGLE_LOG", "debug")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let log = stderr(&output);
+    assert!(log.contains("[debug boggle::dictionary] loaded "), "{}", log);
+    assert!(log.contains("[debug boggle::trie] built a trie of "), "{}", log);
+    assert!(log.contains("[debug boggle::optimize] step 9 of 10: "), "{}", log);
+}
+
+#[test]
+fn bench() {
+    let output = boggle(&["bench", "--iterations", "1", "test/dictionary", "test/board1"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    let rows: Vec<_> = out.lines().collect();
+    assert_eq!(rows.len(), 10);
+    assert!(rows[0].starts_with("solver") && rows[0].ends_with("allocations"));
+    for (row, solver) in rows[1..6].iter().zip(&["single-threaded", "trie", "parallel", "recursive", "dawg"]) {
+        let columns: Vec<_> = row.split_whitespace().collect();
+        assert_eq!((columns[0], columns[1]), (*solver, "126"), "{}", row);
+        assert!(columns[2].ends_with("ms"));
+        assert_ne!(columns[4], "0", "{}", row);
+    }
+    // the builds count every word in the dictionary
+    assert!(rows[6].starts_with("compile ") && rows[7].starts_with("compile parallel "));
+    assert_eq!(rows[6].split_whitespace().nth(1), rows[7].split_whitespace().nth(2));
+    assert!(rows[8].starts_with("parallel compile: ") && rows[8].ends_with("x as fast"));
+    let pruning: Vec<usize> = rows[9].split_whitespace().filter_map(|n| n.parse().ok()).collect();
+    assert!(rows[9].starts_with("pruning: ") && pruning.len() == 3, "{}", rows[9]);
+    assert!(pruning[0] >= 126 && pruning[0] <= pruning[2] && pruning[2] <= pruning[1], "{}", rows[9]);
+}
+
+#[test]
+fn selftest() {
+    let output = boggle(&["selftest"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("boggle "));
+    assert!(out.contains("trie: 126 words ok"));
+    assert!(out.contains("single-threaded: 126 words ok"));
+    assert!(out.contains("dawg: 126 words ok"));
+}
@@ -0,0 +1,162 @@
+//! Runs the same board and dictionary through this crate and any number of
+//! other solvers, then reports how long each took and where their answers
+//! differ from ours.
+//!
+//! ```text
+//! cargo run --release --example compare -- [--runs N] [--json] dictionary board [solver...]
+//! ```
+//!
+//! Each solver is a program run as `solver dictionary board`. The first word
+//! of every line it prints counts as a found word if it's in the dictionary,
+//! so a plain word list works, and so does `boggle` itself.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::{self, Command};
+use std::time::{Duration, Instant};
+
+use boggle::{Board, SolveOptions};
+
+/// Anything that can solve a board, in process or not.
+trait Solver {
+    fn name(&self) -> String;
+    fn solve(&self, dictionary_path: &str, board_path: &str) -> Result<BTreeSet<String>, String>;
+}
+
+struct ThisCrate;
+
+impl Solver for ThisCrate {
+    fn name(&self) -> String {
+        format!("boggle {} (in process)", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn solve(&self, dictionary_path: &str, board_path: &str) -> Result<BTreeSet<String>, String> {
+        let dictionary = fs::read_to_string(dictionary_path).map_err(|e| e.to_string())?;
+        let raw = fs::read_to_string(board_path).map_err(|e| e.to_string())?;
+        let board = Board::parse(&raw).map_err(|e| e.to_string())?;
+        Ok(board.solve(&dictionary, &SolveOptions::default()).iter().map(|s| s.word.to_string()).collect())
+    }
+}
+
+struct Binary {
+    path: String,
+    words: BTreeSet<String>,
+}
+
+impl Solver for Binary {
+    fn name(&self) -> String {
+        self.path.clone()
+    }
+
+    fn solve(&self, dictionary_path: &str, board_path: &str) -> Result<BTreeSet<String>, String> {
+        let output = Command::new(&self.path).args([dictionary_path, board_path]).output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(format!("exited with {}", output.status));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|word| self.words.contains(*word))
+            .map(String::from)
+            .collect())
+    }
+}
+
+struct Row {
+    name: String,
+    median: Duration,
+    found: usize,
+    missing: Vec<String>,
+    extra: Vec<String>,
+}
+
+fn run(solver: &dyn Solver, runs: usize, dictionary: &str, board: &str) -> Result<(Duration, BTreeSet<String>), String> {
+    let mut times = Vec::with_capacity(runs);
+    let mut words = BTreeSet::new();
+    for _ in 0..runs {
+        let start = Instant::now();
+        words = solver.solve(dictionary, board)?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+    Ok((times[times.len() / 2], words))
+}
+
+fn markdown(rows: &[Row]) {
+    println!("| solver | median | words | missing | extra |");
+    println!("|---|---:|---:|---:|---:|");
+    for row in rows {
+        println!("| {} | {:.2?} | {} | {} | {} |", row.name, row.median, row.found, row.missing.len(), row.extra.len());
+    }
+    for row in rows.iter().filter(|r| !r.missing.is_empty() || !r.extra.is_empty()) {
+        println!();
+        println!("**{}** missing: {}", row.name, row.missing.join(", "));
+        println!("**{}** extra: {}", row.name, row.extra.join(", "));
+    }
+}
+
+fn json(rows: &[Row]) {
+    let list = |words: &[String]| words.iter().map(|w| format!("{:?}", w)).collect::<Vec<_>>().join(",");
+    let rows: Vec<_> = rows.iter()
+        .map(|r| format!("{{\"solver\":{:?},\"median_ns\":{},\"words\":{},\"missing\":[{}],\"extra\":[{}]}}",
+                         r.name, r.median.as_nanos(), r.found, list(&r.missing), list(&r.extra)))
+        .collect();
+    println!("[{}]", rows.join(","));
+}
+
+fn main() {
+    let mut runs = 5;
+    let mut as_json = false;
+    let mut positional = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => runs = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0).unwrap_or_else(|| usage()),
+            "--json" => as_json = true,
+            _ => positional.push(arg),
+        }
+    }
+    if positional.len() < 2 {
+        usage();
+    }
+    let (dictionary, board) = (&positional[0], &positional[1]);
+
+    let words: BTreeSet<_> = match fs::read_to_string(dictionary) {
+        Ok(raw) => raw.lines().map(|w| w.trim().to_string()).collect(),
+        Err(err) => fail(&format!("{}: {}", dictionary, err)),
+    };
+    let mut solvers: Vec<Box<dyn Solver>> = vec![Box::new(ThisCrate)];
+    for path in &positional[2..] {
+        solvers.push(Box::new(Binary { path: path.clone(), words: words.clone() }));
+    }
+
+    let mut rows = Vec::new();
+    let mut reference = None;
+    for solver in &solvers {
+        let (median, found) = run(solver.as_ref(), runs, dictionary, board)
+            .unwrap_or_else(|err| fail(&format!("{}: {}", solver.name(), err)));
+        let expected = reference.get_or_insert_with(|| found.clone());
+        rows.push(Row {
+            name: solver.name(),
+            median,
+            found: found.len(),
+            missing: expected.difference(&found).cloned().collect(),
+            extra: found.difference(expected).cloned().collect(),
+        });
+    }
+
+    if as_json {
+        json(&rows);
+    } else {
+        markdown(&rows);
+    }
+}
+
+fn usage() -> ! {
+    fail("USAGE: compare [--runs N] [--json] dictionary board [solver...]")
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
@@ -0,0 +1,95 @@
+#ifndef BOGGLE_H
+#define BOGGLE_H
+
+/* Generated with `cbindgen --config cbindgen.toml --output include/boggle.h` from ffi/; don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A board parsed by `boggle_board_parse`.
+typedef struct BoggleBoard BoggleBoard;
+
+// A word list loaded by `boggle_dict_load`, ready to solve any number of
+// boards with.
+typedef struct BoggleDict BoggleDict;
+
+// A board cell, counting from the top left.
+typedef struct BoggleCell {
+  uintptr_t row;
+  uintptr_t col;
+} BoggleCell;
+
+// A word found on a board and the cells spelling it out.
+typedef struct BoggleWord {
+  const char *word;
+  uint32_t score;
+  const struct BoggleCell *path;
+  uintptr_t path_len;
+} BoggleWord;
+
+// What `boggle_solve` found, highest scoring first.
+typedef struct BoggleResults {
+  const struct BoggleWord *words;
+  uintptr_t len;
+} BoggleResults;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The message for the last call on this thread that returned NULL, or NULL
+// if there hasn't been one. It's valid until the next failing call on the
+// thread.
+const char *boggle_last_error(void);
+
+// Loads a word list, one word per line, or a dictionary compiled with
+// `boggle compile-dict`, from the file at `path`.
+//
+// # Safety
+//
+// `path` must be a valid NUL-terminated string.
+struct BoggleDict *boggle_dict_load(const char *path);
+
+// Frees a dictionary from `boggle_dict_load`.
+//
+// # Safety
+//
+// `dict` must be NULL or from `boggle_dict_load`, and not already freed.
+void boggle_dict_free(struct BoggleDict *dict);
+
+// Parses a board, one row per line, e.g. `"cats\nxxxx\nxxxx\nxxxx"`.
+//
+// # Safety
+//
+// `text` must be a valid NUL-terminated string.
+struct BoggleBoard *boggle_board_parse(const char *text);
+
+// Frees a board from `boggle_board_parse`.
+//
+// # Safety
+//
+// `board` must be NULL or from `boggle_board_parse`, and not already freed.
+void boggle_board_free(struct BoggleBoard *board);
+
+// Finds every word from `dict` on `board`, with classic scoring. The
+// results don't borrow from either, so they can be freed in any order.
+//
+// # Safety
+//
+// `dict` and `board` must be live handles; neither may be NULL.
+struct BoggleResults *boggle_solve(const struct BoggleDict *dict, const struct BoggleBoard *board);
+
+// Frees results from `boggle_solve`.
+//
+// # Safety
+//
+// `results` must be NULL or from `boggle_solve`, and not already freed.
+void boggle_free_results(struct BoggleResults *results);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BOGGLE_H */
@@ -0,0 +1,243 @@
+//! A C interface to the solver, for game engines and other languages that
+//! can call C. `include/boggle.h` declares it; regenerate it with cbindgen
+//! (see `cbindgen.toml`) after changing anything here.
+//!
+//! Each handle comes from one function and goes back to its `_free`
+//! function, which ignores NULL. Functions that fail return NULL and leave a
+//! message for `boggle_last_error`. Strings are NUL-terminated UTF-8.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io;
+use std::mem::ManuallyDrop;
+use std::os::raw::c_char;
+use std::ptr;
+use std::str;
+
+use boggle::compiled::{self, CompiledDictionary};
+use boggle::{Board, Dictionary, Error, SolveOptions, Solutions, Trie};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail<T>(err: Error) -> *mut T {
+    let message = CString::new(err.to_string()).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    ptr::null_mut()
+}
+
+unsafe fn text<'a>(s: *const c_char) -> Result<&'a str, Error> {
+    CStr::from_ptr(s).to_str().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into())
+}
+
+/// A word list loaded by `boggle_dict_load`, ready to solve any number of
+/// boards with.
+pub struct BoggleDict {
+    trie: ManuallyDrop<Trie<'static>>,
+    // owned by the handle and borrowed by `trie`
+    words: *mut str,
+}
+
+impl Drop for BoggleDict {
+    fn drop(&mut self) {
+        unsafe {
+            ManuallyDrop::drop(&mut self.trie);
+            drop(Box::from_raw(self.words));
+        }
+    }
+}
+
+/// A board parsed by `boggle_board_parse`.
+pub struct BoggleBoard {
+    board: Board,
+}
+
+/// A board cell, counting from the top left.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoggleCell {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A word found on a board and the cells spelling it out.
+#[repr(C)]
+pub struct BoggleWord {
+    pub word: *const c_char,
+    pub score: u32,
+    pub path: *const BoggleCell,
+    pub path_len: usize,
+}
+
+/// What `boggle_solve` found, highest scoring first.
+#[repr(C)]
+pub struct BoggleResults {
+    pub words: *const BoggleWord,
+    pub len: usize,
+}
+
+// what a `BoggleResults` points into, freed with it
+#[repr(C)]
+struct OwnedResults {
+    results: BoggleResults,
+    words: Vec<BoggleWord>,
+    strings: Vec<CString>,
+    paths: Vec<Vec<BoggleCell>>,
+}
+
+/// The message for the last call on this thread that returned NULL, or NULL
+/// if there hasn't been one. It's valid until the next failing call on the
+/// thread.
+#[no_mangle]
+pub extern "C" fn boggle_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Loads a word list, one word per line, or a dictionary compiled with
+/// `boggle compile-dict`, from the file at `path`.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_dict_load(path: *const c_char) -> *mut BoggleDict {
+    let load = || -> Result<String, Error> {
+        let raw = fs::read(text(path)?)?;
+        if compiled::is_compiled(&raw) {
+            Ok(CompiledDictionary::from_bytes(&raw)?.as_str().to_string())
+        } else {
+            let raw = str::from_utf8(&raw).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            Ok(Dictionary::parse(raw).as_str().to_string())
+        }
+    };
+    match load() {
+        Ok(words) => {
+            let words = Box::into_raw(words.into_boxed_str());
+            let trie = ManuallyDrop::new(Trie::from_dictionary(&*words));
+            Box::into_raw(Box::new(BoggleDict { trie, words }))
+        }
+        Err(err) => fail(err),
+    }
+}
+
+/// Frees a dictionary from `boggle_dict_load`.
+///
+/// # Safety
+///
+/// `dict` must be NULL or from `boggle_dict_load`, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_dict_free(dict: *mut BoggleDict) {
+    if !dict.is_null() {
+        drop(Box::from_raw(dict));
+    }
+}
+
+/// Parses a board, one row per line, e.g. `"cats\nxxxx\nxxxx\nxxxx"`.
+///
+/// # Safety
+///
+/// `text` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_board_parse(text: *const c_char) -> *mut BoggleBoard {
+    match self::text(text).and_then(Board::parse) {
+        Ok(board) => Box::into_raw(Box::new(BoggleBoard { board })),
+        Err(err) => fail(err),
+    }
+}
+
+/// Frees a board from `boggle_board_parse`.
+///
+/// # Safety
+///
+/// `board` must be NULL or from `boggle_board_parse`, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_board_free(board: *mut BoggleBoard) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Finds every word from `dict` on `board`, with classic scoring. The
+/// results don't borrow from either, so they can be freed in any order.
+///
+/// # Safety
+///
+/// `dict` and `board` must be live handles; neither may be NULL.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_solve(dict: *const BoggleDict, board: *const BoggleBoard) -> *mut BoggleResults {
+    let mut solutions = Solutions((*board).board.solve_with_trie(&(*dict).trie, &SolveOptions::default()));
+    solutions.sort_by_score();
+
+    let strings: Vec<_> = solutions.iter()
+        .map(|s| CString::new(s.word).expect("dictionary words are letters"))
+        .collect();
+    let paths: Vec<Vec<_>> = solutions.iter()
+        .map(|s| s.path.iter().map(|&(row, col)| BoggleCell { row, col }).collect())
+        .collect();
+    let words: Vec<_> = solutions.iter()
+        .zip(&strings)
+        .zip(&paths)
+        .map(|((s, word), path)| BoggleWord { word: word.as_ptr(), score: s.score, path: path.as_ptr(), path_len: path.len() })
+        .collect();
+
+    let results = BoggleResults { words: words.as_ptr(), len: words.len() };
+    Box::into_raw(Box::new(OwnedResults { results, words, strings, paths })) as *mut BoggleResults
+}
+
+/// Frees results from `boggle_solve`.
+///
+/// # Safety
+///
+/// `results` must be NULL or from `boggle_solve`, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn boggle_free_results(results: *mut BoggleResults) {
+    if !results.is_null() {
+        drop(Box::from_raw(results as *mut OwnedResults));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::slice;
+
+    #[test]
+    fn solve() {
+        let path = std::env::temp_dir().join(format!("boggle-ffi-{}", std::process::id()));
+        fs::write(&path, "CAT\ncats\ndog\n").unwrap();
+        let path = CString::new(path.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let dict = boggle_dict_load(path.as_ptr());
+            let board = boggle_board_parse(b"cats\nxxxx\nxxxx\nxxxx\0".as_ptr() as *const c_char);
+            assert!(!dict.is_null() && !board.is_null());
+            let results = boggle_solve(dict, board);
+            boggle_board_free(board);
+            boggle_dict_free(dict);
+
+            let words = slice::from_raw_parts((*results).words, (*results).len);
+            let found: Vec<_> = words.iter().map(|w| (CStr::from_ptr(w.word).to_str().unwrap(), w.score)).collect();
+            assert_eq!(found, vec![("cat", 1), ("cats", 1)]);
+            let path = slice::from_raw_parts(words[1].path, words[1].path_len);
+            assert_eq!(path.last(), Some(&BoggleCell { row: 0, col: 3 }));
+            boggle_free_results(results);
+
+            assert!(boggle_board_parse(b"c4ts\0".as_ptr() as *const c_char).is_null());
+            assert!(CStr::from_ptr(boggle_last_error()).to_str().unwrap().contains("invalid character"));
+            assert!(boggle_dict_load(b"/no/such/dictionary\0".as_ptr() as *const c_char).is_null());
+            boggle_dict_free(ptr::null_mut());
+        }
+        fs::remove_file(path.to_str().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn header() {
+        let header = include_str!("../include/boggle.h");
+        for line in include_str!("lib.rs").lines().filter(|l| l.contains("extern \"C\" fn ")) {
+            let name = line.split("fn ").nth(1).and_then(|rest| rest.split('(').next()).unwrap();
+            assert!(header.contains(&format!("{}(", name)), "{} is missing from include/boggle.h", name);
+        }
+    }
+}
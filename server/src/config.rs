@@ -0,0 +1,382 @@
+//! Server configuration, built in code with [`ServerConfig::builder`] or read
+//! from TOML with [`ServerConfig::from_toml`]; both go through the same
+//! checks, so a `ServerConfig` is always usable.
+//!
+//! ```toml
+//! bind = "0.0.0.0:8080"
+//! presets = ["classic", "big"]
+//! cache_size = 4096
+//! rate_limit = 120
+//! time_limit_ms = 500
+//!
+//! [[dictionaries]]
+//! name = "en"
+//! path = "/usr/share/boggle/en.dict"
+//!
+//! [storage]
+//! files = "/var/lib/boggle"
+//! ```
+
+use std::error;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use boggle::score;
+use boggle::SolveOptions;
+use serde::Deserialize;
+
+/// A named word list the server loads at startup.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DictionaryConfig {
+    pub name: String,
+    /// A word list, or one compiled with `boggle compile-dict`.
+    pub path: PathBuf,
+}
+
+/// A set of house rules clients may ask to be solved under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    /// Classic scoring, words of 3 letters or more.
+    Classic,
+    /// Big Boggle: classic scoring, words of 4 letters or more.
+    Big,
+    /// Word Hunt's scoring.
+    WordHunt,
+}
+
+impl Preset {
+    /// The solve options the preset stands for.
+    pub fn options(self) -> SolveOptions {
+        use Preset::*;
+        match self {
+            Classic => SolveOptions::default(),
+            Big => SolveOptions { min_len: Some(4), ..SolveOptions::default() },
+            WordHunt => SolveOptions { scorer: Box::new(score::WordHunt), ..SolveOptions::default() },
+        }
+    }
+}
+
+/// Parses a preset's name as the config spells it, e.g. `word_hunt`.
+impl FromStr for Preset {
+    type Err = ConfigError;
+
+    fn from_str(name: &str) -> Result<Preset, ConfigError> {
+        match name {
+            "classic" => Ok(Preset::Classic),
+            "big" => Ok(Preset::Big),
+            "word_hunt" => Ok(Preset::WordHunt),
+            _ => Err(ConfigError::UnknownPreset(name.to_string())),
+        }
+    }
+}
+
+/// Where the server keeps what it saves between runs: for now, each solved
+/// board's answer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum StorageConfig {
+    /// Nothing outlives the process.
+    Memory,
+    /// Files under a directory; see `boggle::storage::FileStorage`.
+    Files(PathBuf),
+}
+
+/// Everything that can be wrong with a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The TOML didn't parse, or had the wrong keys or types.
+    Toml(String),
+    /// No dictionary to solve with.
+    NoDictionaries,
+    /// Two dictionaries have this name.
+    DuplicateDictionary(String),
+    /// No preset is allowed, so no request could be answered.
+    NoPresets,
+    /// No preset has this name.
+    UnknownPreset(String),
+    /// A rate limit of zero requests, which would refuse everything.
+    ZeroRateLimit,
+    /// A time limit of zero, which would cut every solve short.
+    ZeroTimeLimit,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConfigError::*;
+        match *self {
+            Toml(ref err) => write!(f, "invalid config: {}", err),
+            NoDictionaries => write!(f, "no dictionaries configured"),
+            DuplicateDictionary(ref name) => write!(f, "more than one dictionary named {:?}", name),
+            NoPresets => write!(f, "no rules presets allowed"),
+            UnknownPreset(ref name) => write!(f, "no rules preset named {:?}", name),
+            ZeroRateLimit => write!(f, "rate limit must allow at least one request a minute"),
+            ZeroTimeLimit => write!(f, "time limit must be more than zero"),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+/// A checked server configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    bind: SocketAddr,
+    dictionaries: Vec<DictionaryConfig>,
+    presets: Vec<Preset>,
+    cache_size: usize,
+    rate_limit: Option<u32>,
+    time_limit: Option<Duration>,
+    storage: StorageConfig,
+}
+
+impl ServerConfig {
+    /// A builder with every setting at its default.
+    pub fn builder() -> ServerConfigBuilder {
+        ServerConfigBuilder::default()
+    }
+
+    /// Reads a configuration from TOML; settings it leaves out keep the
+    /// builder's defaults.
+    pub fn from_toml(text: &str) -> Result<ServerConfig, ConfigError> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct File {
+            bind: Option<SocketAddr>,
+            #[serde(default)]
+            dictionaries: Vec<DictionaryConfig>,
+            presets: Option<Vec<Preset>>,
+            cache_size: Option<usize>,
+            rate_limit: Option<u32>,
+            time_limit_ms: Option<u64>,
+            storage: Option<StorageConfig>,
+        }
+
+        let file: File = toml::from_str(text).map_err(|err| ConfigError::Toml(err.message().to_string()))?;
+        let mut builder = ServerConfig::builder();
+        builder.dictionaries = file.dictionaries;
+        if let Some(bind) = file.bind {
+            builder = builder.bind(bind);
+        }
+        if let Some(presets) = file.presets {
+            builder.presets = presets;
+        }
+        if let Some(n) = file.cache_size {
+            builder = builder.cache_size(n);
+        }
+        builder.rate_limit = file.rate_limit;
+        builder.time_limit = file.time_limit_ms.map(Duration::from_millis);
+        if let Some(storage) = file.storage {
+            builder = builder.storage(storage);
+        }
+        builder.build()
+    }
+
+    pub fn bind(&self) -> SocketAddr {
+        self.bind
+    }
+
+    /// Changes the port bound, e.g. for a command-line flag overriding the
+    /// config file.
+    pub fn set_port(&mut self, port: u16) {
+        self.bind.set_port(port);
+    }
+
+    /// At least one, with distinct names; the first is used when a request
+    /// doesn't name one.
+    pub fn dictionaries(&self) -> &[DictionaryConfig] {
+        &self.dictionaries
+    }
+
+    /// At least one; the first is used when a request doesn't name one.
+    pub fn presets(&self) -> &[Preset] {
+        &self.presets
+    }
+
+    /// How many solved boards to keep answers for; 0 turns the cache off.
+    pub fn cache_size(&self) -> usize {
+        self.cache_size
+    }
+
+    /// Requests allowed per client per minute, or `None` for no limit.
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limit
+    }
+
+    /// How long a solve may search before answering with the words found so
+    /// far, or `None` to always finish.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub fn storage(&self) -> &StorageConfig {
+        &self.storage
+    }
+}
+
+/// Builds a [`ServerConfig`]. Defaults: bind to `127.0.0.1:8080`, allow
+/// only the classic preset, cache 1024 boards, no rate or time limit, keep
+/// nothing.
+/// There's no default dictionary.
+#[derive(Debug, Clone)]
+pub struct ServerConfigBuilder {
+    bind: SocketAddr,
+    dictionaries: Vec<DictionaryConfig>,
+    presets: Vec<Preset>,
+    cache_size: usize,
+    rate_limit: Option<u32>,
+    time_limit: Option<Duration>,
+    storage: StorageConfig,
+}
+
+impl Default for ServerConfigBuilder {
+    fn default() -> Self {
+        ServerConfigBuilder {
+            bind: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            dictionaries: Vec::new(),
+            presets: vec![Preset::Classic],
+            cache_size: 1024,
+            rate_limit: None,
+            time_limit: None,
+            storage: StorageConfig::Memory,
+        }
+    }
+}
+
+impl ServerConfigBuilder {
+    pub fn bind(mut self, bind: SocketAddr) -> Self {
+        self.bind = bind;
+        self
+    }
+
+    /// Adds a dictionary to load from `path`.
+    pub fn dictionary<N: Into<String>, P: Into<PathBuf>>(mut self, name: N, path: P) -> Self {
+        self.dictionaries.push(DictionaryConfig { name: name.into(), path: path.into() });
+        self
+    }
+
+    /// Replaces the allowed presets.
+    pub fn presets<I: IntoIterator<Item = Preset>>(mut self, presets: I) -> Self {
+        self.presets = presets.into_iter().collect();
+        self
+    }
+
+    pub fn cache_size(mut self, boards: usize) -> Self {
+        self.cache_size = boards;
+        self
+    }
+
+    pub fn rate_limit(mut self, per_minute: u32) -> Self {
+        self.rate_limit = Some(per_minute);
+        self
+    }
+
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    pub fn storage(mut self, storage: StorageConfig) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    pub fn build(self) -> Result<ServerConfig, ConfigError> {
+        if self.dictionaries.is_empty() {
+            return Err(ConfigError::NoDictionaries);
+        }
+        for (i, dictionary) in self.dictionaries.iter().enumerate() {
+            if self.dictionaries[..i].iter().any(|d| d.name == dictionary.name) {
+                return Err(ConfigError::DuplicateDictionary(dictionary.name.clone()));
+            }
+        }
+        if self.presets.is_empty() {
+            return Err(ConfigError::NoPresets);
+        }
+        if self.rate_limit == Some(0) {
+            return Err(ConfigError::ZeroRateLimit);
+        }
+        if self.time_limit == Some(Duration::ZERO) {
+            return Err(ConfigError::ZeroTimeLimit);
+        }
+
+        let mut presets = self.presets;
+        let mut seen = Vec::new();
+        presets.retain(|p| if seen.contains(p) { false } else { seen.push(*p); true });
+        Ok(ServerConfig {
+            bind: self.bind,
+            dictionaries: self.dictionaries,
+            presets,
+            cache_size: self.cache_size,
+            rate_limit: self.rate_limit,
+            time_limit: self.time_limit,
+            storage: self.storage,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let config = ServerConfig::from_toml(r#"
+            bind = "0.0.0.0:9000"
+            presets = ["big", "word_hunt", "big"]
+            rate_limit = 60
+            time_limit_ms = 250
+
+            [[dictionaries]]
+            name = "en"
+            path = "en.dict"
+
+            [storage]
+            files = "/var/lib/boggle"
+        "#).unwrap();
+        let built = ServerConfig::builder()
+            .bind("0.0.0.0:9000".parse().unwrap())
+            .dictionary("en", "en.dict")
+            .presets(vec![Preset::Big, Preset::WordHunt])
+            .rate_limit(60)
+            .time_limit(Duration::from_millis(250))
+            .storage(StorageConfig::Files("/var/lib/boggle".into()))
+            .build()
+            .unwrap();
+        assert_eq!(config, built);
+        assert_eq!(config.cache_size(), 1024);
+        assert_eq!(config.presets(), &[Preset::Big, Preset::WordHunt]);
+
+        let minimal = ServerConfig::from_toml("[[dictionaries]]\nname = \"en\"\npath = \"en\"").unwrap();
+        assert_eq!(minimal.bind(), SocketAddr::from(([127, 0, 0, 1], 8080)));
+        assert_eq!(minimal.storage(), &StorageConfig::Memory);
+    }
+
+    #[test]
+    fn invalid() {
+        let one = ServerConfig::builder().dictionary("en", "en");
+        assert_eq!(ServerConfig::builder().build(), Err(ConfigError::NoDictionaries));
+        assert_eq!(one.clone().dictionary("en", "other").build(), Err(ConfigError::DuplicateDictionary("en".to_string())));
+        assert_eq!(one.clone().presets(None).build(), Err(ConfigError::NoPresets));
+        assert_eq!(one.clone().rate_limit(0).build(), Err(ConfigError::ZeroRateLimit));
+        assert_eq!(one.time_limit(Duration::ZERO).build(), Err(ConfigError::ZeroTimeLimit));
+
+        for bad in &["bind = 8080", "colour = \"red\"", "presets = [\"speed\"]", "[storage]\nredis = \"x\""] {
+            assert!(matches!(ServerConfig::from_toml(bad), Err(ConfigError::Toml(_))), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn presets() {
+        let board = boggle::Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        assert_eq!(board.solve("cat\ncats", &Preset::Classic.options()).len(), 2);
+        assert_eq!(board.solve("cat\ncats", &Preset::Big.options()).len(), 1);
+        assert_eq!(board.solve("cats", &Preset::WordHunt.options())[0].score, 400);
+        assert_eq!("word_hunt".parse(), Ok(Preset::WordHunt));
+        assert_eq!("speed".parse::<Preset>(), Err(ConfigError::UnknownPreset("speed".to_string())));
+    }
+}
@@ -0,0 +1,189 @@
+//! Games played against the server's clock for spectators to follow: a board
+//! and a timer that players report the words they find to, and that anyone
+//! can watch. Watching shows how many words each player has found but never
+//! which, so players can watch too.
+//!
+//! Without websockets, spectators long-poll: they ask for a game as of a
+//! version they've seen, and the answer waits until it changes, the game
+//! ends or [`MAX_WAIT`] passes. Every spectator waiting is woken by the same
+//! change, so each report reaches all of them at once.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// The longest a watch waits for a change; each waiting spectator holds one
+/// of the server's connections meanwhile.
+pub const MAX_WAIT: Duration = Duration::from_secs(25);
+
+/// The most games kept at once, finished ones included.
+pub const MAX_GAMES: usize = 1024;
+
+/// The longest game that can be started.
+pub const MAX_SECONDS: u64 = 60 * 60;
+
+// how long a finished game can still be watched before a new one may replace it
+const KEPT: Duration = Duration::from_secs(60 * 60);
+
+struct Game {
+    board: String,
+    // the board's words, which no view shows
+    words: HashSet<String>,
+    length: Duration,
+    started: Instant,
+    players: BTreeMap<String, HashSet<String>>,
+    version: u64,
+}
+
+impl Game {
+    fn left(&self, now: Instant) -> Duration {
+        self.length.saturating_sub(now.duration_since(self.started))
+    }
+
+    fn view(&self, id: u64, now: Instant) -> View {
+        let left = self.left(now);
+        View {
+            game: id,
+            board: self.board.clone(),
+            // a game with any time left isn't shown as having none
+            seconds_left: (left.as_millis() as u64).div_ceil(1000),
+            over: left.is_zero(),
+            players: self.players.iter().map(|(name, found)| (name.clone(), found.len())).collect(),
+            version: self.version,
+        }
+    }
+}
+
+/// What anyone watching a game sees of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct View {
+    pub game: u64,
+    pub board: String,
+    pub seconds_left: u64,
+    pub over: bool,
+    /// Each player and how many words they've found.
+    pub players: BTreeMap<String, usize>,
+    /// Up by one with each word found, for waiting on the next.
+    pub version: u64,
+}
+
+/// Every game going, and the spectators waiting on them.
+#[derive(Default)]
+pub struct Games {
+    // the next game's id, and the games
+    games: Mutex<(u64, HashMap<u64, Game>)>,
+    changed: Condvar,
+}
+
+impl Games {
+    pub fn new() -> Games {
+        Games::default()
+    }
+
+    /// Starts a game of `seconds` on `board`, whose words are `words`, and
+    /// shows it, or gives `None` if [`MAX_GAMES`] are already kept.
+    pub fn start(&self, board: String, words: HashSet<String>, seconds: u64) -> Option<View> {
+        let now = Instant::now();
+        let mut games = self.games.lock().unwrap();
+        if games.1.len() >= MAX_GAMES {
+            games.1.retain(|_, game| now.duration_since(game.started) < game.length + KEPT);
+            if games.1.len() >= MAX_GAMES {
+                return None;
+            }
+        }
+        let id = games.0;
+        games.0 += 1;
+        let game = Game { board, words, length: Duration::from_secs(seconds), started: now, players: BTreeMap::new(), version: 0 };
+        let view = game.view(id, now);
+        games.1.insert(id, game);
+        Some(view)
+    }
+
+    /// Adds those of `found` that are on the board to `player`'s words while
+    /// the game lasts, waking its spectators if any were new, and shows the
+    /// game, or gives `None` if there's no game `id`.
+    pub fn found(&self, id: u64, player: &str, found: &[String]) -> Option<View> {
+        let now = Instant::now();
+        let mut games = self.games.lock().unwrap();
+        let game = games.1.get_mut(&id)?;
+        if !game.left(now).is_zero() {
+            let words = &game.words;
+            let kept = game.players.entry(player.to_string()).or_default();
+            let before = kept.len();
+            kept.extend(found.iter().map(|word| word.to_lowercase()).filter(|word| words.contains(word)));
+            if kept.len() > before {
+                game.version += 1;
+                self.changed.notify_all();
+            }
+        }
+        Some(game.view(id, now))
+    }
+
+    /// Shows game `id` once its version is past `after`, it's over or `wait`
+    /// has passed, or gives `None` if there's no such game.
+    pub fn watch(&self, id: u64, after: u64, wait: Duration) -> Option<View> {
+        let deadline = Instant::now() + wait;
+        let mut games = self.games.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let game = games.1.get(&id)?;
+            let until = deadline.saturating_duration_since(now).min(game.left(now));
+            if game.version > after || until.is_zero() {
+                return Some(game.view(id, now));
+            }
+            games = self.changed.wait_timeout(games, until).unwrap().0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn found() {
+        let games = Games::new();
+        let view = games.start("cats".to_string(), words(&["cat", "cats", "tax"]).into_iter().collect(), 60).unwrap();
+        assert_eq!((view.game, view.seconds_left, view.over, view.version), (0, 60, false, 0));
+        let view = games.found(0, "ann", &words(&["CAT", "dog", "cat"])).unwrap();
+        assert_eq!((view.players.get("ann"), view.version), (Some(&1), 1));
+        // nothing new, so nothing for spectators to wake for
+        assert_eq!(games.found(0, "ann", &words(&["cat"])).unwrap().version, 1);
+        let view = games.found(0, "bob", &words(&["tax", "cats"])).unwrap();
+        assert_eq!(view.players, [("ann".to_string(), 1), ("bob".to_string(), 2)].iter().cloned().collect());
+        assert_eq!(games.found(1, "ann", &words(&["cat"])), None);
+
+        // once time's up, words found are too late
+        let over = games.start("cats".to_string(), words(&["cat"]).into_iter().collect(), 0).unwrap();
+        let view = games.found(over.game, "ann", &words(&["cat"])).unwrap();
+        assert!(view.over && view.players.is_empty());
+        assert_eq!(games.watch(over.game, 0, MAX_WAIT), Some(view));
+    }
+
+    #[test]
+    fn watch() {
+        let games = Arc::new(Games::new());
+        games.start("cats".to_string(), words(&["cat", "cats"]).into_iter().collect(), 60).unwrap();
+        assert_eq!(games.watch(0, 0, Duration::from_millis(10)).unwrap().version, 0);
+        assert_eq!(games.watch(1, 0, Duration::from_millis(10)), None);
+
+        let spectators: Vec<_> = (0..3).map(|_| {
+            let games = games.clone();
+            thread::spawn(move || games.watch(0, 0, Duration::from_secs(10)).unwrap())
+        }).collect();
+        thread::sleep(Duration::from_millis(50));
+        games.found(0, "ann", &words(&["cats"])).unwrap();
+        for spectator in spectators {
+            let view = spectator.join().unwrap();
+            assert_eq!((view.version, view.players.get("ann")), (1, Some(&1)));
+        }
+    }
+}
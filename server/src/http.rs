@@ -0,0 +1,136 @@
+//! Just enough HTTP/1.1 to answer one JSON request per connection: a request
+//! line, headers and a `Content-Length` body in, a status and a JSON body
+//! out, then the connection closes. There's no upgrading to websockets, which
+//! is why spectators long-poll `/games/watch` instead.
+
+use std::io::{self, BufRead, Read, Write};
+
+/// The largest body accepted; a board is a few dozen bytes.
+pub const MAX_BODY: usize = 16 * 1024;
+
+// a request line or header longer than this isn't one a client of ours would send
+const MAX_LINE: usize = 8 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// The path, without any query string.
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// Always JSON.
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Response {
+        Response { status, body }
+    }
+
+    /// `{"error": message}` with `status`.
+    pub fn error(status: u16, message: &str) -> Response {
+        Response::json(status, serde_json::json!({ "error": message }).to_string())
+    }
+}
+
+fn invalid(message: &str) -> Response {
+    Response::error(400, message)
+}
+
+fn unreadable(err: io::Error) -> Response {
+    Response::error(400, &format!("couldn't read the request: {}", err))
+}
+
+fn line<R: BufRead>(reader: &mut R) -> Result<String, Response> {
+    let mut line = String::new();
+    reader.by_ref().take(MAX_LINE as u64).read_line(&mut line).map_err(unreadable)?;
+    if !line.ends_with('\n') {
+        return Err(invalid("request line or header too long or cut off"));
+    }
+    Ok(line.trim_end().to_string())
+}
+
+/// Reads one request, or gives the error response to send back instead.
+pub fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let request_line = line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => (method, target),
+        _ => return Err(invalid("malformed request line")),
+    };
+    let path = target.split('?').next().unwrap_or("").to_string();
+
+    let mut length = 0;
+    loop {
+        let header = line(reader)?;
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':').ok_or_else(|| invalid("malformed header"))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value.trim().parse().map_err(|_| invalid("malformed Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(invalid("chunked bodies aren't supported"));
+        }
+    }
+    if length > MAX_BODY {
+        return Err(Response::error(413, "body too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(unreadable)?;
+    Ok(Request { method: method.to_string(), path, body })
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+pub fn write_response<W: Write>(writer: &mut W, response: &Response) -> io::Result<()> {
+    write!(writer, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           response.status, reason(response.status), response.body.len(), response.body)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn request() {
+        let raw = "POST /solve?x=1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 4\r\n\r\ncats";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request, Request { method: "POST".to_string(), path: "/solve".to_string(), body: b"cats".to_vec() });
+
+        let raw = "GET / HTTP/1.0\n\n";
+        assert_eq!(read_request(&mut raw.as_bytes()).unwrap().body, b"");
+
+        for bad in &["GET /\r\n\r\n", "POST /solve HTTP/1.1\r\nContent-Length: x\r\n\r\n",
+                     "POST /solve HTTP/1.1\r\nContent-Length: 10\r\n\r\ncats", "POST /solve HTTP/1.1\r\nHost"] {
+            assert_eq!(read_request(&mut bad.as_bytes()).map_err(|r| r.status), Err(400), "{:?}", bad);
+        }
+        let too_big = format!("POST /solve HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        assert_eq!(read_request(&mut too_big.as_bytes()).map_err(|r| r.status), Err(413));
+    }
+
+    #[test]
+    fn response() {
+        let mut out = Vec::new();
+        write_response(&mut out, &Response::error(404, "no such path")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(),
+                   "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 24\r\nConnection: close\r\n\r\n{\"error\":\"no such path\"}");
+    }
+}
@@ -0,0 +1,183 @@
+//! Named scores for a board, such as the day's `boggle daily` board, kept in
+//! [`Storage`] under `leaderboard/{hash of the board}/{name}` so they outlive
+//! the process when storage is files. The scores come from the server,
+//! which checks each submitted word against the board itself, and entries
+//! that look like a solver's rather than a player's are kept [`Flag`]ged.
+
+use boggle::provenance::fnv1a;
+use boggle::score::{Rarity, Scorer, Tier};
+use boggle::storage::Storage;
+use boggle::{Board, Error};
+use serde::{Deserialize, Serialize};
+
+/// The longest name a score can be kept under.
+pub const MAX_NAME: usize = 32;
+
+/// Words a second over a whole game past which no player keeps up.
+pub const MAX_RATE: f64 = 1.0;
+
+/// The share of rare words past which a list looks like a dictionary's.
+pub const MAX_RARE: f64 = 0.5;
+
+/// The fewest words a list needs before its rarity or order says anything.
+pub const MIN_WORDS: usize = 10;
+
+/// Why a submission looks like it came from a solver rather than a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flag {
+    /// More than [`MAX_RATE`] words a second over the game.
+    TooFast,
+    /// More than [`MAX_RARE`] of the words rare by the dictionary's counts.
+    Rare,
+    /// The words in alphabetical order, as a solver lists them.
+    Alphabetical,
+}
+
+/// A player's best score on a board.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub score: u32,
+    /// How many of the submitted words were on the board.
+    pub words: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<Flag>,
+}
+
+/// What's suspicious about `words`, the accepted ones in the order they were
+/// submitted, found over a game of `seconds`. Without `rarity`, from a
+/// dictionary with counts, no word is taken to be rare.
+pub fn flags<S: Scorer>(words: &[String], seconds: Option<f64>, rarity: Option<&Rarity<S>>) -> Vec<Flag> {
+    let mut flags = Vec::new();
+    if seconds.is_some_and(|seconds| words.len() as f64 > MAX_RATE * seconds) {
+        flags.push(Flag::TooFast);
+    }
+    if words.len() < MIN_WORDS {
+        return flags;
+    }
+    if let Some(rarity) = rarity {
+        let rare = words.iter().filter(|word| rarity.tier(word.as_bytes()) == Tier::Rare).count();
+        if rare as f64 > MAX_RARE * words.len() as f64 {
+            flags.push(Flag::Rare);
+        }
+    }
+    if words.windows(2).all(|pair| pair[0] < pair[1]) {
+        flags.push(Flag::Alphabetical);
+    }
+    flags
+}
+
+/// Whether `name` can have a score kept under it: 1 to [`MAX_NAME`]
+/// letters, digits, `-` or `_`, so it's also a storage key.
+pub fn valid_name(name: &str) -> bool {
+    (1..=MAX_NAME).contains(&name.len()) && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// The scores on every board, kept in `storage`.
+pub struct Leaderboard {
+    storage: Box<dyn Storage>,
+}
+
+impl Leaderboard {
+    pub fn new(storage: Box<dyn Storage>) -> Leaderboard {
+        Leaderboard { storage }
+    }
+
+    // however the board was written, e.g. in either case
+    fn prefix(board: &Board) -> String {
+        format!("leaderboard/{:016x}/", fnv1a(board.text().to_lowercase().as_bytes()))
+    }
+
+    /// Keeps `entry` for `board` unless its player already has a score there
+    /// at least as high, returning whether it was kept.
+    pub fn submit(&self, board: &Board, entry: &Entry) -> Result<bool, Error> {
+        let key = format!("{}{}", Leaderboard::prefix(board), entry.name);
+        let best = self.storage.get(&key)?.and_then(|value| serde_json::from_slice::<Entry>(&value).ok());
+        if best.is_some_and(|best| best.score >= entry.score) {
+            return Ok(false);
+        }
+        self.storage.put(&key, &serde_json::to_vec(entry).expect("entries serialize"))?;
+        Ok(true)
+    }
+
+    /// Every score kept for `board`, highest first, ties by name; any that
+    /// can't be read are left out.
+    pub fn entries(&self, board: &Board) -> Result<Vec<Entry>, Error> {
+        let mut entries = Vec::new();
+        for key in self.storage.list(&Leaderboard::prefix(board))? {
+            if let Some(entry) = self.storage.get(&key)?.and_then(|value| serde_json::from_slice(&value).ok()) {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a: &Entry, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+        Ok(entries)
+    }
+}
+
+/// The percentage of `entries` scoring less than `score`, or 100 if there
+/// are none.
+pub fn percentile(entries: &[Entry], score: u32) -> f64 {
+    if entries.is_empty() {
+        return 100.0;
+    }
+    100.0 * entries.iter().filter(|entry| entry.score < score).count() as f64 / entries.len() as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use boggle::score::Classic;
+    use boggle::storage::MemoryStorage;
+
+    fn entry(name: &str, score: u32) -> Entry {
+        Entry { name: name.to_string(), score, words: score as usize, flags: Vec::new() }
+    }
+
+    #[test]
+    fn flagged() {
+        let words = |list: &str| list.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let sorted = words("ate cat eat mat oat pat rat sat tea vat");
+        let shuffled = words("tea cat eat mat oat pat rat sat ate vat");
+        let none: Option<&Rarity<Classic>> = None;
+        assert_eq!(flags(&sorted, Some(60.0), none), vec![Flag::Alphabetical]);
+        assert_eq!(flags(&shuffled, Some(9.0), none), vec![Flag::TooFast]);
+        assert_eq!(flags(&shuffled, None, none), Vec::new());
+        // too few words to tell order from chance
+        assert_eq!(flags(&words("ate cat eat"), Some(2.0), none), vec![Flag::TooFast]);
+
+        let mut rarity = Rarity::new(Classic, "tea\ncat\neat\nmat\noat\n");
+        rarity.common = 2;
+        rarity.uncommon = 4;
+        assert_eq!(flags(&shuffled, None, Some(&rarity)), vec![Flag::Rare]);
+        rarity.uncommon = 5;
+        assert_eq!(flags(&shuffled, None, Some(&rarity)), Vec::new());
+    }
+
+    #[test]
+    fn leaderboard() {
+        let leaderboard = Leaderboard::new(Box::new(MemoryStorage::new()));
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        assert!(leaderboard.submit(&board, &entry("ann", 3)).unwrap());
+        assert!(leaderboard.submit(&board, &entry("bob", 5)).unwrap());
+        assert!(leaderboard.submit(&board, &entry("cy", 3)).unwrap());
+        // only a better score replaces a player's own
+        assert!(!leaderboard.submit(&board, &entry("bob", 4)).unwrap());
+        assert!(leaderboard.submit(&board, &entry("ann", 4)).unwrap());
+        let shouted = Board::parse("CATS\nXXXX\nXXXX\nXXXX").unwrap();
+        assert_eq!(leaderboard.entries(&shouted).unwrap(), vec![entry("bob", 5), entry("ann", 4), entry("cy", 3)]);
+        assert!(leaderboard.entries(&Board::parse("dogs\nxxxx\nxxxx\nxxxx").unwrap()).unwrap().is_empty());
+
+        let entries = leaderboard.entries(&board).unwrap();
+        assert_eq!(percentile(&entries, 4), 100.0 / 3.0);
+        assert_eq!((percentile(&entries, 0), percentile(&entries, 6), percentile(&[], 0)), (0.0, 100.0, 100.0));
+    }
+
+    #[test]
+    fn names() {
+        assert!(valid_name("ann") && valid_name("Player_2-b") && valid_name(&"a".repeat(MAX_NAME)));
+        for bad in ["", "a b", "../x", "a/b", "é", &"a".repeat(MAX_NAME + 1)] {
+            assert!(!valid_name(bad), "{:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! The pieces of a Boggle solving server, kept out of the solver library so
+//! it stays free of network and config-file code. `boggle serve` runs it.
+
+pub mod config;
+pub mod games;
+pub mod http;
+pub mod leaderboard;
+pub mod service;
+
+use std::io::BufReader;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use boggle::Error;
+
+pub use crate::config::{ConfigError, DictionaryConfig, Preset, ServerConfig, ServerConfigBuilder, StorageConfig};
+use crate::service::Service;
+
+/// The most connections answered at once; more wait to be accepted until one
+/// finishes.
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// A listening server with its dictionaries loaded.
+pub struct Server {
+    service: Arc<Service>,
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Loads the dictionaries and binds the address in `config`.
+    pub fn bind(config: &ServerConfig) -> Result<Server, Error> {
+        let service = Arc::new(Service::new(config)?);
+        let listener = TcpListener::bind(config.bind())?;
+        Ok(Server { service, listener })
+    }
+
+    /// The address bound, e.g. to find the port the OS picked for port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Answers connections, each on its own thread and at most
+    /// [`MAX_CONNECTIONS`] at once. A connection that can't be accepted is
+    /// reported on stderr and skipped, so this only returns if the listener
+    /// stops.
+    pub fn run(self) -> Result<(), Error> {
+        let slots = Arc::new(Slots { free: Mutex::new(MAX_CONNECTIONS), freed: Condvar::new() });
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("couldn't accept a connection: {}", err);
+                    // e.g. out of file descriptors, which a moment's wait may free
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+            };
+            let slot = Slots::take(&slots);
+            let service = self.service.clone();
+            if let Err(err) = thread::Builder::new().spawn(move || {
+                answer(&service, stream);
+                drop(slot);
+            }) {
+                eprintln!("couldn't start a thread for a connection: {}", err);
+            }
+        }
+        Ok(())
+    }
+}
+
+// a count of the connections that may still be answered, shared with the threads answering them
+struct Slots {
+    free: Mutex<usize>,
+    freed: Condvar,
+}
+
+// one connection's place, given back when it's dropped
+struct Slot(Arc<Slots>);
+
+impl Slots {
+    // waits for a connection to finish if there are already as many as allowed
+    fn take(slots: &Arc<Slots>) -> Slot {
+        let mut free = slots.free.lock().unwrap();
+        while *free == 0 {
+            free = slots.freed.wait(free).unwrap();
+        }
+        *free -= 1;
+        Slot(slots.clone())
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *self.0.free.lock().unwrap() += 1;
+        self.0.freed.notify_one();
+    }
+}
+
+// one request and its response; a client that hangs up or stalls just loses its answer
+fn answer(service: &Service, stream: TcpStream) {
+    let client = match stream.peer_addr() {
+        Ok(addr) => addr.ip(),
+        Err(_) => return,
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let mut reader = BufReader::new(&stream);
+    let response = match http::read_request(&mut reader) {
+        Ok(request) => service.handle(&request, client),
+        Err(response) => response,
+    };
+    let _ = http::write_response(&mut &stream, &response);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn slots() {
+        let slots = Arc::new(Slots { free: Mutex::new(1), freed: Condvar::new() });
+        let first = Slots::take(&slots);
+        let (sender, receiver) = mpsc::channel();
+        let waiting = slots.clone();
+        let second = thread::spawn(move || {
+            let slot = Slots::take(&waiting);
+            sender.send(()).unwrap();
+            drop(slot);
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(first);
+        assert!(receiver.recv_timeout(Duration::from_secs(10)).is_ok());
+        second.join().unwrap();
+        assert_eq!(*slots.free.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,701 @@
+//! The requests the server answers, apart from the sockets they arrive on:
+//!
+//! `POST /solve` with `{"board": "cats\nxxxx\nxxxx\nxxxx"}`, and optionally
+//! `"dictionary"` and `"preset"` naming ones the config allows, answers
+//! `{"found": 3, "score": 3, "words": [{"word": "cat", "score": 1, "path":
+//! [[0,0],[0,1],[0,2]]}, ...]}` with the words in alphabetical order.
+//!
+//! `"dictionaries": ["en", "slang"]` instead of `"dictionary"` searches
+//! several at once, and each word lists the ones it's in as `"sources"`.
+//! With a time limit configured, a solve from one dictionary that runs past
+//! it answers the words found so far, marked `"partial": true`.
+//!
+//! `GET /about` answers `{"dictionaries": [{"name": "en", "words": 178590,
+//! "license": "CC0-1.0", "attribution": null, "source": null}, ...]}`, with
+//! what `boggle compile-dict` recorded about each list, so clients can check
+//! the terms they may pass the words on under.
+//!
+//! `POST /scores` with `{"board": ..., "name": "ann", "words": ["cat",
+//! ...]}` scores the words on the board under the first dictionary and
+//! preset, so everyone's scores compare, and keeps the player's best on the
+//! board's leaderboard. It answers `{"name": "ann", "score": 2, "accepted":
+//! ["cat", "cats"], "rejected": ["dog"], "best": true, "rank": 1, "players":
+//! 4}`, `best` saying whether this score replaced an earlier one. Giving the
+//! game's `"seconds"` and submitting the words in the order they were found
+//! lets the server flag scores that look like a solver's, as `"flags":
+//! ["too_fast", "rare", "alphabetical"]` in both answers; see
+//! [`leaderboard::flags`] for what each means. Flagged scores are kept, for
+//! whoever runs the server to judge.
+//!
+//! `POST /leaderboard` with `{"board": ..., "top": 10, "score": 2}` answers
+//! `{"players": 4, "top": [{"name": "ann", "score": 2, "words": 2}, ...],
+//! "percentile": 75.0}`, the share of players scoring below `score`; `top`
+//! defaults to 10 and `score` to none.
+//!
+//! `POST /games` with `{"board": ..., "seconds": 180}` starts a game on the
+//! server's clock, answering what spectators see of it: `{"game": 0,
+//! "board": ..., "seconds_left": 180, "over": false, "players": {},
+//! "version": 0}`. Players report words to `POST /games/found` with
+//! `{"game": 0, "player": "ann", "words": ["cat"]}`, and spectators follow
+//! each word found with `POST /games/watch` and `{"game": 0, "after": 3}`,
+//! answered once the game is past version 3 or over; see [`games`] for how
+//! long it waits. Both answer the same view, which counts each player's
+//! words but doesn't list them. Words are judged with the first dictionary
+//! and preset, as for `/scores`.
+//!
+//! With `[storage]` set to files, whole answers are also kept there, so a
+//! restarted server needn't solve a board again, and so are leaderboards,
+//! which are otherwise kept in memory until the server stops.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::IpAddr;
+use std::str;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use boggle::compiled::{CompiledDictionary, Metadata};
+use boggle::dictionary::{self, FileFormat};
+use boggle::federation::{self, Federation};
+use boggle::provenance::fnv1a;
+use boggle::score::{Classic, Rarity};
+use boggle::storage::{FileStorage, MemoryStorage, Storage};
+use boggle::{Board, CancelToken, Dictionary, Error, Identified, Solutions, Trie};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Preset, ServerConfig, StorageConfig};
+use crate::games::{self, Games};
+use crate::http::{Request, Response};
+use crate::leaderboard::{self, Entry, Flag, Leaderboard};
+
+/// The largest board solved, so one request can't tie up a thread for long.
+pub const MAX_BOARD: usize = 10;
+
+/// The most entries `/leaderboard` lists.
+pub const MAX_TOP: usize = 100;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SolveRequest {
+    board: String,
+    dictionary: Option<String>,
+    dictionaries: Option<Vec<String>>,
+    preset: Option<Preset>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoreRequest {
+    board: String,
+    name: String,
+    words: Vec<String>,
+    seconds: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LeaderboardRequest {
+    board: String,
+    top: Option<usize>,
+    score: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StartRequest {
+    board: String,
+    seconds: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FoundRequest {
+    game: u64,
+    player: String,
+    words: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WatchRequest {
+    game: u64,
+    after: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct Word<'a> {
+    #[serde(flatten)]
+    found: Identified<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sources: Option<Vec<&'a str>>,
+}
+
+#[derive(Serialize)]
+struct About<'a> {
+    name: &'a str,
+    words: usize,
+    #[serde(flatten)]
+    metadata: Metadata,
+}
+
+#[derive(Serialize)]
+struct Solved<'a> {
+    found: usize,
+    score: u32,
+    words: Vec<Word<'a>>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+}
+
+#[derive(Serialize)]
+struct Scored {
+    name: String,
+    score: u32,
+    accepted: Vec<String>,
+    rejected: Vec<String>,
+    best: bool,
+    rank: usize,
+    players: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    flags: Vec<Flag>,
+}
+
+#[derive(Serialize)]
+struct Standings {
+    players: usize,
+    top: Vec<Entry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentile: Option<f64>,
+}
+
+// the dictionaries searched, by their place in the config
+type CacheKey = (Vec<usize>, Preset, String);
+
+// the most recently solved boards' responses, oldest evicted first
+struct Cache {
+    capacity: usize,
+    bodies: HashMap<CacheKey, String>,
+    order: VecDeque<CacheKey>,
+}
+
+impl Cache {
+    fn get(&self, key: &CacheKey) -> Option<String> {
+        self.bodies.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: CacheKey, body: String) {
+        if self.capacity == 0 || self.bodies.contains_key(&key) {
+            return;
+        }
+        if self.order.len() == self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.bodies.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.bodies.insert(key, body);
+    }
+}
+
+/// The loaded dictionaries and everything kept between requests.
+pub struct Service {
+    // each trie borrows its words, which stay loaded for the life of the process
+    dictionaries: Vec<(String, Trie<'static>)>,
+    // a hash of each one's words, so stored answers are only used with the lists they came from
+    hashes: Vec<u64>,
+    // the `/about` body, which can't change once they're loaded
+    about: String,
+    presets: Vec<Preset>,
+    cache: Mutex<Cache>,
+    // where answers outlive the process; `None` keeps them only in `cache`
+    storage: Option<Box<dyn Storage>>,
+    leaderboard: Leaderboard,
+    // how common the first dictionary's words are, if it came with counts
+    rarity: Option<Rarity<Classic>>,
+    games: Games,
+    rate_limit: Option<u32>,
+    time_limit: Option<Duration>,
+    // when each client's current minute started, and its requests in it
+    clients: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+// a word list, gzipped or not and with or without counts, or a dictionary compiled with `boggle compile-dict`
+// and what it records about the list, with its words most used first if it had counts
+fn load(path: &str) -> Result<(&'static str, Metadata, Option<String>), Error> {
+    let file = dictionary::load(path)?;
+    let (words, metadata) = if file.format == FileFormat::Compiled {
+        let dictionary = CompiledDictionary::from_bytes(&file.bytes)?;
+        (dictionary.as_str().to_string(), dictionary.metadata())
+    } else {
+        (Dictionary::parse(&file.words()?).as_str().to_string(), Metadata::default())
+    };
+    Ok((Box::leak(words.into_boxed_str()), metadata, file.ranked))
+}
+
+impl Service {
+    /// Loads every dictionary in `config`.
+    pub fn new(config: &ServerConfig) -> Result<Service, Error> {
+        let mut dictionaries = Vec::new();
+        let mut hashes = Vec::new();
+        let mut about = Vec::new();
+        let mut rarity = None;
+        for dictionary in config.dictionaries() {
+            let (words, metadata, ranked) = load(&dictionary.path.to_string_lossy())?;
+            if dictionaries.is_empty() {
+                rarity = ranked.map(|ranked| Rarity::new(Classic, &ranked));
+            }
+            about.push(About { name: &dictionary.name, words: words.lines().count(), metadata });
+            hashes.push(fnv1a(words.as_bytes()));
+            dictionaries.push((dictionary.name.clone(), Trie::from_dictionary(words)));
+        }
+        let about = serde_json::json!({ "dictionaries": about }).to_string();
+        let cache = Cache { capacity: config.cache_size(), bodies: HashMap::new(), order: VecDeque::new() };
+        // a `MemoryStorage` would only be an unbounded copy of the cache
+        let storage: Option<Box<dyn Storage>> = match *config.storage() {
+            StorageConfig::Memory => None,
+            StorageConfig::Files(ref root) => Some(Box::new(FileStorage::new(root))),
+        };
+        // but the leaderboards are kept nowhere else
+        let leaderboard = Leaderboard::new(match *config.storage() {
+            StorageConfig::Memory => Box::new(MemoryStorage::new()),
+            StorageConfig::Files(ref root) => Box::new(FileStorage::new(root)),
+        });
+        Ok(Service {
+            dictionaries,
+            hashes,
+            about,
+            presets: config.presets().to_vec(),
+            cache: Mutex::new(cache),
+            storage,
+            leaderboard,
+            rarity,
+            games: Games::new(),
+            rate_limit: config.rate_limit(),
+            time_limit: config.time_limit(),
+            clients: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn handle(&self, request: &Request, client: IpAddr) -> Response {
+        if !self.allow(client, Instant::now()) {
+            return Response::error(429, "rate limit exceeded, try again in a minute");
+        }
+        match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/solve") => self.solve(&request.body).unwrap_or_else(|err| err),
+            (_, "/solve") => Response::error(405, "use POST"),
+            ("GET", "/about") => Response::json(200, self.about.clone()),
+            (_, "/about") => Response::error(405, "use GET"),
+            ("POST", "/scores") => self.score(&request.body).unwrap_or_else(|err| err),
+            (_, "/scores") => Response::error(405, "use POST"),
+            ("POST", "/leaderboard") => self.standings(&request.body).unwrap_or_else(|err| err),
+            (_, "/leaderboard") => Response::error(405, "use POST"),
+            ("POST", "/games") => self.start(&request.body).unwrap_or_else(|err| err),
+            ("POST", "/games/found") => self.found(&request.body).unwrap_or_else(|err| err),
+            ("POST", "/games/watch") => self.watch(&request.body).unwrap_or_else(|err| err),
+            (_, "/games") | (_, "/games/found") | (_, "/games/watch") => Response::error(405, "use POST"),
+            _ => Response::error(404, "no such path"),
+        }
+    }
+
+    fn allow(&self, client: IpAddr, now: Instant) -> bool {
+        let limit = match self.rate_limit {
+            Some(limit) => limit,
+            None => return true,
+        };
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() > 1024 {
+            clients.retain(|_, &mut (start, _)| now.duration_since(start) < WINDOW);
+        }
+        let (start, count) = clients.entry(client).or_insert((now, 0));
+        if now.duration_since(*start) >= WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        *count += 1;
+        *count <= limit
+    }
+
+    fn solve(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: SolveRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        let find = |name: &String| {
+            self.dictionaries.iter().position(|(n, _)| n == name)
+                .ok_or_else(|| Response::error(400, &format!("no dictionary named {:?}", name)))
+        };
+        let dictionaries = match (request.dictionary, request.dictionaries) {
+            (Some(_), Some(_)) => return Err(Response::error(400, "give dictionary or dictionaries, not both")),
+            (Some(ref name), None) => vec![find(name)?],
+            (None, Some(ref names)) if names.is_empty() || names.len() > federation::MAX_MEMBERS => {
+                return Err(Response::error(400, &format!("dictionaries needs 1 to {} names", federation::MAX_MEMBERS)));
+            }
+            (None, Some(ref names)) => names.iter().map(find).collect::<Result<_, _>>()?,
+            (None, None) => vec![0],
+        };
+        let federated = dictionaries.len() > 1;
+        let preset = match request.preset {
+            Some(preset) if self.presets.contains(&preset) => preset,
+            Some(_) => return Err(Response::error(400, "preset not allowed")),
+            None => self.presets[0],
+        };
+
+        let key = (dictionaries, preset, request.board);
+        if let Some(body) = self.cache.lock().unwrap().get(&key) {
+            return Ok(Response::json(200, body));
+        }
+        let stored = self.stored(&key);
+        if let Some(body) = stored.and_then(|(storage, key)| storage.get(&key).ok().flatten()).and_then(|body| String::from_utf8(body).ok()) {
+            self.cache.lock().unwrap().insert(key, body.clone());
+            return Ok(Response::json(200, body));
+        }
+        let board = parse(&key.2)?;
+
+        let mut federation = Federation::new();
+        for &i in &key.0 {
+            federation.add(&self.dictionaries[i].0, &self.dictionaries[i].1);
+        }
+        let trie = &self.dictionaries[key.0[0]].1;
+        let (solutions, partial) = match self.time_limit {
+            _ if federated => (board.solve_federated(&federation, &preset.options()), false),
+            Some(limit) => board.solve_with_cancel(trie, &preset.options(), &CancelToken::with_time_limit(limit)),
+            None => (board.solve_with_trie(trie, &preset.options()), false),
+        };
+        let mut solutions = Solutions(solutions);
+        solutions.sort_alphabetically();
+        let (found, score) = (solutions.len(), solutions.total_score());
+        let words = solutions.identify(&board).into_iter().map(|found| {
+            let sources = if federated { Some(federation.sources(found.solution.word)) } else { None };
+            Word { found, sources }
+        }).collect();
+        let solved = Solved { found, score, words, partial };
+        let body = serde_json::to_string(&solved).expect("solutions serialize");
+        // the whole answer might come in under the limit next time
+        if !partial {
+            if let Some((storage, stored)) = self.stored(&key) {
+                // failing to keep it only means solving the board again
+                let _ = storage.put(&stored, body.as_bytes());
+            }
+            self.cache.lock().unwrap().insert(key, body.clone());
+        }
+        Ok(Response::json(200, body))
+    }
+
+    fn score(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: ScoreRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        if !leaderboard::valid_name(&request.name) {
+            return Err(Response::error(400, &format!("names are 1 to {} letters, digits, - or _", leaderboard::MAX_NAME)));
+        }
+        let board = parse(&request.board)?;
+        // the whole board, however long it takes, so no real word is turned away
+        let solutions = board.solve_with_trie(&self.dictionaries[0].1, &self.presets[0].options());
+        let scores: HashMap<&str, u32> = solutions.iter().map(|solution| (solution.word, solution.score)).collect();
+        let (mut score, mut accepted, mut rejected) = (0, Vec::new(), Vec::new());
+        let mut seen = HashSet::new();
+        for word in request.words {
+            let word = word.to_lowercase();
+            if !seen.insert(word.clone()) {
+                continue;
+            }
+            match scores.get(word.as_str()) {
+                Some(points) => {
+                    score += points;
+                    accepted.push(word);
+                }
+                None => rejected.push(word),
+            }
+        }
+
+        let flags = leaderboard::flags(&accepted, request.seconds, self.rarity.as_ref());
+        let entry = Entry { name: request.name, score, words: accepted.len(), flags };
+        let best = self.leaderboard.submit(&board, &entry).map_err(|err| Response::error(500, &err.to_string()))?;
+        let entries = self.leaderboard.entries(&board).map_err(|err| Response::error(500, &err.to_string()))?;
+        let rank = entries.iter().position(|kept| kept.name == entry.name).map_or(0, |i| i + 1);
+        let scored = Scored { name: entry.name, score, accepted, rejected, best, rank, players: entries.len(), flags: entry.flags };
+        Ok(Response::json(200, serde_json::to_string(&scored).expect("scores serialize")))
+    }
+
+    fn standings(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: LeaderboardRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        let board = parse(&request.board)?;
+        let mut entries = self.leaderboard.entries(&board).map_err(|err| Response::error(500, &err.to_string()))?;
+        let percentile = request.score.map(|score| leaderboard::percentile(&entries, score));
+        let players = entries.len();
+        entries.truncate(request.top.unwrap_or(10).min(MAX_TOP));
+        let standings = Standings { players, top: entries, percentile };
+        Ok(Response::json(200, serde_json::to_string(&standings).expect("standings serialize")))
+    }
+
+    fn start(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: StartRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        if request.seconds > games::MAX_SECONDS {
+            return Err(Response::error(400, &format!("games can last at most {} seconds", games::MAX_SECONDS)));
+        }
+        let board = parse(&request.board)?;
+        let solutions = board.solve_with_trie(&self.dictionaries[0].1, &self.presets[0].options());
+        let words = solutions.iter().map(|solution| solution.word.to_string()).collect();
+        let view = self.games.start(request.board, words, request.seconds)
+            .ok_or_else(|| Response::error(503, "too many games going, try again later"))?;
+        Ok(Response::json(200, serde_json::to_string(&view).expect("games serialize")))
+    }
+
+    fn found(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: FoundRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        if !leaderboard::valid_name(&request.player) {
+            return Err(Response::error(400, &format!("names are 1 to {} letters, digits, - or _", leaderboard::MAX_NAME)));
+        }
+        let view = self.games.found(request.game, &request.player, &request.words).ok_or_else(|| Response::error(404, "no such game"))?;
+        Ok(Response::json(200, serde_json::to_string(&view).expect("games serialize")))
+    }
+
+    fn watch(&self, body: &[u8]) -> Result<Response, Response> {
+        let request: WatchRequest = serde_json::from_slice(body).map_err(|err| Response::error(400, &err.to_string()))?;
+        // with nothing seen yet there's nothing to wait past
+        let (after, wait) = match request.after {
+            Some(after) => (after, games::MAX_WAIT),
+            None => (0, Duration::ZERO),
+        };
+        let view = self.games.watch(request.game, after, wait).ok_or_else(|| Response::error(404, "no such game"))?;
+        Ok(Response::json(200, serde_json::to_string(&view).expect("games serialize")))
+    }
+
+    // the storage and key an answer is kept under, if it's kept anywhere but the cache
+    fn stored(&self, (dictionaries, preset, board): &CacheKey) -> Option<(&dyn Storage, String)> {
+        let storage = self.storage.as_deref()?;
+        let hashes: Vec<_> = dictionaries.iter().map(|&i| format!("{:016x}", self.hashes[i])).collect();
+        let hash = fnv1a(format!("{} {:?} {}", hashes.join(","), preset, board).as_bytes());
+        Some((storage, format!("answers/{:016x}", hash)))
+    }
+}
+
+// a board small enough to answer for
+fn parse(text: &str) -> Result<Board, Response> {
+    let board = Board::parse(text).map_err(|err| Response::error(400, &err.to_string()))?;
+    if board.len() > MAX_BOARD {
+        return Err(Response::error(400, &format!("boards can be at most {}x{}", MAX_BOARD, MAX_BOARD)));
+    }
+    Ok(board)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::net::Ipv4Addr;
+
+    use boggle::compiled;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    fn service(config: crate::config::ServerConfigBuilder) -> Service {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../test/dictionary");
+        Service::new(&config.dictionary("en", path).build().unwrap()).unwrap()
+    }
+
+    fn post(service: &Service, body: &str) -> Response {
+        post_to(service, "/solve", body)
+    }
+
+    fn post_to(service: &Service, path: &str, body: &str) -> Response {
+        let request = Request { method: "POST".to_string(), path: path.to_string(), body: body.as_bytes().to_vec() };
+        service.handle(&request, CLIENT)
+    }
+
+    #[test]
+    fn solve() {
+        let service = service(ServerConfig::builder().presets(vec![Preset::Classic, Preset::Big]));
+        let response = post(&service, r#"{"board": "cats\nxxxx\nxxxx\nxxxx"}"#);
+        assert_eq!(response.status, 200);
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(json["found"], 3);
+        assert_eq!(json["score"], 3);
+        assert_eq!(json["words"][1], serde_json::json!({ "word": "cats", "score": 1, "path": [[0, 0], [0, 1], [0, 2], [0, 3]], "id": "12b92392971786b0" }));
+        assert_eq!(post(&service, r#"{"board": "cats\nxxxx\nxxxx\nxxxx"}"#), response);
+
+        let big = post(&service, r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "preset": "big", "dictionary": "en"}"#);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&big.body).unwrap()["found"], 1);
+
+        for bad in &["cats", r#"{"board": "c4ts"}"#, r#"{"board": "cats", "dictionary": "fr"}"#,
+                     r#"{"board": "cats", "preset": "word_hunt"}"#, r#"{"board": "cats", "colour": "red"}"#] {
+            assert_eq!(post(&service, bad).status, 400, "{}", bad);
+        }
+        let huge = format!(r#"{{"board": "{}"}}"#, vec!["x".repeat(11); 11].join("\\n"));
+        assert_eq!(post(&service, &huge).status, 400);
+
+        let get = Request { method: "GET".to_string(), path: "/solve".to_string(), body: Vec::new() };
+        assert_eq!(service.handle(&get, CLIENT).status, 405);
+        assert_eq!(service.handle(&Request { path: "/".to_string(), ..get }, CLIENT).status, 404);
+    }
+
+    #[test]
+    fn time_limit() {
+        let board = r#"{"board": "cats\nxxxx\nxxxx\nxxxx"}"#;
+        let roomy = service(ServerConfig::builder().time_limit(Duration::from_secs(60)));
+        let json: serde_json::Value = serde_json::from_str(&post(&roomy, board).body).unwrap();
+        assert_eq!((&json["found"], json.get("partial")), (&serde_json::json!(3), None));
+
+        // cut off at the search's first check
+        let tight = service(ServerConfig::builder().time_limit(Duration::from_nanos(1)));
+        let json: serde_json::Value = serde_json::from_str(&post(&tight, board).body).unwrap();
+        assert_eq!((&json["found"], &json["partial"]), (&serde_json::json!(0), &serde_json::json!(true)));
+        assert!(tight.cache.lock().unwrap().order.is_empty());
+    }
+
+    #[test]
+    fn federated() {
+        let slang = std::env::temp_dir().join(format!("boggle-server-slang-{}", std::process::id()));
+        fs::write(&slang, "cats\nsxx\n").unwrap();
+        let service = service(ServerConfig::builder().dictionary("slang", &slang));
+        let response = post(&service, r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "dictionaries": ["en", "slang"]}"#);
+        fs::remove_file(&slang).unwrap();
+        assert_eq!(response.status, 200);
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(json["found"], 4);
+        assert_eq!(json["words"][1]["sources"], serde_json::json!(["en", "slang"]));
+        assert_eq!((&json["words"][2]["word"], &json["words"][2]["sources"]), (&serde_json::json!("sxx"), &serde_json::json!(["slang"])));
+
+        for bad in &[r#"{"board": "cats", "dictionaries": []}"#, r#"{"board": "cats", "dictionaries": ["en", "fr"]}"#,
+                     r#"{"board": "cats", "dictionary": "en", "dictionaries": ["en"]}"#] {
+            assert_eq!(post(&service, bad).status, 400, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn about() {
+        let licensed = std::env::temp_dir().join(format!("boggle-server-licensed-{}", std::process::id()));
+        let metadata = Metadata { license: Some("CC0-1.0".to_string()), ..Metadata::default() };
+        fs::write(&licensed, compiled::compile_with_metadata("cats\ndogs\n", &metadata)).unwrap();
+        let zipped = concat!(env!("CARGO_MANIFEST_DIR"), "/../test/words.gz");
+        let service = service(ServerConfig::builder().dictionary("free", &licensed).dictionary("zipped", zipped));
+        fs::remove_file(&licensed).unwrap();
+
+        let get = Request { method: "GET".to_string(), path: "/about".to_string(), body: Vec::new() };
+        let response = service.handle(&get, CLIENT);
+        assert_eq!(response.status, 200);
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(json["dictionaries"][2]["name"], "en");
+        assert_eq!(json["dictionaries"][2]["license"], serde_json::Value::Null);
+        assert_eq!((&json["dictionaries"][1]["name"], &json["dictionaries"][1]["words"]), (&serde_json::json!("zipped"), &serde_json::json!(5000)));
+        assert_eq!(json["dictionaries"][0], serde_json::json!({
+            "name": "free", "words": 2, "license": "CC0-1.0", "attribution": null, "source": null,
+        }));
+        assert_eq!(service.handle(&Request { method: "POST".to_string(), ..get }, CLIENT).status, 405);
+    }
+
+    #[test]
+    fn storage() {
+        let root = std::env::temp_dir().join(format!("boggle-server-storage-{}", std::process::id()));
+        let config = || ServerConfig::builder().presets(vec![Preset::Classic, Preset::Big]).storage(StorageConfig::Files(root.clone()));
+        let board = r#"{"board": "cats\nxxxx\nxxxx\nxxxx"}"#;
+        let response = post(&service(config()), board);
+        assert_eq!(response.status, 200);
+        let storage = FileStorage::new(&root);
+        let keys = storage.list("answers/").unwrap();
+        assert_eq!(storage.get(&keys[0]).unwrap(), Some(response.body.clone().into_bytes()));
+
+        // a new server answers from storage rather than solving again
+        storage.put(&keys[0], b"{\"found\": 0}").unwrap();
+        let restarted = service(config());
+        assert_eq!(post(&restarted, board).body, "{\"found\": 0}");
+        // as it must under the same preset and dictionary named, but not another preset
+        let named = post(&restarted, r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "preset": "classic", "dictionary": "en"}"#);
+        assert_eq!(named.body, "{\"found\": 0}");
+        let big = post(&restarted, r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "preset": "big"}"#);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&big.body).unwrap()["found"], 1);
+        assert_eq!(storage.list("answers/").unwrap().len(), 2);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn leaderboard() {
+        let root = std::env::temp_dir().join(format!("boggle-server-leaderboard-{}", std::process::id()));
+        let config = || ServerConfig::builder().storage(StorageConfig::Files(root.clone()));
+        let first = service(config());
+        let submit = |service: &Service, name: &str, words: &str| {
+            let body = format!(r#"{{"board": "cats\nxxxx\nxxxx\nxxxx", "name": "{}", "words": [{}]}}"#, name, words);
+            let response = post_to(service, "/scores", &body);
+            assert_eq!(response.status, 200, "{}", response.body);
+            serde_json::from_str::<serde_json::Value>(&response.body).unwrap()
+        };
+        let json = submit(&first, "ann", r#""CAT", "cats", "cat", "dog""#);
+        assert_eq!(json, serde_json::json!({
+            "name": "ann", "score": 2, "accepted": ["cat", "cats"], "rejected": ["dog"], "best": true, "rank": 1, "players": 1,
+        }));
+        assert_eq!((&submit(&first, "bob", r#""cat", "cats", "sat""#)["rank"], &submit(&first, "cy", "")["rank"]), (&2.into(), &3.into()));
+        assert_eq!(submit(&first, "bob", r#""cat", "cats", "tax""#)["best"], true);
+        assert_eq!(submit(&first, "ann", r#""cat""#)["best"], false);
+        // three words in a second and a half is faster than anyone finds them
+        let fast = post_to(&first, "/scores", r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "name": "dee", "words": ["tax", "cat", "cats"], "seconds": 1.5}"#);
+        let json: serde_json::Value = serde_json::from_str(&fast.body).unwrap();
+        assert_eq!((&json["score"], &json["flags"]), (&3.into(), &serde_json::json!(["too_fast"])));
+
+        // kept in storage, so a restarted server still has them
+        let restarted = service(config());
+        let standings = post_to(&restarted, "/leaderboard", r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "top": 2, "score": 2}"#);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&standings.body).unwrap(), serde_json::json!({
+            "players": 4, "top": [{"name": "bob", "score": 3, "words": 3}, {"name": "dee", "score": 3, "words": 3, "flags": ["too_fast"]}],
+            "percentile": 25.0,
+        }));
+        let other = post_to(&restarted, "/leaderboard", r#"{"board": "dogs\nxxxx\nxxxx\nxxxx"}"#);
+        assert_eq!(other.body, r#"{"players":0,"top":[]}"#);
+        fs::remove_dir_all(&root).unwrap();
+
+        for bad in &[r#"{"board": "cats", "name": "a b", "words": []}"#, r#"{"board": "c4ts", "name": "ann", "words": []}"#,
+                     r#"{"board": "cats", "words": []}"#, r#"{"board": "cats", "name": "ann", "words": [], "score": 9}"#] {
+            assert_eq!(post_to(&restarted, "/scores", bad).status, 400, "{}", bad);
+        }
+        let get = Request { method: "GET".to_string(), path: "/leaderboard".to_string(), body: Vec::new() };
+        assert_eq!(restarted.handle(&get, CLIENT).status, 405);
+        assert_eq!(restarted.handle(&Request { path: "/scores".to_string(), ..get }, CLIENT).status, 405);
+    }
+
+    #[test]
+    fn games() {
+        let service = std::sync::Arc::new(service(ServerConfig::builder()));
+        let view = |response: Response| {
+            assert_eq!(response.status, 200, "{}", response.body);
+            serde_json::from_str::<serde_json::Value>(&response.body).unwrap()
+        };
+        let started = view(post_to(&service, "/games", r#"{"board": "cats\nxxxx\nxxxx\nxxxx", "seconds": 60}"#));
+        assert_eq!((&started["game"], &started["version"], &started["players"]), (&0.into(), &0.into(), &serde_json::json!({})));
+
+        let waiting = service.clone();
+        let spectator = std::thread::spawn(move || view(post_to(&waiting, "/games/watch", r#"{"game": 0, "after": 0}"#)));
+        let found = view(post_to(&service, "/games/found", r#"{"game": 0, "player": "ann", "words": ["cats", "dog"]}"#));
+        // the spectator sees the count, and no one sees the words
+        assert_eq!((&found["version"], &found["players"]), (&1.into(), &serde_json::json!({ "ann": 1 })));
+        assert_eq!(spectator.join().unwrap(), found);
+        assert_eq!(view(post_to(&service, "/games/watch", r#"{"game": 0}"#)), found);
+
+        assert_eq!(post_to(&service, "/games/watch", r#"{"game": 1}"#).status, 404);
+        for (path, bad) in &[("/games", r#"{"board": "cats", "seconds": 3601}"#), ("/games", r#"{"board": "c4ts", "seconds": 60}"#),
+                             ("/games/found", r#"{"game": 0, "player": "a b", "words": []}"#), ("/games/watch", r#"{"game": "0"}"#)] {
+            assert_eq!(post_to(&service, path, bad).status, 400, "{}", bad);
+        }
+        let get = Request { method: "GET".to_string(), path: "/games/watch".to_string(), body: Vec::new() };
+        assert_eq!(service.handle(&get, CLIENT).status, 405);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let service = service(ServerConfig::builder().rate_limit(2));
+        let now = Instant::now();
+        assert!(service.allow(CLIENT, now) && service.allow(CLIENT, now));
+        assert!(!service.allow(CLIENT, now));
+        assert!(service.allow(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), now));
+        assert!(service.allow(CLIENT, now + WINDOW));
+    }
+
+    #[test]
+    fn cache() {
+        let mut cache = Cache { capacity: 2, bodies: HashMap::new(), order: VecDeque::new() };
+        let key = |board: &str| (vec![0], Preset::Classic, board.to_string());
+        for board in &["a", "b", "c"] {
+            cache.insert(key(board), board.to_string());
+        }
+        assert_eq!(cache.get(&key("a")), None);
+        assert_eq!(cache.get(&key("c")), Some("c".to_string()));
+
+        let mut off = Cache { capacity: 0, ..cache };
+        off.insert(key("d"), "d".to_string());
+        assert_eq!(off.get(&key("d")), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! A global allocator that counts allocations, for keeping the solver's hot
+//! paths from quietly growing new ones. Install it in a test or benchmark
+//! binary, never in a library:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: boggle::alloc_audit::CountingAllocator = boggle::alloc_audit::CountingAllocator;
+//! ```
+//!
+//! `tests/allocations.rs` uses it to hold a 4x4 solve to a budget.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting every allocation and reallocation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations made since the program started, on every thread. Always 0
+/// unless [`CountingAllocator`] is the global allocator.
+pub fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, returning its result and how many allocations were made while
+/// it ran. Other threads' allocations count too, so measure with nothing
+/// else running.
+pub fn count<R, F: FnOnce() -> R>(f: F) -> (R, usize) {
+    let before = allocations();
+    let result = f();
+    (result, allocations() - before)
+}
@@ -0,0 +1,161 @@
+//! Letters beyond `a..=z`. The solvers work on one byte per letter, so an
+//! [`Alphabet`] maps a language's extra letters, such as Spanish "ñ" or
+//! German "ä", onto the few ASCII bytes after `z`. Boards and word lists are
+//! encoded as they're read, with [`Board::parse_in`](crate::Board::parse_in)
+//! and [`Dictionary::parse_in`](crate::Dictionary::parse_in), solved like any
+//! other, and the words found decoded with [`Alphabet::decode`].
+
+use std::borrow::Cow;
+
+/// How many letters the solvers tell apart: `a..=z` and [`EXTRA`] more.
+pub const LETTERS: usize = 26 + EXTRA;
+
+/// The most letters an alphabet can add to `a..=z`.
+pub const EXTRA: usize = 5;
+
+// the byte the first extra letter is encoded as, right after `z`
+const FIRST: u8 = b'z' + 1;
+
+/// Whether `c` is a letter as the solvers see it: `a..=z`, or a byte any
+/// alphabet's extra letter may be encoded as. That's only right for words
+/// already encoded, where nothing else can be past `z`; text that hasn't
+/// been, such as a raw word list, is checked with [`Alphabet::is_letter`].
+pub fn is_letter(c: u8) -> bool {
+    (b'a'..b'a' + LETTERS as u8).contains(&c)
+}
+
+/// The lowercase letters `a..=z` plus up to [`EXTRA`] more.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Alphabet {
+    extra: Vec<char>,
+}
+
+impl Alphabet {
+    /// Just `a..=z`.
+    pub fn english() -> Alphabet {
+        Alphabet::default()
+    }
+
+    /// `a..=z` and "ñ".
+    pub fn spanish() -> Alphabet {
+        Alphabet { extra: vec!['ñ'] }
+    }
+
+    /// `a..=z`, "ä", "ö", "ü" and "ß".
+    pub fn german() -> Alphabet {
+        Alphabet { extra: vec!['ä', 'ö', 'ü', 'ß'] }
+    }
+
+    /// `a..=z` and the lowercase letters in `extra`, or `None` if there are
+    /// more than [`EXTRA`] of them or any is already in `a..=z`, isn't
+    /// lowercase or repeats.
+    pub fn new(extra: &str) -> Option<Alphabet> {
+        let mut letters = Vec::new();
+        for c in extra.chars() {
+            if c.is_ascii() || !c.is_lowercase() || letters.contains(&c) || letters.len() == EXTRA {
+                return None;
+            }
+            letters.push(c);
+        }
+        Some(Alphabet { extra: letters })
+    }
+
+    /// The alphabet for a language code: "en", "es" or "de".
+    pub fn for_language(code: &str) -> Option<Alphabet> {
+        match code {
+            "en" => Some(Alphabet::english()),
+            "es" => Some(Alphabet::spanish()),
+            "de" => Some(Alphabet::german()),
+            _ => None,
+        }
+    }
+
+    /// The letters added to `a..=z`, in the order they're encoded.
+    pub fn extra(&self) -> &[char] {
+        &self.extra
+    }
+
+    /// Whether `c` is `a..=z` or the byte one of this alphabet's extra
+    /// letters is encoded as, so `{` only counts for an alphabet with an
+    /// extra letter for it to stand for.
+    pub fn is_letter(&self, c: u8) -> bool {
+        c.is_ascii_lowercase() || (FIRST..FIRST + self.extra.len() as u8).contains(&c)
+    }
+
+    /// Whether `c` is one of the alphabet's letters, in either case.
+    pub fn contains(&self, c: char) -> bool {
+        c.is_ascii_alphabetic() || self.code(c).is_some()
+    }
+
+    // the byte an extra letter, in either case, is encoded as
+    fn code(&self, c: char) -> Option<u8> {
+        let mut lower = c.to_lowercase();
+        let c = match (lower.next(), lower.next()) {
+            (Some(c), None) => c,
+            _ => return None,
+        };
+        self.extra.iter().position(|&e| e == c).map(|i| FIRST + i as u8)
+    }
+
+    /// `text` with each extra letter replaced by the byte the solvers know it
+    /// as. Everything else is left alone, so ASCII text comes back borrowed.
+    pub fn encode<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(text.chars().map(|c| self.code(c).map_or(c, char::from)).collect())
+    }
+
+    /// A word from a solver with its extra letters put back; the reverse of
+    /// `encode`.
+    pub fn decode<'t>(&self, word: &'t str) -> Cow<'t, str> {
+        if !word.bytes().any(|c| c >= FIRST) {
+            return Cow::Borrowed(word);
+        }
+        Cow::Owned(word.chars()
+            .map(|c| match (c as u32).checked_sub(FIRST as u32) {
+                Some(i) if (i as usize) < self.extra.len() => self.extra[i as usize],
+                _ => c,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let german = Alphabet::german();
+        let encoded = german.encode("Straße über");
+        assert!(encoded.bytes().all(|c| c.is_ascii()));
+        assert!(encoded.bytes().filter(|c| c.is_ascii_lowercase() || *c >= FIRST).all(is_letter));
+        assert_eq!(german.decode(&encoded), "Straße über");
+        assert!(matches!(german.encode("strasse"), Cow::Borrowed(_)));
+        assert!(matches!(german.decode("strasse"), Cow::Borrowed(_)));
+        // letters outside the alphabet aren't encoded
+        assert_eq!(german.encode("ñ"), "ñ");
+        assert!(german.contains('Ü') && german.contains('x') && !german.contains('ñ') && !german.contains('-'));
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(Alphabet::new("ñ"), Some(Alphabet::spanish()));
+        assert_eq!(Alphabet::new("äöüß"), Some(Alphabet::german()));
+        assert_eq!(Alphabet::new(""), Some(Alphabet::english()));
+        for bad in ["a", "Ñ", "ññ", "-", "áéíóúü"] {
+            assert_eq!(Alphabet::new(bad), None, "{}", bad);
+        }
+        assert_eq!(Alphabet::for_language("es"), Some(Alphabet::spanish()));
+        assert_eq!(Alphabet::for_language("fr"), None);
+    }
+
+    #[test]
+    fn letters() {
+        let (english, german) = (Alphabet::english(), Alphabet::german());
+        assert!(english.is_letter(b'a') && english.is_letter(b'z') && !english.is_letter(b'A'));
+        assert!(b"{|}~\x7f".iter().all(|&c| is_letter(c) && !english.is_letter(c)));
+        assert!(b"{|}~".iter().all(|&c| german.is_letter(c)) && !german.is_letter(0x7f));
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use crate::solution::Solution;
+
+/// Statistics for tuning a board, from
+/// [`Board::analyze`](crate::Board::analyze).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Analysis<'a> {
+    /// `through[row][col]`: how many of the words can be traced through the
+    /// cell.
+    pub through: Vec<Vec<usize>>,
+    pub found: usize,
+    pub score: u32,
+    /// `by_length[n]`: how many of the words have `n` letters, and what they
+    /// score together.
+    pub by_length: Vec<(usize, u32)>,
+    /// The cells no word can be traced through, in reading order.
+    pub dead: Vec<(usize, usize)>,
+    /// The longest word, ties alphabetically, or `None` with no words at all.
+    pub longest: Option<Solution<'a>>,
+}
+
+/// Strips a single `-s`, `-ed` or `-ing` suffix, leaving at least three
+/// letters. This is deliberately naive: "hated" stems to "hat", not "hate".
+pub fn stem(word: &str) -> &str {
+    for suffix in &["ing", "ed", "s"] {
+        if word.ends_with(suffix) && word.len() >= suffix.len() + 3 {
+            if *suffix == "s" && word.ends_with("ss") {
+                continue;
+            }
+            return &word[..word.len() - suffix.len()];
+        }
+    }
+    word
+}
+
+/// Words sharing a stem, e.g. "play", "plays", "played" and "playing".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Family<'a> {
+    pub stem: &'a str,
+    pub words: Vec<&'a str>,
+    /// The best score of any member, for rules where only one form counts.
+    pub score: u32,
+}
+
+/// Groups solutions into families by `stem`, ordered by stem.
+pub fn families<'a>(solutions: &[Solution<'a>]) -> Vec<Family<'a>> {
+    let mut families = BTreeMap::new();
+    for solution in solutions {
+        let stem = stem(solution.word);
+        let family = families.entry(stem).or_insert_with(|| Family { stem, words: Vec::new(), score: 0 });
+        family.words.push(solution.word);
+        family.score = family.score.max(solution.score);
+    }
+    families.into_values().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stems() {
+        assert_eq!(stem("play"), "play");
+        assert_eq!(stem("plays"), "play");
+        assert_eq!(stem("played"), "play");
+        assert_eq!(stem("playing"), "play");
+        assert_eq!(stem("glass"), "glass");
+        assert_eq!(stem("sing"), "sing");
+        assert_eq!(stem("bed"), "bed");
+    }
+
+    #[test]
+    fn group() {
+        let solutions: Vec<_> = [("playing", 3), ("art", 1), ("play", 1), ("plays", 2), ("played", 3)]
+            .iter()
+            .map(|&(word, score)| Solution { word, score, path: Vec::new() })
+            .collect();
+        let families = families(&solutions);
+        assert_eq!(families, vec![
+            Family { stem: "art", words: vec!["art"], score: 1 },
+            Family { stem: "play", words: vec!["playing", "play", "plays", "played"], score: 3 },
+        ]);
+    }
+}
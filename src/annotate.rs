@@ -0,0 +1,207 @@
+//! Notes on the words found, such as a definition or translation, for output
+//! to show next to each one. The crate bundles a frequency annotator,
+//! [`Rarity`], and definitions from a [`Glossary`] file; other sources plug
+//! in by implementing [`WordAnnotator`], or [`Definer`] for definitions.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[cfg(feature = "fs")]
+use crate::error::Error;
+use crate::score::{Rarity, Scorer};
+use crate::solution::Solution;
+
+/// Notes on a word, as `(key, value)` pairs, e.g. `("tier", "rare")`.
+pub type Annotations = Vec<(String, String)>;
+
+/// Something that knows more about words than the dictionary does. Output
+/// layers call it on each solution they print.
+pub trait WordAnnotator {
+    /// Notes on `solution`, in the order they should be shown; empty if
+    /// there's nothing to say about it.
+    fn annotate(&self, solution: &Solution) -> Annotations;
+}
+
+/// Says nothing about any word.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoAnnotations;
+
+impl WordAnnotator for NoAnnotations {
+    fn annotate(&self, _solution: &Solution) -> Annotations {
+        Vec::new()
+    }
+}
+
+/// Notes each word's [`Tier`](crate::score::Tier) as `tier`.
+impl<S: Scorer> WordAnnotator for Rarity<S> {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        vec![("tier".to_string(), self.tier(solution.word.as_bytes()).to_string())]
+    }
+}
+
+impl<A: WordAnnotator + ?Sized> WordAnnotator for Arc<A> {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        (**self).annotate(solution)
+    }
+}
+
+impl<A: WordAnnotator + ?Sized> WordAnnotator for Box<A> {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        (**self).annotate(solution)
+    }
+}
+
+impl<A: WordAnnotator + ?Sized> WordAnnotator for &A {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        (**self).annotate(solution)
+    }
+}
+
+/// Every annotator's notes, one after another.
+impl<A: WordAnnotator> WordAnnotator for Vec<A> {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        self.iter().flat_map(|annotator| annotator.annotate(solution)).collect()
+    }
+}
+
+/// Something that knows what words mean.
+pub trait Definer {
+    /// A short definition of `word`, or `None` if it isn't known.
+    fn define(&self, word: &str) -> Option<String>;
+}
+
+/// Notes the definition of each word `D` can define as `definition`.
+#[derive(Debug, Clone)]
+pub struct Definitions<D>(pub D);
+
+impl<D: Definer> WordAnnotator for Definitions<D> {
+    fn annotate(&self, solution: &Solution) -> Annotations {
+        self.0.define(solution.word).map(|definition| ("definition".to_string(), definition)).into_iter().collect()
+    }
+}
+
+/// Definitions read from a file, the first given for each word kept.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Glossary {
+    definitions: HashMap<String, String>,
+}
+
+impl Glossary {
+    /// Reads one word per line with its definition after a tab, or the lines
+    /// of a WordNet `data.*` file, which give each sense's words then its
+    /// gloss after a `|`; WordNet's examples are left off. Words are looked
+    /// up in lowercase, and lines that are neither are skipped, such as
+    /// WordNet's license.
+    pub fn parse(text: &str) -> Glossary {
+        let mut glossary = Glossary::default();
+        for line in text.lines().filter(|line| !line.starts_with(char::is_whitespace)) {
+            if let Some((word, definition)) = line.split_once('\t') {
+                glossary.add(word, definition);
+            } else if let Some((words, gloss)) = wordnet(line) {
+                for word in words {
+                    glossary.add(word, gloss);
+                }
+            }
+        }
+        glossary
+    }
+
+    /// Reads the glossary file at `path`, which may be gzipped; see `parse`.
+    #[cfg(feature = "fs")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Glossary, Error> {
+        let raw = std::fs::read(path)?;
+        let raw = if crate::gzip::is_gzip(&raw) { crate::gzip::decompress(&raw)? } else { raw };
+        Ok(Glossary::parse(crate::dictionary::utf8(&raw)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    fn add(&mut self, word: &str, definition: &str) {
+        let (word, definition) = (word.trim().to_lowercase(), definition.trim());
+        if !word.is_empty() && !definition.is_empty() {
+            self.definitions.entry(word).or_insert_with(|| definition.to_string());
+        }
+    }
+}
+
+impl Definer for Glossary {
+    fn define(&self, word: &str) -> Option<String> {
+        self.definitions.get(&word.to_lowercase()).cloned()
+    }
+}
+
+// a WordNet synset's words and gloss: `offset lex_filenum ss_type w_cnt word lex_id... | gloss; "example"`,
+// the word count in hex and adjectives marked like `big(a)`
+fn wordnet(line: &str) -> Option<(Vec<&str>, &str)> {
+    let (fields, gloss) = line.split_once(" | ")?;
+    let mut fields = fields.split(' ');
+    if !fields.next()?.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut fields = fields.skip(2);
+    let count = usize::from_str_radix(fields.next()?, 16).ok()?;
+    let words: Vec<_> = fields.step_by(2).take(count)
+        .map(|word| word.split_once('(').map_or(word, |(word, _)| word))
+        .filter(|word| !word.contains('_'))
+        .collect();
+    let gloss = gloss.split("; \"").next().unwrap_or(gloss);
+    Some((words, gloss))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::score::Classic;
+
+    struct Length;
+
+    impl WordAnnotator for Length {
+        fn annotate(&self, solution: &Solution) -> Annotations {
+            vec![("letters".to_string(), solution.len().to_string())]
+        }
+    }
+
+    #[test]
+    fn annotate() {
+        let solution = Solution { word: "zax", score: 1, path: Vec::new() };
+        assert!(NoAnnotations.annotate(&solution).is_empty());
+
+        let annotators: Vec<Box<dyn WordAnnotator>> = vec![
+            Box::new(Arc::new(Rarity::new(Classic, "the\nzax\n"))),
+            Box::new(NoAnnotations),
+            Box::new(Length),
+        ];
+        let notes = annotators.annotate(&solution);
+        assert_eq!(notes, [("tier".to_string(), "common".to_string()), ("letters".to_string(), "3".to_string())]);
+    }
+
+    #[test]
+    fn glossary() {
+        let glossary = Glossary::parse(concat!(
+            "  1 This software and database is being provided to you, the LICENSEE, by\n",
+            "02121620 05 n 02 true_cat 0 cat 0 001 @ 02120997 n 0000 | feline mammal usually having thick soft fur; \"cats purr\"\n",
+            "01382086 00 a 02 big(a) 0 large 0 000 | above average in size or number or quantity\n",
+            "00001740 03 n 01 Cat 0 000 | a spiteful woman gossip\n",
+            "Zax\ta tool for cutting roofing slates\n",
+            "dog\t\n",
+            "not a definition\n",
+        ));
+        assert_eq!(glossary.len(), 4);
+        assert_eq!(glossary.define("cat").as_deref(), Some("feline mammal usually having thick soft fur"));
+        assert_eq!(glossary.define("BIG").as_deref(), Some("above average in size or number or quantity"));
+        assert_eq!(glossary.define("zax").as_deref(), Some("a tool for cutting roofing slates"));
+        assert_eq!(glossary.define("true_cat"), None);
+        assert_eq!(glossary.define("dog"), None);
+
+        let definitions = Definitions(glossary);
+        let zax = Solution { word: "zax", score: 1, path: Vec::new() };
+        assert_eq!(definitions.annotate(&zax), [("definition".to_string(), "a tool for cutting roofing slates".to_string())]);
+        assert!(definitions.annotate(&Solution { word: "dog", ..zax }).is_empty());
+    }
+}
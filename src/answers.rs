@@ -0,0 +1,257 @@
+//! A compact encoding of a board's answers, for sending them to a client
+//! that already has the dictionary, e.g. so a daily puzzle can be checked
+//! offline. Words are stored as their line numbers in the dictionary and
+//! paths as a start cell and one 3-bit direction per step, so a 4x4 board's
+//! answers take a few bytes per word.
+//!
+//! ```text
+//! magic      4 bytes   "BGA\x01"
+//! size       varint    the board's rows
+//! count      varint    words
+//! index*     varint    each word's line in the dictionary, in ascending order,
+//!                      as the gap from the one before (the first from 0)
+//! cells*     varint    how many cells each word's path has
+//! paths      bits      each path's start cell, in just enough bits for the
+//!                      board's cells, then 3 bits per step; least
+//!                      significant bit first, padded to a byte
+//! ```
+//!
+//! Varints are LEB128: 7 bits per byte, least significant first, with the
+//! high bit set on every byte but the last.
+
+use std::collections::HashMap;
+
+use crate::board::{Board, DIRECTIONS};
+use crate::error::Error;
+use crate::score::Scorer;
+use crate::solution::Solution;
+
+const MAGIC: &[u8; 4] = b"BGA\x01";
+
+fn push_varint(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn varint(bytes: &[u8], at: &mut usize) -> Result<usize, Error> {
+    let mut n = 0usize;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*at).ok_or(Error::CorruptAnswers)?;
+        *at += 1;
+        n |= ((byte & 0x7f) as usize).checked_shl(shift).ok_or(Error::CorruptAnswers)?;
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(Error::CorruptAnswers)
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, value: usize, width: u32) {
+        for bit in 0..width {
+            if self.bits.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if value >> bit & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+}
+
+struct BitReader<'b> {
+    bytes: &'b [u8],
+    bits: usize,
+}
+
+impl<'b> BitReader<'b> {
+    fn read(&mut self, width: u32) -> Result<usize, Error> {
+        let mut value = 0;
+        for bit in 0..width {
+            let byte = self.bytes.get(self.bits / 8).ok_or(Error::CorruptAnswers)?;
+            value |= ((byte >> (self.bits % 8) & 1) as usize) << bit;
+            self.bits += 1;
+        }
+        Ok(value)
+    }
+}
+
+// the bits needed to tell `n` things apart
+fn width(n: usize) -> u32 {
+    usize::BITS - n.saturating_sub(1).leading_zeros()
+}
+
+// the cell one step from `(x, y)` in direction `d`, following the board's topology
+fn step(board: &Board, (x, y): (usize, usize), d: usize) -> Option<(usize, usize)> {
+    let n = board.len() as isize;
+    let (dx, dy) = DIRECTIONS[d];
+    if !board.topology().diagonals && dx != 0 && dy != 0 {
+        return None;
+    }
+    let (mut i, mut j) = (x as isize + dx, y as isize + dy);
+    if board.topology().wrap {
+        i = i.rem_euclid(n);
+        j = j.rem_euclid(n);
+    }
+    if i < 0 || j < 0 || i >= n || j >= n {
+        return None;
+    }
+    Some((i as usize, j as usize))
+}
+
+/// Encodes `solutions`, found on `board` with the dictionary `words`, one per
+/// line. Decode with the same dictionary text, e.g. from
+/// [`Dictionary::as_str`](crate::Dictionary::as_str). Scores aren't stored.
+///
+/// # Panics
+///
+/// If a word isn't in `words`, or a path steps between cells that aren't
+/// next to each other.
+pub fn encode(solutions: &[Solution], words: &str, board: &Board) -> Vec<u8> {
+    let lines: HashMap<&str, usize> = words.lines().enumerate().map(|(i, w)| (w, i)).collect();
+    let mut sorted: Vec<_> = solutions.iter()
+        .map(|s| (*lines.get(s.word).unwrap_or_else(|| panic!("{:?} isn't in the dictionary", s.word)), s))
+        .collect();
+    sorted.sort_by_key(|&(line, _)| line);
+
+    let mut out = MAGIC.to_vec();
+    push_varint(&mut out, board.len());
+    push_varint(&mut out, sorted.len());
+    let mut last = 0;
+    for &(line, _) in &sorted {
+        push_varint(&mut out, line - last);
+        last = line;
+    }
+    for (_, solution) in &sorted {
+        push_varint(&mut out, solution.path.len());
+    }
+
+    let mut paths = BitWriter::default();
+    let cell_width = width(board.cells());
+    for (_, solution) in &sorted {
+        let (x, y) = solution.start();
+        paths.push(x * board.len() + y, cell_width);
+        for pair in solution.path.windows(2) {
+            let d = (0..DIRECTIONS.len()).find(|&d| step(board, pair[0], d) == Some(pair[1]))
+                .unwrap_or_else(|| panic!("{:?} and {:?} aren't adjacent", pair[0], pair[1]));
+            paths.push(d, 3);
+        }
+    }
+    out.extend(paths.bytes);
+    out
+}
+
+/// Decodes answers made by [`encode`] for `board` and the dictionary
+/// `words`, in dictionary order, scoring them with `scorer`. Bytes that are
+/// cut short, for another board or dictionary, or whose paths don't spell
+/// their words by the board's rules are an error.
+pub fn decode<'a>(bytes: &[u8], words: &'a str, board: &Board, scorer: &dyn Scorer) -> Result<Vec<Solution<'a>>, Error> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(Error::CorruptAnswers);
+    }
+    let mut at = MAGIC.len();
+    if varint(bytes, &mut at)? != board.len() {
+        return Err(Error::CorruptAnswers);
+    }
+    let count = varint(bytes, &mut at)?;
+    // every word takes at least two bytes, which bounds what's allocated for a bad count
+    if count > bytes.len() {
+        return Err(Error::CorruptAnswers);
+    }
+
+    let lines: Vec<&str> = words.lines().collect();
+    let mut found = Vec::with_capacity(count);
+    let mut line = 0usize;
+    for i in 0..count {
+        let gap = varint(bytes, &mut at)?;
+        line = if i == 0 { gap } else { line.checked_add(gap).ok_or(Error::CorruptAnswers)? };
+        found.push(*lines.get(line).ok_or(Error::CorruptAnswers)?);
+    }
+    let lens = (0..count).map(|_| varint(bytes, &mut at)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut paths = BitReader { bytes: &bytes[at..], bits: 0 };
+    let cell_width = width(board.cells());
+    let mut solutions = Vec::with_capacity(count);
+    for (word, len) in found.into_iter().zip(lens) {
+        let start = paths.read(cell_width)?;
+        if start >= board.cells() || len == 0 || len > board.cells() {
+            return Err(Error::CorruptAnswers);
+        }
+        let mut path = vec![(start / board.len(), start % board.len())];
+        for _ in 1..len {
+            let next = step(board, *path.last().unwrap(), paths.read(3)?).ok_or(Error::CorruptAnswers)?;
+            path.push(next);
+        }
+        crate::validate_path(board, word, &path).map_err(|_| Error::CorruptAnswers)?;
+        let score = scorer.score(word.as_bytes(), &path, board);
+        solutions.push(Solution { word, score, path });
+    }
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{SolveOptions, Topology};
+    use crate::score::Classic;
+
+    const DICTIONARY: &str = include_str!("../test/dictionary");
+
+    #[test]
+    fn round_trip() {
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let mut solutions = board.solve(DICTIONARY, &SolveOptions::default());
+        let bytes = encode(&solutions, DICTIONARY, &board);
+        assert_eq!(solutions.len(), 126);
+        assert!(bytes.len() < solutions.len() * 5, "{} bytes", bytes.len());
+
+        let lines: Vec<_> = DICTIONARY.lines().collect();
+        solutions.sort_by_key(|s| lines.iter().position(|w| *w == s.word));
+        assert_eq!(decode(&bytes, DICTIONARY, &board, &Classic).unwrap(), solutions);
+
+        // around the edge of a board that wraps
+        let board = Board::parse("quit\nxxx\nxxa").unwrap().with_topology(Topology { wrap: true, ..Topology::default() });
+        let words = "quit\ntax\n";
+        let solutions = vec![Solution { word: "quit", score: 1, path: vec![(0, 0), (0, 1), (0, 2)] },
+                             Solution { word: "tax", score: 1, path: vec![(0, 2), (2, 2), (2, 1)] }];
+        let bytes = encode(&solutions, words, &board);
+        assert_eq!(decode(&bytes, words, &board, &Classic).unwrap(), solutions);
+    }
+
+    #[test]
+    fn corrupt() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "cat\ncats\ndog\ntax\n";
+        let bytes = encode(&board.solve(words, &SolveOptions::default()), words, &board);
+        assert_eq!(decode(&bytes, words, &board, &Classic).unwrap().len(), 3);
+        assert!(decode(&[], words, &board, &Classic).is_err());
+        for len in 0..bytes.len() {
+            assert!(decode(&bytes[..len], words, &board, &Classic).is_err(), "{}", len);
+        }
+        assert!(decode(&bytes, "bat\ncat\ncats\ntax\n", &board, &Classic).is_err());
+        assert!(decode(&bytes, words, &Board::parse("dogs\nxxxx\nxxxx\nxxxx").unwrap(), &Classic).is_err());
+        assert!(decode(&bytes, words, &Board::parse("abc\ndef\nghi").unwrap(), &Classic).is_err());
+
+        // paths that spell their words, but not by the rules: one using a cell twice, and one along a
+        // diagonal on a board without them
+        let words = "cat\ntat\n";
+        let reused = [Solution { word: "tat", score: 1, path: vec![(0, 2), (0, 1), (0, 2)] }];
+        assert!(decode(&encode(&reused, words, &board), words, &board, &Classic).is_err());
+        let board = Board::parse("cxxx\nxaxx\nxxtx\nxxxx").unwrap();
+        let diagonal = [Solution { word: "cat", score: 1, path: vec![(0, 0), (1, 1), (2, 2)] }];
+        let bytes = encode(&diagonal, words, &board);
+        assert_eq!(decode(&bytes, words, &board, &Classic).unwrap(), diagonal);
+        let straight = board.with_topology(Topology { diagonals: false, wrap: false });
+        assert!(decode(&bytes, words, &straight, &Classic).is_err());
+    }
+}
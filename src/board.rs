@@ -1,20 +1,229 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
 use std::fmt;
-use std::iter::Iterator;
+use std::mem;
+use std::iter::{self, Iterator};
 use std::ops::Index;
+use std::slice;
 use std::str;
+use std::time::{Duration, Instant};
 
-use typed_arena::Arena;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "parallel")]
+use rayon::ThreadPoolBuilder;
+use smallvec::SmallVec;
 
+use crate::alphabet::{is_letter, Alphabet, LETTERS};
+use crate::analysis::Analysis;
+use crate::cancel::{CancelToken, Watch};
+use crate::compiled::{CompiledDictionary, Node};
+use crate::dawg::{Dawg, State};
 use crate::error::Error;
-use crate::trie::TrieNode;
+use crate::federation::Federation;
+use crate::fixed::FixedBoard;
+use crate::json;
+use crate::rng::{RngSource, SplitMix64};
+use crate::rules::{ranked_words, GuessRule, PluralFilter, Rules};
+use crate::score::{Classic, Scorer};
+use crate::solution::{Solution, Solutions, Summary};
+use crate::trace::{Step, Trace, Tracer};
+use crate::trie::{letter_bit, Seen, Trie};
 use crate::multivec::{Vec2, Vec3};
 
-pub struct Board<'word> {
-    board: Vec<&'word [u8]>,
-    letters: [bool; 26],
+/// Knobs for [`Board::solve`].
+pub struct SolveOptions {
+    /// How each found word is scored; defaults to [`Classic`].
+    pub scorer: Box<dyn Scorer>,
+    /// House rules every reported word must pass; empty by default.
+    pub rules: Rules,
+    /// Drops plurals whose singular was also found; off by default.
+    pub plurals: Option<PluralFilter>,
+    /// Words shorter than this aren't reported; defaults to 3. Tries built
+    /// with [`Trie::from_dictionary`] and compiled dictionaries never hold
+    /// shorter words, so only `solve` can go lower, e.g. `Some(2)`.
+    pub min_len: Option<usize>,
+    /// Dictionary words longer than this are skipped while loading; defaults
+    /// to the number of cells on the board, the longest word that could fit.
+    /// Since the trie then has no deeper branches, this is also the search's
+    /// depth cutoff, e.g. `Some(10)` for casual play.
+    pub max_len: Option<usize>,
+    /// How many threads to search with; defaults to the available
+    /// parallelism. With 1, if a thread pool can't be started, or without the
+    /// `parallel` feature, the search runs on the calling thread.
+    pub threads: Option<usize>,
+    /// Shuffles the order cells are explored in, so the first words found
+    /// (and the paths reported for them) vary from seed to seed. The set of
+    /// words found is the same either way.
+    pub seed: Option<u64>,
+    /// Restricts the search to cells marked `true`: words must start and stay
+    /// inside the region. Indexed by `(row, col)` and must be the board's size.
+    pub region: Option<Vec2<bool>>,
+    /// Keeps only this many words, the highest-scoring, so memory stays
+    /// bounded on boards with hundreds of thousands of them; the rest are
+    /// scored and counted as the search finds them, then dropped. Words are
+    /// scored on the calling thread as they come, so the search runs there
+    /// too, and `plurals` is ignored, since it needs every word. Keeps every
+    /// word by default.
+    pub keep: Option<usize>,
+    /// How many cells deep [`Board::solve_until`]'s recursive search may go;
+    /// when the dictionary has words long enough to go deeper on the board,
+    /// it searches iteratively instead, so a list of absurdly long words
+    /// can't overflow the stack. Defaults to [`MAX_DEPTH`].
+    pub max_depth: Option<usize>,
 }
 
-impl<'word> fmt::Debug for Board<'word> {
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            scorer: Box::new(Classic),
+            rules: Rules::new(),
+            plurals: None,
+            min_len: None,
+            max_len: None,
+            threads: None,
+            seed: None,
+            region: None,
+            keep: None,
+            max_depth: None,
+        }
+    }
+}
+
+impl SolveOptions {
+    /// Defaults tuned for battery-powered devices: the search stays on a single
+    /// thread, so only one search trie is built at a time.
+    pub fn low_power() -> SolveOptions {
+        SolveOptions { threads: Some(1), ..SolveOptions::default() }
+    }
+}
+
+/// Counts from loading a dictionary into the search trie.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LoadStats {
+    /// Words inserted into the trie.
+    pub loaded: usize,
+    /// Words skipped for being longer than the maximum word length.
+    pub too_long: usize,
+}
+
+/// What [`Board::solve_measured`] counted and timed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SolveStats {
+    pub load: LoadStats,
+    /// Dictionary words left out of the trie because the board couldn't
+    /// spell them.
+    pub pruned: usize,
+    pub trie_nodes: usize,
+    /// Cells the search stepped into, counting each time a path reached one
+    /// that spelled a prefix of some word.
+    pub expanded: usize,
+    /// Building the trie.
+    pub load_time: Duration,
+    pub search_time: Duration,
+    /// Filtering plurals from what the search found, or trimming it to
+    /// `SolveOptions::keep`.
+    pub finish_time: Duration,
+}
+
+/// When [`Board::solve_until`] can stop searching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// The `n` highest-scoring words. Branches whose longest word couldn't
+    /// outscore the `n`th best found so far aren't explored; of words scoring
+    /// the same, those the search reaches first are kept.
+    TopKByScore(usize),
+    /// Words scoring at least this much between them, e.g. to tell whether
+    /// a board is worth that much without solving all of it.
+    StopAtScore(u32),
+}
+
+/// The words [`Board::pruning`] counted at each step of the filtering.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Pruning {
+    pub words: usize,
+    /// Those with every letter on the board.
+    pub letters: usize,
+    /// Those the board also has enough of each letter for, with each pair
+    /// of letters in a row next to each other somewhere: the words a search
+    /// trie is built from.
+    pub kept: usize,
+}
+
+/// How long [`Board::profile_single_threaded`] spent searching for one word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordProfile<'a> {
+    pub word: &'a str,
+    pub time: Duration,
+    /// Cells the search stepped into looking for the word; 0 if the word was
+    /// ruled out before searching, e.g. for needing letters the board lacks.
+    pub expansions: usize,
+    pub found: bool,
+}
+
+// the shortest word reported unless `SolveOptions::min_len` says otherwise
+const MIN_LEN: usize = 3;
+
+// steps a cancellable search takes between checks of its token, which may read the clock
+const CANCEL_CHECKS: usize = 1024;
+
+/// How many cells deep [`Board::solve_recursive`] recurses at most, and
+/// [`Board::solve_until`] unless [`SolveOptions::max_depth`] says otherwise;
+/// boards and dictionaries that could go deeper are searched iteratively.
+pub const MAX_DEPTH: usize = 512;
+
+// inline for paths of up to 16 cells; longer ones, on big boards, move to the heap
+type Path = SmallVec<[(usize, usize); 16]>;
+
+// the parts of `SolveOptions` the search itself needs; unlike the scorer and rules they can be shared across threads
+#[derive(Clone, Copy)]
+struct Search<'o> {
+    min_len: usize,
+    max_len: usize,
+    seed: Option<u64>,
+    region: Option<&'o Vec2<bool>>,
+}
+
+/// A square grid of lowercase letters. Cells are addressed `(row, col)` from
+/// the top left.
+///
+/// A cell may also hold the two-letter "Qu" tile from real Boggle dice,
+/// written `qu` in the input and stored as [`Board::QU`], or one of the other
+/// [`Board::DIGRAPHS`] some editions' dice have.
+pub struct Board {
+    board: Vec<Vec<u8>>,
+    letters: [u8; LETTERS],
+    // for each letter, the mask of letters that can come right after it in a word: next in the same tile,
+    // or first on a tile next to one ending in it
+    followers: [u32; LETTERS],
+    topology: Topology,
+    // every cell's neighbors under `topology`, worked out once rather than on each step of a search: those of
+    // the cell at row-major index i are `adjacent[adjacent_from[i]..adjacent_from[i + 1]]`
+    adjacent: Vec<(usize, usize)>,
+    adjacent_from: Vec<usize>,
+}
+
+/// Which cells count as adjacent. The default is classic Boggle's: all eight
+/// surrounding cells, stopping at the edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Topology {
+    /// Diagonal cells are adjacent; with `false`, only the four cells sharing
+    /// an edge are.
+    pub diagonals: bool,
+    /// The board wraps around, so the leftmost column is next to the
+    /// rightmost and the top row to the bottom, as on a torus.
+    pub wrap: bool,
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Topology { diagonals: true, wrap: false }
+    }
+}
+
+impl fmt::Debug for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Board:\t")?;
         for row in self.board.iter() {
@@ -22,298 +231,3520 @@ impl<'word> fmt::Debug for Board<'word> {
         }
         Ok(())
     }
-}
+}
+
+impl Board {
+    /// The cell value of a "Qu" tile, which spells both letters.
+    pub const QU: u8 = b'Q';
+
+    /// The most paths [`Board::analyze`] follows for one word.
+    pub const MAX_ANALYZED_PATHS: usize = 10_000;
+
+    /// Every multi-letter tile, as it's written in a board's rows, with the
+    /// cell value it's stored as.
+    pub const DIGRAPHS: [(&'static str, u8); 6] =
+        [("qu", Board::QU), ("he", b'H'), ("in", b'I'), ("th", b'T'), ("er", b'E'), ("an", b'A')];
+
+    /// Parses one row per line. Every row must have as many cells as there
+    /// are rows, counting each `qu` as a single "Qu" cell. Letters may be in
+    /// either case; surrounding whitespace, `\r\n` line endings and blank
+    /// lines are ignored.
+    ///
+    /// A row may instead list its cells separated by spaces, e.g. `th e qu a`,
+    /// where each cell is a letter or one of the [`Board::DIGRAPHS`]; that's
+    /// the only way to write tiles other than "Qu".
+    ///
+    /// A board needs at least one cell; blank input is an error, as is any
+    /// character other than an ASCII letter.
+    pub fn parse(raw: &str) -> Result<Board, Error> {
+        Board::from_lines(&rows(raw, &Alphabet::english())?)
+    }
+
+    /// Like `parse`, but the cells may also be `alphabet`'s extra letters,
+    /// in either case. The board keeps them encoded, as the solvers need, so
+    /// decode the words found with [`Alphabet::decode`].
+    pub fn parse_in(raw: &str, alphabet: &Alphabet) -> Result<Board, Error> {
+        let encoded: Vec<_> = rows(raw, alphabet)?.into_iter().map(|row| alphabet.encode(row)).collect();
+        Board::from_lines(&encoded.iter().map(|row| &row[..]).collect::<Vec<_>>())
+    }
+
+    /// Builds a board from its rows, each written as a line of
+    /// [`Board::parse`]'s input, for boards put together in code, such as
+    /// from rolled dice.
+    pub fn from_rows(rows: Vec<String>) -> Result<Board, Error> {
+        let rows: Vec<_> = rows.iter().map(|row| row.trim()).collect();
+        if rows.is_empty() {
+            return Err(Error::EmptyBoard);
+        }
+        check_letters(&rows, &Alphabet::english())?;
+        Board::from_lines(&rows)
+    }
+
+    fn from_lines(rows: &[&str]) -> Result<Board, Error> {
+        let board = rows.iter()
+            .enumerate()
+            .map(|(row, l)| {
+                parse_row(l.as_bytes()).map_err(|col| {
+                    let tile = l.split_whitespace().nth(col).unwrap_or_default().to_string();
+                    Error::InvalidTile { row, col, tile }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some((row, cells)) = board.iter().enumerate().find(|(_, r)| r.len() != board.len()) {
+            return Err(Error::BoardRowLength { row, expected: board.len(), got: cells.len() });
+        }
+        Ok(Board::from_cells(board))
+    }
+
+    /// Like `parse`, but for input that can't be trusted: rather than stop at
+    /// the first problem, it lists them all, in the order they come, and
+    /// makes a board of what's left once characters that aren't letters and
+    /// cells that aren't tiles are dropped, if that's still square. It never
+    /// panics, whatever `raw` holds.
+    ///
+    /// ```
+    /// use boggle::{Board, Error};
+    ///
+    /// let (board, problems) = Board::parse_lossy("ca!t\nx-xx\nxxx");
+    /// assert_eq!(board.unwrap().text(), "cat\nxxx\nxxx");
+    /// assert!(matches!(problems[..], [Error::InvalidCharacter { row: 0, col: 2, ch: '!' }, Error::InvalidCharacter { row: 1, col: 1, .. }]));
+    ///
+    /// let (board, problems) = Board::parse_lossy("ab\ncd\nef");
+    /// assert!(board.is_none() && problems.len() == 3);
+    /// ```
+    pub fn parse_lossy(raw: &str) -> (Option<Board>, Vec<Error>) {
+        let english = Alphabet::english();
+        let mut problems = Vec::new();
+        let mut board = Vec::new();
+        for (row, line) in raw.lines().map(str::trim).filter(|l| !l.is_empty()).enumerate() {
+            let mut kept = String::with_capacity(line.len());
+            for (col, ch) in line.chars().enumerate() {
+                if english.contains(ch) || ch == ' ' || ch == '\t' {
+                    kept.push(ch);
+                } else {
+                    problems.push(Error::InvalidCharacter { row, col, ch });
+                }
+            }
+            // only a row of cells separated by whitespace can have one that isn't a tile
+            let cells = parse_row(kept.as_bytes()).unwrap_or_else(|_| {
+                kept.split_whitespace()
+                    .enumerate()
+                    .filter_map(|(col, letters)| {
+                        let c = cell(letters.as_bytes());
+                        if c.is_none() {
+                            problems.push(Error::InvalidTile { row, col, tile: letters.to_string() });
+                        }
+                        c
+                    })
+                    .collect()
+            });
+            board.push(cells);
+        }
+
+        if board.is_empty() {
+            problems.push(Error::EmptyBoard);
+        }
+        let expected = board.len();
+        let uneven: Vec<_> = board.iter()
+            .enumerate()
+            .filter(|(_, cells)| cells.len() != expected)
+            .map(|(row, cells)| Error::BoardRowLength { row, expected, got: cells.len() })
+            .collect();
+        let square = uneven.is_empty() && !board.is_empty();
+        problems.extend(uneven);
+        (if square { Some(Board::from_cells(board)) } else { None }, problems)
+    }
+
+    // a board of rows that are all as long as there are rows, and at least one of them
+    fn from_cells(board: Vec<Vec<u8>>) -> Board {
+        let board = Board { board, letters: [0; LETTERS], followers: [0; LETTERS], topology: Topology::default(), adjacent: Vec::new(), adjacent_from: Vec::new() };
+        let mut board = board.with_neighbors();
+        board.refresh();
+        board
+    }
+
+    /// The board as `parse` reads it, with the cells of each row separated by
+    /// spaces if there are tiles other than "Qu", or a `q` next to a `u`. A
+    /// board of just one such tile reads back as two cells, though, and an
+    /// alphabet's extra letters stay encoded; see [`Alphabet::decode`].
+    pub fn text(&self) -> String {
+        let delimited = self.board.iter().flatten().any(|&c| c != Board::QU && c.is_ascii_uppercase())
+            || self.board.iter().any(|row| row.windows(2).any(|w| w == b"qu"));
+        self.board.iter()
+            .map(|row| {
+                let cells: Vec<_> = row.iter().map(|c| str::from_utf8(tile(c)).expect("tiles are ascii")).collect();
+                cells.join(if delimited { " " } else { "" })
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Puts `tile` in the cell at `(row, col)`: a letter or one of the
+    /// [`Board::DIGRAPHS`], in either case. Fails with
+    /// [`Error::InvalidTile`] for anything else, leaving the board as it was.
+    ///
+    /// Panics if the cell is off the board.
+    pub fn set_tile(&mut self, cell: (usize, usize), tile: &str) -> Result<(), Error> {
+        self.set_tile_in(cell, tile, &Alphabet::english())
+    }
+
+    /// Like `set_tile`, but `tile` may also be one of `alphabet`'s extra
+    /// letters, in either case, checked and encoded as [`Board::parse_in`]
+    /// reads a cell, so use the alphabet the board was parsed with.
+    ///
+    /// Panics if the cell is off the board.
+    pub fn set_tile_in(&mut self, (row, col): (usize, usize), tile: &str, alphabet: &Alphabet) -> Result<(), Error> {
+        assert!(row < self.len() && col < self.len(), "index out of bounds!");
+        let invalid = || Error::InvalidTile { row, col, tile: tile.to_string() };
+        if !tile.chars().all(|c| alphabet.contains(c)) {
+            return Err(invalid());
+        }
+        let cell = match alphabet.encode(tile).to_ascii_lowercase().as_bytes() {
+            &[c] if alphabet.is_letter(c) => c,
+            letters => Board::DIGRAPHS.iter()
+                .find(|(tile, _)| tile.as_bytes() == letters)
+                .map(|&(_, c)| c)
+                .ok_or_else(invalid)?,
+        };
+        self.board[row][col] = cell;
+        self.refresh();
+        Ok(())
+    }
+
+    /// Swaps the tiles in cells `a` and `b`.
+    ///
+    /// Panics if either cell is off the board.
+    pub fn swap_tiles(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (tile_a, tile_b) = (self[a], self[b]);
+        self.board[a.0][a.1] = tile_b;
+        self.board[b.0][b.1] = tile_a;
+        self.refresh();
+    }
+
+    /// Turns the board a quarter turn clockwise.
+    pub fn rotate(&mut self) {
+        self.rearrange(|n, i, j| (n - j, i));
+    }
+
+    /// Flips the board left to right.
+    pub fn mirror(&mut self) {
+        self.rearrange(|n, i, j| (i, n - j));
+    }
+
+    // puts the tile from cell `from(n, i, j)` in each cell (i, j), `n` being the last index
+    fn rearrange(&mut self, from: Symmetry) {
+        let n = self.len() - 1;
+        let board = (0..=n).map(|i| (0..=n).map(|j| self[from(n, i, j)]).collect()).collect();
+        self.board = board;
+        self.refresh();
+    }
+
+    // brings `letters` and `followers` up to date after the tiles change; the neighbor table only depends on
+    // the board's size and topology, which no edit changes
+    fn refresh(&mut self) {
+        let mut letters = [0u8; LETTERS];
+        for &c in self.board.iter().flat_map(|r| r.iter()) {
+            for &l in tile(&c) {
+                let count = &mut letters[(l - b'a') as usize];
+                *count = count.saturating_add(1);
+            }
+        }
+        self.letters = letters;
+        self.refresh_followers();
+    }
+
+    /// The letters spelled by the cell at `(row, col)`: one letter, or two for
+    /// a "Qu" tile or another of the [`Board::DIGRAPHS`].
+    pub fn tile(&self, cell: (usize, usize)) -> &'static [u8] {
+        tile(&self[cell])
+    }
+
+    /// The number of rows, which is also the number of columns.
+    pub fn len(&self) -> usize {
+        self.board.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.board.is_empty()
+    }
+
+    /// The number of cells on the board.
+    pub fn cells(&self) -> usize {
+        self.len() * self.len()
+    }
+
+    // the longest word that could fit, using every cell once
+    fn max_letters(&self) -> usize {
+        self.board.iter().flat_map(|r| r.iter()).map(|c| tile(c).len()).sum()
+    }
+
+    // the most cells a search of `trie` could step through for one word: a cell per tile, so no more than
+    // the longest word's letters, and never more than the cells there are
+    fn depth(&self, trie: &Trie, max_len: usize) -> usize {
+        trie.node(Trie::ROOT).longest().min(max_len).min(self.cells())
+    }
+
+    /// The board with cells adjacent according to `topology` instead.
+    pub fn with_topology(self, topology: Topology) -> Board {
+        let mut board = Board { topology, ..self }.with_neighbors();
+        board.refresh_followers();
+        board
+    }
+
+    // fills in `adjacent` for the board's size and topology
+    fn with_neighbors(mut self) -> Board {
+        let n = self.len() as isize;
+        self.adjacent.clear();
+        self.adjacent_from.clear();
+        for x in 0..self.len() {
+            for y in 0..self.len() {
+                let start = self.adjacent.len();
+                self.adjacent_from.push(start);
+                for &(x_off, y_off) in DIRECTIONS.iter() {
+                    if !self.topology.diagonals && x_off != 0 && y_off != 0 {
+                        continue;
+                    }
+                    let (mut i, mut j) = (x as isize + x_off, y as isize + y_off);
+                    if self.topology.wrap {
+                        i = i.rem_euclid(n);
+                        j = j.rem_euclid(n);
+                    } else if self.get((i, j)).is_none() {
+                        continue;
+                    }
+                    // on a small wrapped board several directions can land on the same cell, or back on this one
+                    let cell = (i as usize, j as usize);
+                    if cell != (x, y) && !self.adjacent[start..].contains(&cell) {
+                        self.adjacent.push(cell);
+                    }
+                }
+            }
+        }
+        self.adjacent_from.push(self.adjacent.len());
+        self
+    }
+
+    // fills in `followers` for the board's tiles and topology
+    fn refresh_followers(&mut self) {
+        let mut followers = [0; LETTERS];
+        for x in 0..self.len() {
+            for y in 0..self.len() {
+                let tile = self.tile((x, y));
+                for pair in tile.windows(2) {
+                    followers[(pair[0] - b'a') as usize] |= letter_bit(pair[1]);
+                }
+                let last = &mut followers[(tile[tile.len() - 1] - b'a') as usize];
+                *last = self.neighbors((x, y)).fold(*last, |mask, cell| mask | letter_bit(self.tile(cell)[0]));
+            }
+        }
+        self.followers = followers;
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// The cells adjacent to `cell` under the board's [`Topology`], worked
+    /// out when the board was made.
+    pub fn neighbors_of(&self, (x, y): (usize, usize)) -> &[(usize, usize)] {
+        let i = x * self.len() + y;
+        &self.adjacent[self.adjacent_from[i]..self.adjacent_from[i + 1]]
+    }
+
+    fn neighbors(&self, cell: (usize, usize)) -> iter::Copied<slice::Iter<'_, (usize, usize)>> {
+        self.neighbors_of(cell).iter().copied()
+    }
+
+    /// How many cells differ between this board and `other` once `other` is
+    /// rotated or reflected to match as closely as possible. Boards of
+    /// different sizes differ in every cell of the larger one.
+    pub fn distance(&self, other: &Board) -> usize {
+        if self.len() != other.len() {
+            return self.cells().max(other.cells());
+        }
+
+        let n = self.len() - 1;
+        SYMMETRIES.iter()
+            .map(|to| {
+                (0..=n).flat_map(|i| (0..=n).map(move |j| (i, j)))
+                    .filter(|&(i, j)| self[(i, j)] != other[to(n, i, j)])
+                    .count()
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// How many times each letter `a..=z`, then each of an alphabet's extra
+    /// letters, appears on the board, saturating at 255.
+    pub fn letter_counts(&self) -> [u8; LETTERS] {
+        self.letters
+    }
+
+    // for each cell, the letter mask of its neighbors, to compare against `TrieNode::children`
+    fn neighbor_letters(&self) -> Vec2<u32> {
+        let mut masks = Vec2::fill(self.len(), self.len(), 0);
+        for i in 0..self.len() {
+            for j in 0..self.len() {
+                masks[(i, j)] = self.neighbors((i, j)).fold(0, |mask, cell| mask | letter_bit(self.tile(cell)[0]));
+            }
+        }
+        masks
+    }
+
+    // whether the board has enough of each of the word's letters, with each pair of them next to each other
+    // somewhere; false for words with anything but letters, e.g. an apostrophe in text that didn't go through
+    // `Dictionary`
+    fn could_spell(&self, word: &[u8]) -> bool {
+        if !word.iter().all(|&w| is_letter(w)) || word.windows(2).any(|pair| self.followers[(pair[0] - b'a') as usize] & letter_bit(pair[1]) == 0) {
+            return false;
+        }
+        let mut needed = [0u8; LETTERS];
+        word.iter().all(|&w| {
+            let count = &mut needed[(w - b'a') as usize];
+            *count = count.saturating_add(1);
+            *count <= self.letters[(w - b'a') as usize]
+        })
+    }
+
+    /// How many of `words`, of 3 letters or more and short enough to fit,
+    /// are left at each step of the filtering done before a search. See
+    /// [`Pruning`].
+    pub fn pruning(&self, words: &str) -> Pruning {
+        let mut pruning = Pruning::default();
+        for word in dictionary(words).map(str::as_bytes).filter(|w| w.len() >= 3 && w.len() <= self.max_letters()) {
+            pruning.words += 1;
+            if word.iter().all(|&w| is_letter(w) && self.letters[(w - b'a') as usize] > 0) {
+                pruning.letters += 1;
+                pruning.kept += self.could_spell(word) as usize;
+            }
+        }
+        pruning
+    }
+
+    /// The words from `words` that make it through every step
+    /// [`Board::pruning`] counts, in order: the ones a search trie would be
+    /// built from, which include every word on the board.
+    pub fn spellable<'a>(&self, words: &'a str) -> Vec<&'a str> {
+        dictionary(words).filter(|w| w.len() >= 3 && w.len() <= self.max_letters() && self.could_spell(w.as_bytes())).collect()
+    }
+
+    // a cheap filter for `can_contain`: whether each letter of `word` can be traced from a tile next to the
+    // one before, all of them on the board, ignoring whether a cell is reused along the way
+    // which cells, in row-major order, are on some walk spelling `word` from cell to neighbouring cell,
+    // cells reused or not: every cell a path of it can use, and maybe a few no path can
+    fn walkable(&self, word: &[u8]) -> Vec<bool> {
+        let n = self.len();
+        // ends[(k, i, j)]: the first k + 1 letters can be walked ending on (i, j); starts[(k, i, j)]: the
+        // letters from k on can be walked starting from it
+        let mut ends = Vec3::fill(word.len(), n, n, false);
+        let mut starts = Vec3::fill(word.len(), n, n, false);
+        for k in 0..word.len() {
+            for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))) {
+                let tile = self.tile((i, j));
+                if tile.len() <= k + 1 && &word[k + 1 - tile.len()..=k] == tile {
+                    ends[(k, i, j)] = k + 1 == tile.len() || self.neighbors((i, j)).any(|(x, y)| ends[(k - tile.len(), x, y)]);
+                }
+            }
+        }
+        for k in (0..word.len()).rev() {
+            for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))) {
+                let tile = self.tile((i, j));
+                if word[k..].starts_with(tile) {
+                    starts[(k, i, j)] = k + tile.len() == word.len() || self.neighbors((i, j)).any(|(x, y)| starts[(k + tile.len(), x, y)]);
+                }
+            }
+        }
+        (0..n * n)
+            .map(|cell| {
+                let (i, j) = (cell / n, cell % n);
+                let tile = self.tile((i, j)).len();
+                tile <= word.len() && (0..=word.len() - tile).any(|k| starts[(k, i, j)] && ends[(k + tile - 1, i, j)])
+            })
+            .collect()
+    }
+
+    fn could_trace(&self, word: &[u8]) -> bool {
+        // adjacencies[(k, i, j)]: the first k + 1 letters of the word can be traced ending on (i, j)
+        let mut adjacencies = Vec3::fill(word.len(), self.len(), self.len(), false);
+        for k in 0..word.len() {
+            for i in 0..self.len() {
+                for j in 0..self.len() {
+                    let tile = self.tile((i, j));
+                    if tile.len() > k + 1 || &word[k + 1 - tile.len()..=k] != tile {
+                        continue;
+                    }
+
+                    let reached = if k + 1 == tile.len() {
+                        true
+                    } else {
+                        self.neighbors((i, j)).any(|(x, y)| adjacencies[(k - tile.len(), x, y)])
+                    };
+                    if reached {
+                        if word.len() - 1 == k {
+                            return true;
+                        }
+                        adjacencies[(k, i, j)] = true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// A path spelling `word` that uses no cell twice, or `None` if it can't
+    /// be traced on the board. `word` is in lowercase, with "qu" for a "Qu"
+    /// tile and any letters beyond a-z encoded with [`Alphabet::encode`] by
+    /// the same alphabet the board was parsed with, as the board keeps no
+    /// alphabet of its own; no length or dictionary rules apply.
+    pub fn find_word(&self, word: &str) -> Option<Vec<(usize, usize)>> {
+        if !self.might_contain(word.as_bytes()) {
+            return None;
+        }
+        self.find_path(word.as_bytes(), |_| true).map(|path| path.to_vec())
+    }
+
+    /// Like `find_word`, but a guided search that stays fast for long words
+    /// on big boards: it first works out which cells each remaining part of
+    /// the word could be traced from, then tries the cells with the fewest
+    /// ways on first, and gives up on a path once the unused cells don't hold
+    /// the letters still to come. Any path it finds is as valid as
+    /// `find_word`'s, though not always the same one.
+    pub fn find_word_guided(&self, word: &str) -> Option<Vec<(usize, usize)>> {
+        let word = word.as_bytes();
+        if !self.might_contain(word) {
+            return None;
+        }
+        // tails[(k, i, j)]: `word[k..]` can be traced starting on (i, j), ignoring whether a cell is reused
+        let mut tails = Vec3::fill(word.len(), self.len(), self.len(), false);
+        for k in (0..word.len()).rev() {
+            for i in 0..self.len() {
+                for j in 0..self.len() {
+                    let tile = self.tile((i, j));
+                    let end = k + tile.len();
+                    tails[(k, i, j)] = word[k..].starts_with(tile)
+                        && (end == word.len() || self.neighbors((i, j)).any(|(x, y)| tails[(end, x, y)]));
+                }
+            }
+        }
+        // needed[k]: how many of each letter `word[k..]` has, and left: how many the unused cells have
+        let mut needed = vec![[0usize; LETTERS]; word.len() + 1];
+        for k in (0..word.len()).rev() {
+            needed[k] = needed[k + 1];
+            needed[k][(word[k] - b'a') as usize] += 1;
+        }
+        // whether the unused cells reachable from `cell` still hold the letters of `word[matched..]`
+        let mut seen = vec![false; self.cells()];
+        let mut queue = Vec::new();
+        let mut room = |cell: (usize, usize), matched: usize, used: &Vec2<bool>| {
+            let mut left = [0usize; LETTERS];
+            seen.iter_mut().for_each(|seen| *seen = false);
+            queue.clear();
+            queue.push(cell);
+            while let Some(at) = queue.pop() {
+                for next in self.neighbors(at) {
+                    if !used[next] && !seen[next.0 * self.len() + next.1] {
+                        seen[next.0 * self.len() + next.1] = true;
+                        self.tile(next).iter().for_each(|&b| left[(b - b'a') as usize] += 1);
+                        queue.push(next);
+                    }
+                }
+            }
+            needed[matched].iter().zip(&left).all(|(needed, left)| needed <= left)
+        };
+
+        let mut used = Vec2::fill(self.len(), self.len(), false);
+        // the cells that could come next after `from` with `matched` letters spelled, the fewest ways on last
+        let candidates = |from: Option<(usize, usize)>, matched: usize, used: &Vec2<bool>| {
+            let cells: Vec<_> = match from {
+                Some(cell) => self.neighbors(cell).collect(),
+                None => (0..self.len()).flat_map(|i| (0..self.len()).map(move |j| (i, j))).collect(),
+            };
+            let mut next: Vec<_> = cells.into_iter()
+                .filter(|&cell| tails[(matched, cell.0, cell.1)] && !used[cell])
+                .map(|cell| {
+                    let end = matched + self.tile(cell).len();
+                    let ways = if end == word.len() {
+                        0
+                    } else {
+                        self.neighbors(cell).filter(|&(x, y)| !used[(x, y)] && tails[(end, x, y)]).count()
+                    };
+                    (Reverse(ways), cell)
+                })
+                .collect();
+            next.sort_unstable();
+            next.into_iter().map(|(_, cell)| cell).collect::<Vec<_>>()
+        };
+        let mut path = Vec::new();
+        let mut matched = 0;
+        // one list of cells still to try for each cell of `path`, and one for its first cell
+        let mut frames = vec![candidates(None, 0, &used)];
+        loop {
+            let cell = match frames.last_mut()?.pop() {
+                Some(cell) => cell,
+                None => {
+                    frames.pop();
+                    if let Some(cell) = path.pop() {
+                        used[cell] = false;
+                        matched -= self.tile(cell).len();
+                    }
+                    continue;
+                }
+            };
+            let tile = self.tile(cell);
+            if matched + tile.len() == word.len() {
+                path.push(cell);
+                return Some(path);
+            }
+            used[cell] = true;
+            if !room(cell, matched + tile.len(), &used) {
+                used[cell] = false;
+                continue;
+            }
+            matched += tile.len();
+            path.push(cell);
+            frames.push(candidates(Some(cell), matched, &used));
+        }
+    }
+
+    /// Like `find_word`, but always the least path in `(row, col)` order, so
+    /// it's the same whichever way the word was found.
+    pub fn canonical_path(&self, word: &str) -> Option<Vec<(usize, usize)>> {
+        if !self.might_contain(word.as_bytes()) {
+            return None;
+        }
+        let word = word.as_bytes();
+        // pushed greatest first so the least path is finished first
+        let mut stack: Vec<(Path, usize)> = Vec::new();
+        for i in (0..self.len()).rev() {
+            for j in (0..self.len()).rev() {
+                if word.starts_with(self.tile((i, j))) {
+                    let mut path = Path::new();
+                    path.push((i, j));
+                    stack.push((path, self.tile((i, j)).len()));
+                }
+            }
+        }
+
+        while let Some((path, matched)) = stack.pop() {
+            if matched == word.len() {
+                return Some(path.to_vec());
+            }
+            let mut next: Vec<_> = self.neighbors(*path.last().unwrap())
+                .filter(|&cell| word[matched..].starts_with(self.tile(cell)) && !path.contains(&cell))
+                .collect();
+            next.sort_unstable_by(|a, b| b.cmp(a));
+            for cell in next {
+                let mut path = path.clone();
+                path.push(cell);
+                stack.push((path, matched + self.tile(cell).len()));
+            }
+        }
+
+        None
+    }
+
+    /// Whether `word` can be traced on the board through adjacent cells
+    /// without using any cell twice, as the rules require. Like `find_word`,
+    /// `word` is in lowercase, with "qu" for a "Qu" tile and extra letters
+    /// encoded by the alphabet the board was parsed with, and no length or
+    /// dictionary rules apply.
+    pub fn can_contain(&self, word: &str) -> bool {
+        self.might_contain(word.as_bytes()) && self.find_path(word.as_bytes(), |_| true).is_some()
+    }
+
+    // the checks `find_word` and `can_contain` make before searching for a path
+    fn might_contain(&self, word: &[u8]) -> bool {
+        !word.is_empty() && word.iter().copied().all(is_letter) && self.could_spell(word) && self.could_trace(word)
+    }
+
+    // finds a path spelling `word` without reusing cells for which `accept` returns true
+    pub(crate) fn find_path<F>(&self, word: &[u8], mut accept: F) -> Option<Path>
+        where F: FnMut(&[(usize, usize)]) -> bool
+    {
+        // each item is a path so far and how many letters of `word` it spells
+        let mut stack: Vec<(Path, usize)> = Vec::new();
+        for i in 0..self.len() {
+            for j in 0..self.len() {
+                if word.starts_with(self.tile((i, j))) {
+                    let mut path = Path::new();
+                    path.push((i, j));
+                    stack.push((path, self.tile((i, j)).len()));
+                }
+            }
+        }
+
+        while let Some((path, matched)) = stack.pop() {
+            if matched == word.len() {
+                if accept(&path) {
+                    return Some(path);
+                }
+                continue;
+            }
+
+            for cell in self.neighbors(*path.last().unwrap()) {
+                if word[matched..].starts_with(self.tile(cell)) && !path.contains(&cell) {
+                    let mut path = path.clone();
+                    path.push(cell);
+                    stack.push((path, matched + self.tile(cell).len()));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Finds the words from `words` that can be traced through `cell`. Each
+    /// solution's path goes through `cell`, even when the word could also be
+    /// traced elsewhere.
+    pub fn words_through<'a>(&self, cell: (usize, usize), words: &'a str) -> Vec<Solution<'a>> {
+        let mut solutions = self.solve_trie(words);
+        solutions.retain_mut(|solution| {
+            if solution.path.contains(&cell) {
+                return true;
+            }
+            match self.find_path(solution.word.as_bytes(), |path| path.contains(&cell)) {
+                Some(path) => {
+                    solution.path = path.to_vec();
+                    true
+                }
+                None => false,
+            }
+        });
+        solutions
+    }
+
+    /// Statistics for tuning the board: how many of the words from `words`
+    /// each cell is part of, counting every way a word can be traced, how
+    /// they score by length, and which cells no word uses.
+    ///
+    /// A word's paths are only followed until they've used every cell it
+    /// could pass through, or [`Board::MAX_ANALYZED_PATHS`] of them, so
+    /// words like "aaaaaaaa" on a board of "a" stay quick; past that cap a
+    /// cell only counts for the paths seen.
+    pub fn analyze<'a>(&self, words: &'a str, options: &SolveOptions) -> Analysis<'a> {
+        let mut solutions = Solutions(self.solve(words, options));
+        let mut through = vec![vec![0; self.len()]; self.len()];
+        let mut used = vec![false; self.cells()];
+        let mut by_length = Vec::new();
+        for solution in solutions.iter() {
+            // a cell counts a word once however many of its paths use it
+            used.iter_mut().for_each(|u| *u = false);
+            let mut left = self.walkable(solution.word.as_bytes()).iter().filter(|&&walkable| walkable).count();
+            let mut paths = 0;
+            self.find_path(solution.word.as_bytes(), |path| {
+                for &(x, y) in path {
+                    // every cell of a path is walkable, so this never runs out
+                    if !std::mem::replace(&mut used[x * self.len() + y], true) {
+                        left -= 1;
+                    }
+                }
+                paths += 1;
+                left == 0 || paths == Board::MAX_ANALYZED_PATHS
+            });
+            for (x, row) in through.iter_mut().enumerate() {
+                for (y, count) in row.iter_mut().enumerate() {
+                    *count += used[x * self.len() + y] as usize;
+                }
+            }
+            if by_length.len() <= solution.len() {
+                by_length.resize(solution.len() + 1, (0, 0));
+            }
+            by_length[solution.len()].0 += 1;
+            by_length[solution.len()].1 += solution.score;
+        }
+
+        let dead = (0..self.len())
+            .flat_map(|x| (0..self.len()).map(move |y| (x, y)))
+            .filter(|&(x, y)| through[x][y] == 0)
+            .collect();
+        solutions.sort_by_length();
+        Analysis {
+            through,
+            found: solutions.len(),
+            score: solutions.total_score(),
+            by_length,
+            dead,
+            longest: solutions.0.into_iter().next(),
+        }
+    }
+
+    /// The letter at `(row, col)`, or `None` if it's off the board.
+    pub fn get(&self, (x, y): (isize, isize)) -> Option<&u8> {
+        if x.is_negative() || x >= self.len() as isize || y.is_negative() || y >= self.len() as isize {
+            None
+        } else {
+            self.board.get(x as usize).and_then(|r| r.get(y as usize))
+        }
+    }
+
+    /// Finds the words from `words` on the board by searching for each word
+    /// separately, so it shares no code with the trie solvers and can
+    /// cross-check them. Words are checked in sorted order, so that work on a
+    /// prefix is reused by every word sharing it, but returned in dictionary
+    /// order.
+    pub fn solve_single_threaded<'a>(&self, words: &'a str) -> Vec<Solution<'a>> {
+        // each word's first line, sorted; repeats end up side by side
+        let mut sorted = Vec::with_capacity(words.lines().count());
+        sorted.extend(dictionary(words).enumerate());
+        sorted.sort_unstable_by_key(|&(i, word)| (word, i));
+        sorted.dedup_by_key(|&mut (_, word)| word);
+
+        let mut stack = Vec::with_capacity(4098);
+        let mut prefixes = Prefixes { board: self, word: Vec::with_capacity(self.max_letters()), reached: Vec::new() };
+        let mut found = Vec::new();
+        for (i, word) in sorted {
+            let bytes = word.as_bytes();
+            if word.len() < 3 || word.len() > self.max_letters() || !self.could_spell(bytes) || !prefixes.traces(bytes) {
+                continue;
+            }
+            found.extend(self.search_single(word, &mut stack, &mut 0).map(|solution| (i, solution)));
+        }
+        found.sort_unstable_by_key(|&(i, _)| i);
+        found.into_iter().map(|(_, solution)| solution).collect()
+    }
+
+    /// Like `solve_single_threaded`, but also times each word, returning the
+    /// `top` slowest, slowest first, to find the dictionary entries the
+    /// per-word search struggles with.
+    pub fn profile_single_threaded<'a>(&self, words: &'a str, top: usize) -> (Vec<Solution<'a>>, Vec<WordProfile<'a>>) {
+        let mut stack = Vec::with_capacity(4098);
+        let mut seen = HashSet::new();
+        let mut solutions = Vec::new();
+        let mut profiles = Vec::new();
+        for word in dictionary(words).filter(|word| seen.insert(*word)) {
+            let mut expansions = 0;
+            let start = Instant::now();
+            let solution = self.find_single(word, &mut stack, &mut expansions);
+            let time = start.elapsed();
+            profiles.push(WordProfile { word, time, expansions, found: solution.is_some() });
+            solutions.extend(solution);
+        }
+        profiles.sort_by(|a, b| b.time.cmp(&a.time).then(b.expansions.cmp(&a.expansions)));
+        profiles.truncate(top);
+        (solutions, profiles)
+    }
+
+    // searches for `word` from every cell in turn, adding the cells it steps into to `expansions`
+    fn find_single<'a>(&self, word: &'a str, stack: &mut Vec<WordDfsItem>, expansions: &mut usize) -> Option<Solution<'a>> {
+        let bytes = word.as_bytes();
+        if word.len() < 3 || word.len() > self.max_letters() || !self.could_spell(bytes) || !self.could_trace(bytes) {
+            return None;
+        }
+        self.search_single(word, stack, expansions)
+    }
+
+    // `find_single` once the cheap checks have passed
+    fn search_single<'a>(&self, word: &'a str, stack: &mut Vec<WordDfsItem>, expansions: &mut usize) -> Option<Solution<'a>> {
+        let bytes = word.as_bytes();
+
+        for i in 0..self.len() {
+            for j in 0..self.len() {
+                stack.truncate(0);
+                stack.push(WordDfsItem { x: i, y: j, visited: Visited::new(self.len()), path: Path::new(), matched: 0 });
+
+                while let Some(mut curr) = stack.pop() {
+                    *expansions += 1;
+                    let tile = self.tile((curr.x, curr.y));
+                    if !bytes[curr.matched..].starts_with(tile) {
+                        continue;
+                    }
+
+                    curr.path.push((curr.x, curr.y));
+                    let matched = curr.matched + tile.len();
+                    if matched == word.len() {
+                        let score = Classic.score(bytes, &curr.path, self);
+                        return Some(Solution { word, score, path: curr.path.to_vec() });
+                    }
+
+                    curr.visited.insert((curr.x, curr.y));
+                    for (x, y) in self.neighbors((curr.x, curr.y)) {
+                        if !curr.visited.contains((x, y)) {
+                            let path = curr.path.clone();
+                            stack.push(WordDfsItem { x, y, visited: curr.visited.clone(), path, matched });
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the words from `words` on the board with one search over a trie of
+    /// the dictionary. Words are scored with [`Classic`].
+    pub fn solve_trie<'a>(&self, words: &'a str) -> Vec<Solution<'a>> {
+        let search = Search { min_len: MIN_LEN, max_len: self.max_letters(), seed: None, region: None };
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        self.classic_solutions(&trie, search)
+    }
+
+    // every word `search_trie` finds, scored with `Classic`
+    fn classic_solutions<'a>(&self, trie: &Trie<'a>, search: Search) -> Vec<Solution<'a>> {
+        let mut solutions = Vec::new();
+        self.search_trie(trie, search, !0, |word, path| {
+            let score = Classic.score(word.as_bytes(), path, self);
+            solutions.push(Solution { word, score, path: path.to_vec() });
+        }, &mut ());
+        solutions
+    }
+
+    /// Like `solve_trie`, but searches recursively, marking each cell on the
+    /// way in and clearing it on the way back out, so no search state is
+    /// copied per step. The recursion goes a level deeper per letter, so if
+    /// the longest word that could be on the board is longer than
+    /// [`MAX_DEPTH`], the board is searched as `solve_trie` searches it.
+    pub fn solve_recursive<'a>(&self, words: &'a str) -> Vec<Solution<'a>> {
+        self.recursive(words, MAX_DEPTH)
+    }
+
+    // `solve_recursive`, recursing at most `max_depth` deep
+    fn recursive<'a>(&self, words: &'a str, max_depth: usize) -> Vec<Solution<'a>> {
+        let search = Search { min_len: MIN_LEN, max_len: self.max_letters(), seed: None, region: None };
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        let depth = self.depth(&trie, search.max_len);
+        if depth > max_depth {
+            debug!("words could go {} cells deep, past {}, so searching iteratively", depth, max_depth);
+            return self.classic_solutions(&trie, search);
+        }
+        let mut walk = Walk {
+            board: self,
+            trie: &trie,
+            neighbor_letters: self.neighbor_letters(),
+            seen: Seen::new(&trie),
+            visited: Visited::new(self.len()),
+            path: Path::new(),
+            solutions: Vec::new(),
+        };
+        for i in 0..self.len() {
+            for j in 0..self.len() {
+                if let Some(node) = trie.step(Trie::ROOT, self.tile((i, j))) {
+                    walk.visit((i, j), node);
+                }
+            }
+        }
+        walk.solutions
+    }
+
+    /// Like `solve_trie`, but searches from cells with different letters on
+    /// different threads, using all available parallelism. Without the
+    /// `parallel` feature it's the same as `solve_trie`.
+    pub fn solve_parallel<'a>(&self, words: &'a str) -> Vec<Solution<'a>> {
+        let search = Search { min_len: MIN_LEN, max_len: self.max_letters(), seed: None, region: None };
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        self.search_shared(&trie, search, threads).0
+            .into_iter()
+            .map(|(word, path)| {
+                let score = Classic.score(word.as_bytes(), &path, self);
+                Solution { word, score, path: path.to_vec() }
+            })
+            .collect()
+    }
+
+    /// Like `solve_trie`, but drops words rejected by `options.rules` or
+    /// `options.plurals` and scores the rest with `options.scorer`.
+    pub fn solve<'a>(&self, words: &'a str, options: &SolveOptions) -> Vec<Solution<'a>> {
+        self.solve_with_stats(words, options).0
+    }
+
+    /// The total score of every word from `words` on the board, using the
+    /// default options and so official Boggle points.
+    pub fn score(&self, words: &str) -> u32 {
+        self.solve(words, &SolveOptions::default()).iter().map(|s| s.score).sum()
+    }
+
+    /// Like `solve`, but also reports how the dictionary was loaded.
+    pub fn solve_with_stats<'a>(&self, words: &'a str, options: &SolveOptions) -> (Vec<Solution<'a>>, LoadStats) {
+        let (trie, stats) = self.load_trie(dictionary(words), self.search(options));
+        (self.collect(&trie, options).solutions(), stats)
+    }
+
+    /// Like `solve`, but also counts the work each phase of the solve did
+    /// and times it.
+    pub fn solve_measured<'a>(&self, words: &'a str, options: &SolveOptions) -> (Vec<Solution<'a>>, SolveStats) {
+        let started = Instant::now();
+        let (trie, load, pruned) = self.load_trie_pruning(dictionary(words), self.search(options));
+        let loaded = Instant::now();
+        let collector = self.collect(&trie, options);
+        let searched = Instant::now();
+        let expanded = collector.expanded;
+        let solutions = collector.solutions();
+        let stats = SolveStats {
+            load,
+            pruned,
+            trie_nodes: trie.node_count(),
+            expanded,
+            load_time: loaded - started,
+            search_time: searched - loaded,
+            finish_time: searched.elapsed(),
+        };
+        (solutions, stats)
+    }
+
+    /// Like `solve`, but totals up every word found alongside the best of
+    /// them; with `options.keep` set, only that many are ever held at once.
+    pub fn summarize<'a>(&self, words: &'a str, options: &SolveOptions) -> Summary<'a> {
+        let (trie, _) = self.load_trie(dictionary(words), self.search(options));
+        self.collect(&trie, options).summary()
+    }
+
+    /// Like `solve`, but searches a trie built once with
+    /// [`Trie::from_dictionary`], which any number of boards can share, even
+    /// from several threads at once. Solving many boards this way skips
+    /// rebuilding the trie for each one.
+    pub fn solve_with_trie<'a>(&self, trie: &Trie<'a>, options: &SolveOptions) -> Vec<Solution<'a>> {
+        self.collect(trie, options).solutions()
+    }
+
+    /// Like `solve_with_trie`, but stops searching once `goal` is met, going
+    /// down the branches with the longest words first so high-scoring words
+    /// turn up early; on 4x4 and 5x5 boards, where `solve` has a faster
+    /// search, `Goal::StopAtScore` uses that instead. The words for `Goal::TopKByScore` come highest first;
+    /// those for `Goal::StopAtScore` come in the order they were found, and
+    /// score less than the goal only if the whole board does. The search runs
+    /// on the calling thread, ignoring `options.threads`, `seed`, `keep` and
+    /// `plurals`; with a scorer whose [`Scorer::max_points`] is `None`,
+    /// `Goal::TopKByScore` searches the whole board.
+    pub fn solve_until<'a>(&self, trie: &Trie<'a>, goal: Goal, options: &SolveOptions) -> Vec<Solution<'a>> {
+        let search = self.search(options);
+        if let Goal::StopAtScore(threshold) = goal {
+            if let Some(solutions) = self.stop_at_score_fixed(trie, threshold, options) {
+                return solutions;
+            }
+        }
+        let mut race = Race {
+            board: self,
+            trie,
+            goal,
+            options,
+            search,
+            neighbor_letters: self.neighbor_letters(),
+            seen: Seen::new(trie),
+            visited: Visited::new(self.len()),
+            path: Path::new(),
+            solutions: Vec::new(),
+            total: 0,
+            lowest: 0,
+        };
+        let depth = self.depth(trie, search.max_len);
+        if depth > options.max_depth.unwrap_or(MAX_DEPTH) {
+            debug!("words could go {} cells deep, so searching iteratively", depth);
+            self.search_trie(trie, search, !0, |word, path| {
+                // the lengths `Race::visit` checks as it goes
+                if !race.met() && word.len() >= search.min_len && word.len() <= search.max_len {
+                    race.found(word, path);
+                }
+            }, &mut ());
+            if let Goal::TopKByScore(_) = goal {
+                race.solutions.sort_by_key(|s| Reverse(s.score));
+            }
+            return race.solutions;
+        }
+        let mut starts: Vec<_> = (0..self.len())
+            .flat_map(|i| (0..self.len()).map(move |j| (i, j)))
+            .filter(|&cell| race.inside(cell))
+            .filter_map(|cell| trie.step(Trie::ROOT, self.tile(cell)).map(|node| (cell, node)))
+            .collect();
+        starts.sort_by_key(|&(_, node)| Reverse(trie.node(node).longest()));
+        for (cell, node) in starts {
+            if !race.worth(node) {
+                break;
+            }
+            race.visit(cell, node);
+        }
+        if let Goal::TopKByScore(_) = goal {
+            race.solutions.sort_by_key(|s| Reverse(s.score));
+        }
+        race.solutions
+    }
+
+    // `Goal::StopAtScore` for boards `FixedBoard` can search, which gets there sooner than `Race` even without
+    // going for long words first
+    fn stop_at_score_fixed<'a>(&self, trie: &Trie<'a>, threshold: u32, options: &SolveOptions) -> Option<Vec<Solution<'a>>> {
+        let search = self.search(options);
+        if search.seed.is_some() || search.region.is_some() {
+            return None;
+        }
+        let mut solutions = Vec::new();
+        let mut total = 0;
+        let mut add = |word: &'a str, path: &[(usize, usize)]| {
+            if word.len() >= search.min_len && options.rules.allows(word.as_bytes(), self) {
+                let score = options.scorer.score(word.as_bytes(), path, self);
+                total += score;
+                solutions.push(Solution { word, score, path: path.to_vec() });
+            }
+            total < threshold
+        };
+        if threshold == 0 {
+            // met before the first word
+        } else if let Some(board) = FixedBoard::<4>::new(self) {
+            board.search_while(trie, search.max_len, !0, &mut add);
+        } else if let Some(board) = FixedBoard::<5>::new(self) {
+            board.search_while(trie, search.max_len, !0, &mut add);
+        } else {
+            return None;
+        }
+        Some(solutions)
+    }
+
+    /// Like `solve_with_trie`, but checks `cancel` as it goes and stops once
+    /// it's cancelled, returning the words found so far and whether it
+    /// stopped early. The search runs on the calling thread whatever
+    /// `options.threads` says, finding words in the same order as
+    /// `solve_iter`.
+    pub fn solve_with_cancel<'a>(&self, trie: &Trie<'a>, options: &SolveOptions, cancel: &CancelToken) -> (Vec<Solution<'a>>, bool) {
+        let mut collector = Collector::new(self, options);
+        let mut dfs = Dfs::new(self, trie, self.search(options), !0);
+        let mut watch = Watch { token: cancel, stopped: false };
+        while let Some((word, path)) = dfs.next(self, trie, &mut watch) {
+            collector.add(word, &path);
+        }
+        (collector.solutions(), watch.stopped)
+    }
+
+    /// Like `solve`, but yields each word as soon as the search reaches it,
+    /// so a caller can show results as they come or stop early. The search
+    /// runs on the calling thread, finding words in the same order as
+    /// `solve_trie`. `options.plurals` is ignored, since it needs every word
+    /// found first.
+    pub fn solve_iter<'b, 'a>(&'b self, words: &'a str, options: &'b SolveOptions) -> SolveIter<'b, 'a> {
+        let search = self.search(options);
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        let dfs = Dfs::new(self, &trie, search, !0);
+        SolveIter { board: self, options, trie, dfs }
+    }
+
+    /// Like `solve_iter`, but goes down the branches leading to the most
+    /// frequent words first, so an interactive client gets the common words
+    /// straight away while the rarer ones trickle in. `ranked` lists words
+    /// from most to least frequent, one a line, as for
+    /// [`Rarity`](crate::score::Rarity); the words come out in that order,
+    /// and those it doesn't list come last.
+    ///
+    /// ```
+    /// use boggle::{Board, SolveOptions};
+    ///
+    /// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    /// let options = SolveOptions::default();
+    /// let words: Vec<_> = board.solve_iter_by_frequency("act\ncat\ncats\nsat", "cats\ncat", &options).map(|s| s.word).collect();
+    /// assert_eq!(&words[..2], ["cats", "cat"]);
+    /// ```
+    pub fn solve_iter_by_frequency<'b, 'a>(&'b self, words: &'a str, ranked: &str, options: &'b SolveOptions) -> FrequentFirst<'b, 'a> {
+        let search = self.search(options);
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        // ranks[node]: the rank of the word ending at `node`, and best[node]: of the most frequent word at or below it
+        let mut ranks = vec![usize::MAX; trie.node_count()];
+        let mut best = ranks.clone();
+        for (rank, word) in ranked_words(ranked).enumerate() {
+            let mut node = Some(Trie::ROOT);
+            for &c in word.as_bytes() {
+                node = node.and_then(|node| trie.step(node, &[c]));
+                if let Some(node) = node {
+                    best[node] = best[node].min(rank);
+                }
+            }
+            if let Some(node) = node {
+                ranks[node] = ranks[node].min(rank);
+            }
+        }
+        let unvisited = self.unvisited(search);
+        let mut queue = BinaryHeap::new();
+        for cell in self.starts(&unvisited, None) {
+            if let Some(node) = trie.step(Trie::ROOT, self.tile(cell)) {
+                let mut path = Path::new();
+                path.push(cell);
+                queue.push(Pending { key: Reverse(best[node]), visited: unvisited.clone(), path, node, word: false });
+            }
+        }
+        FrequentFirst { board: self, options, max_len: search.max_len, ranks, best, neighbor_letters: self.neighbor_letters(), seen: Seen::new(&trie), trie, queue }
+    }
+
+    /// Records how a single-threaded search for `words` explores the board,
+    /// whichever cells `options` limits it to and in the order its seed
+    /// picks, stopping recording after `limit` steps.
+    pub fn trace<'a>(&self, words: &'a str, options: &SolveOptions, limit: usize) -> Trace<'a> {
+        let search = self.search(options);
+        let (trie, _) = self.load_trie(dictionary(words), search);
+        let mut trace = Trace::new(limit);
+        self.search_trie(&trie, search, !0, |_, _| {}, &mut trace);
+        trace
+    }
+
+    /// Like `solve`, but searches a dictionary compiled ahead of time, so no
+    /// trie needs building. The search runs on the calling thread whatever
+    /// `options.threads` says.
+    pub fn solve_compiled<'a>(&self, dictionary: &CompiledDictionary<'a>, options: &SolveOptions) -> Vec<Solution<'a>> {
+        let mut collector = Collector::new(self, options);
+        self.search_lexicon(dictionary, self.search(options), |word, path| collector.add(word, path));
+        collector.solutions()
+    }
+
+    /// Like `solve_compiled`, but searches every dictionary in `federation`
+    /// as if they were one; [`Federation::sources`] tells which each word
+    /// came from.
+    pub fn solve_federated<'a>(&self, federation: &Federation<'_, 'a>, options: &SolveOptions) -> Vec<Solution<'a>> {
+        let mut collector = Collector::new(self, options);
+        self.search_lexicon(federation, self.search(options), |word, path| collector.add(word, path));
+        collector.solutions()
+    }
+
+    fn search<'o>(&self, options: &'o SolveOptions) -> Search<'o> {
+        Search {
+            min_len: options.min_len.unwrap_or(MIN_LEN),
+            max_len: options.max_len.unwrap_or_else(|| self.max_letters()),
+            seed: options.seed,
+            region: options.region.as_ref(),
+        }
+    }
+
+    /// Like `solve_trie`, but searches a [`Dawg`] built ahead of time, which
+    /// takes far less memory than the trie for a large dictionary.
+    pub fn solve_dawg<'a>(&self, dawg: &Dawg<'a>) -> Vec<Solution<'a>> {
+        let mut solutions = Vec::new();
+        let search = Search { min_len: MIN_LEN, max_len: self.max_letters(), seed: None, region: None };
+        self.search_lexicon(dawg, search, |word, path| {
+            let score = Classic.score(word.as_bytes(), path, self);
+            solutions.push(Solution { word, score, path: path.to_vec() });
+        });
+        solutions
+    }
+
+    // searches `trie` as `options` asks, keeping what `options.keep` allows
+    fn collect<'b, 'o, 'a>(&'b self, trie: &Trie<'a>, options: &'o SolveOptions) -> Collector<'b, 'o, 'a> {
+        let search = self.search(options);
+        debug!("searching {} cells for {} words on {} threads", self.cells(), trie.len(), threads(options));
+        let mut collector = Collector::new(self, options);
+        if options.keep.is_some() {
+            collector.expanded = self.search_words(trie, search, !0, |word, path| collector.add(word, path));
+        } else {
+            let (found, expanded) = self.search_shared(trie, search, threads(options));
+            for (word, path) in found {
+                collector.add(word, &path);
+            }
+            collector.expanded = expanded;
+        }
+        collector
+    }
+
+    // One trie is shared by every task. A word can only start on a cell whose tile begins with its first
+    // letter, so the start cells are split up by that letter: no two tasks find the same word, and each word
+    // is found along the same path a single search would take.
+    #[cfg(feature = "parallel")]
+    fn search_shared<'a>(&self, trie: &Trie<'a>, search: Search, threads: usize) -> (Vec<(&'a str, Path)>, usize) {
+        let collect = |task: usize, first_letters: u32| {
+            let mut found = Vec::new();
+            let search = Search { seed: search.seed.map(|s| s.wrapping_add(task as u64)), ..search };
+            let expanded = self.search_words(trie, search, first_letters, |word, path| {
+                found.push((word, SmallVec::from_slice(path)));
+            });
+            (found, expanded)
+        };
+
+        if threads <= 1 {
+            return collect(0, !0);
+        }
+        let pool = match ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool,
+            Err(_) => return collect(0, !0),
+        };
+
+        let results: Vec<_> = pool.install(|| {
+            (0..LETTERS).into_par_iter()
+                .filter(|&c| self.letters[c] > 0)
+                .map(|c| collect(c, 1 << c))
+                .collect()
+        });
+        let expanded = results.iter().map(|(_, expanded)| expanded).sum();
+        (results.into_iter().flat_map(|(found, _)| found).collect(), expanded)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn search_shared<'a>(&self, trie: &Trie<'a>, search: Search, _threads: usize) -> (Vec<(&'a str, Path)>, usize) {
+        let mut found = Vec::new();
+        let expanded = self.search_words(trie, search, !0, |word, path| {
+            found.push((word, SmallVec::from_slice(path)));
+        });
+        (found, expanded)
+    }
+
+    // `search_trie` without tracing, switching to a `FixedBoard` for standard sizes when nothing calls
+    // for the general search
+    fn search_words<'a, F>(&self, trie: &Trie<'a>, search: Search, first_letters: u32, found: F) -> usize
+        where F: FnMut(&'a str, &[(usize, usize)])
+    {
+        if search.seed.is_none() && search.region.is_none() {
+            if let Some(board) = FixedBoard::<4>::new(self) {
+                return board.search_limited(trie, search.max_len, first_letters, found);
+            }
+            if let Some(board) = FixedBoard::<5>::new(self) {
+                return board.search_limited(trie, search.max_len, first_letters, found);
+            }
+        }
+        self.search_trie(trie, search, first_letters, found, &mut ())
+    }
+
+    // a trie of the dictionary words that could be on the board
+    fn load_trie<'a, I>(&self, words: I, search: Search) -> (Trie<'a>, LoadStats)
+        where I: IntoIterator<Item = &'a str>
+    {
+        let (trie, stats, _) = self.load_trie_pruning(words, search);
+        (trie, stats)
+    }
+
+    // `load_trie`, also counting the words the board couldn't spell
+    fn load_trie_pruning<'a, I>(&self, words: I, search: Search) -> (Trie<'a>, LoadStats, usize)
+        where I: IntoIterator<Item = &'a str>
+    {
+        let mut trie = Trie::new();
+        let mut stats = LoadStats::default();
+        let mut pruned = 0;
+        for word in words {
+            if word.len() > search.max_len {
+                stats.too_long += 1;
+            } else if word.len() < search.min_len {
+                continue;
+            } else if self.could_spell(word.as_bytes()) {
+                trie.insert(word.as_bytes());
+                stats.loaded += 1;
+            } else {
+                pruned += 1;
+            }
+        }
+        debug!("built a trie of {} words in {} nodes, skipping {} too long for the board", stats.loaded, trie.node_count(), stats.too_long);
+        (trie, stats, pruned)
+    }
+
+    // calls `found` once per word in `trie` on the board with the first path it was found along, only
+    // starting from cells whose tile's first letter is in the `first_letters` mask; returns how many cells
+    // that spell a prefix it stepped into
+    fn search_trie<'a, F, T>(&self, trie: &Trie<'a>, search: Search, first_letters: u32, mut found: F, tracer: &mut T) -> usize
+        where F: FnMut(&'a str, &[(usize, usize)]),
+              T: Tracer<'a>
+    {
+        let mut dfs = Dfs::new(self, trie, search, first_letters);
+        while let Some((word, path)) = dfs.next(self, trie, tracer) {
+            found(word, &path);
+        }
+        dfs.expanded
+    }
+
+    // as `search_trie`, but over a dictionary built ahead of time; its nodes can't be marked, so words
+    // found are remembered by where they are in the dictionary's text
+    fn search_lexicon<'a, L, F>(&self, dictionary: &L, search: Search, mut found: F)
+        where L: Lexicon<'a>,
+              F: FnMut(&'a str, &[(usize, usize)])
+    {
+        struct DfsItem<N> {
+            visited: Visited,
+            path: Path,
+            x: usize,
+            y: usize,
+            node: N,
+            // letters spelled so far
+            len: usize,
+        }
+
+        let unvisited = self.unvisited(search);
+        let mut rng = search.seed.map(SplitMix64::new);
+        let starts = self.starts(&unvisited, rng.as_mut());
+        let neighbor_letters = self.neighbor_letters();
+        let dead_end = |node, len, (x, y)| dictionary.word(node, len).is_none() && dictionary.children(node) & neighbor_letters[(x, y)] == 0;
+        let mut seen = HashSet::new();
+
+        let mut stack = Vec::with_capacity(4098);
+        for (i, j) in starts {
+            let len = self.tile((i, j)).len();
+            let node = match dictionary.step(dictionary.root(), self.tile((i, j))) {
+                Some(node) if len <= search.max_len => node,
+                _ => continue,
+            };
+
+            stack.truncate(0);
+            let mut path = SmallVec::new();
+            path.push((i, j));
+            stack.push(DfsItem { x: i, y: j, node, len, visited: unvisited.clone(), path });
+
+            while let Some(mut curr) = stack.pop() {
+                curr.visited.insert((curr.x, curr.y));
+
+                let mut neighbors: SmallVec<[(usize, usize); 8]> = self.neighbors((curr.x, curr.y)).collect();
+                if let Some(ref mut rng) = rng {
+                    rng.shuffle(&mut neighbors);
+                }
+                for (x, y) in neighbors {
+                    let len = curr.len + self.tile((x, y)).len();
+                    if curr.visited.contains((x, y)) || len > search.max_len {
+                        continue;
+                    }
+
+                    if let Some(next) = dictionary.step(curr.node, self.tile((x, y))) {
+                        if dead_end(next, len, (x, y)) {
+                            continue;
+                        }
+
+                        let mut path = curr.path.clone();
+                        path.push((x, y));
+                        stack.push(DfsItem { node: next, len, x, y, visited: curr.visited.clone(), path });
+                    }
+                }
+
+                if let Some(word) = dictionary.word(curr.node, curr.len) {
+                    if seen.insert(word.as_ptr()) {
+                        found(word, &curr.path);
+                    }
+                }
+            }
+        }
+    }
+
+    // cells outside the region start out visited, so they're never entered
+    fn unvisited(&self, search: Search) -> Visited {
+        let mut visited = Visited::new(self.len());
+        if let Some(region) = search.region {
+            for i in 0..self.len() {
+                for j in 0..self.len() {
+                    if !region[(i, j)] {
+                        visited.insert((i, j));
+                    }
+                }
+            }
+        }
+        visited
+    }
+
+    // the cells a search may start from, shuffled if there's a seed
+    fn starts(&self, unvisited: &Visited, rng: Option<&mut SplitMix64>) -> Vec<(usize, usize)> {
+        let mut starts: Vec<_> = (0..self.len())
+            .flat_map(|i| (0..self.len()).map(move |j| (i, j)))
+            .filter(|&cell| !unvisited.contains(cell))
+            .collect();
+        if let Some(rng) = rng {
+            rng.shuffle(&mut starts);
+        }
+        starts
+    }
+}
+
+/// Why a path doesn't spell a word on a board; see [`validate_path`]. Each
+/// names the first offending cell by its index in the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    /// The path has no cells.
+    Empty,
+    /// The cell isn't on the board.
+    OutOfBounds { step: usize, cell: (usize, usize) },
+    /// The cell was already used earlier in the path.
+    Reused { step: usize, cell: (usize, usize) },
+    /// The cell isn't adjacent to the one before it.
+    NotAdjacent { step: usize, cell: (usize, usize) },
+    /// The cell's tile isn't the word's next letter, or the word has already
+    /// been spelled.
+    WrongLetter { step: usize, cell: (usize, usize) },
+    /// The path spells only the start of the word.
+    TooShort,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PathError::*;
+        match *self {
+            Empty => write!(f, "empty path"),
+            OutOfBounds { step, cell } => write!(f, "cell {:?} at step {} is off the board", cell, step),
+            Reused { step, cell } => write!(f, "cell {:?} at step {} was already used", cell, step),
+            NotAdjacent { step, cell } => write!(f, "cell {:?} at step {} isn't next to the one before", cell, step),
+            WrongLetter { step, cell } => write!(f, "cell {:?} at step {} doesn't spell the next letter", cell, step),
+            TooShort => write!(f, "the path ends before the word does"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// How [`parse_any`] reads a board's text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One row per line, as [`Board::parse`] reads them.
+    Grid,
+    /// The rows on one line, separated by commas: `abcd,efgh,ijkl,mnop`.
+    Inline,
+    /// One row per line and one tile per comma-separated cell, which may be
+    /// quoted: `th,e,"qu",a`. A cell may be any of the [`Board::DIGRAPHS`].
+    Csv,
+    /// An array of rows, or an object with them under `"rows"` and
+    /// optionally the [`Topology`]'s `"diagonals"` and `"wrap"`. Each row is
+    /// a string, as a line of [`Format::Grid`], or an array of tiles, as in
+    /// [`Format::Csv`].
+    Json,
+}
+
+impl Format {
+    /// Guesses from the text: JSON starts with `[` or `{`; otherwise commas
+    /// mean [`Format::Csv`] over several lines or [`Format::Inline`] on one.
+    pub fn detect(raw: &str) -> Format {
+        let raw = raw.trim();
+        if raw.starts_with('[') || raw.starts_with('{') {
+            Format::Json
+        } else if !raw.contains(',') {
+            Format::Grid
+        } else if raw.lines().filter(|l| !l.trim().is_empty()).nth(1).is_some() {
+            Format::Csv
+        } else {
+            Format::Inline
+        }
+    }
+}
+
+/// Parses a board written in `format`, or in the one [`Format::detect`]
+/// guesses, with cells from `alphabet` as for [`Board::parse_in`].
+///
+/// ```
+/// use boggle::board::{parse_any, Format};
+/// use boggle::alphabet::Alphabet;
+///
+/// let english = Alphabet::english();
+/// let inline = parse_any("cat,xqx,xxx", None, &english).unwrap();
+/// let csv = parse_any("c,a,t\nx,qu,x\nx,x,x\n", None, &english).unwrap();
+/// let json = parse_any(r#"["cat", ["x", "qu", "x"], "xxx"]"#, Some(Format::Json), &english).unwrap();
+/// assert_eq!(inline.tile((1, 1)), b"q");
+/// assert_eq!(csv.tile((1, 1)), b"qu");
+/// assert_eq!(csv.solve_trie("cat\nqua"), json.solve_trie("cat\nqua"));
+/// ```
+pub fn parse_any(raw: &str, format: Option<Format>, alphabet: &Alphabet) -> Result<Board, Error> {
+    match format.unwrap_or_else(|| Format::detect(raw)) {
+        Format::Grid => Board::parse_in(raw, alphabet),
+        Format::Inline => Board::parse_in(&raw.trim().replace(',', "\n"), alphabet),
+        Format::Csv => {
+            let rows = raw.lines()
+                .filter(|l| !l.trim().is_empty())
+                .enumerate()
+                .map(|(row, line)| tile_row(row, &line.split(',').map(|cell| cell.trim().trim_matches('"')).collect::<Vec<_>>(), alphabet))
+                .collect::<Result<Vec<_>, _>>()?;
+            Board::parse_in(&rows.join("\n"), alphabet)
+        }
+        Format::Json => {
+            let json = json::parse(raw).map_err(|offset| Error::InvalidJson { offset })?;
+            let flag = |key, default| match json.get(key) {
+                None => Ok(default),
+                Some(&json::Value::Bool(flag)) => Ok(flag),
+                Some(_) => Err(Error::NotABoard),
+            };
+            let topology = Topology { diagonals: flag("diagonals", true)?, wrap: flag("wrap", false)? };
+            let rows = match *json.get("rows").unwrap_or(&json) {
+                json::Value::Array(ref rows) => rows,
+                _ => return Err(Error::NotABoard),
+            };
+            let rows = rows.iter()
+                .enumerate()
+                .map(|(row, value)| match *value {
+                    json::Value::String(ref line) if !line.contains('\n') => Ok(line.clone()),
+                    json::Value::Array(ref cells) => {
+                        let cells = cells.iter()
+                            .map(|cell| match *cell {
+                                json::Value::String(ref cell) => Ok(&cell[..]),
+                                _ => Err(Error::NotABoard),
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        tile_row(row, &cells, alphabet)
+                    }
+                    _ => Err(Error::NotABoard),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Board::parse_in(&rows.join("\n"), alphabet)?.with_topology(topology))
+        }
+    }
+}
+
+// a row of tiles as a line of cells separated by spaces, for `Board::parse_in`; fails on any cell that isn't a letter or digraph
+fn tile_row(row: usize, cells: &[&str], alphabet: &Alphabet) -> Result<String, Error> {
+    let is_tile = |cell: &str| {
+        let mut chars = cell.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => alphabet.contains(c),
+            _ => Board::DIGRAPHS.iter().any(|(tile, _)| tile.eq_ignore_ascii_case(cell)),
+        }
+    };
+    if let Some((col, tile)) = cells.iter().enumerate().find(|(_, cell)| !is_tile(cell)) {
+        return Err(Error::InvalidTile { row, col, tile: tile.to_string() });
+    }
+    Ok(cells.join(" "))
+}
+
+/// Checks that `path` spells `word` on `board`: every cell on the board and
+/// used once, each adjacent to the one before under the board's
+/// [`Topology`], and their tiles reading `word` in order, with "qu" for a
+/// "Qu" tile. No length or dictionary rules apply.
+///
+/// ```
+/// use boggle::{validate_path, Board, PathError};
+///
+/// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+/// assert_eq!(validate_path(&board, "cat", &[(0, 0), (0, 1), (0, 2)]), Ok(()));
+/// assert_eq!(validate_path(&board, "cat", &[(0, 0), (0, 1)]), Err(PathError::TooShort));
+/// ```
+pub fn validate_path(board: &Board, word: &str, path: &[(usize, usize)]) -> Result<(), PathError> {
+    if path.is_empty() {
+        return Err(PathError::Empty);
+    }
+    let word = word.as_bytes();
+    let mut matched = 0;
+    for (step, &cell) in path.iter().enumerate() {
+        if cell.0 >= board.len() || cell.1 >= board.len() {
+            return Err(PathError::OutOfBounds { step, cell });
+        }
+        if path[..step].contains(&cell) {
+            return Err(PathError::Reused { step, cell });
+        }
+        if step > 0 && !board.neighbors(path[step - 1]).any(|n| n == cell) {
+            return Err(PathError::NotAdjacent { step, cell });
+        }
+        let tile = board.tile(cell);
+        if !word[matched..].starts_with(tile) {
+            return Err(PathError::WrongLetter { step, cell });
+        }
+        matched += tile.len();
+    }
+    if matched < word.len() {
+        return Err(PathError::TooShort);
+    }
+    Ok(())
+}
+
+/// The words on a board, found one at a time by [`Board::solve_iter`].
+pub struct SolveIter<'b, 'a> {
+    board: &'b Board,
+    options: &'b SolveOptions,
+    trie: Trie<'a>,
+    dfs: Dfs,
+}
+
+impl<'b, 'a> Iterator for SolveIter<'b, 'a> {
+    type Item = Solution<'a>;
+
+    fn next(&mut self) -> Option<Solution<'a>> {
+        let min_len = self.options.min_len.unwrap_or(MIN_LEN);
+        loop {
+            let (word, path) = self.dfs.next(self.board, &self.trie, &mut ())?;
+            if word.len() >= min_len && self.options.rules.allows(word.as_bytes(), self.board) {
+                let score = self.options.scorer.score(word.as_bytes(), &path, self.board);
+                return Some(Solution { word, score, path: path.to_vec() });
+            }
+        }
+    }
+}
+
+/// The words on a board, most frequent first, found by
+/// [`Board::solve_iter_by_frequency`].
+pub struct FrequentFirst<'b, 'a> {
+    board: &'b Board,
+    options: &'b SolveOptions,
+    max_len: usize,
+    trie: Trie<'a>,
+    ranks: Vec<usize>,
+    best: Vec<usize>,
+    neighbor_letters: Vec2<u32>,
+    seen: Seen,
+    queue: BinaryHeap<Pending>,
+}
+
+// a cell for `FrequentFirst` to step into, or with `word`, a word it has reached and will hand out once no more
+// frequent one could still turn up
+struct Pending {
+    key: Reverse<usize>,
+    visited: Visited,
+    path: Path,
+    node: usize,
+    word: bool,
+}
+
+// only the key counts, with words ahead of the cells that tie with them
+impl PartialEq for Pending {
+    fn eq(&self, other: &Pending) -> bool {
+        (self.key, self.word) == (other.key, other.word)
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Pending) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Pending) -> Ordering {
+        (self.key, self.word).cmp(&(other.key, other.word))
+    }
+}
+
+impl<'b, 'a> Iterator for FrequentFirst<'b, 'a> {
+    type Item = Solution<'a>;
+
+    fn next(&mut self) -> Option<Solution<'a>> {
+        let min_len = self.options.min_len.unwrap_or(MIN_LEN);
+        loop {
+            let mut curr = self.queue.pop()?;
+            let node = self.trie.node(curr.node);
+            let word = unsafe { str::from_utf8_unchecked(node.word) };
+            if curr.word {
+                if word.len() >= min_len && self.options.rules.allows(node.word, self.board) {
+                    let score = self.options.scorer.score(node.word, &curr.path, self.board);
+                    return Some(Solution { word, score, path: curr.path.to_vec() });
+                }
+                continue;
+            }
+
+            let cell = *curr.path.last().unwrap();
+            curr.visited.insert(cell);
+            for next_cell in self.board.neighbors(cell) {
+                if curr.visited.contains(next_cell) {
+                    continue;
+                }
+                if let Some(next) = self.trie.step(curr.node, self.board.tile(next_cell)) {
+                    let next_node = self.trie.node(next);
+                    // as in `search_trie`, too long for this board or a dead end
+                    if next_node.word.len() > self.max_len || !next_node.is_word() && next_node.children & self.neighbor_letters[next_cell] == 0 {
+                        continue;
+                    }
+                    let mut path = curr.path.clone();
+                    path.push(next_cell);
+                    self.queue.push(Pending { key: Reverse(self.best[next]), visited: curr.visited.clone(), path, node: next, word: false });
+                }
+            }
+            if node.is_word() && self.seen.insert(curr.node) {
+                self.queue.push(Pending { key: Reverse(self.ranks[curr.node]), visited: curr.visited, path: curr.path, node: curr.node, word: true });
+            }
+        }
+    }
+}
+
+// scores what a search finds as `options` says and keeps what `options.keep` allows, tallying every word
+struct Collector<'b, 'o, 'a> {
+    board: &'b Board,
+    options: &'o SolveOptions,
+    min_len: usize,
+    found: usize,
+    score: u32,
+    by_length: Vec<usize>,
+    // with `keep`, trimmed back to the best `keep` whenever it doubles
+    solutions: Vec<Solution<'a>>,
+    // cells the search stepped into
+    expanded: usize,
+}
+
+// highest score first, ties alphabetically
+fn rank(a: &Solution, b: &Solution) -> Ordering {
+    b.score.cmp(&a.score).then(a.word.cmp(b.word))
+}
+
+impl<'b, 'o, 'a> Collector<'b, 'o, 'a> {
+    fn new(board: &'b Board, options: &'o SolveOptions) -> Self {
+        let min_len = options.min_len.unwrap_or(MIN_LEN);
+        Collector { board, options, min_len, found: 0, score: 0, by_length: Vec::new(), solutions: Vec::new(), expanded: 0 }
+    }
+
+    fn add(&mut self, word: &'a str, path: &[(usize, usize)]) {
+        if word.len() < self.min_len || !self.options.rules.allows(word.as_bytes(), self.board) {
+            return;
+        }
+        let score = self.options.scorer.score(word.as_bytes(), path, self.board);
+        self.found += 1;
+        self.score += score;
+        if self.by_length.len() <= word.len() {
+            self.by_length.resize(word.len() + 1, 0);
+        }
+        self.by_length[word.len()] += 1;
+
+        self.solutions.push(Solution { word, score, path: path.to_vec() });
+        if let Some(keep) = self.options.keep {
+            if self.solutions.len() > keep.max(1) * 2 {
+                self.trim(keep);
+            }
+        }
+    }
+
+    fn trim(&mut self, keep: usize) {
+        if keep < self.solutions.len() {
+            self.solutions.select_nth_unstable_by(keep, rank);
+            self.solutions.truncate(keep);
+        }
+    }
+
+    fn solutions(mut self) -> Vec<Solution<'a>> {
+        match (self.options.keep, &self.options.plurals) {
+            (Some(keep), _) => self.trim(keep),
+            (None, Some(plurals)) => plurals.apply(&mut self.solutions),
+            (None, None) => {}
+        }
+        self.solutions
+    }
+
+    fn summary(mut self) -> Summary<'a> {
+        // the plural filter drops words after they're counted
+        let counted = self.options.keep.is_some() || self.options.plurals.is_none();
+        let (found, score, by_length) = (self.found, self.score, mem::take(&mut self.by_length));
+        let mut best = Solutions(self.solutions());
+        best.sort_by(rank);
+        if counted {
+            return Summary { found, score, by_length, best };
+        }
+
+        let mut by_length = Vec::new();
+        for solution in best.iter() {
+            if by_length.len() <= solution.len() {
+                by_length.resize(solution.len() + 1, 0);
+            }
+            by_length[solution.len()] += 1;
+        }
+        Summary { found: best.len(), score: best.total_score(), by_length, best }
+    }
+}
+
+// a step of `Dfs`'s search still to take
+#[derive(Debug)]
+struct DfsItem {
+    visited: Visited,
+    path: Path,
+    x: usize,
+    y: usize,
+    node: usize,
+    // the tracer's id for the step that pushed this one
+    parent: Option<usize>,
+}
+
+// the state of `Board::search_trie`'s search, kept between words so it can be resumed for `SolveIter`
+struct Dfs {
+    max_len: usize,
+    first_letters: u32,
+    unvisited: Visited,
+    rng: Option<SplitMix64>,
+    starts: std::vec::IntoIter<(usize, usize)>,
+    neighbor_letters: Vec2<u32>,
+    seen: Seen,
+    stack: Vec<DfsItem>,
+    // cells stepped into so far, the starting ones included
+    expanded: usize,
+}
+
+impl Dfs {
+    fn new(board: &Board, trie: &Trie, search: Search, first_letters: u32) -> Dfs {
+        let unvisited = board.unvisited(search);
+        let mut rng = search.seed.map(SplitMix64::new);
+        let starts = board.starts(&unvisited, rng.as_mut()).into_iter();
+        Dfs {
+            max_len: search.max_len,
+            first_letters,
+            unvisited,
+            rng,
+            starts,
+            neighbor_letters: board.neighbor_letters(),
+            seen: Seen::new(trie),
+            stack: Vec::with_capacity(4098),
+            expanded: 0,
+        }
+    }
+
+    // searches on to the next word not found before, with the path it was found along
+    fn next<'a, T: Tracer<'a>>(&mut self, board: &Board, trie: &Trie<'a>, tracer: &mut T) -> Option<(&'a str, Path)> {
+        loop {
+            let mut curr = match self.stack.pop() {
+                Some(curr) => curr,
+                None => {
+                    let (i, j) = self.starts.next()?;
+                    if self.first_letters & letter_bit(board.tile((i, j))[0]) == 0 {
+                        continue;
+                    }
+                    let node = match trie.step(Trie::ROOT, board.tile((i, j))) {
+                        Some(node) => node,
+                        None => continue,
+                    };
+                    let mut path = SmallVec::new();
+                    path.push((i, j));
+                    DfsItem { x: i, y: j, node, visited: self.unvisited.clone(), path, parent: None }
+                }
+            };
+            curr.visited.insert((curr.x, curr.y));
+            self.expanded += 1;
+            if self.expanded % CANCEL_CHECKS == 1 && tracer.cancelled() {
+                self.stack.clear();
+                self.starts = Vec::new().into_iter();
+                return None;
+            }
+
+            let node = trie.node(curr.node);
+            let word = unsafe { str::from_utf8_unchecked(node.word) };
+            let step = if !node.is_word() {
+                Step::Prefix
+            } else if self.seen.insert(curr.node) {
+                Step::Word
+            } else {
+                Step::Repeat
+            };
+            let id = tracer.step(curr.parent, (curr.x, curr.y), word, step);
+
+            let mut neighbors: SmallVec<[(usize, usize); 8]> = board.neighbors((curr.x, curr.y)).collect();
+            if let Some(ref mut rng) = self.rng {
+                rng.shuffle(&mut neighbors);
+            }
+            for (x, y) in neighbors {
+                if curr.visited.contains((x, y)) {
+                    continue;
+                }
+
+                if let Some(next) = trie.step(curr.node, board.tile((x, y))) {
+                    let next_node = trie.node(next);
+                    let next_word = || unsafe { str::from_utf8_unchecked(next_node.word) };
+                    // a shared trie wasn't cut off at this board's maximum word length
+                    if next_node.word.len() > self.max_len {
+                        tracer.step(Some(id), (x, y), next_word(), Step::TooLong);
+                        continue;
+                    }
+                    // a node none of whose children's letters are next to its cell can't be extended
+                    if !next_node.is_word() && next_node.children & self.neighbor_letters[(x, y)] == 0 {
+                        tracer.step(Some(id), (x, y), next_word(), Step::DeadEnd);
+                        continue;
+                    }
+
+                    let mut path = curr.path.clone();
+                    path.push((x, y));
+                    self.stack.push(DfsItem { node: next, x, y, visited: curr.visited.clone(), path, parent: Some(id) });
+                }
+            }
+
+            if let Step::Word = step {
+                return Some((word, curr.path));
+            }
+        }
+    }
+}
+
+// the state of `Board::solve_recursive`'s search, shared down the recursion rather than copied
+struct Walk<'b, 'a> {
+    board: &'b Board,
+    trie: &'b Trie<'a>,
+    neighbor_letters: Vec2<u32>,
+    seen: Seen,
+    visited: Visited,
+    path: Path,
+    solutions: Vec<Solution<'a>>,
+}
+
+impl<'b, 'a> Walk<'b, 'a> {
+    fn visit(&mut self, cell: (usize, usize), node: usize) {
+        self.visited.insert(cell);
+        self.path.push(cell);
+
+        let trie_node = self.trie.node(node);
+        if trie_node.is_word() && self.seen.insert(node) {
+            let word = unsafe { str::from_utf8_unchecked(trie_node.word) };
+            let score = Classic.score(trie_node.word, &self.path, self.board);
+            self.solutions.push(Solution { word, score, path: self.path.to_vec() });
+        }
+
+        let board = self.board;
+        for next_cell in board.neighbors(cell) {
+            if self.visited.contains(next_cell) {
+                continue;
+            }
+            if let Some(next) = self.trie.step(node, board.tile(next_cell)) {
+                let next_node = self.trie.node(next);
+                // as in `search_trie`, a node none of whose children's letters are next to its cell is a dead end
+                if !next_node.is_word() && next_node.children & self.neighbor_letters[next_cell] == 0 {
+                    continue;
+                }
+                self.visit(next_cell, next);
+            }
+        }
+
+        self.path.pop();
+        self.visited.remove(cell);
+    }
+}
+
+// `Walk`, but exploring the neighbors with the longest words first and giving up on branches `goal` can't
+// use, which is all of them once it's met
+struct Race<'b, 'a> {
+    board: &'b Board,
+    trie: &'b Trie<'a>,
+    goal: Goal,
+    options: &'b SolveOptions,
+    search: Search<'b>,
+    neighbor_letters: Vec2<u32>,
+    seen: Seen,
+    visited: Visited,
+    path: Path,
+    solutions: Vec<Solution<'a>>,
+    // the score of `solutions`, and the lowest of them once there are as many as `Goal::TopKByScore` keeps
+    total: u32,
+    lowest: u32,
+}
+
+impl<'b, 'a> Race<'b, 'a> {
+    fn visit(&mut self, cell: (usize, usize), node: usize) {
+        self.visited.insert(cell);
+        self.path.push(cell);
+
+        let trie_node = self.trie.node(node);
+        let len = trie_node.word.len();
+        if trie_node.is_word() && len >= self.search.min_len && len <= self.search.max_len && self.seen.insert(node) {
+            let path = mem::take(&mut self.path);
+            self.found(unsafe { str::from_utf8_unchecked(trie_node.word) }, &path);
+            self.path = path;
+        }
+
+        let board = self.board;
+        let mut next: SmallVec<[((usize, usize), usize); 8]> = board.neighbors(cell)
+            .filter(|&next_cell| !self.visited.contains(next_cell) && self.inside(next_cell))
+            .filter_map(|next_cell| self.trie.step(node, board.tile(next_cell)).map(|next| (next_cell, next)))
+            .filter(|&(next_cell, next)| {
+                let next_node = self.trie.node(next);
+                next_node.word.len() <= self.search.max_len
+                    && (next_node.is_word() || next_node.children & self.neighbor_letters[next_cell] != 0)
+            })
+            .collect();
+        next.sort_unstable_by_key(|&(_, next)| Reverse(self.trie.node(next).longest()));
+        for (next_cell, next) in next {
+            // the rest have no longer words, so if this one isn't worth it, neither are they
+            if !self.worth(next) {
+                break;
+            }
+            self.visit(next_cell, next);
+        }
+
+        self.path.pop();
+        self.visited.remove(cell);
+    }
+
+    fn inside(&self, cell: (usize, usize)) -> bool {
+        self.search.region.is_none_or(|region| region[cell])
+    }
+
+    // whether words through `node` could still count towards the goal
+    fn worth(&self, node: usize) -> bool {
+        match self.goal {
+            Goal::TopKByScore(k) => {
+                let best = self.options.scorer.max_points(self.trie.node(node).longest().min(self.search.max_len));
+                self.solutions.len() < k || (k > 0 && best.is_none_or(|best| self.lowest < best))
+            }
+            Goal::StopAtScore(threshold) => self.total < threshold,
+        }
+    }
+
+    // whether `Goal::StopAtScore` has been reached; `Goal::TopKByScore` has to see every word to know it has
+    // the best
+    fn met(&self) -> bool {
+        matches!(self.goal, Goal::StopAtScore(threshold) if self.total >= threshold)
+    }
+
+    // scores `word`, found along `path`, for the goal if the rules allow it
+    fn found(&mut self, word: &'a str, path: &[(usize, usize)]) {
+        if self.options.rules.allows(word.as_bytes(), self.board) {
+            let score = self.options.scorer.score(word.as_bytes(), path, self.board);
+            self.add(Solution { word, score, path: path.to_vec() });
+        }
+    }
+
+    fn add(&mut self, solution: Solution<'a>) {
+        let keep = match self.goal {
+            Goal::TopKByScore(k) => k,
+            Goal::StopAtScore(_) => usize::MAX,
+        };
+        if self.solutions.len() >= keep {
+            // making room by dropping the last found of the lowest scoring, if the new word beats it
+            match self.solutions.iter().rposition(|s| s.score == self.lowest) {
+                Some(at) if self.lowest < solution.score => self.total -= self.solutions.remove(at).score,
+                _ => return,
+            }
+        }
+        self.total += solution.score;
+        self.solutions.push(solution);
+        if self.solutions.len() >= keep {
+            self.lowest = self.solutions.iter().map(|s| s.score).min().unwrap_or(0);
+        }
+    }
+}
+
+// `Board::could_trace`'s tables for the word before, kept so the next word only works out the letters past
+// the prefix they share
+struct Prefixes<'b> {
+    board: &'b Board,
+    word: Vec<u8>,
+    // reached[k][cell]: the first k + 1 letters of `word` can be traced ending on `cell`, in row-major order;
+    // layers past `word` are spare, kept to be reused
+    reached: Vec<Vec<bool>>,
+}
+
+impl<'b> Prefixes<'b> {
+    // `could_trace`, for words given in sorted order
+    fn traces(&mut self, word: &[u8]) -> bool {
+        let board = self.board;
+        let n = board.len();
+        let common = self.word.iter().zip(word).take_while(|(a, b)| a == b).count();
+        self.word.truncate(common);
+
+        for k in common..word.len() {
+            self.word.push(word[k]);
+            if self.reached.len() == k {
+                self.reached.push(vec![false; n * n]);
+            }
+            let (before, rest) = self.reached.split_at_mut(k);
+            let layer = &mut rest[0];
+            let mut any = false;
+            for (cell, reached) in layer.iter_mut().enumerate() {
+                let tile = board.tile((cell / n, cell % n));
+                *reached = tile.len() <= k + 1 && &word[k + 1 - tile.len()..=k] == tile
+                    && (k + 1 == tile.len() || board.neighbors((cell / n, cell % n)).any(|(x, y)| before[k - tile.len()][x * n + y]));
+                any |= *reached;
+            }
+            // with no cell reached by this prefix or the one before, no tile can carry on from it
+            if !any && k > 0 && !before[k - 1].contains(&true) {
+                return false;
+            }
+        }
+        self.reached[word.len() - 1].contains(&true)
+    }
+}
+
+// a partial path in `Board::find_single`
+#[derive(Debug)]
+struct WordDfsItem {
+    visited: Visited,
+    path: Path,
+    x: usize,
+    y: usize,
+    // how many letters of the word the path spells before this cell
+    matched: usize,
+}
+
+// the cells a path has been through, one bit per cell in row-major order; boards of up to 11x11 fit in
+// one `u128`, so the search copies 16 bytes per step instead of cloning a grid
+#[derive(Debug, Clone)]
+enum Visited {
+    Small { size: usize, bits: u128 },
+    Large { size: usize, bits: Vec<u64> },
+}
+
+impl Visited {
+    fn new(size: usize) -> Visited {
+        if size * size <= 128 {
+            Visited::Small { size, bits: 0 }
+        } else {
+            Visited::Large { size, bits: vec![0; (size * size).div_ceil(64)] }
+        }
+    }
+
+    fn contains(&self, (x, y): (usize, usize)) -> bool {
+        match *self {
+            Visited::Small { size, bits } => bits & 1 << (x * size + y) != 0,
+            Visited::Large { size, ref bits } => bits[(x * size + y) / 64] & 1 << ((x * size + y) % 64) != 0,
+        }
+    }
+
+    fn insert(&mut self, (x, y): (usize, usize)) {
+        match *self {
+            Visited::Small { size, ref mut bits } => *bits |= 1 << (x * size + y),
+            Visited::Large { size, ref mut bits } => bits[(x * size + y) / 64] |= 1 << ((x * size + y) % 64),
+        }
+    }
+
+    fn remove(&mut self, (x, y): (usize, usize)) {
+        match *self {
+            Visited::Small { size, ref mut bits } => *bits &= !(1 << (x * size + y)),
+            Visited::Large { size, ref mut bits } => bits[(x * size + y) / 64] &= !(1 << ((x * size + y) % 64)),
+        }
+    }
+}
+
+// a dictionary the search can walk without marking nodes
+pub(crate) trait Lexicon<'a> {
+    type Node: Copy;
+
+    fn root(&self) -> Self::Node;
+    // the letter mask of the node's children
+    fn children(&self, node: Self::Node) -> u32;
+    fn step(&self, node: Self::Node, letters: &[u8]) -> Option<Self::Node>;
+    // the word the node ends, given that it's `len` letters deep
+    fn word(&self, node: Self::Node, len: usize) -> Option<&'a str>;
+}
+
+impl<'a> Lexicon<'a> for CompiledDictionary<'a> {
+    type Node = Node;
+
+    fn root(&self) -> Node {
+        CompiledDictionary::root(self)
+    }
+
+    fn children(&self, node: Node) -> u32 {
+        node.children
+    }
+
+    fn step(&self, node: Node, letters: &[u8]) -> Option<Node> {
+        CompiledDictionary::step(self, node, letters)
+    }
+
+    fn word(&self, node: Node, len: usize) -> Option<&'a str> {
+        CompiledDictionary::word(self, node, len)
+    }
+}
+
+impl<'a> Lexicon<'a> for Trie<'a> {
+    type Node = usize;
+
+    fn root(&self) -> usize {
+        Trie::ROOT
+    }
+
+    fn children(&self, node: usize) -> u32 {
+        self.node(node).children
+    }
+
+    fn step(&self, node: usize, letters: &[u8]) -> Option<usize> {
+        Trie::step(self, node, letters)
+    }
+
+    fn word(&self, node: usize, _: usize) -> Option<&'a str> {
+        let node = self.node(node);
+        if node.is_word() {
+            Some(unsafe { str::from_utf8_unchecked(node.word) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Lexicon<'a> for Dawg<'a> {
+    type Node = State;
+
+    fn root(&self) -> State {
+        Dawg::root(self)
+    }
+
+    fn children(&self, state: State) -> u32 {
+        Dawg::children(self, state)
+    }
+
+    fn step(&self, state: State, letters: &[u8]) -> Option<State> {
+        Dawg::step(self, state, letters)
+    }
+
+    fn word(&self, state: State, _: usize) -> Option<&'a str> {
+        Dawg::word(self, state)
+    }
+}
+
+// `SolveOptions::threads`, or the available parallelism
+fn threads(options: &SolveOptions) -> usize {
+    options.threads
+        .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+        .unwrap_or(1)
+}
+
+fn tile(c: &u8) -> &'static [u8] {
+    if c.is_ascii_uppercase() {
+        let (letters, _) = Board::DIGRAPHS.iter().find(|&&(_, cell)| cell == *c).expect("cells are letters or tiles");
+        letters.as_bytes()
+    } else {
+        std::slice::from_ref(&TILES[(c - b'a') as usize])
+    }
+}
+
+// every letter as the solvers see it, the last few standing for an alphabet's extra letters
+const TILES: &[u8; LETTERS] = b"abcdefghijklmnopqrstuvwxyz{|}~\x7f";
+
+// the board's non-blank lines, trimmed and checked
+fn rows<'r>(raw: &'r str, alphabet: &Alphabet) -> Result<Vec<&'r str>, Error> {
+    let rows: Vec<_> = raw.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if rows.is_empty() {
+        return Err(Error::EmptyBoard);
+    }
+    check_letters(&rows, alphabet)?;
+    Ok(rows)
+}
+
+// checks every character is a letter of `alphabet` or separates cells
+fn check_letters(rows: &[&str], alphabet: &Alphabet) -> Result<(), Error> {
+    for (row, line) in rows.iter().enumerate() {
+        if let Some((col, ch)) = line.chars().enumerate().find(|&(_, c)| !alphabet.contains(c) && c != ' ' && c != '\t') {
+            return Err(Error::InvalidCharacter { row, col, ch });
+        }
+    }
+    Ok(())
+}
+
+// one word per line, ignoring surrounding whitespace and blank lines
+fn dictionary(words: &str) -> impl Iterator<Item = &str> {
+    words.lines().map(str::trim).filter(|w| !w.is_empty())
+}
+
+// the cell value of the tile written `letters`, in either case
+fn cell(letters: &[u8]) -> Option<u8> {
+    match letters.to_ascii_lowercase()[..] {
+        [c] => Some(c),
+        ref letters => Board::DIGRAPHS.iter().find(|(tile, _)| tile.as_bytes() == letters).map(|&(_, c)| c),
+    }
+}
+
+// lowercases the row and collapses each `qu` into a single `Board::QU` cell; a row of cells separated by whitespace is read a cell at a time, failing with the index of any that isn't a tile
+fn parse_row(row: &[u8]) -> Result<Vec<u8>, usize> {
+    if row.iter().any(u8::is_ascii_whitespace) {
+        return row.split(u8::is_ascii_whitespace)
+            .filter(|cell| !cell.is_empty())
+            .enumerate()
+            .map(|(col, letters)| cell(letters).ok_or(col))
+            .collect();
+    }
+
+    let row = row.to_ascii_lowercase();
+    let mut cells = Vec::with_capacity(row.len());
+    let mut i = 0;
+    while i < row.len() {
+        if row[i..].starts_with(b"qu") {
+            cells.push(Board::QU);
+            i += 2;
+        } else {
+            cells.push(row[i]);
+            i += 1;
+        }
+    }
+    Ok(cells)
+}
+
+impl Index<(usize, usize)> for Board {
+    type Output = u8;
+
+    fn index(&self, (x, y): (usize, usize)) -> &u8 {
+        self.get((x as isize, y as isize)).expect("index out of bounds!")
+    }
+}
+
+// maps `(i, j)` on a board whose last index is `n` to its image under each rotation and reflection of the square
+type Symmetry = fn(usize, usize, usize) -> (usize, usize);
+const SYMMETRIES: [Symmetry; 8] = [
+    |_, i, j| (i, j),
+    |n, i, j| (j, n - i),
+    |n, i, j| (n - i, n - j),
+    |n, i, j| (n - j, i),
+    |n, i, j| (i, n - j),
+    |n, i, j| (n - i, j),
+    |_, i, j| (j, i),
+    |n, i, j| (n - j, n - i),
+];
+
+pub(crate) const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compiled::compile;
+    const BOARD: &str = "abcd\nefgh\nijkl\nmnop";
+
+    #[test]
+    fn parse() {
+        let board = Board::parse(BOARD).unwrap();
+        assert_eq!(board.len(), 4);
+        assert_eq!(board[(0, 0)], b'a');
+        assert_eq!(board[(0, 3)], b'd');
+        assert_eq!(board[(3, 3)], b'p');
+        assert_eq!(board[(0, 1)], b'b');
+        assert_eq!(board[(1, 0)], b'e');
+    }
+
+    #[test]
+    fn parse_row_length() {
+        let raw = "abcdef\nabcdef\nabcdef\nabcde\nabcdef\nabcdef";
+        assert!(matches!(Board::parse(raw), Err(Error::BoardRowLength { row: 3, expected: 6, got: 5 })));
+        assert!(matches!(Board::parse("abc\nabc"), Err(Error::BoardRowLength { row: 0, expected: 2, got: 3 })));
+    }
+
+    #[test]
+    fn parse_any() {
+        let english = Alphabet::english();
+        let grid = Board::parse(BOARD).unwrap();
+        let words = "abef\nafjo\nmino\nplonk";
+        for (raw, format) in [(BOARD, Format::Grid), ("abcd,efgh,ijkl,mnop\n", Format::Inline), (" abcd , EFGH,ijkl,mnop", Format::Inline),
+                              ("a,b,c,d\ne,f,g,h\n\ni, j ,k,l\r\nm,n,o,\"p\"\n", Format::Csv),
+                              (r#"["abcd", "efgh", ["i", "j", "k", "l"], "m n o p"]"#, Format::Json),
+                              (r#" {"rows": ["abcd", "efgh", "ijkl", "mnop"]}"#, Format::Json)] {
+            assert_eq!(Format::detect(raw), format, "{:?}", raw);
+            let board = super::parse_any(raw, None, &english).unwrap();
+            assert_eq!(board.solve_trie(words), grid.solve_trie(words), "{:?}", raw);
+            assert_eq!(board.topology(), Topology::default());
+        }
+
+        let csv = super::parse_any("th,e\nqu,IN\n", None, &english).unwrap();
+        assert_eq!((csv[(0, 0)], csv[(1, 0)], csv[(1, 1)]), (b'T', Board::QU, b'I'));
+        let json = super::parse_any(r#"{"rows": [["th", "e"], ["qu", "in"]], "wrap": true, "diagonals": false}"#, None, &english).unwrap();
+        assert_eq!(json.solve_trie("thein\nquin"), csv.solve_trie("thein\nquin"));
+        assert_eq!(json.topology(), Topology { diagonals: false, wrap: true });
+        let spanish = super::parse_any("ñ,a\nb,c", None, &Alphabet::spanish()).unwrap();
+        assert_eq!(spanish.len(), 2);
+        // a grid forced to be read as CSV is one cell per row
+        assert!(matches!(super::parse_any("ab\ncd", Some(Format::Csv), &english), Err(Error::InvalidTile { row: 0, col: 0, .. })));
+
+        assert!(matches!(super::parse_any("a,b\nc,1", None, &english), Err(Error::InvalidTile { row: 1, col: 1, ref tile }) if tile == "1"));
+        assert!(matches!(super::parse_any("a,b\nc,", None, &english), Err(Error::InvalidTile { row: 1, col: 1, .. })));
+        assert!(matches!(super::parse_any("a,b\nc,xy", None, &english), Err(Error::InvalidTile { row: 1, col: 1, ref tile }) if tile == "xy"));
+        assert!(matches!(super::parse_any("a,b\nc", None, &english), Err(Error::BoardRowLength { row: 1, .. })));
+        assert!(matches!(super::parse_any("ab,c1", None, &english), Err(Error::InvalidCharacter { row: 1, col: 1, ch: '1' })));
+        assert!(matches!(super::parse_any("[\"ab\", \"cd\"", None, &english), Err(Error::InvalidJson { offset: 11 })));
+        for raw in [r#"{"board": ["ab", "cd"]}"#, r#"[["a", 1], "cd"]"#, r#"[1, 2]"#, r#"{"rows": "abcd"}"#, r#"{"rows": ["ab", "cd"], "wrap": 1}"#, r#"["a\nb"]"#] {
+            assert!(matches!(super::parse_any(raw, None, &english), Err(Error::NotABoard)), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let board = Board::parse("\r\n  QuAt \r\n\r\nxYz\r\nabc\r\n\n").unwrap();
+        assert_eq!(board.len(), 3);
+        assert_eq!(board[(0, 0)], Board::QU);
+        assert_eq!(board[(0, 1)], b'a');
+        assert_eq!(board[(1, 1)], b'y');
+        assert_eq!(board.solve_trie("  quay \r\n\nqua\n").iter().map(|s| s.word).collect::<Vec<_>>(), vec!["qua", "quay"]);
+
+        for (raw, row, col, ch) in [("ab\nc1", 1, 1, '1'), ("añ\nbc", 0, 1, 'ñ'), ("a-b\nc d", 0, 1, '-')] {
+            match Board::parse(raw) {
+                Err(Error::InvalidCharacter { row: r, col: c, ch: h }) => assert_eq!((r, c, h), (row, col, ch)),
+                _ => panic!("parsed {:?}", raw),
+            }
+        }
+    }
+
+    #[test]
+    fn alphabet() {
+        use crate::Dictionary;
+
+        let german = Alphabet::german();
+        let board = Board::parse_in("fuß\nÜbe\nxrx", &german).unwrap();
+        assert_eq!(board.len(), 3);
+        assert!(matches!(Board::parse("fuß\nübe\nrxx"), Err(Error::InvalidCharacter { row: 0, col: 2, ch: 'ß' })));
+        assert!(matches!(Board::parse_in("fuñ\nübe\nrxx", &german), Err(Error::InvalidCharacter { row: 0, col: 2, ch: 'ñ' })));
+        assert!(matches!(Board::parse("fu{\nxxx\nxxx"), Err(Error::InvalidCharacter { row: 0, col: 2, ch: '{' })));
+
+        let dictionary = Dictionary::parse_in("fuß\nüber\nfub\nfuss\n", &german);
+        let words = dictionary.as_str();
+        let decoded = |solutions: Vec<Solution>| {
+            let mut words: Vec<_> = solutions.iter().map(|s| (german.decode(s.word).into_owned(), s.score)).collect();
+            words.sort_unstable();
+            words
+        };
+        let expected = vec![("fub".to_string(), 1), ("fuß".to_string(), 1), ("über".to_string(), 1)];
+        assert_eq!(decoded(board.solve(words, &SolveOptions::default())), expected);
+        assert_eq!(decoded(board.solve_single_threaded(words)), expected);
+        assert_eq!(decoded(board.solve_trie(words)), expected);
+        let bytes = crate::compiled::compile_in(words, &german);
+        assert_eq!(decoded(board.solve_compiled(&CompiledDictionary::from_bytes(&bytes).unwrap(), &SolveOptions::default())), expected);
+
+        let board = Board::parse_in("süß\nxxx\nxxx", &german).unwrap();
+        let word = german.encode("süß");
+        assert_eq!(board.find_word(&word), Some(vec![(0, 0), (0, 1), (0, 2)]));
+        assert!(board.can_contain(&word));
+        assert!(!board.can_contain(&german.encode("süd")));
+    }
+
+    #[test]
+    fn degenerate() {
+        for raw in &["", "\n", "  \n\n"] {
+            assert!(matches!(Board::parse(raw), Err(Error::EmptyBoard)), "parsed empty board {:?}", raw);
+        }
+
+        let words = "a\nab\nabc\nabcd\nque\nqua\nquad";
+        let one = Board::parse("a").unwrap();
+        assert_eq!(one.len(), 1);
+        assert!(one.solve_trie(words).is_empty());
+        assert!(one.solve_single_threaded(words).is_empty());
+        assert!(one.solve(words, &SolveOptions::default()).is_empty());
+
+        // "qu" alone is only two letters, still too short
+        assert!(Board::parse("qu").unwrap().solve_trie(words).is_empty());
+
+        let two = Board::parse("ab\ndc").unwrap();
+        let sorted = |mut s: Vec<Solution<'static>>| { s.sort(); s.into_iter().map(|s| s.word).collect::<Vec<_>>() };
+        assert_eq!(sorted(two.solve_trie(words)), vec!["abc", "abcd"]);
+        assert_eq!(sorted(two.solve_single_threaded(words)), vec!["abc", "abcd"]);
+        assert_eq!(sorted(two.solve(words, &SolveOptions::default())), vec!["abc", "abcd"]);
+
+        let qu = Board::parse("qua\nd").unwrap_err();
+        assert!(matches!(qu, Error::BoardRowLength { row: 1, expected: 2, got: 1 }));
+        let qu = Board::parse("qua\ndx").unwrap();
+        assert_eq!(sorted(qu.solve_trie(words)), vec!["qua", "quad"]);
+        assert_eq!(sorted(qu.solve_single_threaded(words)), vec!["qua", "quad"]);
+    }
+
+    #[test]
+    fn distance() {
+        let board = Board::parse("abc\ndef\nghi").unwrap();
+        assert_eq!(board.distance(&board), 0);
+        let rotated = Board::parse("gda\nheb\nifc").unwrap();
+        assert_eq!(board.distance(&rotated), 0);
+        let mirrored = Board::parse("cba\nfed\nihg").unwrap();
+        assert_eq!(board.distance(&mirrored), 0);
+        let changed = Board::parse("gda\nhxb\nyfc").unwrap();
+        assert_eq!(board.distance(&changed), 2);
+        assert_eq!(changed.distance(&board), 2);
+        assert_eq!(board.distance(&Board::parse("ab\ncd").unwrap()), 9);
+    }
+
+    #[test]
+    fn parse_lossy() {
+        // on input `parse` takes, the same board; on input it doesn't, its error among the rest
+        for raw in ["cat\nxxx\nxxx", "quit\nsare\nnlop\ndeam", "th e\nqu a", "", "ab\nc", "ab\nc1", "th e zz\nqu a", "a\tb\nc d"] {
+            let (board, problems) = Board::parse_lossy(raw);
+            match Board::parse(raw) {
+                Ok(parsed) => {
+                    assert_eq!(board.map(|b| b.text()), Some(parsed.text()));
+                    assert!(problems.is_empty());
+                }
+                Err(err) => assert!(problems.iter().any(|p| p.to_string() == err.to_string()), "{:?}", raw),
+            }
+        }
+
+        let (board, problems) = Board::parse_lossy("th e zz\nqu a");
+        assert_eq!(board.unwrap().text(), "th e\nqu a");
+        assert!(matches!(problems[..], [Error::InvalidTile { row: 0, col: 2, ref tile }] if tile == "zz"));
+        let (board, problems) = Board::parse_lossy("!?\nab\ncd");
+        assert!(board.is_none());
+        assert!(matches!(problems[..], [Error::InvalidCharacter { row: 0, col: 0, ch: '!' }, Error::InvalidCharacter { row: 0, col: 1, ch: '?' },
+                                        Error::BoardRowLength { row: 0, expected: 3, got: 0 }, Error::BoardRowLength { row: 1, expected: 3, got: 2 },
+                                        Error::BoardRowLength { row: 2, expected: 3, got: 2 }]));
+        let (board, problems) = Board::parse_lossy(" \n\t\r\n");
+        assert!(board.is_none() && matches!(problems[..], [Error::EmptyBoard]));
+        let (board, problems) = Board::parse_lossy("\u{0}a\u{fffd}\u{202e}é\r\n");
+        assert_eq!((board.unwrap().text(), problems.len()), ("a".to_string(), 4));
+    }
+
+    #[test]
+    fn from_rows() {
+        let rolled: Vec<String> = "ca\nTQu".lines().map(String::from).collect();
+        let board = Board::from_rows(rolled).unwrap();
+        assert_eq!(board.tile((1, 1)), b"qu");
+        assert_eq!(board.solve_trie("act\ncat\nquit"), Board::parse("ca\ntqu").unwrap().solve_trie("act\ncat\nquit"));
+        let board = Board::from_rows(vec![" th e ".to_string(), "a n".to_string()]).unwrap();
+        assert_eq!(board.tile((0, 0)), b"th");
+
+        assert!(matches!(Board::from_rows(Vec::new()), Err(Error::EmptyBoard)));
+        assert!(matches!(Board::from_rows(vec!["ab".to_string(), "c1".to_string()]), Err(Error::InvalidCharacter { row: 1, col: 1, ch: '1' })));
+        assert!(matches!(Board::from_rows(vec!["ab".to_string(), "".to_string()]), Err(Error::BoardRowLength { row: 1, expected: 2, got: 0 })));
+    }
+
+    #[test]
+    fn edit() {
+        let rows = |board: &Board| {
+            (0..board.len()).map(|i| (0..board.len()).map(|j| board[(i, j)] as char).collect::<String>()).collect::<Vec<_>>().join("\n")
+        };
+        let mut board = Board::parse("abc\ndef\nghi").unwrap();
+        board.rotate();
+        assert_eq!(rows(&board), "gda\nheb\nifc");
+        board.mirror();
+        assert_eq!(rows(&board), "adg\nbeh\ncfi");
+        board.swap_tiles((0, 0), (2, 2));
+        assert_eq!(rows(&board), "idg\nbeh\ncfa");
+
+        // the letters, and so the words found, follow each edit
+        let sorted = |board: &Board| {
+            let mut words: Vec<_> = board.solve("cat\ncats\nquat\nsat", &SolveOptions::default()).into_iter().map(|s| s.word).collect();
+            words.sort();
+            words
+        };
+        let mut board = Board::parse("cat\nxxx\nxxx").unwrap().with_topology(Topology { diagonals: false, wrap: false });
+        assert_eq!(sorted(&board), ["cat"]);
+        board.set_tile((1, 2), "S").unwrap();
+        assert_eq!(sorted(&board), ["cat", "cats"]);
+        assert_eq!(board.letter_counts()[(b's' - b'a') as usize], 1);
+        assert_eq!(board.letter_counts()[(b'x' - b'a') as usize], 5);
+        board.set_tile((0, 0), "Qu").unwrap();
+        assert_eq!(board.tile((0, 0)), b"qu");
+        assert_eq!(sorted(&board), ["quat"]);
+        assert_eq!(board.topology(), Topology { diagonals: false, wrap: false });
+        assert!(!board.can_contain("squat"));
+        board.swap_tiles((0, 0), (1, 1));
+        assert_eq!(board.tile((1, 1)), b"qu");
+        assert!(board.can_contain("squat"));
+
+        // a bad tile leaves the board as it was
+        for tile in ["ab", "1", "", "é", "qux"] {
+            assert!(matches!(board.set_tile((0, 1), tile), Err(Error::InvalidTile { row: 0, col: 1, tile: ref t }) if t == tile));
+        }
+        assert_eq!(rows(&board), "xat\nxQs\nxxx");
+
+        // an alphabet's extra letters go in as `parse_in` would read them
+        let spanish = Alphabet::spanish();
+        let mut board = Board::parse_in("año\nxxx\nxxx", &spanish).unwrap();
+        board.set_tile_in((0, 1), "Ñ", &spanish).unwrap();
+        board.set_tile_in((1, 1), "ñ", &spanish).unwrap();
+        assert_eq!(board.text(), Board::parse_in("año\nxñx\nxxx", &spanish).unwrap().text());
+        assert!(board.can_contain(&spanish.encode("ñoñ")));
+        for tile in ["{", "ä", "ññ"] {
+            assert!(board.set_tile_in((0, 0), tile, &spanish).is_err(), "{}", tile);
+        }
+        assert!(board.set_tile((0, 0), "ñ").is_err());
+        board.set_tile_in((0, 0), "Th", &spanish).unwrap();
+        assert_eq!(board.tile((0, 0)), b"th");
+    }
+
+    #[test]
+    fn letter_counts() {
+        let board = Board::parse("aab\nbcz\nzza").unwrap();
+        let counts = board.letter_counts();
+        assert_eq!(&counts[..3], &[3, 2, 1]);
+        assert_eq!(counts[25], 3);
+        assert_eq!(counts.iter().map(|&c| c as usize).sum::<usize>(), 9);
+    }
+
+    #[test]
+    fn qu() {
+        let board = Board::parse("quen\nexx\nxxx").unwrap();
+        assert_eq!(board.len(), 3);
+        assert_eq!(board[(0, 0)], Board::QU);
+        assert_eq!(board.tile((0, 0)), b"qu");
+        assert_eq!(board.tile((0, 1)), b"e");
+        assert_eq!(board.letter_counts()[(b'u' - b'a') as usize], 1);
+        assert!(board.can_contain("queen"));
+        assert!(!board.can_contain("qeen"));
+
+        let words = "queen\nqueens\nqeen\nnee\nuee";
+        let expected = vec![Solution { word: "queen", score: 2, path: vec![(0, 0), (1, 0), (0, 1), (0, 2)] }];
+        let sorted = |mut s: Vec<Solution<'static>>| { s.sort(); s };
+        assert_eq!(sorted(board.solve_trie(words)).iter().filter(|s| s.word != "nee").cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(sorted(board.solve_single_threaded(words)).iter().filter(|s| s.word != "nee").cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(board.words_through((1, 0), words).iter().find(|s| s.word == "queen").unwrap().path, expected[0].path);
+    }
+
+    #[test]
+    fn non_letters() {
+        // text straight to the solvers, without `Dictionary` to drop what isn't letters
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "cat's\ncat-s\nCATS\nnaïve\ncat\n";
+        let found = |solutions: Vec<Solution<'static>>| solutions.into_iter().map(|s| s.word).collect::<Vec<_>>();
+        assert_eq!(found(board.solve_single_threaded(words)), ["cat"]);
+        assert_eq!(found(board.solve_trie(words)), ["cat"]);
+        assert_eq!(found(board.solve_recursive(words)), ["cat"]);
+        assert_eq!(found(board.solve(words, &SolveOptions::default())), ["cat"]);
+        assert_eq!(board.profile_single_threaded(words, 1).0.len(), 1);
+    }
+
+    #[test]
+    fn digraphs() {
+        let board = Board::parse(" Th er e x \nan  he qu in\nx x x x\nx x x x").unwrap();
+        assert_eq!(board.len(), 4);
+        assert_eq!(board.tile((0, 0)), b"th");
+        assert_eq!(board.tile((0, 1)), b"er");
+        assert_eq!(board.tile((1, 2)), b"qu");
+        assert_eq!(board.letter_counts()[(b'h' - b'a') as usize], 2);
+        assert!(board.can_contain("there"));
+        assert!(!board.can_contain("tere"));
+
+        let words = "there\nthan\nquin\nquine\nere\nthe\ntere\nqua";
+        let expected = vec!["ere", "quin", "quine", "than", "there"];
+        let sorted = |s: Vec<Solution<'static>>| { let mut words: Vec<_> = s.into_iter().map(|s| s.word).collect(); words.sort(); words };
+        assert_eq!(sorted(board.solve_trie(words)), expected);
+        assert_eq!(sorted(board.solve_single_threaded(words)), expected);
+        assert_eq!(sorted(board.solve(words, &SolveOptions::default())), expected);
+        assert_eq!(crate::verify::verify(&board, words), []);
+
+        // undelimited rows only know "Qu"
+        assert_eq!(Board::parse("th\nxx").unwrap().tile((0, 0)), b"t");
+        let err = Board::parse("a b\nc xy").unwrap_err();
+        assert!(matches!(err, Error::InvalidTile { row: 1, col: 1, ref tile } if tile == "xy"), "{:?}", err);
+        assert!(matches!(Board::parse("a b\nc"), Err(Error::BoardRowLength { row: 1, expected: 2, got: 1 })));
+    }
+
+    #[test]
+    fn topology() {
+        let sorted = |board: &Board, cell| {
+            let mut neighbors = board.neighbors_of(cell).to_vec();
+            neighbors.sort();
+            neighbors
+        };
+        let board = Board::parse(BOARD).unwrap().with_topology(Topology { diagonals: false, wrap: false });
+        assert_eq!(sorted(&board, (0, 0)), vec![(0, 1), (1, 0)]);
+        assert_eq!(sorted(&board, (1, 1)), vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+
+        let board = Board::parse(BOARD).unwrap().with_topology(Topology { diagonals: true, wrap: true });
+        assert_eq!(sorted(&board, (0, 0)), vec![(0, 1), (0, 3), (1, 0), (1, 1), (1, 3), (3, 0), (3, 1), (3, 3)]);
+        let board = board.with_topology(Topology { diagonals: false, wrap: true });
+        assert_eq!(sorted(&board, (0, 0)), vec![(0, 1), (0, 3), (1, 0), (3, 0)]);
+
+        // wrapping a tiny board doesn't repeat a neighbor or make a cell its own
+        let board = Board::parse("ab\ncd").unwrap().with_topology(Topology { diagonals: true, wrap: true });
+        assert_eq!(sorted(&board, (0, 0)), vec![(0, 1), (1, 0), (1, 1)]);
+        let board = Board::parse("a").unwrap().with_topology(Topology { diagonals: true, wrap: true });
+        assert!(sorted(&board, (0, 0)).is_empty());
+        // the table follows the board's size across a change of topology
+        let board = Board::parse("abc\ndef\nghi").unwrap().with_topology(Topology { diagonals: false, wrap: true });
+        assert_eq!(sorted(&board, (2, 2)), vec![(0, 2), (1, 2), (2, 0), (2, 1)]);
+        assert_eq!(board.with_topology(Topology::default()).neighbors_of((1, 1)).len(), 8);
+
+        // "bad" needs a diagonal, "tab" wraps from the bottom row to the top and "cab" does both
+        let words = "bad\ncab\ntab";
+        let board = Board::parse("abx\nxdx\ntxc").unwrap();
+        let found = |board: &Board| {
+            let sorted = |solutions: Vec<Solution<'static>>| {
+                let mut words: Vec<_> = solutions.into_iter().map(|s| s.word).collect();
+                words.sort();
+                words
+            };
+            let found = sorted(board.solve(words, &SolveOptions::default()));
+            assert_eq!(found, sorted(board.solve_single_threaded(words)));
+            found
+        };
+        assert_eq!(found(&board), vec!["bad"]);
+        let board = board.with_topology(Topology { diagonals: true, wrap: true });
+        assert_eq!(found(&board), vec!["bad", "cab", "tab"]);
+        let board = board.with_topology(Topology { diagonals: false, wrap: true });
+        assert_eq!(found(&board), vec!["tab"]);
+    }
+
+    #[test]
+    fn neighbors_edge() {
+        let board = Board::parse(BOARD).unwrap();
+        let mut neighbors: Vec<_> = board.neighbors((0, 0)).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![(0, 1), (1, 0), (1, 1)]);
+
+        let mut neighbors: Vec<_> = board.neighbors((3, 3)).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![(2, 2), (2, 3), (3, 2)]);
+    }
+
+    #[test]
+    fn neighbors() {
+        let board = Board::parse(BOARD).unwrap();
+        let mut neighbors: Vec<_> = board.neighbors((1, 1)).collect();
+        assert_eq!(board[(1, 1)], b'f');
+        neighbors.sort();
+        assert_eq!(neighbors, vec![
+            (0, 0), (0, 1), (0, 2),
+            (1, 0), (1, 2),
+            (2, 0), (2, 1), (2, 2)
+        ]);
+    }
+
+    #[test]
+    fn can_contain() {
+        let board = Board::parse(BOARD).unwrap();
+        assert!(board.can_contain("abcd"));
+        assert!(board.can_contain("dcba"));
+        assert!(board.can_contain("afkp"));
+        assert!(board.can_contain("pkfa"));
+        assert!(board.can_contain("mjgd"));
+        assert!(board.can_contain("dgjm"));
+        assert!(board.can_contain("aeim"));
+        assert!(board.can_contain("miea"));
+        assert!(board.can_contain("aefb"));
+        assert!(board.can_contain("bfea"));
+
+        assert!(!board.can_contain("lies"));
+        assert!(!board.can_contain("mapb"));
+
+        // on a 2x2 board every cell is next to every other, but none can be used twice
+        let small = Board::parse("ab\ncd").unwrap();
+        assert!(small.can_contain("abdc"));
+        assert!(small.could_trace(b"abab"));
+        assert!(!small.can_contain("abab"));
+        assert!(!small.can_contain("ABCD"));
+        assert!(!small.can_contain(""));
+    }
+
+    #[test]
+    fn find_word() {
+        let board = Board::parse(BOARD).unwrap();
+        assert_eq!(board.find_word("afkp"), Some(vec![(0, 0), (1, 1), (2, 2), (3, 3)]));
+        // each letter is next to the one before, but it needs the "a" twice
+        assert!(board.could_trace(b"abfab"));
+        assert!(!board.can_contain("abfab"));
+        assert_eq!(board.find_word("abfab"), None);
+        assert_eq!(board.find_word("lies"), None);
+        assert_eq!(board.find_word(""), None);
+        assert_eq!(board.find_word("ABCD"), None);
+
+        let qu = Board::parse("quits\nxxxx\nxxxx\nxxxx").unwrap();
+        assert_eq!(qu.find_word("quit"), Some(vec![(0, 0), (0, 1), (0, 2)]));
+        assert_eq!(qu.find_word("qit"), None);
+    }
+
+    // a board of "a"s but for a "b" in the corner
+    fn far_b(size: usize) -> Board {
+        let mut rows: Vec<String> = (0..size).map(|_| "a".repeat(size)).collect();
+        rows[size - 1] = format!("{}b", "a".repeat(size - 1));
+        Board::parse(&rows.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn find_word_guided() {
+        let board = Board::parse(BOARD1).unwrap();
+        for word in DICTIONARY.lines().chain(["abfab", "", "ABCD", "lies"]) {
+            let path = board.find_word_guided(word);
+            assert_eq!(path.is_some(), board.can_contain(word), "{}", word);
+            assert!(path.is_none_or(|path| super::validate_path(&board, word, &path).is_ok()), "{}", word);
+        }
+        let qu = Board::parse("quits\nxxxx\nxxxx\nxxxx").unwrap();
+        assert_eq!(qu.find_word_guided("quit"), Some(vec![(0, 0), (0, 1), (0, 2)]));
+        assert_eq!(qu.find_word_guided("qit"), None);
+
+        // plenty of ways to get lost among the "a"s before reaching the "b"
+        let board = far_b(10);
+        let word = format!("{}b", "a".repeat(60));
+        assert!(super::validate_path(&board, &word, &board.find_word_guided(&word).unwrap()).is_ok());
+        let (board, snake) = snake();
+        assert!(super::validate_path(&board, &snake, &board.find_word_guided(&snake).unwrap()).is_ok());
+        // with and without room left around the "b" for the last of the "a"s
+        let board = Board::parse("aab\naax\nxxx").unwrap();
+        for word in ["aab", "aaab", "aaaab", "aaaaab", "baaaa", "abaa"] {
+            assert_eq!(board.find_word_guided(word).is_some(), board.can_contain(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn canonical_path() {
+        // "aba" can start from either "a"
+        let board = Board::parse("ab\nba").unwrap();
+        assert_eq!(board.canonical_path("aba"), Some(vec![(0, 0), (0, 1), (1, 1)]));
+        assert_eq!(board.canonical_path("bab"), Some(vec![(0, 1), (0, 0), (1, 0)]));
+        assert_eq!(board.canonical_path("abab"), Some(vec![(0, 0), (0, 1), (1, 1), (1, 0)]));
+        assert_eq!(board.canonical_path("abc"), None);
+
+        let board = Board::parse(BOARD1).unwrap();
+        for solution in board.solve_trie(DICTIONARY) {
+            let path = board.canonical_path(solution.word).unwrap();
+            assert!(path <= solution.path, "{}", solution.word);
+            assert!(crate::validate_path(&board, solution.word, &path).is_ok());
+        }
+    }
+
+    const DICTIONARY: &str = include_str!("../test/dictionary");
+    const BOARD1: &str = include_str!("../test/board1");
+
+    #[test]
+    fn single_threaded() {
+        let board = Board::parse(BOARD1).unwrap();
+        assert_eq!(board.solve_single_threaded(DICTIONARY).len(), 126);
+    }
+
+    #[test]
+    fn trie() {
+        let board = Board::parse(BOARD1).unwrap();
+        assert_eq!(board.solve_trie(DICTIONARY).len(), 126);
+    }
+
+    #[test]
+    fn fixed() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        for raw in &[BOARD1, include_str!("../test/board2"), "quiet\nrhsa\notni\nlpde", "quseat\ntrpln\nieodg\nhsaet\nrcnib"] {
+            let board = Board::parse(raw).unwrap();
+            let search = Search { min_len: MIN_LEN, max_len: board.max_letters(), seed: None, region: None };
+            let (mut general, mut fixed) = (Vec::new(), Vec::new());
+            let expanded = board.search_trie(&trie, search, !0, |word, path| general.push((word, path.to_vec())), &mut ());
+            assert_eq!(board.search_words(&trie, search, !0, |word, path| fixed.push((word, path.to_vec()))), expanded);
+            assert!(general.len() > 10, "{}", raw);
+            assert_eq!(fixed, general, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn solve_iter() {
+        let board = Board::parse(BOARD1).unwrap();
+        let options = SolveOptions { min_len: Some(4), ..SolveOptions::default() };
+        let all: Vec<_> = board.solve_iter(DICTIONARY, &options).collect();
+        let trie: Vec<_> = board.solve_trie(DICTIONARY).into_iter().filter(|s| s.word.len() >= 4).collect();
+        assert_eq!(all.iter().map(|s| (s.word, &s.path)).collect::<Vec<_>>(),
+                   trie.iter().map(|s| (s.word, &s.path)).collect::<Vec<_>>());
+        assert_eq!(board.solve_iter(DICTIONARY, &options).take(5).collect::<Vec<_>>(), &all[..5]);
+    }
 
-impl<'word> Board<'word> {
-    pub fn parse(raw: &str) -> Result<Board, Error> {
-        assert!(raw.is_ascii());
-        let board: Vec<_> = raw.lines().map(|l| l.as_bytes()).collect();
-        if board.iter().any(|l| l.len() != board.len()) {
-            return Err(Error::BoardSize("unequal row and column sizes"));
-        }
+    #[test]
+    fn solve_iter_by_frequency() {
+        let board = Board::parse(BOARD1).unwrap();
+        let options = SolveOptions { min_len: Some(4), ..SolveOptions::default() };
+        // ranked backwards from the end of each word, half of them and some words that aren't on the board
+        let mut words: Vec<_> = board.solve_iter(DICTIONARY, &options).map(|s| s.word).collect();
+        words.sort_by_key(|word| word.bytes().rev().collect::<Vec<_>>());
+        let ranked: Vec<_> = words.iter().step_by(2).flat_map(|&word| [word, "zzzz"]).collect();
+        let found: Vec<_> = board.solve_iter_by_frequency(DICTIONARY, &ranked.join("\n"), &options).collect();
+        assert!(found.iter().all(|s| super::validate_path(&board, s.word, &s.path).is_ok()));
+        let (common, rest) = found.split_at(words.len().div_ceil(2));
+        assert_eq!(common.iter().map(|s| s.word).collect::<Vec<_>>(), words.iter().step_by(2).copied().collect::<Vec<_>>());
+        let mut rest: Vec<_> = rest.iter().map(|s| s.word).collect();
+        rest.sort_by_key(|word| word.bytes().rev().collect::<Vec<_>>());
+        assert_eq!(rest, words.iter().skip(1).step_by(2).copied().collect::<Vec<_>>());
+        assert_eq!(board.solve_iter_by_frequency(DICTIONARY, "", &options).count(), words.len());
+    }
+
+    #[test]
+    fn solve_with_cancel() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let raw = crate::generate::Letters::default().draw(10, &mut SplitMix64::new(1));
+        let board = Board::parse(&raw).unwrap();
+        let options = SolveOptions::default();
+        let (mut all, cancelled) = board.solve_with_cancel(&trie, &options, &CancelToken::new());
+        assert!(!cancelled);
+        let mut solved = board.solve_with_trie(&trie, &options);
+        all.sort_by(rank);
+        solved.sort_by(rank);
+        assert_eq!(all, solved);
+
+        let token = CancelToken::new();
+        token.cancel();
+        assert_eq!(board.solve_with_cancel(&trie, &options, &token), (Vec::new(), true));
 
-        let mut letters = [false; 26];
-        for c in board.iter().flat_map(|r| r.iter().cloned()) {
-            letters[(c - b'a') as usize] = true;
+        // stopping at the second check, the search gets through a first stretch of the board
+        struct Countdown(usize);
+        impl<'a> Tracer<'a> for Countdown {
+            fn step(&mut self, _: Option<usize>, _: (usize, usize), _: &'a str, _: Step) -> usize {
+                0
+            }
+
+            fn cancelled(&mut self) -> bool {
+                self.0 -= 1;
+                self.0 == 0
+            }
+        }
+        let search = board.search(&options);
+        let mut dfs = Dfs::new(&board, &trie, search, !0);
+        let mut countdown = Countdown(2);
+        let mut partial = 0;
+        while dfs.next(&board, &trie, &mut countdown).is_some() {
+            partial += 1;
         }
-        Ok(Board { board, letters })
+        assert_eq!(dfs.expanded, CANCEL_CHECKS + 1);
+        assert!(partial > 0 && partial < solved.len(), "{} of {}", partial, solved.len());
+        assert!(dfs.next(&board, &trie, &mut ()).is_none());
     }
 
-    pub fn len(&self) -> usize {
-        self.board.len()
-    }
+    #[test]
+    fn keep() {
+        let board = Board::parse(BOARD1).unwrap();
+        let all = board.summarize(DICTIONARY, &SolveOptions::default());
+        assert_eq!((all.found, all.score, all.best.len()), (126, 165, 126));
+        assert_eq!(all.by_length.iter().sum::<usize>(), 126);
+        assert_eq!(all.by_length[..3], [0, 0, 0]);
 
-    fn neighbors(&self, (x, y): (usize, usize)) -> Neighbors {
-        Neighbors {
-            x: x as isize,
-            y: y as isize,
-            current: 0,
-            board: &self
+        for &keep in &[0, 1, 5, 200] {
+            let options = SolveOptions { keep: Some(keep), ..SolveOptions::default() };
+            let summary = board.summarize(DICTIONARY, &options);
+            assert_eq!((summary.found, summary.score, &summary.by_length), (all.found, all.score, &all.by_length));
+            assert_eq!(summary.best[..], all.best[..keep.min(126)]);
+            let mut solved = Solutions(board.solve(DICTIONARY, &options));
+            solved.sort_by_score();
+            assert_eq!(solved, summary.best);
         }
+
+        // words the plural filter drops aren't counted
+        let cats = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let plurals = SolveOptions { plurals: Some(PluralFilter::default()), ..SolveOptions::default() };
+        let filtered = cats.summarize("cat\ncats\ntax\n", &plurals);
+        assert_eq!((filtered.found, filtered.score, &filtered.by_length[..]), (2, 2, &[0, 0, 0, 2][..]));
     }
 
-    fn contains_letters(&self, word: &[u8]) -> bool {
-        word.iter().all(|&w| self.letters[(w - b'a') as usize])
+    #[test]
+    fn paths() {
+        let board = Board::parse(BOARD1).unwrap();
+        for solution in board.solve_trie(DICTIONARY).into_iter().chain(board.solve_single_threaded(DICTIONARY)) {
+            let letters: Vec<_> = solution.path.iter().flat_map(|&cell| board.tile(cell)).cloned().collect();
+            assert_eq!(letters, solution.word.as_bytes());
+            assert!(solution.path.windows(2).all(|w| board.neighbors(w[0]).any(|n| n == w[1])));
+        }
     }
 
-    // checks to see if basic conditions for the existance of a word are met
-    // e.g.
-    // are all the letters in the board
-    // is the word long enough
-    // are the letters of the word found in adjacent to each other
-    // you still need to check to see if the word reuses a letter after calling this method
-    fn has_word(&self, word: &[u8]) -> bool {
-        let mut adjacencies = Vec3::fill(word.len(), self.len(), self.len(), false);
-        for (k, &b) in word.iter().enumerate() {
-            for i in 0..self.len() {
-                for j in 0..self.len() {
-                    if b != self[(i, j)] {
-                        continue;
-                    }
+    // every path a solver reports is valid, and breaking one in any of the ways
+    // `validate_path` checks for is caught
+    #[test]
+    fn validate_path() {
+        use super::validate_path;
+        use crate::dice;
 
-                    if k == 0 {
-                        adjacencies[(k, i, j)] = true;
-                        continue;
-                    }
+        let wrapped = Topology { diagonals: false, wrap: true };
+        for seed in 0..8 {
+            let raw = dice::roll(&dice::CLASSIC, seed);
+            let board = Board::parse(&raw).unwrap();
+            let board = if seed % 2 == 0 { board } else { board.with_topology(wrapped) };
+            let solutions = board.solve(DICTIONARY, &SolveOptions::default());
+            assert!(!solutions.is_empty());
+            for Solution { word, path, .. } in solutions {
+                assert_eq!(validate_path(&board, word, &path), Ok(()), "{} on board {}", word, seed);
 
-                    for (x, y) in self.neighbors((i, j)) {
-                        if adjacencies[(k - 1, x, y)] {
-                            adjacencies[(k, i, j)] = true;
-                            if word.len() - 1 == k {
-                                return true;
-                            }
-                        }
-                    }
+                let last = path.len() - 1;
+                assert_eq!(validate_path(&board, word, &path[..last]), Err(PathError::TooShort));
+                let mut reused = path.clone();
+                reused.push(path[last - 1]);
+                assert_eq!(validate_path(&board, word, &reused), Err(PathError::Reused { step: last + 1, cell: path[last - 1] }));
+                let mut off = path.clone();
+                off[last] = (0, board.len());
+                assert_eq!(validate_path(&board, word, &off), Err(PathError::OutOfBounds { step: last, cell: (0, board.len()) }));
+                let far = (0..board.len())
+                    .flat_map(|i| (0..board.len()).map(move |j| (i, j)))
+                    .find(|&cell| !path.contains(&cell) && !board.neighbors(path[last - 1]).any(|n| n == cell));
+                if let Some(far) = far {
+                    let mut jump = path.clone();
+                    jump[last] = far;
+                    assert_eq!(validate_path(&board, word, &jump), Err(PathError::NotAdjacent { step: last, cell: far }));
                 }
+                let mut misspelled = word.to_string().into_bytes();
+                misspelled[0] = if misspelled[0] == b'z' { b'y' } else { b'z' };
+                let misspelled = String::from_utf8(misspelled).unwrap();
+                assert_eq!(validate_path(&board, &misspelled, &path), Err(PathError::WrongLetter { step: 0, cell: path[0] }));
             }
         }
 
-        false
+        let board = Board::parse("quat\nxxx\nxxx").unwrap();
+        assert_eq!(validate_path(&board, "qua", &[(0, 0), (0, 1)]), Ok(()));
+        assert_eq!(validate_path(&board, "qa", &[(0, 0), (0, 1)]), Err(PathError::WrongLetter { step: 0, cell: (0, 0) }));
+        assert_eq!(validate_path(&board, "", &[]), Err(PathError::Empty));
     }
 
-    pub fn get(&self, (x, y): (isize, isize)) -> Option<&u8> {
-        if x.is_negative() || x >= self.len() as isize || y.is_negative() || y >= self.len() as isize {
-            None
-        } else {
-            self.board.get(x as usize).and_then(|r| r.get(y as usize))
-        }
+    #[test]
+    fn solve_scored() {
+        let board = Board::parse(BOARD1).unwrap();
+        let solutions = board.solve(DICTIONARY, &SolveOptions::default());
+        assert_eq!(solutions.len(), 126);
+        assert!(solutions.iter().all(|s| s.score == Classic.score(s.word.as_bytes(), &[], &board)));
     }
 
-    pub fn solve_single_threaded<'a>(&self, words: &'a str) -> Vec<&'a str> {
-        #[derive(Debug)]
-        struct DfsItem<'word> {
-            visited: Vec2<bool>,
-            x: usize,
-            y: usize,
-            word: &'word str,
-        }
+    #[test]
+    fn score() {
+        let board = Board::parse(BOARD1).unwrap();
+        assert_eq!(board.score(DICTIONARY), 165);
+        assert_eq!(Board::parse("xxx\nxxx\nxxx").unwrap().score(DICTIONARY), 0);
+    }
 
-        let mut solutions = Vec::new();
-        let mut stack = Vec::with_capacity(4098);
-        for word in words.lines() {
-            if word.as_bytes().len() < 3 || !self.contains_letters(word.as_bytes()) || !self.has_word(word.as_bytes()) {
-                continue;
-            }
+    #[test]
+    fn solve_until() {
+        let board = Board::parse(BOARD1).unwrap();
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let all = board.solve_with_trie(&trie, &SolveOptions::default());
+        // the search takes its own paths, and the words must still follow them
+        let found = |s: &Solution| all.iter().any(|t| t.word == s.word && t.score == s.score) && super::validate_path(&board, s.word, &s.path).is_ok();
+        let mut scores: Vec<_> = all.iter().map(|s| s.score).collect();
+        scores.sort_by_key(|&score| Reverse(score));
 
-            stack.truncate(0);
-            'found: for i in 0..self.len() {
-                for j in 0..self.len() {
-                    let visited = Vec2::fill(self.len(), self.len(), false);
-                    stack.push(DfsItem { x: i, y: j, visited, word: &word[0..1] });
+        let options = SolveOptions::default();
+        let top = board.solve_until(&trie, Goal::TopKByScore(10), &options);
+        assert_eq!(top.iter().map(|s| s.score).collect::<Vec<_>>(), scores[..10]);
+        assert!(top.iter().all(found));
+        assert_eq!(board.solve_until(&trie, Goal::TopKByScore(1000), &options).len(), 126);
+        assert!(board.solve_until(&trie, Goal::TopKByScore(0), &options).is_empty());
 
-                    while let Some(mut curr) = stack.pop() {
-                        if self[(curr.x, curr.y)] != *curr.word.as_bytes().last().unwrap() {
-                            continue;
-                        }
+        // stopping as soon as the words found are worth enough
+        let some = board.solve_until(&trie, Goal::StopAtScore(50), &options);
+        let total: u32 = some.iter().map(|s| s.score).sum();
+        assert!(total >= 50 && total - some.last().unwrap().score < 50);
+        assert!(some.len() < all.len() && some.iter().all(found));
+        let every = board.solve_until(&trie, Goal::StopAtScore(1000), &options);
+        assert_eq!((every.len(), every.iter().map(|s| s.score).sum::<u32>()), (126, 165));
+        // a region keeps the search off the quicker 4x4 one, going for long words first instead
+        let region = SolveOptions { region: Some(Vec2::fill(4, 4, true)), ..SolveOptions::default() };
+        let raced = board.solve_until(&trie, Goal::StopAtScore(50), &region);
+        let total: u32 = raced.iter().map(|s| s.score).sum();
+        assert!(total >= 50 && total - raced.last().unwrap().score < 50);
+        assert!(raced.len() < all.len() && raced.iter().all(found));
 
-                        if curr.word.len() == word.len() {
-                            solutions.push(word);
-                            break 'found;
-                        }
+        // the options pick the words and score them, as for `solve`
+        use crate::rules::MinLength;
+        use crate::score::{Multiplier, WordHunt};
+        let mut options = SolveOptions { scorer: Box::new(WordHunt), max_len: Some(6), ..SolveOptions::default() };
+        options.rules.push(MinLength(5));
+        let all = board.solve_with_trie(&trie, &options);
+        let mut scores: Vec<_> = all.iter().map(|s| s.score).collect();
+        scores.sort_by_key(|&score| Reverse(score));
+        let top = board.solve_until(&trie, Goal::TopKByScore(5), &options);
+        assert_eq!(top.iter().map(|s| s.score).collect::<Vec<_>>(), scores[..5]);
+        assert!(top.iter().all(|s| (5..=6).contains(&s.len())));
+        let every = board.solve_until(&trie, Goal::StopAtScore(u32::MAX), &options);
+        assert_eq!(every.len(), all.len());
+        // a scorer with no bound on its points can't skip anything, but still finds the best
+        let doubled = SolveOptions { scorer: Box::new(Multiplier::new(Classic, Vec2::fill(4, 4, 2))), ..SolveOptions::default() };
+        let mut scores: Vec<_> = board.solve_with_trie(&trie, &doubled).iter().map(|s| s.score).collect();
+        scores.sort_by_key(|&score| Reverse(score));
+        let top = board.solve_until(&trie, Goal::TopKByScore(3), &doubled);
+        assert_eq!(top.iter().map(|s| s.score).collect::<Vec<_>>(), scores[..3]);
+    }
 
-                        curr.visited[(curr.x, curr.y)] = true;
-                        for (x, y) in self.neighbors((curr.x, curr.y)) {
-                            if !curr.visited[(x, y)] {
-                                stack.push(DfsItem { x, y, visited: curr.visited.clone(), word: &word[0..curr.word.len() + 1] });
-                            }
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn max_depth() {
+        fn words(solutions: Vec<Solution<'_>>) -> Vec<(&str, u32)> {
+            let mut words: Vec<_> = solutions.into_iter().map(|s| (s.word, s.score)).collect();
+            words.sort();
+            words
         }
+        // dictionary "words" a thousand letters long, which no 4x4 board can hold, so they're dropped
+        let long: Vec<String> = ["a", "ab", "abcdefghijklmnopqrstuvwxyz"].iter().map(|unit| unit.repeat(1000)[..1000].to_string()).collect();
+        let adversarial = format!("{}\n{}", DICTIONARY, long.join("\n"));
+        let board = Board::parse(BOARD1).unwrap();
+        assert_eq!(words(board.solve_recursive(&adversarial)), words(board.solve_recursive(DICTIONARY)));
+        let trie = Trie::from_dictionary(&adversarial);
+        let options = SolveOptions::default();
+        let top = board.solve_until(&trie, Goal::TopKByScore(10), &options);
+        // but a limit lower than the board's 16 cells sends the longest-first search the iterative way
+        let shallow = SolveOptions { max_depth: Some(4), ..SolveOptions::default() };
+        let iterative = board.solve_until(&trie, Goal::TopKByScore(10), &shallow);
+        assert_eq!(top.iter().map(|s| s.score).collect::<Vec<_>>(), iterative.iter().map(|s| s.score).collect::<Vec<_>>());
+        assert_eq!(words(board.solve_until(&trie, Goal::TopKByScore(1000), &shallow)), words(board.solve_trie(DICTIONARY)));
 
-        solutions
-    }
+        // both ways leave out the words too short for `min_len`
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let trie = Trie::from_dictionary("cat\ncats");
+        let four = SolveOptions { min_len: Some(4), ..SolveOptions::default() };
+        let expected = words(board.solve_until(&trie, Goal::TopKByScore(10), &four));
+        assert_eq!(expected, vec![("cats", 1)]);
+        assert_eq!(words(board.solve_until(&trie, Goal::TopKByScore(10), &SolveOptions { max_depth: Some(1), ..four })), expected);
+        let trie = Trie::from_dictionary(&adversarial);
 
-    pub fn solve_trie<'a>(&self, words: &'a str) -> Vec<&'a str> {
-        let arena = Arena::new();
-        let trie = TrieNode::root(&arena);
+        // and agree on length limits and rules, whichever way they search
+        let board = Board::parse(BOARD1).unwrap();
+        let limited = || {
+            let mut options = SolveOptions { min_len: Some(4), max_len: Some(6), ..SolveOptions::default() };
+            options.rules.push(crate::rules::NoPlurals);
+            options
+        };
+        let expected = words(board.solve_until(&trie, Goal::TopKByScore(1000), &limited()));
+        assert!(!expected.is_empty());
+        assert!(expected.iter().all(|(word, _)| (4..=6).contains(&word.len()) && (!word.ends_with('s') || word.ends_with("ss"))));
+        assert_eq!(expected, words(board.solve(DICTIONARY, &limited())));
+        let iterative = SolveOptions { max_depth: Some(4), ..limited() };
+        assert_eq!(words(board.solve_until(&trie, Goal::TopKByScore(1000), &iterative)), expected);
 
-        for word in words.lines() {
-            if word.len() >= 3 && self.contains_letters(word.as_bytes()) {
-                trie.insert(word.as_bytes(), &arena);
-            }
-        }
+        // on a board with room for them, they could go a thousand cells deep, past the default limit
+        let rows: Vec<String> = (0..32usize).map(|row| (0..32).map(|col| (b'a' + ((row * 32 + col) % 26) as u8) as char).collect()).collect();
+        let big = Board::parse(&rows.join("\n")).unwrap();
+        assert!(big.depth(&trie, big.max_letters()) > MAX_DEPTH);
+        let expected = words(big.solve_trie(&adversarial));
+        assert!(!expected.is_empty());
+        assert_eq!(words(big.solve_recursive(&adversarial)), expected);
+        assert_eq!(words(big.solve_until(&trie, Goal::StopAtScore(u32::MAX), &options)), expected);
 
-        #[derive(Debug)]
-        struct DfsItem<'trie, 'word: 'trie> {
-            visited: Vec2<bool>,
-            x: usize,
-            y: usize,
-            trie: &'trie TrieNode<'trie, 'word>,
+        // one path through every cell, searched for recursively and iteratively
+        let (board, snake) = snake();
+        let snakes = format!("{}\n{}", snake, &snake[..100]);
+        let trie = Trie::from_dictionary(&snakes);
+        for goal in [Goal::TopKByScore(1), Goal::StopAtScore(12), Goal::StopAtScore(u32::MAX)] {
+            let deep = board.solve_until(&trie, goal, &options);
+            assert!(deep.iter().all(|s| super::validate_path(&board, s.word, &s.path).is_ok()));
+            assert!(!deep.is_empty(), "{:?}", goal);
+            assert!(goal != Goal::StopAtScore(u32::MAX) || deep.iter().any(|s| s.word == snake));
+            assert_eq!(words(deep), words(board.solve_until(&trie, goal, &SolveOptions { max_depth: Some(64), ..SolveOptions::default() })));
         }
+    }
 
-        let mut stack = Vec::with_capacity(4098);
-        let mut solutions = Vec::new();
-        for i in 0..self.len() {
-            for j in 0..self.len() {
-                stack.truncate(0);
-                let visited = Vec2::fill(self.len(), self.len(), false);
-                stack.push(DfsItem { x: i, y: j, trie, visited });
+    #[test]
+    fn solve_rules() {
+        use crate::rules::MinLength;
 
-                while let Some(mut curr) = stack.pop() {
-                    curr.visited[(curr.x, curr.y)] = true;
+        let board = Board::parse(BOARD1).unwrap();
+        let mut options = SolveOptions::default();
+        options.rules.push(MinLength(5));
+        let solutions = board.solve(DICTIONARY, &options);
+        assert!(!solutions.is_empty());
+        assert!(solutions.iter().all(|s| s.len() >= 5));
+    }
 
-                    for (x, y) in self.neighbors((curr.x, curr.y)) {
-                        let next = curr.trie.get(self[(x, y)]);
-                        if let Some(next) = next {
-                            if !curr.visited[(x, y)] {
-                                stack.push(DfsItem { trie: next, x, y, visited: curr.visited.clone() });
-                            }
-                        }
-                    }
+    #[test]
+    fn max_len() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "cat\ncats\ncatsxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx";
+        let (solutions, stats) = board.solve_with_stats(words, &SolveOptions::default());
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(stats, LoadStats { loaded: 2, too_long: 1 });
 
-                    if !curr.trie.seen.replace(true) && curr.trie.word_end {
-                        solutions.push(unsafe { str::from_utf8_unchecked(curr.trie.word )});
-                    }
-                }
-            }
-        }
+        let options = SolveOptions { max_len: Some(3), ..SolveOptions::default() };
+        let (solutions, stats) = board.solve_with_stats(words, &options);
+        assert_eq!(solutions, vec![Solution { word: "cat", score: 1, path: vec![(0, 0), (0, 1), (0, 2)] }]);
+        assert_eq!(stats, LoadStats { loaded: 1, too_long: 2 });
+    }
 
-        solutions
+    #[test]
+    fn pruning() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        // "tact" needs a second t, "cast" has s after a but they aren't adjacent, and the board has no d
+        let words = "cat\ntact\ncast\ndog\nat\nxxx";
+        assert_eq!(board.pruning(words), Pruning { words: 5, letters: 4, kept: 2 });
+        assert_eq!(board.solve_with_stats(words, &SolveOptions::default()).1.loaded, 2);
+        assert_eq!(board.spellable(words), ["cat", "xxx"]);
+        assert_eq!(Board::parse("ca\nst").unwrap().pruning(words), Pruning { words: 5, letters: 3, kept: 2 });
+        // c is only next to a once the board wraps around
+        let board = Board::parse("abc\ndef\nghi").unwrap();
+        assert_eq!(board.pruning("cab").kept, 0);
+        assert_eq!(board.with_topology(Topology { diagonals: true, wrap: true }).pruning("cab").kept, 1);
+        // pairs inside a tile count as next to each other
+        assert_eq!(Board::parse("quix\nxxx\nxxx").unwrap().pruning("quix\nuqi").kept, 1);
     }
-}
 
-impl<'word> Index<(usize, usize)> for Board<'word> {
-    type Output = u8;
+    #[test]
+    fn solve_measured() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let (solutions, stats) = board.solve_measured("cat\ntact\ncast\ndog\nat\nxxx", &SolveOptions::default());
+        assert_eq!(solutions.len(), 2);
+        assert_eq!((stats.load, stats.pruned), (LoadStats { loaded: 2, too_long: 0 }, 3));
+        // the root, c-a-t and x-x-x
+        assert_eq!(stats.trie_nodes, 7);
 
-    fn index(&self, (x, y): (usize, usize)) -> &u8 {
-        self.get((x as isize, y as isize)).expect("index out of bounds!")
+        let board = Board::parse(BOARD1).unwrap();
+        let (solutions, stats) = board.solve_measured(DICTIONARY, &SolveOptions::default());
+        assert_eq!(solutions, board.solve(DICTIONARY, &SolveOptions::default()));
+        let low_power = board.solve_measured(DICTIONARY, &SolveOptions::low_power()).1;
+        assert_eq!(low_power.expanded, stats.expanded);
+        assert!(stats.expanded >= solutions.iter().map(|s| s.path.len()).max().unwrap());
     }
-}
 
-const DIRECTIONS: [(isize, isize); 8] = [
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-];
+    #[test]
+    fn max_len_cuts_off_search() {
+        let board = Board::parse(BOARD1).unwrap();
+        let options = SolveOptions { max_len: Some(4), ..SolveOptions::default() };
+        let capped = board.solve(DICTIONARY, &options);
+        let all = board.solve(DICTIONARY, &SolveOptions::default());
+        assert!(capped.iter().all(|s| s.len() <= 4 && s.path.len() <= 4));
+        assert_eq!(capped.len(), all.iter().filter(|s| s.len() <= 4).count());
+    }
 
-#[derive(Debug)]
-struct Neighbors<'board, 'word: 'board> {
-    x: isize,
-    y: isize,
-    current: usize,
-    board: &'board Board<'word>,
-}
+    #[test]
+    fn min_len() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "at\ncat\ncats";
+        let found = |options: &SolveOptions| {
+            let mut found: Vec<_> = board.solve(words, options).into_iter().map(|s| s.word).collect();
+            found.sort();
+            found
+        };
+        assert_eq!(found(&SolveOptions::default()), vec!["cat", "cats"]);
+        assert_eq!(found(&SolveOptions { min_len: Some(4), ..SolveOptions::default() }), vec!["cats"]);
+        assert_eq!(found(&SolveOptions { min_len: Some(2), ..SolveOptions::default() }), vec!["at", "cat", "cats"]);
+        let options = SolveOptions { min_len: Some(4), max_len: Some(3), ..SolveOptions::default() };
+        assert!(found(&options).is_empty());
+
+        let trie = Trie::from_dictionary(words);
+        let options = SolveOptions { min_len: Some(4), ..SolveOptions::default() };
+        assert_eq!(board.solve_with_trie(&trie, &options).len(), 1);
+    }
 
-impl<'board, 'word> Iterator for Neighbors<'board, 'word> {
-    type Item = (usize, usize);
+    #[test]
+    fn parallel() {
+        let board = Board::parse(BOARD1).unwrap();
+        let mut parallel = board.solve_parallel(DICTIONARY);
+        let mut trie = board.solve_trie(DICTIONARY);
+        parallel.sort();
+        trie.sort();
+        assert_eq!(parallel, trie);
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current >= DIRECTIONS.len() {
-            return None;
-        }
+    #[test]
+    fn threads() {
+        let board = Board::parse(BOARD1).unwrap();
+        let solve = |threads| {
+            let options = SolveOptions { threads: Some(threads), ..SolveOptions::default() };
+            let (mut solutions, stats) = board.solve_with_stats(DICTIONARY, &options);
+            solutions.sort();
+            (solutions, stats)
+        };
+        let single = solve(1);
+        assert_eq!(single.0.len(), 126);
+        assert_eq!(solve(3), single);
+        assert_eq!(solve(8), single);
+    }
+
+    #[test]
+    fn seed() {
+        let board = Board::parse(BOARD1).unwrap();
+        let solve = |seed| {
+            let options = SolveOptions { seed, threads: Some(1), ..SolveOptions::default() };
+            board.solve(DICTIONARY, &options).into_iter().map(|s| s.word).collect::<Vec<_>>()
+        };
+        let unseeded = solve(None);
+        let seeded = solve(Some(42));
+        assert_eq!(seeded, solve(Some(42)));
+        assert_ne!(seeded, unseeded);
 
-        for &(x_off, y_off) in DIRECTIONS[self.current..].iter() {
-            self.current += 1;
-            let x = self.x + x_off;
-            let y = self.y + y_off;
-            if self.board.get((x, y)).is_some() {
-                return Some((x as usize, y as usize))
+        let (mut unseeded, mut seeded) = (unseeded, seeded);
+        unseeded.sort();
+        seeded.sort();
+        assert_eq!(seeded, unseeded);
+    }
+
+    #[test]
+    fn shared_trie() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let mut solve = SolveOptions::default();
+        for raw in &[BOARD1, include_str!("../test/board2"), "cats\nxxxx\nxxxx\nxxxx"] {
+            let board = Board::parse(raw).unwrap();
+            for threads in &[1, 4] {
+                solve.threads = Some(*threads);
+                let mut shared = board.solve_with_trie(&trie, &solve);
+                let mut own = board.solve(DICTIONARY, &solve);
+                shared.sort();
+                own.sort();
+                assert_eq!(shared, own);
             }
         }
 
-        None
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let options = SolveOptions { max_len: Some(3), ..SolveOptions::default() };
+        let words: Vec<_> = board.solve_with_trie(&Trie::from_dictionary("cat\ncats"), &options).into_iter().map(|s| s.word).collect();
+        assert_eq!(words, vec!["cat"]);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    const BOARD: &str = "abcd\nefgh\nijkl\nmnop";
+    #[test]
+    fn profile() {
+        let board = Board::parse(BOARD1).unwrap();
+        let (solutions, slowest) = board.profile_single_threaded(DICTIONARY, 5);
+        assert_eq!(solutions.len(), 126);
+        assert_eq!(slowest.len(), 5);
+        assert!(slowest.windows(2).all(|w| w[0].time >= w[1].time));
+        let (_, all) = board.profile_single_threaded(DICTIONARY, usize::MAX);
+        assert_eq!(all.iter().filter(|p| p.found).count(), 126);
+        assert!(all.iter().any(|p| !p.found && p.expansions > 0));
+
+        let (_, all) = board.profile_single_threaded("zzz\nzzz", 10);
+        assert_eq!(all, vec![WordProfile { word: "zzz", time: all[0].time, expansions: 0, found: false }]);
+    }
 
     #[test]
-    fn parse() {
-        let board = Board::parse(BOARD).unwrap();
-        assert_eq!(board.len(), 4);
-        assert_eq!(board[(0, 0)], b'a');
-        assert_eq!(board[(0, 3)], b'd');
-        assert_eq!(board[(3, 3)], b'p');
-        assert_eq!(board[(0, 1)], b'b');
-        assert_eq!(board[(1, 0)], b'e');
+    fn visited() {
+        for &size in &[4, 11, 12] {
+            let mut visited = Visited::new(size);
+            visited.insert((size - 1, size - 1));
+            visited.insert((0, 1));
+            assert!(visited.contains((size - 1, size - 1)) && visited.contains((0, 1)));
+            assert!(!visited.contains((1, 0)) && !visited.contains((size - 1, size - 2)));
+            visited.remove((0, 1));
+            assert!(!visited.contains((0, 1)) && visited.contains((size - 1, size - 1)));
+        }
+
+        // past 11x11 the cells no longer fit in one mask; the word is in the last row, past the first 128 cells
+        let raw = "xxxxxxxxxxxx\n".repeat(11) + "xxxxxcatsxxx";
+        let board = Board::parse(&raw).unwrap();
+        assert!(matches!(Visited::new(board.len()), Visited::Large { .. }));
+        let mut words: Vec<_> = board.solve_trie("cat\ncats\nact\ntact").into_iter().map(|s| s.word).collect();
+        words.sort();
+        assert_eq!(words, vec!["cat", "cats"]);
     }
 
     #[test]
-    fn neighbors_edge() {
-        let board = Board::parse(BOARD).unwrap();
-        let mut neighbors: Vec<_> = board.neighbors((0, 0)).collect();
-        neighbors.sort();
-        assert_eq!(neighbors, vec![(0, 1), (1, 0), (1, 1)]);
+    fn large_boards() {
+        fn words(solutions: Vec<Solution<'_>>) -> Vec<(&str, u32)> {
+            let mut words: Vec<_> = solutions.into_iter().map(|s| (s.word, s.score)).collect();
+            words.sort();
+            words
+        }
 
-        let mut neighbors: Vec<_> = board.neighbors((3, 3)).collect();
-        neighbors.sort();
-        assert_eq!(neighbors, vec![(2, 2), (2, 3), (3, 2)]);
+        // word-search sized grids, past the inline sizes of `Vec2`, `Vec3` and `Path` and `Visited`'s single mask
+        for &size in &[10, 12, 16] {
+            let mut rows: Vec<Vec<u8>> = (0..size).map(|i| (0..size).map(|j| TILES[(i * 7 + j * 3 + i * j) % 26]).collect()).collect();
+            // a word twice as long as a row snakes along the last two, past any the dictionary has
+            let long: Vec<u8> = b"abcdefghijklmnoprstuvwxy".iter().cycle().take(2 * size).cloned().collect();
+            rows[size - 2].copy_from_slice(&long[..size]);
+            rows[size - 1] = long[size..].iter().rev().cloned().collect();
+            let raw = rows.iter().map(|row| str::from_utf8(row).unwrap()).collect::<Vec<_>>().join("\n");
+            let board = Board::parse(&raw).unwrap();
+            let dictionary = format!("{}{}\n", DICTIONARY, str::from_utf8(&long).unwrap());
+            let dictionary = dictionary.as_str();
+
+            let expected = words(board.solve_trie(dictionary));
+            assert!(expected.contains(&(str::from_utf8(&long).unwrap(), 11)), "{}", size);
+            assert_eq!(words(board.solve_recursive(dictionary)), expected, "{}", size);
+            assert_eq!(words(board.solve(dictionary, &SolveOptions::default())), expected, "{}", size);
+            assert_eq!(words(board.solve_dawg(&Dawg::new(dictionary))), expected, "{}", size);
+            let compiled = crate::compiled::compile(dictionary);
+            assert_eq!(words(board.solve_compiled(&CompiledDictionary::from_bytes(&compiled).unwrap(), &SolveOptions::default())), expected, "{}", size);
+        }
     }
 
     #[test]
-    fn neighbors() {
-        let board = Board::parse(BOARD).unwrap();
-        let mut neighbors: Vec<_> = board.neighbors((1, 1)).collect();
-        assert_eq!(board[(1, 1)], b'f');
-        neighbors.sort();
-        assert_eq!(neighbors, vec![
-            (0, 0), (0, 1), (0, 2),
-            (1, 0), (1, 2),
-            (2, 0), (2, 1), (2, 2)
+    fn trace() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let trace = board.trace("cat\ncats\ncast\ncatsx", &SolveOptions { max_len: Some(4), ..SolveOptions::default() }, 100);
+        let steps: Vec<_> = trace.nodes.iter().map(|n| (n.parent, n.cell, n.prefix, n.step)).collect();
+        assert_eq!(steps, vec![
+            (None, (0, 0), "c", Step::Prefix),
+            (Some(0), (0, 1), "ca", Step::Prefix),
+            (Some(1), (0, 2), "cat", Step::Word),
+            (Some(2), (0, 3), "cats", Step::Word),
         ]);
+        assert!(!trace.truncated);
+        assert!(trace.to_dot().contains("n2 -> n3;"));
+
+        let board = Board::parse(BOARD1).unwrap();
+        let trace = board.trace(DICTIONARY, &SolveOptions::default(), 50);
+        assert_eq!(trace.nodes.len(), 50);
+        assert!(trace.truncated);
+        assert!(trace.nodes.iter().all(|n| n.parent.is_none_or(|p| p < 50)));
     }
 
     #[test]
-    fn has_word() {
-        let board = Board::parse(BOARD).unwrap();
-        assert!(board.has_word(b"abcd"));
-        assert!(board.has_word(b"dcba"));
-        assert!(board.has_word(b"afkp"));
-        assert!(board.has_word(b"pkfa"));
-        assert!(board.has_word(b"mjgd"));
-        assert!(board.has_word(b"dgjm"));
-        assert!(board.has_word(b"aeim"));
-        assert!(board.has_word(b"miea"));
-        assert!(board.has_word(b"aefb"));
-        assert!(board.has_word(b"bfea"));
+    fn compiled() {
+        fn words(solutions: Vec<Solution<'_>>) -> Vec<(&str, u32)> {
+            let mut words: Vec<_> = solutions.into_iter().map(|s| (s.word, s.score)).collect();
+            words.sort();
+            words
+        }
+        let bytes = compile(DICTIONARY);
+        let dictionary = CompiledDictionary::from_bytes(&bytes).unwrap();
+        let board = Board::parse(BOARD1).unwrap();
+        let mut region = Vec2::fill(4, 4, true);
+        region[(1, 1)] = false;
+        for options in [
+            SolveOptions::default(),
+            SolveOptions { max_len: Some(4), ..SolveOptions::default() },
+            SolveOptions { min_len: Some(5), ..SolveOptions::default() },
+            SolveOptions { seed: Some(3), region: Some(region), ..SolveOptions::default() },
+        ] {
+            assert_eq!(words(board.solve_compiled(&dictionary, &options)), words(board.solve(DICTIONARY, &options)));
+        }
+        assert_eq!(board.solve_compiled(&dictionary, &SolveOptions::default()).len(), 126);
 
-        assert!(!board.has_word(b"lies"));
-        assert!(!board.has_word(b"mapb"));
+        let board = Board::parse("quax\nyxx\nxxx").unwrap();
+        let bytes = compile("qua\nquay\nquaz");
+        let dictionary = CompiledDictionary::from_bytes(&bytes).unwrap();
+        let found = board.solve_compiled(&dictionary, &SolveOptions::default());
+        assert_eq!(words(found), vec![("qua", 1), ("quay", 1)]);
     }
 
-    const DICTIONARY: &str = include_str!("../test/dictionary");
-    const BOARD1: &str = include_str!("../test/board1");
+    // a 12x12 board without diagonals and a word snaking through every cell, each cell's neighbors all a
+    // letter apart from it but the next along the snake, so only one path spells it
+    fn snake() -> (Board, String) {
+        let letters = b"abcdefghijklmnoprstuvwxyz";
+        let snake: String = (0..144).map(|at| letters[at % letters.len()] as char).collect();
+        let rows: Vec<String> = snake.as_bytes().chunks(12).enumerate()
+            .map(|(row, cells)| {
+                let cells = cells.iter().map(|&c| c as char);
+                if row % 2 == 1 { cells.rev().collect() } else { cells.collect() }
+            })
+            .collect();
+        let board = Board::parse(&rows.join("\n")).unwrap().with_topology(Topology { diagonals: false, wrap: false });
+        (board, snake)
+    }
 
     #[test]
-    fn single_threaded() {
+    fn recursive() {
         let board = Board::parse(BOARD1).unwrap();
-        assert_eq!(board.solve_single_threaded(DICTIONARY).len(), 126);
+        let mut found: Vec<_> = board.solve_recursive(DICTIONARY).into_iter().map(|s| (s.word, s.score)).collect();
+        let mut trie: Vec<_> = board.solve_trie(DICTIONARY).into_iter().map(|s| (s.word, s.score)).collect();
+        found.sort();
+        trie.sort();
+        assert_eq!(found.len(), 126);
+        assert_eq!(found, trie);
+
+        // cells are cleared on the way back out, so later words can pass through them
+        let board = Board::parse("tac
+xxt
+xxx").unwrap();
+        let words: Vec<_> = board.solve_recursive("act\ncat\ntact").into_iter().map(|s| s.word).collect();
+        assert_eq!(words.len(), 3);
+
+        // as deep as a board this size goes, searched past a lower limit as well
+        let (board, snake) = snake();
+        let words = format!("{}\n{}\n{}", snake, &snake[..100], &snake[30..40]);
+        let solved = |max_depth| {
+            let solutions = board.recursive(&words, max_depth);
+            assert!(solutions.iter().all(|s| super::validate_path(&board, s.word, &s.path).is_ok()));
+            let mut found: Vec<_> = solutions.into_iter().map(|s| (s.word, s.score)).collect();
+            found.sort();
+            found
+        };
+        assert_eq!(solved(MAX_DEPTH).len(), 3);
+        assert_eq!(solved(MAX_DEPTH), solved(64));
+        assert_eq!(solved(MAX_DEPTH), solved(143));
     }
 
     #[test]
-    fn trie() {
+    fn dawg() {
         let board = Board::parse(BOARD1).unwrap();
-        assert_eq!(board.solve_trie(DICTIONARY).len(), 126);
+        let dawg = Dawg::new(DICTIONARY);
+        let mut found: Vec<_> = board.solve_dawg(&dawg).into_iter().map(|s| (s.word, s.score)).collect();
+        let mut trie: Vec<_> = board.solve_trie(DICTIONARY).into_iter().map(|s| (s.word, s.score)).collect();
+        found.sort();
+        trie.sort();
+        assert_eq!(found.len(), 126);
+        assert_eq!(found, trie);
+
+        let board = Board::parse("quax\nyxx\nxxx").unwrap();
+        let words: Vec<_> = board.solve_dawg(&Dawg::new("qua\nquay\nquaz")).into_iter().map(|s| s.word).collect();
+        assert_eq!(words.len(), 2);
+        assert!(words.contains(&"qua") && words.contains(&"quay"));
+    }
+
+    #[test]
+    fn region() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let mut region = Vec2::fill(4, 4, true);
+        region[(0, 3)] = false;
+        let options = SolveOptions { region: Some(region), ..SolveOptions::default() };
+        let words: Vec<_> = board.solve("act\ncat\ncats\ntsa", &options).into_iter().map(|s| s.word).collect();
+        assert_eq!(words, vec!["cat"]);
+
+        let mut region = Vec2::fill(4, 4, true);
+        region[(0, 0)] = false;
+        let options = SolveOptions { region: Some(region), ..SolveOptions::default() };
+        assert!(board.solve("act\ncat\ncats", &options).is_empty());
+    }
+
+    #[test]
+    fn words_through() {
+        let board = Board::parse("caxx\natxx\nxxxx\nxxxx").unwrap();
+        let words = "cat\ntaco";
+        for &cell in &[(0, 1), (1, 0)] {
+            let through: Vec<_> = board.words_through(cell, words).into_iter().map(|s| (s.word, s.path)).collect();
+            assert_eq!(through, vec![("cat", vec![(0, 0), cell, (1, 1)])]);
+        }
+        assert!(board.words_through((3, 3), words).is_empty());
+
+        let board = Board::parse(BOARD1).unwrap();
+        let all = board.solve_trie(DICTIONARY);
+        let through = board.words_through((1, 1), DICTIONARY);
+        assert!(!through.is_empty() && through.len() < all.len());
+        assert!(through.iter().all(|s| s.path.contains(&(1, 1))));
+    }
+
+    #[test]
+    fn analyze() {
+        let board = Board::parse("caxx\natxx\nxxxx\nxxxx").unwrap();
+        let analysis = board.analyze("act\ncat\ntaco", &SolveOptions::default());
+        assert_eq!(analysis.through[0][..2], [2, 2]);
+        assert_eq!(analysis.through[1][..2], [2, 2]);
+        assert_eq!((analysis.found, analysis.score), (2, 2));
+        assert_eq!(analysis.by_length, [(0, 0), (0, 0), (0, 0), (2, 2)]);
+        assert_eq!(analysis.longest.map(|s| s.word), Some("act"));
+        assert_eq!(analysis.dead.len(), 12);
+        assert_eq!(analysis.dead[0], (0, 2));
+
+        let analysis = Board::parse(BOARD1).unwrap().analyze(DICTIONARY, &SolveOptions::default());
+        assert_eq!((analysis.found, analysis.score), (126, 165));
+        assert_eq!(analysis.by_length.iter().map(|&(n, _)| n).sum::<usize>(), 126);
+        assert!(analysis.through.iter().flatten().all(|&n| n <= 126));
+        // stopping once a word's cells are all used counts the same as following every path
+        let board = Board::parse(BOARD1).unwrap();
+        let mut through = vec![vec![0; board.len()]; board.len()];
+        for solution in board.solve(DICTIONARY, &SolveOptions::default()) {
+            let mut used = HashSet::new();
+            board.find_path(solution.word.as_bytes(), |path| {
+                used.extend(path.iter().copied());
+                false
+            });
+            used.into_iter().for_each(|(i, j)| through[i][j] += 1);
+        }
+        assert_eq!(analysis.through, through);
+        assert_eq!(board.walkable(b"fermate").iter().filter(|&&w| w).count(), 7);
+        assert!(board.walkable(b"zzz").iter().all(|&w| !w));
+
+        // 436,984 ways to trace it, but every cell is used within the first few
+        let board = Board::parse(&["aaaa"; 4].join("\n")).unwrap();
+        let analysis = board.analyze("aaaaaaaaa", &SolveOptions::default());
+        assert_eq!((analysis.found, analysis.through), (1, vec![vec![1; 4]; 4]));
+    }
+
+    #[test]
+    fn solve_no_plurals() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let mut options = SolveOptions::default();
+        assert!(board.solve("cat\ncats", &options).iter().any(|s| s.word == "cats"));
+
+        options.plurals = Some(PluralFilter::default());
+        let words: Vec<_> = board.solve("cat\ncats", &options).into_iter().map(|s| s.word).collect();
+        assert_eq!(words, vec!["cat"]);
     }
 }
 
@@ -321,6 +3752,7 @@ mod test {
 mod bench {
     use ::test::Bencher;
     use super::*;
+    use crate::compiled::compile;
 
     const DICTIONARY: &str = include_str!("../test/dictionary");
     const BOARD1: &str = include_str!("../test/board1");
@@ -340,4 +3772,84 @@ mod bench {
             board.solve_trie(DICTIONARY);
         });
     }
+
+    #[bench]
+    fn bench_recursive(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        b.iter(|| {
+            board.solve_recursive(DICTIONARY);
+        });
+    }
+
+    #[bench]
+    fn bench_shared_trie(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let options = SolveOptions::low_power();
+        b.iter(|| {
+            board.solve_with_trie(&trie, &options);
+        });
+    }
+
+    #[bench]
+    fn bench_stop_at_score(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let options = SolveOptions::default();
+        b.iter(|| {
+            board.solve_until(&trie, Goal::StopAtScore(100), &options);
+        });
+    }
+
+    // as `bench_shared_trie`, without switching to the `FixedBoard` search
+    #[bench]
+    fn bench_shared_trie_general(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let search = Search { min_len: MIN_LEN, max_len: board.max_letters(), seed: None, region: None };
+        let options = SolveOptions::low_power();
+        b.iter(|| {
+            let mut collector = Collector::new(&board, &options);
+            board.search_trie(&trie, search, !0, |word, path| collector.add(word, path), &mut ());
+            collector.solutions()
+        });
+    }
+
+    // a word that can only end in the far corner of a board of "a"s, the plain search then the guided one
+    fn far_b() -> (Board, String) {
+        let mut rows: Vec<String> = (0..10).map(|_| "a".repeat(10)).collect();
+        rows[9] = format!("{}b", "a".repeat(9));
+        (Board::parse(&rows.join("\n")).unwrap(), format!("{}b", "a".repeat(30)))
+    }
+
+    #[bench]
+    fn bench_find_word(b: &mut Bencher) {
+        let (board, word) = far_b();
+        b.iter(|| board.find_word(&word));
+    }
+
+    #[bench]
+    fn bench_find_word_guided(b: &mut Bencher) {
+        let (board, word) = far_b();
+        b.iter(|| board.find_word_guided(&word));
+    }
+
+    #[bench]
+    fn bench_dawg(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        let dawg = Dawg::new(DICTIONARY);
+        b.iter(|| {
+            board.solve_dawg(&dawg);
+        });
+    }
+
+    #[bench]
+    fn bench_compiled(b: &mut Bencher) {
+        let board = Board::parse(BOARD1).unwrap();
+        let bytes = compile(DICTIONARY);
+        let dictionary = CompiledDictionary::from_bytes(&bytes).unwrap();
+        b.iter(|| {
+            board.solve_compiled(&dictionary, &SolveOptions::default());
+        });
+    }
 }
\ No newline at end of file
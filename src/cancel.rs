@@ -0,0 +1,71 @@
+//! Stopping a long solve early. Hand a [`CancelToken`] to
+//! [`Board::solve_with_cancel`](crate::Board::solve_with_cancel) and cancel
+//! it from another thread, or give it a time limit, to get back the words
+//! found so far.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::trace::{Step, Tracer};
+
+/// A flag a search checks as it goes, stopping once it's set. Clones share
+/// the flag, so one can be kept to cancel with while another is searching.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// A token that also cancels itself once `limit` has passed from now.
+    pub fn with_time_limit(limit: Duration) -> CancelToken {
+        CancelToken { deadline: Some(Instant::now() + limit), ..CancelToken::default() }
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed) || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+// a tracer that records nothing, only telling the search when to stop, and noting whether it did
+pub(crate) struct Watch<'c> {
+    pub(crate) token: &'c CancelToken,
+    pub(crate) stopped: bool,
+}
+
+impl<'a, 'c> Tracer<'a> for Watch<'c> {
+    fn step(&mut self, _: Option<usize>, _: (usize, usize), _: &'a str, _: Step) -> usize {
+        0
+    }
+
+    fn cancelled(&mut self) -> bool {
+        self.stopped = self.token.is_cancelled();
+        self.stopped
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token() {
+        let token = CancelToken::new();
+        let shared = token.clone();
+        assert!(!token.is_cancelled());
+        shared.cancel();
+        assert!(token.is_cancelled());
+
+        assert!(CancelToken::with_time_limit(Duration::ZERO).is_cancelled());
+        assert!(!CancelToken::with_time_limit(Duration::from_secs(3600)).is_cancelled());
+    }
+}
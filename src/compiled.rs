@@ -0,0 +1,487 @@
+//! A dictionary compiled ahead of time into a flat trie, so solving many
+//! boards doesn't parse the word list and build a trie for each one.
+//!
+//! The format is little-endian and read in place, so the bytes can come from
+//! a file read into memory or a memory map:
+//!
+//! ```text
+//! magic      8 bytes   "BOGGLE\0\x02"
+//! text_len   u32
+//! nodes      u32       node count, at least 1
+//! meta_len   u32
+//! text       text_len bytes: the words, sorted, one per line
+//! meta       meta_len bytes: the word list's `Metadata`, a "key\tvalue" line per field set
+//! node*      12 bytes each, root first, siblings stored next to each other
+//!     children     u32   bit `c - b'a'` set for each child letter
+//!     first_child  u32   index of the child with the lowest letter
+//!     word         u32   offset of this prefix's word in `text`, plus 1; 0 if it isn't a word
+//! ```
+//!
+//! Version 1, magic `"BOGGLE\0\x01"`, is the same without `meta_len` and
+//! `meta`, and still loads.
+//!
+//! The layout depends only on the set of words and the metadata: the words
+//! are sorted, and the nodes laid out breadth first with siblings in letter
+//! order. So the same list always compiles to the same bytes, whatever order
+//! its lines are in, and an index can be cached by its hash or compared
+//! against another version's.
+
+use std::str;
+
+use crate::alphabet::{is_letter, Alphabet};
+use crate::error::Error;
+use crate::trie::letter_bit;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const MAGIC: &[u8; 8] = b"BOGGLE\0\x02";
+/// The format version this build writes, the last byte of its magic.
+pub const VERSION: u8 = MAGIC[7];
+const MAGIC_V1: &[u8; 8] = b"BOGGLE\0\x01";
+const HEADER: usize = MAGIC.len() + 12;
+const NODE: usize = 12;
+
+/// Where a word list came from and the terms it can be passed on under,
+/// which differ between e.g. TWL, SOWPODS and free lists. Each field is one
+/// line of free text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Metadata {
+    /// The license, ideally an SPDX identifier like `CC0-1.0`.
+    pub license: Option<String>,
+    /// Credit the license asks for.
+    pub attribution: Option<String>,
+    /// Where the list can be had, e.g. a URL.
+    pub source: Option<String>,
+}
+
+impl Metadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Metadata::default()
+    }
+
+    fn fields(&self) -> [(&'static str, &Option<String>); 3] {
+        [("license", &self.license), ("attribution", &self.attribution), ("source", &self.source)]
+    }
+
+    // keys this version doesn't know are skipped, so later ones can be added
+    fn parse(text: &str) -> Metadata {
+        let mut metadata = Metadata::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once('\t')) {
+            let field = match key {
+                "license" => &mut metadata.license,
+                "attribution" => &mut metadata.attribution,
+                "source" => &mut metadata.source,
+                _ => continue,
+            };
+            *field = Some(value.to_string());
+        }
+        metadata
+    }
+}
+
+/// A compiled dictionary borrowed from its bytes; see [`compile`] for making
+/// one and [`Board::solve_compiled`](crate::Board::solve_compiled) for
+/// searching it.
+#[derive(Debug, Clone, Copy)]
+pub struct CompiledDictionary<'a> {
+    text: &'a str,
+    meta: &'a str,
+    nodes: &'a [u8],
+}
+
+/// One trie node, as read from the bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Node {
+    pub children: u32,
+    first_child: u32,
+    word: u32,
+}
+
+/// Compiles the words in `words`, one per line, for
+/// [`CompiledDictionary::from_bytes`]. Surrounding whitespace, blank lines
+/// and repeats are ignored, as are words shorter than 3 letters, which no
+/// solver reports, and words with characters other than `a..=z`; clean the
+/// list with [`Dictionary`](crate::Dictionary) first to fold case. Lists
+/// with the same words compile to the same bytes.
+pub fn compile(words: &str) -> Vec<u8> {
+    compile_with_metadata(words, &Metadata::default())
+}
+
+/// Like [`compile`], but words may also have `alphabet`'s extra letters,
+/// encoded as [`Dictionary::parse_in`](crate::Dictionary::parse_in) leaves
+/// them.
+pub fn compile_in(words: &str, alphabet: &Alphabet) -> Vec<u8> {
+    let mut words = usable(words, alphabet);
+    words.sort_unstable();
+    words.dedup();
+    let (text, offsets) = text(&words);
+    let nodes = graft(&[levels(&words, &offsets, 0)], 0);
+    encode(&text, &Metadata::default(), &nodes)
+}
+
+/// Like [`compile`], recording `metadata` for
+/// [`CompiledDictionary::metadata`] to read back.
+///
+/// # Panics
+///
+/// If a field of `metadata` has a line break.
+pub fn compile_with_metadata(words: &str, metadata: &Metadata) -> Vec<u8> {
+    let mut words = usable(words, &Alphabet::english());
+    words.sort_unstable();
+    words.dedup();
+    let (text, offsets) = text(&words);
+    let nodes = graft(&[levels(&words, &offsets, 0)], 0);
+    encode(&text, metadata, &nodes)
+}
+
+/// Like [`compile`], with each letter's words laid out on a thread of their
+/// own. The bytes are the same, so it only saves time, on big lists.
+#[cfg(feature = "parallel")]
+pub fn compile_parallel(words: &str) -> Vec<u8> {
+    let mut words = usable(words, &Alphabet::english());
+    words.par_sort_unstable();
+    words.dedup();
+    let (text, offsets) = text(&words);
+
+    // every word has at least 3 letters, so each of the root's children heads the run of words starting with
+    // its letter
+    let mut runs = Vec::new();
+    let mut lo = 0;
+    while lo < words.len() {
+        let c = words[lo].as_bytes()[0];
+        let end = lo + words[lo..].iter().take_while(|w| w.as_bytes()[0] == c).count();
+        runs.push((c, lo, end));
+        lo = end;
+    }
+    let groups: Vec<_> = runs.par_iter().map(|&(_, lo, hi)| levels(&words[lo..hi], &offsets[lo..hi], 1)).collect();
+    let children = runs.iter().fold(0, |children, &(c, _, _)| children | letter_bit(c));
+    let mut nodes = vec![Node { children, first_child: 1, word: 0 }];
+    nodes.extend(graft(&groups, 1));
+    encode(&text, &Metadata::default(), &nodes)
+}
+
+// the words `compile` keeps, in the list's order
+fn usable<'a>(words: &'a str, alphabet: &Alphabet) -> Vec<&'a str> {
+    words.lines()
+        .map(str::trim)
+        .filter(|w| w.len() >= 3 && w.bytes().all(|c| alphabet.is_letter(c)))
+        .collect()
+}
+
+// the sorted words one per line, and where each starts
+fn text(words: &[&str]) -> (String, Vec<u32>) {
+    let mut text = String::new();
+    let mut offsets = Vec::with_capacity(words.len());
+    for word in words {
+        offsets.push(text.len() as u32);
+        text.push_str(word);
+        text.push('\n');
+    }
+    (text, offsets)
+}
+
+// The trie of the sorted `words`, which share their first `depth` bytes, one level at a time from the node for
+// that prefix down. Each level lists its nodes breadth first, and each node's `first_child` is where its children
+// start in the next level, or where they would if it has none.
+fn levels(words: &[&str], offsets: &[u32], depth: usize) -> Vec<Vec<Node>> {
+    // each run of sorted words shares one node's prefix
+    let mut runs = vec![(0, words.len())];
+    let mut levels = Vec::new();
+    let mut depth = depth;
+    while !runs.is_empty() {
+        let mut level = Vec::with_capacity(runs.len());
+        let mut next = Vec::new();
+        for (mut lo, hi) in runs {
+            // the prefix itself, if it's a word, sorts first
+            let word = if lo < hi && words[lo].len() == depth {
+                lo += 1;
+                offsets[lo - 1] + 1
+            } else {
+                0
+            };
+            let first_child = next.len() as u32;
+            let mut children = 0;
+            while lo < hi {
+                let c = words[lo].as_bytes()[depth];
+                let end = lo + words[lo..hi].iter().take_while(|w| w.as_bytes()[depth] == c).count();
+                children |= letter_bit(c);
+                next.push((lo, end));
+                lo = end;
+            }
+            level.push(Node { children, first_child, word });
+        }
+        levels.push(level);
+        runs = next;
+        depth += 1;
+    }
+    levels
+}
+
+// Lays out sibling subtries from `levels`, the first level of each starting at node `start`: level by level, and
+// within a level one subtrie after another, which is the order a breadth-first walk of the whole trie takes. So
+// every `first_child` moves past the nodes of earlier levels and of earlier subtries in its children's level.
+fn graft(groups: &[Vec<Vec<Node>>], start: usize) -> Vec<Node> {
+    let depth = groups.iter().map(Vec::len).max().unwrap_or(0);
+    let width = |group: &Vec<Vec<Node>>, level: usize| group.get(level).map_or(0, Vec::len);
+    let mut level_start = vec![start];
+    for level in 0..depth {
+        level_start.push(level_start[level] + groups.iter().map(|g| width(g, level)).sum::<usize>());
+    }
+    let mut nodes = Vec::with_capacity(level_start[depth] - start);
+    for level in 0..depth {
+        // where each subtrie's children start in the next level
+        let mut next = level_start[level + 1];
+        for group in groups {
+            for node in group.get(level).into_iter().flatten() {
+                nodes.push(Node { first_child: next as u32 + node.first_child, ..*node });
+            }
+            next += width(group, level + 1);
+        }
+    }
+    nodes
+}
+
+fn encode(text: &str, metadata: &Metadata, nodes: &[Node]) -> Vec<u8> {
+    let mut meta = String::new();
+    for (key, value) in metadata.fields().iter() {
+        if let Some(value) = value {
+            assert!(!value.contains(['\n', '\r']), "{} has a line break", key);
+            meta.push_str(&format!("{}\t{}\n", key, value));
+        }
+    }
+
+    let mut out = Vec::with_capacity(HEADER + text.len() + meta.len() + nodes.len() * NODE);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(nodes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(meta.len() as u32).to_le_bytes());
+    out.extend_from_slice(text.as_bytes());
+    out.extend_from_slice(meta.as_bytes());
+    for node in nodes {
+        for field in &[node.children, node.first_child, node.word] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    out
+}
+
+/// The words one compiled dictionary has and another doesn't; see [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diff<'a> {
+    /// Words only in the new dictionary, sorted.
+    pub added: Vec<&'a str>,
+    /// Words only in the old dictionary, sorted.
+    pub removed: Vec<&'a str>,
+}
+
+/// How `new`'s words differ from `old`'s, read straight from the indexes
+/// without the word lists they were compiled from.
+///
+/// ```
+/// use boggle::compiled::{self, CompiledDictionary};
+///
+/// let (old, new) = (compiled::compile("cat\ndog"), compiled::compile("cat\ncats"));
+/// let diff = compiled::diff(&CompiledDictionary::from_bytes(&old).unwrap(), &CompiledDictionary::from_bytes(&new).unwrap());
+/// assert_eq!((diff.added, diff.removed), (vec!["cats"], vec!["dog"]));
+/// ```
+pub fn diff<'a>(old: &CompiledDictionary<'a>, new: &CompiledDictionary<'a>) -> Diff<'a> {
+    // both lists are sorted, so one pass over them side by side finds every difference
+    let mut diff = Diff::default();
+    let (mut old, mut new) = (old.words().peekable(), new.words().peekable());
+    loop {
+        match (old.peek(), new.peek()) {
+            (Some(a), Some(b)) if a == b => {
+                old.next();
+                new.next();
+            }
+            (Some(a), Some(b)) if a < b => diff.removed.extend(old.next()),
+            (Some(_), None) => diff.removed.extend(old.next()),
+            (_, Some(_)) => diff.added.extend(new.next()),
+            (None, None) => return diff,
+        }
+    }
+}
+
+/// Whether `bytes` start like a compiled dictionary rather than a word list.
+pub fn is_compiled(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC) || bytes.starts_with(MAGIC_V1)
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Option<u32> {
+    let field = bytes.get(at..at + 4)?;
+    Some(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+impl<'a> CompiledDictionary<'a> {
+    /// Checks the header and borrows the words and trie from `bytes` without
+    /// copying them. Node contents aren't checked up front; a corrupt node
+    /// only loses the words below it.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<CompiledDictionary<'a>, Error> {
+        if !is_compiled(bytes) {
+            return Err(Error::CorruptDictionary);
+        }
+        let (header, meta_len) = if bytes.starts_with(MAGIC_V1) {
+            (HEADER - 4, 0)
+        } else {
+            (HEADER, u32_at(bytes, MAGIC.len() + 8).ok_or(Error::CorruptDictionary)? as usize)
+        };
+        let text_len = u32_at(bytes, MAGIC.len()).ok_or(Error::CorruptDictionary)? as usize;
+        let nodes = u32_at(bytes, MAGIC.len() + 4).ok_or(Error::CorruptDictionary)? as usize;
+        let body = nodes.checked_mul(NODE).and_then(|n| n.checked_add(text_len)).and_then(|n| n.checked_add(meta_len));
+        if nodes == 0 || body != bytes.len().checked_sub(header) {
+            return Err(Error::CorruptDictionary);
+        }
+
+        let (text, rest) = bytes[header..].split_at(text_len);
+        let (meta, nodes) = rest.split_at(meta_len);
+        let text = str::from_utf8(text).map_err(|_| Error::CorruptDictionary)?;
+        let meta = str::from_utf8(meta).map_err(|_| Error::CorruptDictionary)?;
+        Ok(CompiledDictionary { text, meta, nodes })
+    }
+
+    /// What was recorded about the word list when it was compiled; empty for
+    /// indexes from before it could be.
+    pub fn metadata(&self) -> Metadata {
+        Metadata::parse(self.meta)
+    }
+
+    /// The words, one per line.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+
+    /// The number of trie nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() / NODE
+    }
+
+    pub(crate) fn root(&self) -> Node {
+        self.node(0).expect("checked in from_bytes")
+    }
+
+    fn node(&self, index: u32) -> Option<Node> {
+        let at = index as usize * NODE;
+        Some(Node {
+            children: u32_at(self.nodes, at)?,
+            first_child: u32_at(self.nodes, at + 4)?,
+            word: u32_at(self.nodes, at + 8)?,
+        })
+    }
+
+    /// The child of `node` for letter `c`, if any.
+    pub(crate) fn get(&self, node: Node, c: u8) -> Option<Node> {
+        if !is_letter(c) || node.children & letter_bit(c) == 0 {
+            return None;
+        }
+        let rank = (node.children & (letter_bit(c) - 1)).count_ones();
+        self.node(node.first_child.checked_add(rank)?)
+    }
+
+    /// Follows `letters` down from `node`, e.g. both letters of a "Qu" tile.
+    pub(crate) fn step(&self, node: Node, letters: &[u8]) -> Option<Node> {
+        letters.iter().try_fold(node, |node, &c| self.get(node, c))
+    }
+
+    /// The word `node` completes, given that it's `len` letters deep.
+    pub(crate) fn word(&self, node: Node, len: usize) -> Option<&'a str> {
+        let start = node.word.checked_sub(1)? as usize;
+        self.text.get(start..start + len)
+    }
+
+    /// Whether `word` is a prefix of some compiled word.
+    pub fn contains(&self, word: &str) -> bool {
+        self.step(self.root(), word.as_bytes()).is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let bytes = compile("cats\ncat\n\n  dog \ncat\nox\nDog\nbar\n");
+        let dictionary = CompiledDictionary::from_bytes(&bytes).unwrap();
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), vec!["bar", "cat", "cats", "dog"]);
+        assert!(dictionary.contains("ca"));
+        assert!(!dictionary.contains("ox"));
+        let raw = "ca{\ncat\n";
+        assert_eq!(CompiledDictionary::from_bytes(&compile(raw)).unwrap().words().collect::<Vec<_>>(), vec!["cat"]);
+        assert_eq!(CompiledDictionary::from_bytes(&compile_in(raw, &Alphabet::spanish())).unwrap().words().count(), 2);
+
+        let cat = dictionary.step(dictionary.root(), b"cat").unwrap();
+        assert_eq!(dictionary.word(cat, 3), Some("cat"));
+        assert_eq!(cat.children, letter_bit(b's'));
+        let ca = dictionary.step(dictionary.root(), b"ca").unwrap();
+        assert_eq!(dictionary.word(ca, 2), None);
+    }
+
+    #[test]
+    fn reproducible() {
+        let bytes = compile("cat\ndog\ncats\nqua\n");
+        assert_eq!(compile("qua\r\ncats\r\n\ndog\ncat\ndog\n"), bytes);
+        assert_ne!(compile("cat\ndog\ncats\n"), bytes);
+
+        let dictionary = include_str!("../test/dictionary");
+        let mut reversed: Vec<_> = dictionary.lines().collect();
+        reversed.reverse();
+        assert_eq!(compile(&reversed.join("\n")), compile(dictionary));
+        let metadata = Metadata { source: Some("test".to_string()), ..Metadata::default() };
+        assert_eq!(compile_with_metadata("cat\ndog", &metadata), compile_with_metadata("dog\ncat", &metadata));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel() {
+        for words in &[include_str!("../test/dictionary"), "", "cat\n", "zebra\nant\nants\nqua\nant\nab\n"] {
+            assert_eq!(compile_parallel(words), compile(words), "{:?}", words);
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let (old, new) = (compile("ant\ncat\ndog\nzebra"), compile("bee\ncat\ncats\nzebra\nzebras"));
+        let (old, new) = (CompiledDictionary::from_bytes(&old).unwrap(), CompiledDictionary::from_bytes(&new).unwrap());
+        assert_eq!(super::diff(&old, &new), Diff { added: vec!["bee", "cats", "zebras"], removed: vec!["ant", "dog"] });
+        assert_eq!(super::diff(&new, &old), Diff { added: vec!["ant", "dog"], removed: vec!["bee", "cats", "zebras"] });
+        assert_eq!(super::diff(&old, &old), Diff::default());
+        assert_eq!((old.node_count(), new.node_count()), (15, 14));
+    }
+
+    #[test]
+    fn corrupt() {
+        let bytes = compile("cat");
+        assert!(CompiledDictionary::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CompiledDictionary::from_bytes(b"cat\n").is_err());
+        assert!(CompiledDictionary::from_bytes(MAGIC).is_err());
+        assert!(CompiledDictionary::from_bytes(&compile("")).is_ok());
+        assert!(CompiledDictionary::from_bytes(&MAGIC_V1[..]).is_err());
+    }
+
+    #[test]
+    fn metadata() {
+        assert!(CompiledDictionary::from_bytes(&compile("cat")).unwrap().metadata().is_empty());
+        let metadata = Metadata { license: Some("CC0-1.0".to_string()), source: Some("https://example.com/words".to_string()), ..Metadata::default() };
+        let bytes = compile_with_metadata("cat\ndog\n", &metadata);
+        let dictionary = CompiledDictionary::from_bytes(&bytes).unwrap();
+        assert_eq!(dictionary.metadata(), metadata);
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), vec!["cat", "dog"]);
+
+        // a version 1 index is the same bytes without the metadata
+        let v2 = compile("cat\ndog\n");
+        let mut v1 = MAGIC_V1.to_vec();
+        v1.extend_from_slice(&v2[MAGIC.len()..MAGIC.len() + 8]);
+        v1.extend_from_slice(&v2[HEADER..]);
+        let old = CompiledDictionary::from_bytes(&v1).unwrap();
+        assert!(is_compiled(&v1) && old.metadata().is_empty());
+        assert_eq!(old.words().collect::<Vec<_>>(), vec!["cat", "dog"]);
+        assert!(old.contains("do"));
+    }
+}
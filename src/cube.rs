@@ -0,0 +1,188 @@
+//! 3D Boggle: a cube of letters, where each cell is adjacent to the up to 26
+//! cells around it, in the layers above and below as well as its own.
+
+use std::str;
+
+use crate::board::Board;
+use crate::error::Error;
+use crate::multivec::VecN;
+use crate::score::classic_points;
+use crate::trie::{Seen, Trie};
+
+// the shortest word reported, as on a board
+const MIN_LEN: usize = 3;
+
+/// A cube of letters, addressed `[layer, row, col]` from the top left of the
+/// first layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cube {
+    tiles: VecN<&'static [u8], 3>,
+}
+
+/// A word found on a [`Cube`], like a [`Solution`](crate::Solution) but with
+/// a path through three dimensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeSolution<'a> {
+    pub word: &'a str,
+    /// Official Boggle points for the word's length.
+    pub score: u32,
+    pub path: Vec<[usize; 3]>,
+}
+
+impl Cube {
+    /// Parses one layer after another, each written as for [`Board::parse`]
+    /// and separated by blank lines. There must be as many layers as each
+    /// has rows.
+    pub fn parse(raw: &str) -> Result<Cube, Error> {
+        let mut layers = vec![String::new()];
+        for line in raw.lines().map(str::trim) {
+            if !line.is_empty() {
+                layers.last_mut().expect("never empty").push_str(line);
+                layers.last_mut().expect("never empty").push('\n');
+            } else if !layers.last().expect("never empty").is_empty() {
+                layers.push(String::new());
+            }
+        }
+        if layers.last().is_some_and(String::is_empty) {
+            layers.pop();
+        }
+
+        let boards = layers.iter().map(|layer| Board::parse(layer)).collect::<Result<Vec<_>, _>>()?;
+        if boards.is_empty() {
+            return Err(Error::EmptyBoard);
+        }
+        if let Some((layer, board)) = boards.iter().enumerate().find(|(_, b)| b.len() != boards.len()) {
+            return Err(Error::CubeLayer { layer, expected: boards.len(), got: board.len() });
+        }
+        let size = boards.len();
+        let mut tiles = VecN::fill([size; 3], &[][..]);
+        for at in tiles.coords().collect::<Vec<_>>() {
+            tiles[at] = boards[at[0]].tile((at[1], at[2]));
+        }
+        Ok(Cube { tiles })
+    }
+
+    /// The number of layers, which is also the number of rows and columns in
+    /// each.
+    pub fn len(&self) -> usize {
+        self.tiles.dims()[0]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The letters spelled by the cell at `[layer, row, col]`.
+    pub fn tile(&self, cell: [usize; 3]) -> &'static [u8] {
+        self.tiles[cell]
+    }
+
+    /// The cells adjacent to `cell`, diagonals across layers included.
+    pub fn neighbors(&self, cell: [usize; 3]) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.tiles.neighbors(cell)
+    }
+
+    /// Finds the words from `words`, one per line, on the cube.
+    pub fn solve<'a>(&self, words: &'a str) -> Vec<CubeSolution<'a>> {
+        let mut trie = Trie::new();
+        for word in words.lines().map(str::trim).filter(|w| w.len() >= MIN_LEN) {
+            trie.insert(word.as_bytes());
+        }
+        self.solve_with_trie(&trie)
+    }
+
+    /// Like `solve`, but searches a trie built ahead of time, which any number
+    /// of cubes can share.
+    pub fn solve_with_trie<'a>(&self, trie: &Trie<'a>) -> Vec<CubeSolution<'a>> {
+        let mut walk = Walk {
+            cube: self,
+            trie,
+            seen: Seen::new(trie),
+            visited: VecN::fill(self.tiles.dims(), false),
+            path: Vec::new(),
+            solutions: Vec::new(),
+        };
+        for cell in self.tiles.coords() {
+            if let Some(node) = trie.step(Trie::ROOT, self.tile(cell)) {
+                walk.visit(cell, node);
+            }
+        }
+        walk.solutions
+    }
+}
+
+// a depth-first search marking each cell on the way in and clearing it on the way back out
+struct Walk<'c, 'a> {
+    cube: &'c Cube,
+    trie: &'c Trie<'a>,
+    seen: Seen,
+    visited: VecN<bool, 3>,
+    path: Vec<[usize; 3]>,
+    solutions: Vec<CubeSolution<'a>>,
+}
+
+impl<'c, 'a> Walk<'c, 'a> {
+    fn visit(&mut self, cell: [usize; 3], node: usize) {
+        self.visited[cell] = true;
+        self.path.push(cell);
+
+        let trie_node = self.trie.node(node);
+        if trie_node.is_word() && trie_node.word.len() >= MIN_LEN && self.seen.insert(node) {
+            let word = str::from_utf8(trie_node.word).expect("letters are ascii");
+            self.solutions.push(CubeSolution { word, score: classic_points(word.len()), path: self.path.clone() });
+        }
+
+        let cube = self.cube;
+        for next_cell in cube.neighbors(cell) {
+            if self.visited[next_cell] {
+                continue;
+            }
+            if let Some(next) = self.trie.step(node, cube.tile(next_cell)) {
+                self.visit(next_cell, next);
+            }
+        }
+
+        self.path.pop();
+        self.visited[cell] = false;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cube() {
+        let cube = Cube::parse("ca\nxx\n\nxt\nxs\n").unwrap();
+        assert_eq!(cube.len(), 2);
+        assert_eq!(cube.tile([1, 0, 1]), b"t");
+        // every other cell of a 2x2x2 cube is adjacent
+        assert_eq!(cube.neighbors([0, 0, 0]).count(), 7);
+
+        // so any word spelled with the cube's letters, none reused, is on it
+        let mut solutions = cube.solve("cat\ncats\nact\ntax\ndog\nat");
+        solutions.sort_by(|a, b| a.word.cmp(b.word));
+        let words: Vec<_> = solutions.iter().map(|s| s.word).collect();
+        assert_eq!(words, ["act", "cat", "cats", "tax"]);
+        assert_eq!(solutions[1], CubeSolution { word: "cat", score: 1, path: vec![[0, 0, 0], [0, 0, 1], [1, 0, 1]] });
+
+        let trie = Trie::from_dictionary(include_str!("../test/dictionary"));
+        let cube = Cube::parse("quits\nsare\nnlop\ndeam\n\ntrsn\neiao\nlpcd\nmegu\n\nbash\niotr\nenal\ndsei\n\nmwon\nrate\nsilp\ncudk").unwrap();
+        let solutions = cube.solve_with_trie(&trie);
+        assert!(solutions.len() > 100);
+        for solution in &solutions {
+            let spelled: Vec<u8> = solution.path.iter().flat_map(|&cell| cube.tile(cell).iter().copied()).collect();
+            assert_eq!(spelled, solution.word.as_bytes());
+            assert!(solution.path.windows(2).all(|w| cube.neighbors(w[0]).any(|n| n == w[1])));
+        }
+        // the first layer's words are all still there with the others stacked on it
+        let first = Board::parse("quits\nsare\nnlop\ndeam").unwrap();
+        let flat = first.solve_with_trie(&trie, &crate::SolveOptions::default());
+        assert!(flat.iter().all(|s| solutions.iter().any(|c| c.word == s.word)));
+
+        assert!(matches!(Cube::parse("ab\ncd\n\nabc\ndef\nghi"), Err(Error::CubeLayer { layer: 1, expected: 2, got: 3 })));
+        assert!(matches!(Cube::parse("ab\ncd"), Err(Error::CubeLayer { layer: 0, expected: 1, got: 2 })));
+        assert!(matches!(Cube::parse("\n\n"), Err(Error::EmptyBoard)));
+        assert!(matches!(Cube::parse("a\n\nb"), Err(Error::CubeLayer { layer: 0, expected: 2, got: 1 })));
+    }
+}
@@ -0,0 +1,230 @@
+//! A DAWG (directed acyclic word graph): a trie whose identical subtrees are
+//! merged, so words share suffixes as well as prefixes. For a large word list
+//! it has several times fewer nodes than the trie, and each node is a few
+//! words rather than a pointer per letter.
+//!
+//! Merged nodes can't point back at the word they end, so each node instead
+//! counts the words at or below it; summing the counts passed over on the
+//! way down numbers every word by its place in sorted order.
+
+use std::collections::HashMap;
+use std::str;
+
+use crate::alphabet::is_letter;
+use crate::trie::letter_bit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Node {
+    /// Bit `c - b'a'` is set when the node has an edge for letter `c`.
+    children: u32,
+    first_edge: u32,
+    word_end: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Edge {
+    to: u32,
+    /// Words numbered before any reached through this edge: the parent's own
+    /// word, if any, and those under its earlier edges.
+    skip: u32,
+}
+
+/// A minimal automaton over a word list, borrowing the words it was built
+/// from. Search it with [`Board::solve_dawg`](crate::Board::solve_dawg).
+#[derive(Debug, Clone)]
+pub struct Dawg<'a> {
+    /// Sorted and deduplicated, so a word's number is its index.
+    words: Vec<&'a str>,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+/// A position in a [`Dawg`]: a node, and the number of words sorting before
+/// any word below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct State {
+    node: u32,
+    index: u32,
+}
+
+// a node's state while building: whether it ends a word and its edges in letter order, enough to tell
+// whether two nodes have the same subtree once their children have been merged
+type Signature = (bool, Vec<(u8, u32)>);
+
+impl<'a> Dawg<'a> {
+    /// Builds the automaton for `words`, one per line. Like
+    /// [`compile`](crate::compiled::compile), it ignores surrounding
+    /// whitespace, blank lines, repeats, words shorter than 3 letters and
+    /// words with characters other than `a..=z`.
+    pub fn new(words: &'a str) -> Dawg<'a> {
+        let mut words: Vec<_> = words.lines()
+            .map(str::trim)
+            .filter(|w| w.len() >= 3 && w.bytes().all(|c| c.is_ascii_lowercase()))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        // Daciuk et al.'s incremental construction: with the words sorted, only the path of the last word
+        // added can still change, so everything hanging off it is merged into `register` as soon as the next
+        // word branches away
+        let mut building: Vec<Signature> = vec![(false, Vec::new())];
+        let mut register: HashMap<Signature, u32> = HashMap::new();
+        // the last word's path, as (parent, letter, child)
+        let mut unchecked: Vec<(u32, u8, u32)> = Vec::new();
+        let mut minimize = |building: &mut Vec<Signature>, unchecked: &mut Vec<(u32, u8, u32)>, down_to: usize| {
+            while unchecked.len() > down_to {
+                let (parent, letter, child) = unchecked.pop().unwrap();
+                let signature = building[child as usize].clone();
+                match register.get(&signature) {
+                    Some(&same) => {
+                        let edge = building[parent as usize].1.iter_mut().find(|e| e.0 == letter).unwrap();
+                        edge.1 = same;
+                    }
+                    None => {
+                        register.insert(signature, child);
+                    }
+                }
+            }
+        };
+
+        let mut previous: &[u8] = &[];
+        for word in &words {
+            let word = word.as_bytes();
+            let common = word.iter().zip(previous).take_while(|(a, b)| a == b).count();
+            minimize(&mut building, &mut unchecked, common);
+
+            let mut node = unchecked.last().map_or(0, |&(_, _, child)| child);
+            for &c in &word[common..] {
+                let child = building.len() as u32;
+                building.push((false, Vec::new()));
+                building[node as usize].1.push((c, child));
+                unchecked.push((node, c, child));
+                node = child;
+            }
+            building[node as usize].0 = true;
+            previous = word;
+        }
+        minimize(&mut building, &mut unchecked, 0);
+
+        // lay out the nodes still reachable, breadth first from the root, with each one's edges together
+        let mut ids = HashMap::new();
+        ids.insert(0u32, 0u32);
+        let mut order = vec![0u32];
+        let mut i = 0;
+        while i < order.len() {
+            for &(_, child) in &building[order[i] as usize].1 {
+                ids.entry(child).or_insert_with(|| {
+                    order.push(child);
+                    order.len() as u32 - 1
+                });
+            }
+            i += 1;
+        }
+
+        // words at or below each node; merged nodes can be laid out before some of their parents, so this
+        // goes depth first rather than in layout order
+        fn count(node: u32, building: &[Signature], counts: &mut HashMap<u32, u32>) -> u32 {
+            if let Some(&n) = counts.get(&node) {
+                return n;
+            }
+            let (word_end, ref edges) = building[node as usize];
+            let n = word_end as u32 + edges.iter().map(|&(_, child)| count(child, building, counts)).sum::<u32>();
+            counts.insert(node, n);
+            n
+        }
+        let mut counts = HashMap::new();
+        count(0, &building, &mut counts);
+
+        let mut nodes = Vec::with_capacity(order.len());
+        let mut edges = Vec::new();
+        for &old in &order {
+            let (word_end, ref out) = building[old as usize];
+            let mut node = Node { children: 0, first_edge: edges.len() as u32, word_end };
+            let mut skip = word_end as u32;
+            for &(c, child) in out {
+                node.children |= letter_bit(c);
+                edges.push(Edge { to: ids[&child], skip });
+                skip += counts[&child];
+            }
+            nodes.push(node);
+        }
+
+        Dawg { words, nodes, edges }
+    }
+
+    /// The number of words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The number of nodes, for comparing against the trie.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The words, in sorted order.
+    pub fn words(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.words.iter().cloned()
+    }
+
+    /// Whether `word` is a prefix of some word in the automaton.
+    pub fn contains(&self, word: &str) -> bool {
+        self.step(self.root(), word.as_bytes()).is_some()
+    }
+
+    pub(crate) fn root(&self) -> State {
+        State { node: 0, index: 0 }
+    }
+
+    /// The letter mask of the edges out of `state`.
+    pub(crate) fn children(&self, state: State) -> u32 {
+        self.nodes[state.node as usize].children
+    }
+
+    /// Follows `letters` down from `state`, e.g. both letters of a "Qu" tile.
+    pub(crate) fn step(&self, state: State, letters: &[u8]) -> Option<State> {
+        letters.iter().try_fold(state, |state, &c| {
+            let node = self.nodes[state.node as usize];
+            if !is_letter(c) || node.children & letter_bit(c) == 0 {
+                return None;
+            }
+            let rank = (node.children & (letter_bit(c) - 1)).count_ones();
+            let edge = self.edges[(node.first_edge + rank) as usize];
+            Some(State { node: edge.to, index: state.index + edge.skip })
+        })
+    }
+
+    /// The word ending at `state`, if any.
+    pub(crate) fn word(&self, state: State) -> Option<&'a str> {
+        if self.nodes[state.node as usize].word_end {
+            Some(self.words[state.index as usize])
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn words() {
+        let dawg = Dawg::new("tapping\ntap\ntaps\ntopping\ntop\ntops\n\n tap \nox\nTops\n");
+        assert_eq!(dawg.words().collect::<Vec<_>>(), vec!["tap", "tapping", "taps", "top", "topping", "tops"]);
+        for (i, word) in dawg.words().enumerate() {
+            let state = dawg.step(dawg.root(), word.as_bytes()).unwrap();
+            assert_eq!(dawg.word(state), Some(word), "{}", i);
+        }
+        assert!(dawg.contains("topp"));
+        assert!(!dawg.contains("tops_"));
+        assert_eq!(dawg.word(dawg.step(dawg.root(), b"topp").unwrap()), None);
+        // "ta" and "to" share one node, as do the ends of all six words: the trie would need 16
+        assert_eq!(dawg.node_count(), 8);
+        assert!(Dawg::new("").is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::board::Board;
+use crate::rng::{RngSource, SplitMix64};
+
+/// One die per string, one face per letter. A `q` face is the "Qu" tile.
+pub type Dice = [&'static str];
+
+/// The 16 dice of classic 4x4 Boggle.
+pub const CLASSIC: [&str; 16] = [
+    "aaeegn", "abbjoo", "achops", "affkps", "aoottw", "cimotu", "deilrx", "delrvy",
+    "distty", "eeghnw", "eeinsu", "ehrtvw", "eiosst", "elrtty", "himnqu", "hlnnrz",
+];
+
+/// The 25 dice of 5x5 Big Boggle.
+pub const BIG: [&str; 25] = [
+    "aaafrs", "aaeeee", "aafirs", "adennn", "aeeeem", "aeegmu", "aegmnn", "afirsy", "bjkqxz",
+    "ccenst", "ceiilt", "ceilpt", "ceipst", "ddhnot", "dhhlor", "dhlnor", "dhlnor", "eiiitt",
+    "emottt", "ensssu", "fiprsy", "gorrvw", "iprrry", "nootuw", "ooottu",
+];
+
+/// Shakes `dice` into a square grid and rolls each one, returning the board as
+/// text for [`Board::parse`](crate::Board::parse). The same seed always rolls
+/// the same board, on any platform and in every version of the crate, so a
+/// tournament can publish the seed instead of the board.
+///
+/// # Panics
+///
+/// If the number of dice isn't a square.
+pub fn roll(dice: &Dice, seed: u64) -> String {
+    roll_with(dice, &mut SplitMix64::new(seed))
+}
+
+/// Like [`roll`], drawing from `rng`.
+pub fn roll_with<R: RngSource>(dice: &Dice, rng: &mut R) -> String {
+    let size = (1..=dice.len()).find(|n| n * n >= dice.len()).unwrap_or(0);
+    assert_eq!(size * size, dice.len(), "{} dice don't make a square board", dice.len());
+
+    let mut order: Vec<_> = dice.iter().collect();
+    rng.shuffle(&mut order);
+
+    let mut board = String::with_capacity(dice.len() * 2);
+    for row in order.chunks(size) {
+        for die in row {
+            match die.as_bytes()[rng.below(die.len())] {
+                b'q' => board.push_str("qu"),
+                c => board.push(c as char),
+            }
+        }
+        board.push('\n');
+    }
+    board
+}
+
+/// Whether some roll of `dice` shows `board`, in any arrangement: every cell
+/// needs a die of its own with that letter on a face, a `q` face for a "Qu"
+/// tile.
+pub fn can_roll(dice: &Dice, board: &Board) -> bool {
+    if dice.len() != board.cells() {
+        return false;
+    }
+    let faces: Vec<u8> = (0..board.len())
+        .flat_map(|i| (0..board.len()).map(move |j| (i, j)))
+        .map(|cell| match board[cell] {
+            Board::QU => b'q',
+            // a bare `q` isn't on any die
+            b'q' => 0,
+            // nor are the other digraphs
+            c if c.is_ascii_uppercase() => 0,
+            c => c,
+        })
+        .collect();
+
+    // Kuhn's augmenting paths, matching each cell to a die showing its letter
+    fn assign(cell: usize, faces: &[u8], dice: &Dice, owner: &mut [Option<usize>], tried: &mut [bool]) -> bool {
+        for (die, sides) in dice.iter().enumerate() {
+            if tried[die] || !sides.as_bytes().contains(&faces[cell]) {
+                continue;
+            }
+            tried[die] = true;
+            if owner[die].is_none_or(|other| assign(other, faces, dice, owner, tried)) {
+                owner[die] = Some(cell);
+                return true;
+            }
+        }
+        false
+    }
+    let mut owner = vec![None; dice.len()];
+    (0..faces.len()).all(|cell| assign(cell, &faces, dice, &mut owner, &mut vec![false; dice.len()]))
+}
+
+#[test]
+fn smoke() {
+    for &(dice, size) in &[(&CLASSIC[..], 4), (&BIG[..], 5)] {
+        let raw = roll(dice, 7);
+        assert_eq!(raw, roll(dice, 7));
+        let board = Board::parse(&raw).unwrap();
+        assert_eq!(board.len(), size);
+        assert_eq!(board.letter_counts().iter().map(|&n| n as usize).sum::<usize>(),
+                   size * size + raw.matches("qu").count());
+    }
+    assert_ne!(roll(&CLASSIC, 1), roll(&CLASSIC, 2));
+    // published seeds have to keep rolling the same boards
+    assert_eq!(roll(&CLASSIC, 42), "oyto\nhdet\nhnhe\nfaji\n");
+    assert_eq!(roll(&BIG, 42), "pnoah\ntexur\neptru\ntnrsa\nuiano\n");
+
+    for seed in 0..20 {
+        assert!(can_roll(&CLASSIC, &Board::parse(&roll(&CLASSIC, seed)).unwrap()));
+        assert!(can_roll(&BIG, &Board::parse(&roll(&BIG, seed)).unwrap()));
+    }
+    // only one die has a z, and none a bare q
+    assert!(can_roll(&CLASSIC, &Board::parse("zoon\ntaxe\nrbsi\nvycm").unwrap()));
+    assert!(!can_roll(&CLASSIC, &Board::parse("zoon\ntaxe\nrbsi\nvyzm").unwrap()));
+    assert!(!can_roll(&CLASSIC, &Board::parse("qoon\ntaxe\nrbsi\nvycm").unwrap()));
+    assert!(!can_roll(&BIG, &Board::parse(&roll(&CLASSIC, 0)).unwrap()));
+}
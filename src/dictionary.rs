@@ -0,0 +1,347 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+use crate::compiled::{self, CompiledDictionary};
+use crate::error::Error;
+use crate::gzip;
+
+/// A word list cleaned up for solving: one lowercase word per line, each
+/// appearing once, in the order first seen.
+///
+/// The solvers take the dictionary as plain text, so pass
+/// [`Dictionary::as_str`] to them; solutions then borrow from the
+/// `Dictionary`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    text: String,
+    len: usize,
+    duplicates: usize,
+    invalid: usize,
+    excluded: usize,
+    normalized: usize,
+    // what the words were encoded with, for encoding blocklists the same way
+    alphabet: Alphabet,
+}
+
+impl Dictionary {
+    /// Maps the word list at `path` into memory rather than reading it, for
+    /// lists too big to copy; see [`mmap`](crate::mmap). The words are used
+    /// as they are in the file, so it should be a clean list.
+    #[cfg(all(feature = "mmap", unix))]
+    pub fn open_mmap<P: AsRef<std::path::Path>>(path: P) -> Result<crate::mmap::MappedDictionary, crate::Error> {
+        crate::mmap::MappedDictionary::open(path)
+    }
+
+    /// Reads one word per line, ignoring surrounding whitespace and blank
+    /// lines and folding uppercase to lowercase. Repeats, including ones that
+    /// only differ in case, are dropped, as are words with characters other
+    /// than letters.
+    pub fn parse(raw: &str) -> Dictionary {
+        Dictionary::parse_in(raw, &Alphabet::english())
+    }
+
+    /// Like `parse`, but words may also have `alphabet`'s extra letters, in
+    /// either case. They're kept encoded, as the solvers need, so decode the
+    /// words found with [`Alphabet::decode`].
+    pub fn parse_in(raw: &str, alphabet: &Alphabet) -> Dictionary {
+        Dictionary::load(raw, alphabet, false)
+    }
+
+    /// Like `parse_in`, but rather than dropping words with apostrophes,
+    /// hyphens or accents, takes those out: "don't" becomes "dont",
+    /// "x-ray" "xray" and "naïve" "naive". Accented letters that are in
+    /// `alphabet` are kept, so "año" stays as it is with
+    /// [`Alphabet::spanish`]. Words that still have characters other than
+    /// letters are dropped as usual.
+    pub fn parse_normalized(raw: &str, alphabet: &Alphabet) -> Dictionary {
+        Dictionary::load(raw, alphabet, true)
+    }
+
+    fn load(raw: &str, alphabet: &Alphabet, normalize: bool) -> Dictionary {
+        let mut dictionary = Dictionary { text: String::with_capacity(raw.len()), alphabet: alphabet.clone(), ..Dictionary::default() };
+        // sized for a typical word of around 8 bytes so big lists don't rehash as they load
+        let mut seen = HashSet::with_capacity(raw.len() / 8);
+        for word in raw.lines().map(str::trim).filter(|w| !w.is_empty()) {
+            let word = match normalize_word(word, alphabet) {
+                Some(word) if normalize => {
+                    dictionary.normalized += 1;
+                    Cow::Owned(word)
+                }
+                _ => Cow::Borrowed(word),
+            };
+            let word = if !word.is_ascii() && word.chars().all(|c| alphabet.contains(c)) {
+                Cow::Owned(alphabet.encode(&word).to_ascii_lowercase())
+            } else if !word.bytes().all(|c| c.is_ascii_alphabetic()) {
+                dictionary.invalid += 1;
+                continue;
+            } else if word.bytes().any(|c| c.is_ascii_uppercase()) {
+                Cow::Owned(word.to_ascii_lowercase())
+            } else {
+                word
+            };
+            if !seen.insert(word.clone()) {
+                dictionary.duplicates += 1;
+                continue;
+            }
+            dictionary.text.push_str(&word);
+            dictionary.text.push('\n');
+            dictionary.len += 1;
+        }
+        debug!("loaded {} words, skipping {} invalid and {} repeated; normalized {}",
+               dictionary.len, dictionary.invalid, dictionary.duplicates, dictionary.normalized);
+        dictionary
+    }
+
+    /// Drops the words `keep` returns false for, e.g. `|w| !w.contains('q')`
+    /// for a set with no "Qu" die. Filter before building a trie from it.
+    pub fn retain<F: FnMut(&str) -> bool>(&mut self, mut keep: F) {
+        let mut text = String::with_capacity(self.text.len());
+        let mut kept = 0;
+        for word in self.text.lines().filter(|w| keep(w)) {
+            text.push_str(word);
+            text.push('\n');
+            kept += 1;
+        }
+        self.excluded += self.len - kept;
+        self.len = kept;
+        self.text = text;
+    }
+
+    /// Drops the words in `blocklist`, e.g. a profanity or proper noun list,
+    /// read one per line like [`Dictionary::parse`] reads words.
+    pub fn exclude(&mut self, blocklist: &str) {
+        let blocked: HashSet<_> = blocklist.lines().map(|w| self.alphabet.encode(w.trim()).to_ascii_lowercase()).collect();
+        self.retain(|word| !blocked.contains(word));
+    }
+
+    /// The words, one per line, for the solvers.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many lines repeated an earlier word.
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// How many lines were skipped for holding something other than letters.
+    pub fn invalid(&self) -> usize {
+        self.invalid
+    }
+
+    /// How many words [`Dictionary::parse_normalized`] changed to make them
+    /// all letters.
+    pub fn normalized(&self) -> usize {
+        self.normalized
+    }
+
+    /// How many words [`Dictionary::retain`] and [`Dictionary::exclude`] have
+    /// dropped.
+    pub fn excluded(&self) -> usize {
+        self.excluded
+    }
+}
+
+/// What a dictionary file holds, as [`decode`] tells from its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileFormat {
+    /// One word per line, as [`Dictionary::parse`] reads them.
+    WordList,
+    /// A word, a tab and how often the word is used on each line, as many
+    /// corpora's frequency lists are written.
+    Counts,
+    /// A dictionary from [`compile`](crate::compiled::compile).
+    Compiled,
+}
+
+/// A dictionary file's contents, decompressed if they were gzipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryFile {
+    pub format: FileFormat,
+    pub gzipped: bool,
+    /// A compiled dictionary's bytes as they are, or the words one per line,
+    /// without their counts.
+    pub bytes: Vec<u8>,
+    /// For [`FileFormat::Counts`], the same words most used first, for
+    /// ranking them with [`Rarity`](crate::score::Rarity).
+    pub ranked: Option<String>,
+}
+
+impl DictionaryFile {
+    /// The words one per line, ready for [`Dictionary::parse`].
+    pub fn words(&self) -> Result<Cow<'_, str>, Error> {
+        if self.format == FileFormat::Compiled {
+            return Ok(Cow::Owned(CompiledDictionary::from_bytes(&self.bytes)?.as_str().to_string()));
+        }
+        Ok(Cow::Borrowed(utf8(&self.bytes)?))
+    }
+}
+
+/// Reads the dictionary file at `path`; see [`decode`].
+#[cfg(feature = "fs")]
+pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<DictionaryFile, Error> {
+    decode(std::fs::read(path)?)
+}
+
+/// Works out what `raw` holds: a word list, a list of words with counts or a
+/// compiled dictionary, any of them gzipped. A list is taken to have counts
+/// if its first line has a tab.
+pub fn decode(raw: Vec<u8>) -> Result<DictionaryFile, Error> {
+    let gzipped = gzip::is_gzip(&raw);
+    let raw = if gzipped { gzip::decompress(&raw)? } else { raw };
+    if compiled::is_compiled(&raw) {
+        return Ok(DictionaryFile { format: FileFormat::Compiled, gzipped, bytes: raw, ranked: None });
+    }
+    let first = raw.split(|&b| b == b'\n').find(|line| !line.trim_ascii().is_empty()).unwrap_or_default();
+    if !first.contains(&b'\t') {
+        return Ok(DictionaryFile { format: FileFormat::WordList, gzipped, bytes: raw, ranked: None });
+    }
+
+    let mut counted = Vec::new();
+    for (line, text) in utf8(&raw)?.lines().enumerate().filter(|(_, text)| !text.trim().is_empty()) {
+        let (word, count) = text.split_once('\t').ok_or(Error::InvalidCount { line })?;
+        match (word.trim(), count.trim().parse::<u64>()) {
+            (word, Ok(count)) if !word.is_empty() => counted.push((word, count)),
+            _ => return Err(Error::InvalidCount { line }),
+        }
+    }
+    let words: String = counted.iter().flat_map(|&(word, _)| [word, "\n"]).collect();
+    // most used first, ties in the order listed
+    counted.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    let ranked = counted.iter().flat_map(|&(word, _)| [word, "\n"]).collect();
+    Ok(DictionaryFile { format: FileFormat::Counts, gzipped, bytes: words.into_bytes(), ranked: Some(ranked) })
+}
+
+pub(crate) fn utf8(raw: &[u8]) -> Result<&str, Error> {
+    std::str::from_utf8(raw).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err).into())
+}
+
+// `word` without apostrophes, hyphens and accents on letters `alphabet` lacks, or `None` if it has none of them
+fn normalize_word(word: &str, alphabet: &Alphabet) -> Option<String> {
+    if word.chars().all(|c| alphabet.contains(c)) {
+        return None;
+    }
+    let normalized: String = word.chars()
+        .filter(|c| !matches!(c, '\'' | '\u{2019}' | '-'))
+        .map(|c| if alphabet.contains(c) { c } else { unaccented(c) })
+        .collect();
+    if normalized == word { None } else { Some(normalized) }
+}
+
+// the letter under a Latin accent, or `c` itself
+fn unaccented(c: char) -> char {
+    const ACCENTED: [(&str, char); 8] =
+        [("àáâãäå", 'a'), ("ç", 'c'), ("èéêë", 'e'), ("ìíîï", 'i'), ("ñ", 'n'), ("òóôõöø", 'o'), ("ùúûü", 'u'), ("ýÿ", 'y')];
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let base = ACCENTED.iter().find(|(accented, _)| accented.contains(lower)).map(|&(_, base)| base);
+    match base {
+        Some(base) if c.is_uppercase() => base.to_ascii_uppercase(),
+        Some(base) => base,
+        None => c,
+    }
+}
+
+impl fmt::Display for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Board;
+
+    #[test]
+    fn parse() {
+        let dictionary = Dictionary::parse("cat\n CAT\r\n\ndog\ncat\ndon't\nnaïve\nCats\n");
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), vec!["cat", "dog", "cats"]);
+        assert_eq!(dictionary.len(), 3);
+        assert_eq!(dictionary.duplicates(), 2);
+        assert_eq!(dictionary.invalid(), 2);
+        assert!(Dictionary::parse("\n \n").is_empty());
+
+        let spanish = Alphabet::spanish();
+        let dictionary = Dictionary::parse_in("año\nAÑO\nniño\nnaïve\ncat\n", &spanish);
+        assert_eq!((dictionary.len(), dictionary.duplicates(), dictionary.invalid()), (3, 1, 1));
+        assert_eq!(dictionary.words().map(|w| spanish.decode(w)).collect::<Vec<_>>(), vec!["año", "niño", "cat"]);
+        assert!(dictionary.as_str().is_ascii());
+        let mut dictionary = dictionary;
+        dictionary.exclude("Niño\n");
+        assert_eq!(dictionary.len(), 2);
+    }
+
+    #[test]
+    fn normalized() {
+        let raw = "don't\nX-Ray\nnaïve\nCAFÉ\ncafe\nrock’n’roll\nfuß\nniño\ncat\n";
+        let dictionary = Dictionary::parse_normalized(raw, &Alphabet::english());
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), ["dont", "xray", "naive", "cafe", "rocknroll", "nino", "cat"]);
+        assert_eq!((dictionary.normalized(), dictionary.duplicates(), dictionary.invalid()), (6, 1, 1));
+
+        let spanish = Alphabet::spanish();
+        let dictionary = Dictionary::parse_normalized("niño\ncafé\nd'año\n", &spanish);
+        assert_eq!(dictionary.words().map(|w| spanish.decode(w)).collect::<Vec<_>>(), ["niño", "cafe", "daño"]);
+        assert_eq!(dictionary.normalized(), 2);
+        assert_eq!(Dictionary::parse(raw).normalized(), 0);
+    }
+
+    #[test]
+    fn exclude() {
+        let mut dictionary = Dictionary::parse("cat\ndarn\nquit\ndog\nparis\n");
+        dictionary.exclude(" DARN\nParis\nnotaword\n");
+        assert_eq!(dictionary.words().collect::<Vec<_>>(), vec!["cat", "quit", "dog"]);
+        dictionary.retain(|w| !w.contains('q'));
+        assert_eq!(dictionary.as_str(), "cat\ndog\n");
+        assert_eq!((dictionary.len(), dictionary.excluded()), (2, 3));
+    }
+
+    #[test]
+    fn solvers_agree() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let dictionary = Dictionary::parse("cat\nCAT\ncat\ncats");
+        let mut single: Vec<_> = board.solve_single_threaded(dictionary.as_str()).into_iter().map(|s| s.word).collect();
+        let mut trie: Vec<_> = board.solve_trie(dictionary.as_str()).into_iter().map(|s| s.word).collect();
+        single.sort();
+        trie.sort();
+        assert_eq!(single, vec!["cat", "cats"]);
+        assert_eq!(single, trie);
+    }
+
+    #[test]
+    fn files() {
+        let list = decode(b"cat\ndog\n".to_vec()).unwrap();
+        assert_eq!((list.format, list.gzipped, &list.ranked), (FileFormat::WordList, false, &None));
+        assert_eq!(list.words().unwrap(), "cat\ndog\n");
+
+        let counts = decode(b"\ncat\t12\ndog\t 40\nCats\t12\n\n".to_vec()).unwrap();
+        assert_eq!(counts.format, FileFormat::Counts);
+        assert_eq!(counts.words().unwrap(), "cat\ndog\nCats\n");
+        assert_eq!(counts.ranked.as_deref(), Some("dog\ncat\nCats\n"));
+        for (raw, line) in [("cat\t1\ndog\n", 1), ("cat\tmany\n", 0), ("\t3\n", 0), ("a\t1\n\nb\t-1", 2)] {
+            assert!(matches!(decode(raw.as_bytes().to_vec()), Err(Error::InvalidCount { line: l }) if l == line), "{:?}", raw);
+        }
+
+        let gzipped = decode(include_bytes!("../test/words.gz").to_vec()).unwrap();
+        assert_eq!((gzipped.format, gzipped.gzipped), (FileFormat::WordList, true));
+        assert_eq!(Dictionary::parse(&gzipped.words().unwrap()).len(), 5_000);
+        assert!(matches!(decode(include_bytes!("../test/words.gz")[..100].to_vec()), Err(Error::CorruptGzip)));
+
+        let compiled = decode(crate::compiled::compile("cat\ndog\n")).unwrap();
+        assert_eq!(compiled.format, FileFormat::Compiled);
+        assert_eq!(compiled.words().unwrap(), "cat\ndog\n");
+    }
+}
@@ -0,0 +1,238 @@
+//! Two players' words for the same board, scored by the usual rule for
+//! duplicates: a word both players found is crossed off for both of them.
+//! [`Game::adjudicate`] does the same for any number of players.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::board::{Board, SolveOptions};
+use crate::solution::Solutions;
+
+/// Why a submitted word doesn't count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Rejection {
+    /// Shorter than the minimum length.
+    TooShort,
+    NotInDictionary,
+    /// In the dictionary, but it can't be traced on the board.
+    NotOnBoard,
+    /// In the dictionary and on the board, but ruled out by the options,
+    /// e.g. their rules or plural filter.
+    Disallowed,
+}
+
+/// How one player did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Player {
+    /// Valid words only this player found, with their scores, in the order
+    /// submitted.
+    pub scored: Vec<(String, u32)>,
+    /// Valid words both players found, which neither scores.
+    pub cancelled: Vec<String>,
+    pub rejected: Vec<(String, Rejection)>,
+    pub score: u32,
+}
+
+/// The result of [`judge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Duel<'a> {
+    pub players: [Player; 2],
+    /// The highest-scoring words on the board neither player found, highest
+    /// first, ties alphabetically.
+    pub missed: Solutions<'a>,
+}
+
+/// Scores two players' `submissions` for `board`: each word is checked
+/// against `words` and `options` as [`Board::solve`] would find it, then
+/// words both players found are cancelled. Words are trimmed and lowercased,
+/// and a player's repeats count once. Up to `missed` of the best words
+/// nobody found are kept.
+///
+/// ```
+/// use boggle::duel::{self, Rejection};
+/// use boggle::{Board, SolveOptions};
+///
+/// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+/// let duel = duel::judge(&board, "cat\ncats\ntax\n", &SolveOptions::default(), [&["cat", "cats"], &["cats", "dog"]], 5);
+/// assert_eq!(duel.players[0].score, 1);
+/// assert_eq!(duel.players[1].rejected, [("dog".to_string(), Rejection::NotInDictionary)]);
+/// assert_eq!(duel.missed[0].word, "tax");
+/// ```
+pub fn judge<'a>(board: &Board, words: &'a str, options: &SolveOptions, submissions: [&[&str]; 2], missed: usize) -> Duel<'a> {
+    let submissions = [PlayerSubmission { words: submissions[0] }, PlayerSubmission { words: submissions[1] }];
+    let Adjudication { players, missed: mut missed_words } = adjudicate(board, words, options, &submissions);
+    let mut players = players.into_iter();
+    missed_words.truncate(missed);
+    Duel { players: [players.next().unwrap_or_default(), players.next().unwrap_or_default()], missed: missed_words }
+}
+
+/// A dictionary and rules to judge any number of games with, e.g. every
+/// table of a tournament round.
+pub struct Game<'a> {
+    pub words: &'a str,
+    pub options: SolveOptions,
+}
+
+/// One player's words for a board, as submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerSubmission<'s> {
+    pub words: &'s [&'s str],
+}
+
+/// The result of [`Game::adjudicate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Adjudication<'a> {
+    /// How each player did, in the order they were submitted.
+    pub players: Vec<Player>,
+    /// Every word on the board no player found, highest-scoring first, ties
+    /// alphabetically.
+    pub missed: Solutions<'a>,
+}
+
+impl<'a> Game<'a> {
+    pub fn new(words: &'a str, options: SolveOptions) -> Game<'a> {
+        Game { words, options }
+    }
+
+    /// Scores every player's `submissions` for `board` at once, as
+    /// [`judge`] does for two: a word two or more players found is
+    /// cancelled for all of them. The board is solved once, and each
+    /// distinct word is checked once however many players submit it.
+    ///
+    /// ```
+    /// use boggle::duel::{Game, PlayerSubmission};
+    /// use boggle::{Board, SolveOptions};
+    ///
+    /// let game = Game::new("cat\ncats\ntax\n", SolveOptions::default());
+    /// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    /// let submissions = [&["cat", "cats"][..], &["cats"], &["tax"]].map(|words| PlayerSubmission { words });
+    /// let result = game.adjudicate(&board, &submissions);
+    /// assert_eq!(result.players.iter().map(|p| p.score).collect::<Vec<_>>(), [1, 0, 1]);
+    /// assert!(result.missed.is_empty());
+    /// ```
+    pub fn adjudicate(&self, board: &Board, submissions: &[PlayerSubmission]) -> Adjudication<'a> {
+        adjudicate(board, self.words, &self.options, submissions)
+    }
+}
+
+fn adjudicate<'a>(board: &Board, words: &'a str, options: &SolveOptions, submissions: &[PlayerSubmission]) -> Adjudication<'a> {
+    let solutions = board.solve(words, options);
+    let valid: HashMap<&str, u32> = solutions.iter().map(|s| (s.word, s.score)).collect();
+    let min_len = options.min_len.unwrap_or(3);
+    // only built if someone submits a word that isn't on the board
+    let mut dictionary: Option<HashSet<&str>> = None;
+    // each distinct word's verdict, so a word many players submit is only searched for once
+    let mut verdicts: HashMap<String, Result<u32, Rejection>> = HashMap::new();
+    let mut verdict = |word: &str| -> Result<u32, Rejection> {
+        if let Some(&verdict) = verdicts.get(word) {
+            return verdict;
+        }
+        let verdict = match valid.get(word) {
+            Some(&score) => Ok(score),
+            None if word.len() < min_len => Err(Rejection::TooShort),
+            None => {
+                let dictionary = dictionary.get_or_insert_with(|| words.lines().map(str::trim).collect());
+                if !dictionary.contains(word) {
+                    Err(Rejection::NotInDictionary)
+                } else if board.find_word(word).is_none() {
+                    Err(Rejection::NotOnBoard)
+                } else {
+                    Err(Rejection::Disallowed)
+                }
+            }
+        };
+        verdicts.insert(word.to_string(), verdict);
+        verdict
+    };
+
+    let submitted: Vec<Vec<(String, Result<u32, Rejection>)>> = submissions.iter()
+        .map(|submission| {
+            let mut seen = HashSet::new();
+            submission.words.iter()
+                .map(|w| w.trim().to_ascii_lowercase())
+                .filter(|w| !w.is_empty() && seen.insert(w.clone()))
+                .map(|word| {
+                    let verdict = verdict(&word);
+                    (word, verdict)
+                })
+                .collect()
+        })
+        .collect();
+    // how many players found each valid word
+    let mut finders: HashMap<&str, usize> = HashMap::new();
+    for (word, _) in submitted.iter().flatten().filter(|(_, verdict)| verdict.is_ok()) {
+        *finders.entry(word.as_str()).or_insert(0) += 1;
+    }
+
+    let players = submitted.iter()
+        .map(|verdicts| {
+            let mut player = Player::default();
+            for (word, verdict) in verdicts {
+                match *verdict {
+                    Ok(_) if finders[word.as_str()] > 1 => player.cancelled.push(word.clone()),
+                    Ok(score) => {
+                        player.scored.push((word.clone(), score));
+                        player.score += score;
+                    }
+                    Err(rejection) => player.rejected.push((word.clone(), rejection)),
+                }
+            }
+            player
+        })
+        .collect();
+
+    let mut missed = Solutions(solutions.into_iter().filter(|s| !finders.contains_key(s.word)).collect());
+    missed.sort_by_score();
+    Adjudication { players, missed }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::MinLength;
+
+    #[test]
+    fn judge() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "cat\ncats\ndog\ntax\n";
+        let first: &[&str] = &["cat", " CATS", "cat", "zzz", "ca", "dog"];
+        let duel = super::judge(&board, words, &SolveOptions::default(), [first, &["cats", "tax"]], 5);
+        let [ref one, ref two] = duel.players;
+        assert_eq!(one.scored, [("cat".to_string(), 1)]);
+        assert_eq!((one.score, &one.cancelled[..]), (1, &["cats".to_string()][..]));
+        assert_eq!(one.rejected, [
+            ("zzz".to_string(), Rejection::NotInDictionary),
+            ("ca".to_string(), Rejection::TooShort),
+            ("dog".to_string(), Rejection::NotOnBoard),
+        ]);
+        assert_eq!((two.score, &two.cancelled[..]), (1, &["cats".to_string()][..]));
+        assert!(duel.missed.is_empty());
+
+        let mut options = SolveOptions::default();
+        options.rules.push(MinLength(4));
+        let duel = super::judge(&board, words, &options, [&["cat"], &[]], 1);
+        assert_eq!(duel.players[0].rejected, [("cat".to_string(), Rejection::Disallowed)]);
+        assert_eq!(duel.missed.iter().map(|s| s.word).collect::<Vec<_>>(), ["cats"]);
+    }
+
+    #[test]
+    fn adjudicate() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let game = Game::new("cat\ncats\ndog\ntax\nats\n", SolveOptions::default());
+        let lists: [&[&str]; 4] = [&["cat", "cats", "dog"], &["Cat", "tax"], &["cat", "cats", "sat"], &[]];
+        let result = game.adjudicate(&board, &lists.map(|words| PlayerSubmission { words }));
+        assert_eq!(result.players.len(), 4);
+        let [ref one, ref two, ref three, ref four] = result.players[..] else { panic!() };
+        assert_eq!((one.score, &one.cancelled[..]), (0, &["cat".to_string(), "cats".to_string()][..]));
+        assert_eq!(one.rejected, [("dog".to_string(), Rejection::NotOnBoard)]);
+        assert_eq!((two.score, &two.scored[..]), (1, &[("tax".to_string(), 1)][..]));
+        assert_eq!(three.rejected, [("sat".to_string(), Rejection::NotInDictionary)]);
+        assert_eq!(*four, Player::default());
+        assert_eq!(result.missed.iter().map(|s| s.word).collect::<Vec<_>>(), ["ats"]);
+
+        assert!(game.adjudicate(&board, &[]).players.is_empty());
+    }
+}
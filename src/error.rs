@@ -3,13 +3,44 @@ use std::fmt;
 use std::io;
 use std::convert;
 
-const USAGE: &str = "USAGE: boggle dictionary board";
-
+/// Everything that can go wrong parsing a board or running the CLI.
 #[derive(Debug)]
 pub enum Error {
+    /// The command line couldn't be parsed; the CLI answers with its usage.
     Usage,
     Io(io::Error),
-    BoardSize(&'static str),
+    /// The board has no cells.
+    EmptyBoard,
+    /// Row `row` (0-based) has `got` cells, but a square board with this many
+    /// rows needs `expected`.
+    BoardRowLength { row: usize, expected: usize, got: usize },
+    /// A board cell that isn't a letter, at 0-based `(row, col)` counting
+    /// characters of the row as written.
+    InvalidCharacter { row: usize, col: usize, ch: char },
+    /// A cell of a space-separated row that's neither a letter nor one of the
+    /// [`Board::DIGRAPHS`](crate::Board::DIGRAPHS), at 0-based `(row, col)`
+    /// counting cells.
+    InvalidTile { row: usize, col: usize, tile: String },
+    /// Layer `layer` (0-based) of a [`Cube`](crate::cube::Cube) is `got`
+    /// cells wide, but a cube with this many layers needs `expected`.
+    CubeLayer { layer: usize, expected: usize, got: usize },
+    /// A board written as JSON that isn't JSON, failing at byte `offset`.
+    InvalidJson { offset: usize },
+    /// A board written as JSON that isn't shaped like one: see
+    /// [`Format::Json`](crate::board::Format::Json).
+    NotABoard,
+    /// A compiled dictionary that's truncated, from another version of the
+    /// format, or not one at all.
+    CorruptDictionary,
+    /// A gzipped dictionary that's truncated or corrupt.
+    CorruptGzip,
+    /// Line `line` (0-based) of a word list with counts has no word, or no
+    /// count after its tab.
+    InvalidCount { line: usize },
+    /// Encoded answers that are truncated, or for another board or dictionary.
+    CorruptAnswers,
+    /// `boggle selftest` or `--verify` found a solver giving wrong answers.
+    SelfTest(String),
 }
 
 impl convert::From<io::Error> for Error {
@@ -20,26 +51,48 @@ impl convert::From<io::Error> for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", error::Error::description(self))
-    }
-}
-
-impl error::Error for Error {
-    fn description(&self) -> &str {
         use Error::*;
         match *self {
-            Usage => USAGE,
-            Io(ref err) => err.description(),
-            BoardSize(ref err) => err,
+            Usage => write!(f, "invalid command line arguments"),
+            Io(ref err) => write!(f, "{}", err),
+            EmptyBoard => write!(f, "empty board"),
+            BoardRowLength { row, expected, got } => {
+                write!(f, "row {} has {} cells, expected {} to match the number of rows", row, got, expected)
+            }
+            InvalidCharacter { row, col, ch } => write!(f, "invalid character {:?} in row {}, column {}", ch, row, col),
+            InvalidTile { row, col, ref tile } => write!(f, "unknown tile {:?} in row {}, column {}", tile, row, col),
+            CubeLayer { layer, expected, got } => {
+                write!(f, "layer {} is {} cells wide, expected {} to match the number of layers", layer, got, expected)
+            }
+            InvalidJson { offset } => write!(f, "invalid JSON at byte {}", offset),
+            NotABoard => write!(f, "JSON that isn't a board: expected an array of rows, each a string or an array of tiles"),
+            CorruptDictionary => write!(f, "corrupt compiled dictionary"),
+            CorruptGzip => write!(f, "corrupt gzip file"),
+            InvalidCount { line } => write!(f, "line {} of the word list has no word, or no count after its tab", line),
+            CorruptAnswers => write!(f, "corrupt answers, or for another board or dictionary"),
+            SelfTest(ref err) => write!(f, "self test failed: {}", err),
         }
     }
+}
 
-    fn cause(&self) -> Option<&dyn error::Error> {
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         use Error::*;
         match *self {
             Usage => None,
             Io(ref err) => Some(err),
-            BoardSize(_) => None,
+            EmptyBoard => None,
+            BoardRowLength { .. } => None,
+            InvalidCharacter { .. } => None,
+            InvalidTile { .. } => None,
+            CubeLayer { .. } => None,
+            InvalidJson { .. } => None,
+            NotABoard => None,
+            CorruptDictionary => None,
+            CorruptGzip => None,
+            InvalidCount { .. } => None,
+            CorruptAnswers => None,
+            SelfTest(_) => None,
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,197 @@
+//! Several dictionaries searched as one, e.g. a base list plus a slang pack,
+//! without merging them. The search walks every member at once, so a prefix
+//! stays alive while any member has it, and each word found can be traced
+//! back to the members it came from.
+
+use crate::board::Lexicon;
+use crate::compiled::{CompiledDictionary, Node};
+use crate::trie::Trie;
+
+/// The most dictionaries one federation can hold.
+pub const MAX_MEMBERS: usize = 8;
+
+/// One member of a [`Federation`].
+#[derive(Debug, Clone, Copy)]
+pub enum Index<'t, 'a> {
+    Trie(&'t Trie<'a>),
+    Compiled(CompiledDictionary<'a>),
+}
+
+impl<'t, 'a> From<&'t Trie<'a>> for Index<'t, 'a> {
+    fn from(trie: &'t Trie<'a>) -> Self {
+        Index::Trie(trie)
+    }
+}
+
+impl<'t, 'a> From<CompiledDictionary<'a>> for Index<'t, 'a> {
+    fn from(dictionary: CompiledDictionary<'a>) -> Self {
+        Index::Compiled(dictionary)
+    }
+}
+
+// where a search is in one member
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum MemberNode {
+    Trie(usize),
+    Compiled(Node),
+}
+
+impl<'t, 'a> Index<'t, 'a> {
+    fn root(&self) -> MemberNode {
+        match *self {
+            Index::Trie(_) => MemberNode::Trie(Trie::ROOT),
+            Index::Compiled(ref dictionary) => MemberNode::Compiled(Lexicon::root(dictionary)),
+        }
+    }
+
+    fn children(&self, node: MemberNode) -> u32 {
+        match (*self, node) {
+            (Index::Trie(trie), MemberNode::Trie(node)) => trie.node(node).children,
+            (Index::Compiled(_), MemberNode::Compiled(node)) => node.children,
+            _ => unreachable!("a member's nodes come from that member"),
+        }
+    }
+
+    fn step(&self, node: MemberNode, letters: &[u8]) -> Option<MemberNode> {
+        match (*self, node) {
+            (Index::Trie(trie), MemberNode::Trie(node)) => Lexicon::step(trie, node, letters).map(MemberNode::Trie),
+            (Index::Compiled(ref dictionary), MemberNode::Compiled(node)) => {
+                Lexicon::step(dictionary, node, letters).map(MemberNode::Compiled)
+            }
+            _ => unreachable!("a member's nodes come from that member"),
+        }
+    }
+
+    fn word(&self, node: MemberNode, len: usize) -> Option<&'a str> {
+        match (*self, node) {
+            (Index::Trie(trie), MemberNode::Trie(node)) => Lexicon::word(trie, node, len),
+            (Index::Compiled(ref dictionary), MemberNode::Compiled(node)) => Lexicon::word(dictionary, node, len),
+            _ => unreachable!("a member's nodes come from that member"),
+        }
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.step(self.root(), word.as_bytes()).and_then(|node| self.word(node, word.len())).is_some()
+    }
+}
+
+/// Named dictionaries for
+/// [`Board::solve_federated`](crate::Board::solve_federated), first added
+/// first.
+#[derive(Debug, Clone, Default)]
+pub struct Federation<'t, 'a> {
+    members: Vec<(String, Index<'t, 'a>)>,
+}
+
+// where a search is in each member, `None` once the prefix has left a member behind
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FederatedNode([Option<MemberNode>; MAX_MEMBERS]);
+
+impl<'t, 'a> Federation<'t, 'a> {
+    pub fn new() -> Federation<'t, 'a> {
+        Federation::default()
+    }
+
+    /// Adds a member called `name`.
+    ///
+    /// # Panics
+    ///
+    /// If the federation already has [`MAX_MEMBERS`].
+    pub fn add<I: Into<Index<'t, 'a>>>(&mut self, name: &str, index: I) {
+        assert!(self.members.len() < MAX_MEMBERS, "a federation holds at most {} dictionaries", MAX_MEMBERS);
+        self.members.push((name.to_string(), index.into()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// The names of the members with `word`, in the order they were added.
+    pub fn sources(&self, word: &str) -> Vec<&str> {
+        self.members.iter().filter(|(_, index)| index.contains(word)).map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+impl<'t, 'a> Lexicon<'a> for Federation<'t, 'a> {
+    type Node = FederatedNode;
+
+    fn root(&self) -> FederatedNode {
+        let mut nodes = [None; MAX_MEMBERS];
+        for (node, (_, index)) in nodes.iter_mut().zip(&self.members) {
+            *node = Some(index.root());
+        }
+        FederatedNode(nodes)
+    }
+
+    fn children(&self, node: FederatedNode) -> u32 {
+        self.members.iter().zip(&node.0)
+            .filter_map(|((_, index), node)| node.map(|node| index.children(node)))
+            .fold(0, |children, c| children | c)
+    }
+
+    fn step(&self, node: FederatedNode, letters: &[u8]) -> Option<FederatedNode> {
+        let mut next = [None; MAX_MEMBERS];
+        for ((next, (_, index)), node) in next.iter_mut().zip(&self.members).zip(&node.0) {
+            *next = node.and_then(|node| index.step(node, letters));
+        }
+        if next.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(FederatedNode(next))
+    }
+
+    // the first member's copy, so the search sees each word once however many members have it
+    fn word(&self, node: FederatedNode, len: usize) -> Option<&'a str> {
+        self.members.iter().zip(&node.0)
+            .find_map(|((_, index), node)| node.and_then(|node| index.word(node, len)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compiled::{self, CompiledDictionary};
+    use crate::{Board, SolveOptions};
+
+    #[test]
+    fn union() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let base = Trie::from_dictionary("cat\ndog\n");
+        let bytes = compiled::compile("cats\ncat\ntax\n");
+        let mut federation = Federation::new();
+        federation.add("base", &base);
+        federation.add("slang", CompiledDictionary::from_bytes(&bytes).unwrap());
+
+        let mut words: Vec<_> = board.solve_federated(&federation, &SolveOptions::default()).into_iter().map(|s| s.word).collect();
+        words.sort_unstable();
+        assert_eq!(words, ["cat", "cats", "tax"]);
+        assert_eq!(federation.sources("cat"), ["base", "slang"]);
+        assert_eq!(federation.sources("tax"), ["slang"]);
+        assert!(federation.sources("ca").is_empty());
+    }
+
+    #[test]
+    fn matches_merged() {
+        let words = include_str!("../test/dictionary");
+        let (evens, odds): (Vec<_>, Vec<_>) = words.lines().enumerate().partition(|(i, _)| i % 2 == 0);
+        let join = |half: Vec<(usize, &str)>| half.into_iter().map(|(_, w)| format!("{}\n", w)).collect::<String>();
+        let (evens, odds) = (join(evens), join(odds));
+        let (evens, odds) = (Trie::from_dictionary(&evens), Trie::from_dictionary(&odds));
+        let mut federation = Federation::new();
+        federation.add("evens", &evens);
+        federation.add("odds", &odds);
+
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let sorted = |solutions: Vec<crate::Solution>| {
+            let mut words: Vec<_> = solutions.into_iter().map(|s| (s.word.to_string(), s.score)).collect();
+            words.sort_unstable();
+            words
+        };
+        assert_eq!(sorted(board.solve_federated(&federation, &SolveOptions::default())),
+                   sorted(board.solve(words, &SolveOptions::default())));
+    }
+}
@@ -0,0 +1,109 @@
+//! Which of a board's words a person could plausibly spot, as opposed to
+//! every word a solver can dig out of the dictionary. Holding players to the
+//! findable words, rather than all of them, gives a fairer idea of how well
+//! they did. [`Plausible`] is the bundled guess; others plug in by
+//! implementing [`Findable`].
+
+use crate::board::Board;
+use crate::rules::{CommonWords, GuessRule};
+use crate::solution::{Solution, Solutions};
+
+/// Judges whether a person could be expected to find a word.
+pub trait Findable {
+    fn findable(&self, solution: &Solution, board: &Board) -> bool;
+}
+
+/// Findable if the word's path doesn't wind about too much and, with a
+/// frequency list, the word is common enough to come to mind.
+///
+/// A path turns wherever it changes direction, and turns sharply where it
+/// doubles back by more than a right angle. A word found without its path
+/// is judged on its frequency alone.
+#[derive(Debug, Clone)]
+pub struct Plausible {
+    pub max_turns: usize,
+    pub max_sharp_turns: usize,
+    /// Only these words are findable; `None` allows any word.
+    pub common: Option<CommonWords>,
+}
+
+impl Default for Plausible {
+    /// At most 3 turns, 1 of them sharp, and any word.
+    fn default() -> Self {
+        Plausible { max_turns: 3, max_sharp_turns: 1, common: None }
+    }
+}
+
+impl Findable for Plausible {
+    fn findable(&self, solution: &Solution, board: &Board) -> bool {
+        let (turns, sharp) = turns(&solution.path, board);
+        turns <= self.max_turns
+            && sharp <= self.max_sharp_turns
+            && self.common.as_ref().is_none_or(|common| common.allows(solution.word.as_bytes(), board))
+    }
+}
+
+impl<F: Fn(&Solution, &Board) -> bool> Findable for F {
+    fn findable(&self, solution: &Solution, board: &Board) -> bool {
+        self(solution, board)
+    }
+}
+
+impl<'a> Solutions<'a> {
+    /// The words `judge` thinks are findable on `board`, in the same order.
+    pub fn findable<F: Findable + ?Sized>(&self, board: &Board, judge: &F) -> Solutions<'a> {
+        Solutions(self.iter().filter(|s| judge.findable(s, board)).cloned().collect())
+    }
+}
+
+// how many times `path` changes direction, and how many of those double back
+fn turns(path: &[(usize, usize)], board: &Board) -> (usize, usize) {
+    let wrap = board.topology().wrap;
+    let axis = |from: usize, to: usize| {
+        let d = to as isize - from as isize;
+        // a step across a wrapped edge goes one cell the other way
+        if wrap && d.abs() > 1 { -d.signum() } else { d }
+    };
+    let steps: Vec<_> = path.windows(2).map(|w| (axis(w[0].0, w[1].0), axis(w[0].1, w[1].1))).collect();
+    steps.windows(2)
+        .filter(|w| w[0] != w[1])
+        .fold((0, 0), |(turns, sharp), w| {
+            let dot = w[0].0 * w[1].0 + w[0].1 * w[1].1;
+            (turns + 1, sharp + (dot < 0) as usize)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Topology;
+
+    #[test]
+    fn plausible() {
+        let board = Board::parse("abcd\nefgh\nijkl\nmnop").unwrap();
+        let solution = |word, path: &[(usize, usize)]| Solution { word, score: 1, path: path.to_vec() };
+        let straight = solution("abcd", &[(0, 0), (0, 1), (0, 2), (0, 3)]);
+        let corner = solution("abcgk", &[(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+        // right, down-left, right, up-right, right: doubling back twice
+        let zigzag = solution("abefcd", &[(0, 0), (0, 1), (1, 0), (1, 1), (0, 2), (0, 3)]);
+        let winding = solution("abfgkl", &[(0, 0), (0, 1), (1, 1), (1, 2), (2, 2), (2, 3)]);
+        assert_eq!(turns(&straight.path, &board), (0, 0));
+        assert_eq!(turns(&corner.path, &board), (1, 0));
+        assert_eq!(turns(&zigzag.path, &board), (4, 2));
+        assert_eq!(turns(&winding.path, &board), (4, 0));
+
+        let solutions = Solutions(vec![straight, corner, zigzag, winding]);
+        let judge = Plausible::default();
+        let words = |s: Solutions<'static>| s.iter().map(|s| s.word).collect::<Vec<_>>();
+        assert_eq!(words(solutions.findable(&board, &judge)), ["abcd", "abcgk"]);
+
+        let judge = Plausible { common: Some(CommonWords::new("abcgk\nabefcd\n", 2)), ..Plausible::default() };
+        assert_eq!(words(solutions.findable(&board, &judge)), ["abcgk"]);
+        let short = |s: &Solution, _: &Board| s.len() <= 4;
+        assert_eq!(words(solutions.findable(&board, &short)), ["abcd"]);
+
+        // across the wrapped edge, d then a keeps going right
+        let board = board.with_topology(Topology { diagonals: true, wrap: true });
+        assert_eq!(turns(&[(0, 2), (0, 3), (0, 0), (0, 1)], &board), (0, 0));
+    }
+}
@@ -0,0 +1,191 @@
+//! A search specialized for one board size, which [`Board`]'s solvers switch
+//! to on their own for the standard 4x4 and 5x5 boards. Knowing the size up
+//! front lets the neighbor table be worked out at compile time, keeps the
+//! cells visited in a `u64` updated in place, and puts the whole search
+//! state on the stack.
+
+use std::str;
+
+use crate::board::{Board, Topology, DIRECTIONS};
+use crate::trie::{letter_bit, Seen, Trie};
+
+// the most cells a `u64` visited mask covers, an 8x8 board
+const MAX_CELLS: usize = 64;
+
+// the cells next to each cell of an n by n board, in `DIRECTIONS` order, and how many there are
+const fn neighbor_table(n: usize) -> ([[u8; 8]; MAX_CELLS], [u8; MAX_CELLS]) {
+    let mut cells = [[0; 8]; MAX_CELLS];
+    let mut lens = [0; MAX_CELLS];
+    let mut cell = 0;
+    while cell < n * n {
+        let (x, y) = ((cell / n) as isize, (cell % n) as isize);
+        let mut d = 0;
+        while d < DIRECTIONS.len() {
+            let (i, j) = (x + DIRECTIONS[d].0, y + DIRECTIONS[d].1);
+            if i >= 0 && j >= 0 && i < n as isize && j < n as isize {
+                cells[cell][lens[cell] as usize] = (i as usize * n + j as usize) as u8;
+                lens[cell] += 1;
+            }
+            d += 1;
+        }
+        cell += 1;
+    }
+    (cells, lens)
+}
+
+/// An `N` by `N` board with classic adjacency, for up to 8x8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedBoard<const N: usize> {
+    tiles: [[&'static [u8]; N]; N],
+    // the letters of each cell's neighbors, as a `TrieNode::children`-style mask
+    neighbor_letters: [[u32; N]; N],
+}
+
+impl<const N: usize> FixedBoard<N> {
+    const CELLS: usize = {
+        assert!(N > 0 && N * N <= MAX_CELLS, "FixedBoard is for boards of 1x1 to 8x8");
+        N * N
+    };
+    const NEIGHBORS: ([[u8; 8]; MAX_CELLS], [u8; MAX_CELLS]) = neighbor_table(N);
+
+    /// A copy of `board`, or `None` if it isn't `N` by `N` or its cells
+    /// aren't adjacent the classic way.
+    pub fn new(board: &Board) -> Option<FixedBoard<N>> {
+        if board.len() != N || board.topology() != Topology::default() {
+            return None;
+        }
+        let mut tiles = [[&[][..]; N]; N];
+        for (x, row) in tiles.iter_mut().enumerate() {
+            for (y, tile) in row.iter_mut().enumerate() {
+                *tile = board.tile((x, y));
+            }
+        }
+        let mut neighbor_letters = [[0; N]; N];
+        for cell in 0..Self::CELLS {
+            let (cells, lens) = &Self::NEIGHBORS;
+            for &next in &cells[cell][..lens[cell] as usize] {
+                neighbor_letters[cell / N][cell % N] |= letter_bit(tiles[next as usize / N][next as usize % N][0]);
+            }
+        }
+        Some(FixedBoard { tiles, neighbor_letters })
+    }
+
+    fn tile(&self, cell: usize) -> &'static [u8] {
+        self.tiles[cell / N][cell % N]
+    }
+
+    /// Calls `found` once for each word in `trie` on the board, with the
+    /// `(row, col)` cells of the first path it was found along.
+    pub fn search<'a, F>(&self, trie: &Trie<'a>, found: F)
+        where F: FnMut(&'a str, &[(usize, usize)])
+    {
+        self.search_limited(trie, usize::MAX, !0, found);
+    }
+
+    // as `search`, skipping words longer than `max_len` and starting only from cells whose first letter is
+    // in the `first_letters` mask; finds the same words along the same paths as `Board::search_trie`, and
+    // returns how many cells it stepped into, counted as that does
+    pub(crate) fn search_limited<'a, F>(&self, trie: &Trie<'a>, max_len: usize, first_letters: u32, mut found: F) -> usize
+        where F: FnMut(&'a str, &[(usize, usize)])
+    {
+        self.search_while(trie, max_len, first_letters, |word, path| {
+            found(word, path);
+            true
+        })
+    }
+
+    // as `search_limited`, but stopping as soon as `found` returns false
+    pub(crate) fn search_while<'a, F>(&self, trie: &Trie<'a>, max_len: usize, first_letters: u32, mut found: F) -> usize
+        where F: FnMut(&'a str, &[(usize, usize)]) -> bool
+    {
+        #[derive(Clone, Copy)]
+        struct Frame {
+            cell: u8,
+            node: usize,
+            // neighbors left to try, taken from the end of the table
+            left: u8,
+        }
+
+        let (neighbors, lens) = &Self::NEIGHBORS;
+        let mut seen = Seen::new(trie);
+        let mut stack = [Frame { cell: 0, node: 0, left: 0 }; MAX_CELLS];
+        let mut path = [(0, 0); MAX_CELLS];
+        let mut expanded = 0;
+
+        for start in 0..Self::CELLS {
+            let tile = self.tile(start);
+            if first_letters & letter_bit(tile[0]) == 0 {
+                continue;
+            }
+            let node = match trie.step(Trie::ROOT, tile) {
+                Some(node) => node,
+                None => continue,
+            };
+
+            expanded += 1;
+            let mut depth = 0;
+            let mut visited = 1u64 << start;
+            stack[0] = Frame { cell: start as u8, node, left: lens[start] };
+            path[0] = (start / N, start % N);
+            if trie.node(node).is_word() && seen.insert(node) && !found(unsafe { str::from_utf8_unchecked(trie.node(node).word) }, &path[..1]) {
+                return expanded;
+            }
+
+            loop {
+                let frame = &mut stack[depth];
+                if frame.left == 0 {
+                    visited &= !(1 << frame.cell);
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                    continue;
+                }
+                // the general search pops its stack, so it tries the last neighbor first
+                frame.left -= 1;
+                let cell = neighbors[frame.cell as usize][frame.left as usize] as usize;
+                if visited & 1 << cell != 0 {
+                    continue;
+                }
+                let next = match trie.step(frame.node, self.tile(cell)) {
+                    Some(next) => next,
+                    None => continue,
+                };
+                let node = trie.node(next);
+                if node.word.len() > max_len || !node.is_word() && node.children & self.neighbor_letters[cell / N][cell % N] == 0 {
+                    continue;
+                }
+
+                expanded += 1;
+                depth += 1;
+                visited |= 1 << cell;
+                stack[depth] = Frame { cell: cell as u8, node: next, left: lens[cell] };
+                path[depth] = (cell / N, cell % N);
+                if node.is_word() && seen.insert(next) && !found(unsafe { str::from_utf8_unchecked(node.word) }, &path[..=depth]) {
+                    return expanded;
+                }
+            }
+        }
+        expanded
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn neighbor_table() {
+        let (cells, lens) = super::neighbor_table(4);
+        assert_eq!(&lens[..16], &[3, 5, 5, 3, 5, 8, 8, 5, 5, 8, 8, 5, 3, 5, 5, 3]);
+        assert_eq!(&cells[0][..3], &[4, 5, 1]);
+        assert_eq!(&cells[5][..8], &[9, 10, 6, 2, 1, 0, 4, 8]);
+    }
+
+    #[test]
+    fn wrong_size_or_topology() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        assert!(FixedBoard::<5>::new(&board).is_none());
+        assert!(FixedBoard::<4>::new(&board.with_topology(Topology { wrap: true, ..Topology::default() })).is_none());
+    }
+}
@@ -0,0 +1,320 @@
+//! Boards drawn letter by letter from a frequency table instead of rolled
+//! from dice, and boards of either kind held to a target number of words by
+//! drawing again until one lands in range.
+
+use std::ops::RangeInclusive;
+
+use crate::board::{Board, Goal, SolveOptions};
+use crate::rng::RngSource;
+use crate::score::Scorer;
+use crate::trie::Trie;
+
+/// How often each of `a..=z` turns up in English text, in hundredths of a
+/// percent.
+pub const ENGLISH: [u32; 26] = [
+    817, 149, 278, 425, 1270, 223, 202, 609, 697, 15, 77, 403, 241,
+    675, 751, 193, 10, 599, 633, 906, 276, 98, 236, 15, 197, 7,
+];
+
+/// Draws each cell's letter from `weights`, then swaps letters in or out
+/// until the number of vowel cells is within `vowels`. A `q` is the "Qu"
+/// tile; `y` counts as a consonant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Letters {
+    /// How likely each of `a..=z` is, relative to the others.
+    pub weights: [u32; 26],
+    pub vowels: RangeInclusive<usize>,
+}
+
+impl Default for Letters {
+    /// English frequencies and any number of vowels.
+    fn default() -> Self {
+        Letters { weights: ENGLISH, vowels: 0..=usize::MAX }
+    }
+}
+
+impl Letters {
+    /// A `size` by `size` board as text for [`Board::parse`].
+    ///
+    /// # Panics
+    ///
+    /// If `vowels` is empty, or the weights leave no letter to draw, or no
+    /// vowel or consonant to swap in when the vowels are out of range.
+    pub fn draw<R: RngSource>(&self, size: usize, rng: &mut R) -> String {
+        assert!(!self.vowels.is_empty(), "no number of vowels is allowed");
+        let any = |_: u8| true;
+        let mut cells: Vec<u8> = (0..size * size).map(|_| self.pick(rng, any)).collect();
+        // each swap moves the count one closer, and swapped-in letters are never swapped back out
+        loop {
+            let count = cells.iter().filter(|&&c| is_vowel(c)).count();
+            let swap_in = if count < *self.vowels.start() {
+                is_vowel
+            } else if count > *self.vowels.end() {
+                is_consonant
+            } else {
+                break;
+            };
+            let candidates: Vec<_> = (0..cells.len()).filter(|&i| !swap_in(cells[i])).collect();
+            if candidates.is_empty() {
+                break;
+            }
+            cells[candidates[rng.below(candidates.len())]] = self.pick(rng, swap_in);
+        }
+
+        let mut board = String::with_capacity(cells.len() * 2);
+        for row in cells.chunks(size.max(1)) {
+            for &c in row {
+                match c {
+                    b'q' => board.push_str("qu"),
+                    c => board.push(c as char),
+                }
+            }
+            board.push('\n');
+        }
+        board
+    }
+
+    // one letter passing `keep`, drawn by weight
+    fn pick<R: RngSource, F: Fn(u8) -> bool>(&self, rng: &mut R, keep: F) -> u8 {
+        let weight = |i: usize| if keep(b'a' + i as u8) { self.weights[i] } else { 0 };
+        let total: u32 = (0..26).map(weight).sum();
+        assert!(total > 0, "no letter left to draw");
+        let mut roll = rng.below(total as usize) as u32;
+        for i in 0..26 {
+            if roll < weight(i) {
+                return b'a' + i as u8;
+            }
+            roll -= weight(i);
+        }
+        unreachable!("the roll is below the total weight")
+    }
+}
+
+fn is_vowel(c: u8) -> bool {
+    b"aeiou".contains(&c)
+}
+
+fn is_consonant(c: u8) -> bool {
+    !is_vowel(c)
+}
+
+/// How many words a generated board must have, and how many boards to try
+/// before giving up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub words: RangeInclusive<usize>,
+    /// Defaults to 1000.
+    pub attempts: usize,
+}
+
+impl Target {
+    pub fn new(words: RangeInclusive<usize>) -> Target {
+        Target { words, attempts: 1000 }
+    }
+
+    /// Calls `draw` for boards until one has an allowed number of words
+    /// from `trie`, or the attempts run out. `draw` can roll dice, e.g.
+    /// `|rng| dice::roll_with(&dice::CLASSIC, rng)`, or draw from
+    /// [`Letters`].
+    pub fn generate<R, F>(&self, trie: &Trie, rng: &mut R, draw: F) -> Option<Generated>
+        where R: RngSource,
+              F: FnMut(&mut R) -> String
+    {
+        self.generate_where(trie, rng, draw, |_| true)
+    }
+
+    /// Like [`generate`](Target::generate), but also draws again whenever a
+    /// word from `banned` can be traced on the board, so none can be read
+    /// along a path, let alone a row, column or diagonal. As with any trie,
+    /// banned words need at least three letters.
+    ///
+    /// ```
+    /// use boggle::generate::Target;
+    /// use boggle::rng::SplitMix64;
+    /// use boggle::Trie;
+    ///
+    /// let (words, banned) = (Trie::from_dictionary("cat\ntea"), Trie::from_dictionary("tac"));
+    /// let mut boards = ["cat\nxxx\nxxx\n", "tea\nxxx\nxxx\n"].iter().cycle();
+    /// let generated = Target::new(1..=1)
+    ///     .generate_avoiding(&words, &banned, &mut SplitMix64::new(1), |_| boards.next().unwrap().to_string())
+    ///     .unwrap();
+    /// // "tac" reads backwards along the first board's top row
+    /// assert_eq!((generated.board.as_str(), generated.attempts), ("tea\nxxx\nxxx\n", 2));
+    /// ```
+    pub fn generate_avoiding<R, F>(&self, trie: &Trie, banned: &Trie, rng: &mut R, draw: F) -> Option<Generated>
+        where R: RngSource,
+              F: FnMut(&mut R) -> String
+    {
+        // a point a word, so the search stops at the first banned one
+        let options = SolveOptions { scorer: Box::new(EachWord), ..SolveOptions::low_power() };
+        self.generate_where(trie, rng, draw, |board| {
+            board.solve_until(banned, Goal::StopAtScore(1), &options).is_empty()
+        })
+    }
+
+    /// Like [`generate`](Target::generate), but also draws again until every
+    /// word of `theme` can be traced on the board, checked with
+    /// [`Board::find_word_guided`] so long theme words on big boards don't
+    /// hold up each attempt. Theme words are in lowercase, with "qu" for a
+    /// "Qu" tile.
+    ///
+    /// ```
+    /// use boggle::generate::Target;
+    /// use boggle::rng::SplitMix64;
+    /// use boggle::Trie;
+    ///
+    /// let words = Trie::from_dictionary("cat\ntea");
+    /// let mut boards = ["cat\nxxx\nxxx\n", "tea\nxxx\nxxx\n"].iter().cycle();
+    /// let generated = Target::new(1..=1)
+    ///     .generate_containing(&words, &["tea"], &mut SplitMix64::new(1), |_| boards.next().unwrap().to_string())
+    ///     .unwrap();
+    /// assert_eq!((generated.board.as_str(), generated.attempts), ("tea\nxxx\nxxx\n", 2));
+    /// ```
+    pub fn generate_containing<R, F>(&self, trie: &Trie, theme: &[&str], rng: &mut R, draw: F) -> Option<Generated>
+        where R: RngSource,
+              F: FnMut(&mut R) -> String
+    {
+        self.generate_where(trie, rng, draw, |board| theme.iter().all(|word| board.find_word_guided(word).is_some()))
+    }
+
+    // draws boards until one `keep` accepts has an allowed number of words
+    fn generate_where<R, F, K>(&self, trie: &Trie, rng: &mut R, mut draw: F, mut keep: K) -> Option<Generated>
+        where R: RngSource,
+              F: FnMut(&mut R) -> String,
+              K: FnMut(&Board) -> bool
+    {
+        // each attempt solves one small board, so threads would cost more than they save; a point a word
+        // lets the search stop as soon as a board has too many
+        let options = SolveOptions { scorer: Box::new(EachWord), ..SolveOptions::low_power() };
+        let too_many = Goal::StopAtScore(self.words.end().saturating_add(1).min(u32::MAX as usize) as u32);
+        for attempt in 1..=self.attempts {
+            let text = draw(rng);
+            let board = Board::parse(&text).expect("generated boards are letters");
+            if !keep(&board) {
+                debug!("attempt {}: turned down", attempt);
+                continue;
+            }
+            let words = board.solve_until(trie, too_many, &options).len();
+            debug!("attempt {}: {} words", attempt, words);
+            if self.words.contains(&words) {
+                return Some(Generated { board: text, words, attempts: attempt });
+            }
+        }
+        None
+    }
+}
+
+// scores words for counting them
+struct EachWord;
+
+impl Scorer for EachWord {
+    fn score(&self, _word: &[u8], _path: &[(usize, usize)], _board: &Board) -> u32 {
+        1
+    }
+
+    fn max_points(&self, _len: usize) -> Option<u32> {
+        Some(1)
+    }
+}
+
+/// A board [`Target::generate`] settled on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    /// The board as text for [`Board::parse`].
+    pub board: String,
+    pub words: usize,
+    /// How many boards were drawn, this one included.
+    pub attempts: usize,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dice::{self, CLASSIC};
+    use crate::rng::SplitMix64;
+
+    const DICTIONARY: &str = include_str!("../test/dictionary");
+
+    #[test]
+    fn letters() {
+        let letters = Letters { vowels: 5..=6, ..Letters::default() };
+        for seed in 0..50 {
+            let raw = letters.draw(4, &mut SplitMix64::new(seed));
+            assert_eq!(raw, letters.draw(4, &mut SplitMix64::new(seed)));
+            let board = Board::parse(&raw).unwrap();
+            assert_eq!(board.len(), 4);
+            let vowels = raw.bytes().filter(|&c| is_vowel(c)).count() - raw.matches("qu").count();
+            assert!(letters.vowels.contains(&vowels), "{}", raw);
+        }
+
+        // only e and t to draw from
+        let mut weights = [0; 26];
+        weights[4] = 1;
+        weights[19] = 1;
+        let all_vowels = Letters { weights, vowels: 9..=9 }.draw(3, &mut SplitMix64::new(1));
+        assert_eq!(all_vowels.matches('e').count(), 9);
+        let none = Letters { weights, vowels: 0..=0 }.draw(3, &mut SplitMix64::new(1));
+        assert_eq!(none, "ttt\nttt\nttt\n");
+    }
+
+    #[test]
+    fn target() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let letters = Letters::default();
+        let target = Target::new(20..=40);
+        let generated = target.generate(&trie, &mut SplitMix64::new(3), |rng| letters.draw(4, rng)).unwrap();
+        assert!(target.words.contains(&generated.words));
+        let board = Board::parse(&generated.board).unwrap();
+        assert_eq!(board.solve_with_trie(&trie, &SolveOptions::default()).len(), generated.words);
+
+        let rolled = target.generate(&trie, &mut SplitMix64::new(3), |rng| dice::roll_with(&CLASSIC, rng)).unwrap();
+        assert!(dice::can_roll(&CLASSIC, &Board::parse(&rolled.board).unwrap()));
+
+        let impossible = Target { words: 10_000..=10_000, attempts: 5 };
+        assert_eq!(impossible.generate(&trie, &mut SplitMix64::new(3), |rng| letters.draw(4, rng)), None);
+    }
+
+    #[test]
+    fn banned() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let target = Target::new(20..=40);
+        let first = target.generate(&trie, &mut SplitMix64::new(3), |rng| dice::roll_with(&CLASSIC, rng)).unwrap();
+        let board = Board::parse(&first.board).unwrap();
+        // ban a word on the board the target settled on, and one read straight along its top row backwards
+        let mut on_board: Vec<_> = board.solve_with_trie(&trie, &SolveOptions::default()).into_iter().map(|s| s.word.to_string()).collect();
+        on_board.truncate(1);
+        on_board.push(board.tile((0, 2)).iter().chain(board.tile((0, 1))).chain(board.tile((0, 0))).map(|&c| c as char).collect());
+        let banned_words = on_board.join("\n");
+        let banned = Trie::from_dictionary(&banned_words);
+
+        let generated = target.generate_avoiding(&trie, &banned, &mut SplitMix64::new(3), |rng| dice::roll_with(&CLASSIC, rng)).unwrap();
+        assert_ne!(generated.board, first.board);
+        assert!(generated.attempts > first.attempts && target.words.contains(&generated.words));
+        let board = Board::parse(&generated.board).unwrap();
+        assert!(on_board.iter().all(|word| !board.can_contain(word)), "{:?} on {}", on_board, generated.board);
+        assert!(crate::submission::review(&board, &crate::submission::Checks { banned: &banned_words, ..Default::default() }).banned.is_empty());
+
+        // nothing but banned boards to draw
+        let always = Target { words: 0..=usize::MAX, attempts: 5 };
+        assert_eq!(always.generate_avoiding(&trie, &banned, &mut SplitMix64::new(3), |_| first.board.clone()), None);
+    }
+
+    #[test]
+    fn theme() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let target = Target::new(0..=usize::MAX);
+        let roll = |rng: &mut SplitMix64| dice::roll_with(&CLASSIC, rng);
+        let first = target.generate(&trie, &mut SplitMix64::new(5), roll).unwrap();
+        assert_eq!(first.attempts, 1);
+        // a word the first board doesn't have, but some later one does
+        let mut rng = SplitMix64::new(5);
+        let theme = (0..1000).map(|_| Board::parse(&roll(&mut rng)).unwrap())
+            .flat_map(|board| board.solve_with_trie(&trie, &SolveOptions::default()).into_iter().map(|s| s.word).collect::<Vec<_>>())
+            .find(|word| !Board::parse(&first.board).unwrap().can_contain(word))
+            .unwrap();
+        let generated = target.generate_containing(&trie, &[theme], &mut SplitMix64::new(5), roll).unwrap();
+        assert!(generated.attempts > 1);
+        assert!(Board::parse(&generated.board).unwrap().can_contain(theme));
+        assert_eq!(target.generate_containing(&trie, &[], &mut SplitMix64::new(5), roll), Some(first));
+    }
+}
@@ -0,0 +1,319 @@
+//! Just enough of gzip to read compressed word lists, so the library needn't
+//! depend on a decompressor: RFC 1952's container around RFC 1951's DEFLATE.
+
+use crate::error::Error;
+
+// the first two bytes of every gzip member
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+// header flags, after which come the optional fields in this order
+const FHCRC: u8 = 2;
+const FEXTRA: u8 = 4;
+const FNAME: u8 = 8;
+const FCOMMENT: u8 = 16;
+
+// the shortest and longest match each length code stands for, before its extra bits
+const LENGTH_BASE: [u16; 29] =
+    [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145,
+    8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+// the order a dynamic block lists the code lengths of its code length code in
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &b| CRC_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Whether `raw` starts like a gzip file.
+pub(crate) fn is_gzip(raw: &[u8]) -> bool {
+    raw.starts_with(&MAGIC)
+}
+
+/// Decompresses every member of `raw` in turn, as `gunzip` does, checking
+/// each one's length and checksum.
+pub(crate) fn decompress(raw: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::with_capacity(raw.len() * 4);
+    let mut at = 0;
+    while at < raw.len() {
+        at = member(raw, at, &mut out).ok_or(Error::CorruptGzip)?;
+    }
+    Ok(out)
+}
+
+// decompresses the member starting at `at` onto `out`, returning where the next one starts
+fn member(raw: &[u8], at: usize, out: &mut Vec<u8>) -> Option<usize> {
+    let header = raw.get(at..at + 10)?;
+    if header[..2] != MAGIC || header[2] != 8 {
+        return None;
+    }
+    let flags = header[3];
+    let mut at = at + 10;
+    if flags & FEXTRA != 0 {
+        let len = u16::from_le_bytes([*raw.get(at)?, *raw.get(at + 1)?]) as usize;
+        at += 2 + len;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            at += raw.get(at..)?.iter().position(|&b| b == 0)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        at += 2;
+    }
+
+    let start = out.len();
+    let mut bits = Bits { bytes: raw.get(at..)?, at: 0, bit: 0 };
+    inflate(&mut bits, out)?;
+    let at = at + bits.at + (bits.bit > 0) as usize;
+    let trailer = raw.get(at..at + 8)?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let len = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc32(&out[start..]) != crc || (out.len() - start) as u32 != len {
+        return None;
+    }
+    Some(at + 8)
+}
+
+// reads bits least significant first, as DEFLATE packs them
+struct Bits<'a> {
+    bytes: &'a [u8],
+    at: usize,
+    bit: u8,
+}
+
+impl<'a> Bits<'a> {
+    fn bit(&mut self) -> Option<u16> {
+        let bit = (*self.bytes.get(self.at)? >> self.bit) & 1;
+        self.bit += 1;
+        if self.bit == 8 {
+            self.bit = 0;
+            self.at += 1;
+        }
+        Some(bit as u16)
+    }
+
+    fn bits(&mut self, n: u8) -> Option<usize> {
+        let mut value = 0;
+        for i in 0..n {
+            value |= (self.bit()? as usize) << i;
+        }
+        Some(value)
+    }
+
+    // skips to the next byte boundary, for a stored block
+    fn align(&mut self) {
+        if self.bit > 0 {
+            self.bit = 0;
+            self.at += 1;
+        }
+    }
+}
+
+// a canonical Huffman code, as how many codes there are of each length and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    // the code giving each symbol the length it has in `lengths`, 0 for none; `None` if there are more codes
+    // of some length than fit, though an incomplete code is fine
+    fn new(lengths: &[u8]) -> Option<Huffman> {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = (left << 1) - count as i32;
+            if left < 0 {
+                return None;
+            }
+        }
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate().filter(|&(_, &len)| len != 0) {
+            symbols[offsets[len as usize] as usize] = symbol as u16;
+            offsets[len as usize] += 1;
+        }
+        counts[0] = 0;
+        Some(Huffman { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Option<u16> {
+        // the first code of each length, and where its symbols start
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for &count in &self.counts[1..] {
+            code |= bits.bit()? as i32;
+            let count = count as i32;
+            if code - count < first {
+                return self.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+}
+
+fn inflate(bits: &mut Bits, out: &mut Vec<u8>) -> Option<()> {
+    loop {
+        let last = bits.bit()? == 1;
+        match bits.bits(2)? {
+            0 => {
+                bits.align();
+                let header = bits.bytes.get(bits.at..bits.at + 4)?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if len != !u16::from_le_bytes([header[2], header[3]]) {
+                    return None;
+                }
+                let start = bits.at + 4;
+                out.extend_from_slice(bits.bytes.get(start..start + len as usize)?);
+                bits.at = start + len as usize;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                codes(bits, out, &Huffman::new(&lengths)?, &Huffman::new(&[5; 30])?)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic(bits)?;
+                codes(bits, out, &literals, &distances)?;
+            }
+            _ => return None,
+        }
+        if last {
+            return Some(());
+        }
+    }
+}
+
+// reads the codes a dynamic block starts with: first the code its code lengths are written in, then those
+fn dynamic(bits: &mut Bits) -> Option<(Huffman, Huffman)> {
+    let literals = bits.bits(5)? + 257;
+    let distances = bits.bits(5)? + 1;
+    let code_lengths = bits.bits(4)? + 4;
+    if literals > 286 || distances > 30 {
+        return None;
+    }
+    let mut lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[symbol] = bits.bits(3)? as u8;
+    }
+    let code = Huffman::new(&lengths)?;
+
+    let mut lengths = Vec::with_capacity(literals + distances);
+    while lengths.len() < literals + distances {
+        let (len, repeat) = match code.decode(bits)? {
+            len @ 0..=15 => (len as u8, 1),
+            16 => (*lengths.last()?, 3 + bits.bits(2)?),
+            17 => (0, 3 + bits.bits(3)?),
+            _ => (0, 11 + bits.bits(7)?),
+        };
+        if lengths.len() + repeat > literals + distances {
+            return None;
+        }
+        lengths.extend(std::iter::repeat_n(len, repeat));
+    }
+    // a block with no end-of-block code could never end
+    if lengths[256] == 0 {
+        return None;
+    }
+    Some((Huffman::new(&lengths[..literals])?, Huffman::new(&lengths[literals..])?))
+}
+
+// the literals and matches of a compressed block, up to its end-of-block code
+fn codes(bits: &mut Bits, out: &mut Vec<u8>, literals: &Huffman, distances: &Huffman) -> Option<()> {
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+        if symbol < 256 {
+            out.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Some(());
+        }
+        let code = symbol - 257;
+        let len = *LENGTH_BASE.get(code)? as usize + bits.bits(LENGTH_EXTRA[code])?;
+        let code = distances.decode(bits)? as usize;
+        let distance = *DISTANCE_BASE.get(code)? as usize + bits.bits(DISTANCE_EXTRA[code])?;
+        if distance > out.len() {
+            return None;
+        }
+        // a match may run into the bytes it's copying, so copy one at a time
+        let from = out.len() - distance;
+        for i in 0..len {
+            out.push(out[from + i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // "cat\ndog\n" in a fixed Huffman block, and "cat\n" stored as it is, both from Python's gzip module
+    const FIXED: [u8; 28] = [31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 75, 78, 44, 225, 74, 201, 79, 231, 2, 0, 33, 144, 34, 241, 8, 0, 0, 0];
+    const STORED: [u8; 27] = [31, 139, 8, 0, 0, 0, 0, 0, 4, 3, 1, 4, 0, 251, 255, 99, 97, 116, 10, 10, 115, 68, 234, 4, 0, 0, 0];
+
+    #[test]
+    fn gunzip() {
+        assert!(is_gzip(&FIXED) && !is_gzip(b"cat\n"));
+        assert_eq!(decompress(&FIXED).unwrap(), b"cat\ndog\n");
+        assert_eq!(decompress(&STORED).unwrap(), b"cat\n");
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414f_a339);
+
+        // the first 5,000 words of the test dictionary, in dynamic Huffman blocks
+        let words = decompress(include_bytes!("../test/words.gz")).unwrap();
+        let expected: String = include_str!("../test/dictionary").lines().take(5_000).flat_map(|w| [w, "\n"]).collect();
+        assert_eq!(words, expected.as_bytes());
+
+        // members one after another decompress to their contents one after another
+        assert_eq!(decompress(&[&STORED[..], &FIXED].concat()).unwrap(), b"cat\ncat\ndog\n");
+        // with a file name and a comment in the header
+        let mut named = FIXED[..10].to_vec();
+        named[3] = FNAME | FCOMMENT;
+        named.extend_from_slice(b"words\0a comment\0");
+        named.extend_from_slice(&FIXED[10..]);
+        assert_eq!(decompress(&named).unwrap(), b"cat\ndog\n");
+
+        let mut wrong_crc = FIXED;
+        wrong_crc[20] ^= 1;
+        let mut wrong_len = FIXED;
+        wrong_len[24] += 1;
+        let mut garbled = FIXED;
+        garbled[12] ^= 0x40;
+        for raw in [&FIXED[..27], &FIXED[..12], &wrong_crc, &wrong_len, &garbled, &[&FIXED[..], b"x"].concat(), &FIXED[..2]] {
+            assert!(matches!(decompress(raw), Err(Error::CorruptGzip)), "{:?}", raw);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Just enough of a JSON reader for boards written as JSON, so the library
+//! needn't depend on one.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Fields in the order written.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The field `key` of an object.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `raw` as a single value, failing with the byte offset where it
+/// stops being JSON.
+pub(crate) fn parse(raw: &str) -> Result<Value, usize> {
+    let mut parser = Parser { bytes: raw.as_bytes(), at: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.space();
+    if parser.at < parser.bytes.len() {
+        return Err(parser.at);
+    }
+    Ok(value)
+}
+
+// how many arrays and objects deep a value may go; a board needs three at most, and a recursive parser
+// shouldn't let input choose how deep it recurses
+const MAX_DEPTH: usize = 8;
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    at: usize,
+    // the arrays and objects `at` is inside
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn space(&mut self) {
+        while self.bytes.get(self.at).is_some_and(u8::is_ascii_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    // skips whitespace, then `byte` if it's next
+    fn eat(&mut self, byte: u8) -> bool {
+        self.space();
+        let next = self.bytes.get(self.at) == Some(&byte);
+        self.at += next as usize;
+        next
+    }
+
+    fn value(&mut self) -> Result<Value, usize> {
+        self.space();
+        match self.bytes.get(self.at) {
+            Some(b'{' | b'[') if self.depth == MAX_DEPTH => Err(self.at),
+            Some(b'{') => {
+                self.at += 1;
+                self.depth += 1;
+                let mut fields = Vec::new();
+                if !self.eat(b'}') {
+                    loop {
+                        self.space();
+                        let key = self.string()?;
+                        if !self.eat(b':') {
+                            return Err(self.at);
+                        }
+                        fields.push((key, self.value()?));
+                        if self.eat(b'}') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.at);
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Value::Object(fields))
+            }
+            Some(b'[') => {
+                self.at += 1;
+                self.depth += 1;
+                let mut items = Vec::new();
+                if !self.eat(b']') {
+                    loop {
+                        items.push(self.value()?);
+                        if self.eat(b']') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.at);
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Value::Array(items))
+            }
+            Some(b'"') => self.string().map(Value::String),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => {
+                let start = self.at;
+                while self.bytes.get(self.at).is_some_and(|&b| b.is_ascii_digit() || b"+-.eE".contains(&b)) {
+                    self.at += 1;
+                }
+                let number = std::str::from_utf8(&self.bytes[start..self.at]).expect("ascii");
+                number.parse().map(Value::Number).map_err(|_| start)
+            }
+            _ => Err(self.at),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, usize> {
+        if !self.bytes[self.at..].starts_with(word.as_bytes()) {
+            return Err(self.at);
+        }
+        self.at += word.len();
+        Ok(value)
+    }
+
+    fn string(&mut self) -> Result<String, usize> {
+        if self.bytes.get(self.at) != Some(&b'"') {
+            return Err(self.at);
+        }
+        self.at += 1;
+        // the input is UTF-8 and only ever split at ASCII, so the bytes stay UTF-8
+        let mut bytes = Vec::new();
+        loop {
+            let start = self.at;
+            match *self.bytes.get(self.at).ok_or(self.at)? {
+                b'"' => break,
+                b'\\' => {
+                    self.at += 1;
+                    let c = match *self.bytes.get(self.at).ok_or(start)? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let high = self.hex()?;
+                            let code = if (0xd800..0xdc00).contains(&high) {
+                                // the first half of a surrogate pair, which must be followed by the second
+                                if !self.bytes[self.at + 1..].starts_with(b"\\u") {
+                                    return Err(start);
+                                }
+                                self.at += 2;
+                                let low = self.hex()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(start);
+                                }
+                                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                            } else {
+                                high
+                            };
+                            char::from_u32(code).ok_or(start)?
+                        }
+                        _ => return Err(start),
+                    };
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                b if b < 0x20 => return Err(self.at),
+                b => bytes.push(b),
+            }
+            self.at += 1;
+        }
+        self.at += 1;
+        Ok(String::from_utf8(bytes).expect("split at ASCII"))
+    }
+
+    // the four hex digits after a `\u`, leaving `at` on the last
+    fn hex(&mut self) -> Result<u32, usize> {
+        let digits = self.bytes.get(self.at + 1..self.at + 5).ok_or(self.at)?;
+        let code = std::str::from_utf8(digits).ok().and_then(|d| u32::from_str_radix(d, 16).ok()).ok_or(self.at)?;
+        self.at += 4;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values() {
+        let value = parse(r#" {"rows": [["qu", "a"], "bc"], "wrap": true, "n": -1.5e2, "none": null, "s": "\"\\\/\n\u00e9\ud83d\ude00ñ"} "#).unwrap();
+        assert_eq!(value.get("rows"), Some(&Value::Array(vec![
+            Value::Array(vec![Value::String("qu".to_string()), Value::String("a".to_string())]),
+            Value::String("bc".to_string()),
+        ])));
+        assert_eq!(value.get("wrap"), Some(&Value::Bool(true)));
+        assert_eq!(value.get("n"), Some(&Value::Number(-150.0)));
+        assert_eq!(value.get("none"), Some(&Value::Null));
+        assert_eq!(value.get("s"), Some(&Value::String("\"\\/\né😀ñ".to_string())));
+        assert_eq!(value.get("missing"), None);
+        assert_eq!(parse("[]"), Ok(Value::Array(Vec::new())));
+        assert_eq!(parse("{}"), Ok(Value::Object(Vec::new())));
+
+        for (raw, offset) in [("", 0), ("[1,]", 3), ("[1 2]", 3), ("{\"a\" 1}", 5), ("{a: 1}", 1), ("tru", 0), ("\"abc", 4),
+                              ("\"\\x\"", 1), ("\"\\ud800\"", 1), ("\"\\u12\"", 2), ("[1] x", 4), ("\"a\tb\"", 2), ("1e", 0)] {
+            assert_eq!(parse(raw), Err(offset), "{:?}", raw);
+        }
+
+        // nesting is limited, rather than left to overflow the stack
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Err(MAX_DEPTH));
+        assert_eq!(parse(&format!("{{\"a\": {}}}", nested(MAX_DEPTH))), Err(MAX_DEPTH + 5));
+        assert_eq!(parse(&"[".repeat(200_000)), Err(MAX_DEPTH));
+    }
+}
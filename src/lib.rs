@@ -0,0 +1,88 @@
+//! A Boggle solver.
+//!
+//! Parse a square grid of lowercase letters with [`Board::parse`], then find
+//! every dictionary word traceable through adjacent cells without reusing a
+//! cell. The dictionary is plain text with one lowercase word per line.
+//!
+//! ```
+//! use boggle::{Board, SolveOptions};
+//!
+//! let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+//! let solutions = board.solve("cat\ncats\ndog", &SolveOptions::default());
+//! let words: Vec<_> = solutions.iter().map(|s| s.word).collect();
+//! assert_eq!(words.len(), 2);
+//! assert!(words.contains(&"cat") && words.contains(&"cats"));
+//! ```
+//!
+//! [`Board::solve`] is the main entry point and takes [`SolveOptions`] for
+//! scoring, house rules, threading and more; [`Board::solve_trie`] and
+//! [`Board::solve_single_threaded`] are the simpler strategies it grew out of,
+//! and [`strategy`] puts them all behind one trait.
+//!
+//! # Stability
+//!
+//! The items re-exported at the crate root, and the `rules` and `score`
+//! modules, are the stable surface; `tests/api.rs` pins their signatures.
+//! The other modules are public for experimenting with, but may change
+//! between minor versions.
+#![cfg_attr(feature = "unstable", feature(test))]
+
+#[cfg(feature = "unstable")]
+extern crate test;
+
+// `log::debug!` with the `log` feature, and nothing at all without it
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+    }};
+}
+
+#[cfg(feature = "alloc-audit")]
+pub mod alloc_audit;
+pub mod alphabet;
+pub mod annotate;
+pub mod analysis;
+pub mod answers;
+pub mod board;
+pub mod cancel;
+pub mod compiled;
+pub mod cube;
+pub mod dawg;
+pub mod dice;
+pub mod dictionary;
+pub mod duel;
+pub mod error;
+pub mod federation;
+pub mod findable;
+pub mod fixed;
+pub mod generate;
+mod gzip;
+mod json;
+#[cfg(all(feature = "mmap", unix))]
+pub mod mmap;
+pub mod multivec;
+pub mod optimize;
+pub mod provenance;
+pub mod rng;
+pub mod rules;
+pub mod score;
+pub mod solution;
+pub mod solver;
+pub mod storage;
+pub mod strategy;
+pub mod strings;
+pub mod submission;
+pub mod trace;
+pub mod trie;
+pub mod verify;
+
+pub use crate::cancel::CancelToken;
+pub use crate::board::{validate_path, Board, FrequentFirst, Goal, LoadStats, PathError, Pruning, SolveIter, SolveOptions, SolveStats, Topology, WordProfile};
+pub use crate::dictionary::Dictionary;
+pub use crate::error::Error;
+pub use crate::rules::{GuessRule, Rules};
+pub use crate::score::Scorer;
+pub use crate::solution::{Hint, Identified, Solution, Solutions, Summary};
+pub use crate::solver::Solver;
+pub use crate::trie::{Trie, TrieNode};
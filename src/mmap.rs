@@ -0,0 +1,119 @@
+//! Word lists mapped into memory instead of read, for lists big enough that
+//! copying them in is slow and doubles what they take up. The mapping is
+//! shared with the OS page cache, and a trie built over it borrows the words
+//! straight from the file. See [`Dictionary::open_mmap`](crate::Dictionary::open_mmap).
+
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::{ptr, slice, str};
+
+use crate::error::Error;
+
+/// A file's bytes, mapped read-only.
+///
+/// The file mustn't be changed while it's mapped: the bytes would change
+/// under the borrows taken from them, or vanish if it's truncated.
+#[derive(Debug)]
+pub struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+// the mapping is read-only and owned, like a `Box<[u8]>`
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Mmap> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len == 0 {
+            // mmap refuses empty mappings
+            return Ok(Mmap { ptr: ptr::null_mut(), len });
+        }
+        let ptr = unsafe { libc::mmap(ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap { ptr, len })
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            unsafe { libc::munmap(self.ptr, self.len) };
+        }
+    }
+}
+
+/// A word list mapped into memory, one word per line, for passing to the
+/// solvers with [`MappedDictionary::as_str`].
+///
+/// Unlike a [`Dictionary`](crate::Dictionary), the words aren't copied, so
+/// they're not cleaned up either: the list should already be lowercase, with
+/// no repeats. Surrounding whitespace and blank lines are ignored as usual.
+#[derive(Debug)]
+pub struct MappedDictionary {
+    map: Mmap,
+}
+
+impl MappedDictionary {
+    /// Maps the word list at `path`, which must be UTF-8.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedDictionary, Error> {
+        let map = Mmap::open(path)?;
+        str::from_utf8(&map).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(MappedDictionary { map })
+    }
+
+    /// The words, one per line, borrowed from the mapping.
+    pub fn as_str(&self) -> &str {
+        // checked when it was opened, and the mapping can't change
+        unsafe { str::from_utf8_unchecked(&self.map) }
+    }
+
+    /// The file's bytes, e.g. for
+    /// [`CompiledDictionary::from_bytes`](crate::compiled::CompiledDictionary::from_bytes)
+    /// when the file is a compiled dictionary.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Board, Trie};
+
+    #[test]
+    fn mapped() {
+        let dictionary = MappedDictionary::open(concat!(env!("CARGO_MANIFEST_DIR"), "/test/dictionary")).unwrap();
+        assert_eq!(dictionary.as_str(), include_str!("../test/dictionary"));
+        let trie = Trie::from_dictionary(dictionary.as_str());
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        assert_eq!(board.solve_with_trie(&trie, &Default::default()).len(), 126);
+
+        let dir = std::env::temp_dir().join(format!("boggle-mmap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("empty"), "").unwrap();
+        assert_eq!(MappedDictionary::open(dir.join("empty")).unwrap().as_str(), "");
+        std::fs::write(dir.join("latin1"), b"caf\xe9\n").unwrap();
+        assert!(matches!(MappedDictionary::open(dir.join("latin1")), Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::InvalidData));
+        assert!(matches!(MappedDictionary::open(dir.join("missing")), Err(Error::Io(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
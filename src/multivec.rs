@@ -4,12 +4,14 @@ use std::iter;
 
 use smallvec::SmallVec;
 
+/// A fixed-size 3D grid, stored inline for up to 2048 elements and on the
+/// heap past that, so any size works.
 #[derive(Clone, Eq, Ord, PartialOrd, PartialEq, Hash)]
 pub struct Vec3<T> {
     height: usize,
     depth: usize,
     width: usize,
-    data: SmallVec<[T; 2048]>, // 20 characters * 8 x 8 board
+    data: SmallVec<[T; 2048]>, // a 20 letter word on an 8 x 8 board stays inline
 }
 
 impl<T> Vec3<T> {
@@ -24,7 +26,7 @@ impl<T> Vec3<T> {
     pub fn fill(width: usize, height: usize, depth: usize, value: T) -> Vec3<T>
         where T: Clone
     {
-        let data = iter::repeat(value).take(width * height * depth).collect();
+        let data = iter::repeat_n(value, width * height * depth).collect();
         Vec3 {
             width,
             height,
@@ -66,11 +68,13 @@ impl<T: Debug> Debug for Vec3<T> {
     }
 }
 
+/// A fixed-size 2D grid, stored inline for up to 64 elements, i.e. an 8x8
+/// board, and on the heap past that, so any size works.
 #[derive(Clone, Eq, Ord, PartialOrd, PartialEq, Hash)]
 pub struct Vec2<T> {
     height: usize,
     width: usize,
-    data: SmallVec<[T; 64]>, // 8 x 8 board
+    data: SmallVec<[T; 64]>, // an 8 x 8 board stays inline
 }
 
 impl<T> Vec2<T> {
@@ -86,13 +90,18 @@ impl<T> Vec2<T> {
         where T: Clone
     {
 
-        let data = iter::repeat(value).take(width * height).collect();
+        let data = iter::repeat_n(value, width * height).collect();
         Vec2 {
             width,
             height,
             data,
         }
     }
+
+    /// Every element, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
 }
 
 impl<T> Index<(usize, usize)> for Vec2<T> {
@@ -112,7 +121,7 @@ impl<T> IndexMut<(usize, usize)> for Vec2<T> {
 
 impl<T: Debug> Debug for Vec2<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Vec3:\t")?;
+        write!(f, "Vec2:\t")?;
         for i in 0..self.width {
             write!(f, "{:?}:\t", i)?;
             for j in 0..self.height {
@@ -124,6 +133,105 @@ impl<T: Debug> Debug for Vec2<T> {
     }
 }
 
+/// A fixed-size grid with any number of dimensions, addressed by `N`
+/// coordinates, the first varying slowest. Stored inline for up to 64
+/// elements, e.g. a 4x4x4 cube, and on the heap past that.
+#[derive(Clone, Eq, Ord, PartialOrd, PartialEq, Hash)]
+pub struct VecN<T, const N: usize> {
+    dims: [usize; N],
+    data: SmallVec<[T; 64]>,
+}
+
+impl<T, const N: usize> VecN<T, N> {
+    fn idx(&self, at: [usize; N]) -> Option<usize> {
+        let mut idx = 0;
+        for (&i, &dim) in at.iter().zip(&self.dims) {
+            if i >= dim {
+                return None;
+            }
+            idx = idx * dim + i;
+        }
+        Some(idx)
+    }
+
+    pub fn fill(dims: [usize; N], value: T) -> VecN<T, N>
+        where T: Clone
+    {
+        let data = iter::repeat_n(value, dims.iter().product()).collect();
+        VecN { dims, data }
+    }
+
+    /// The size along each dimension.
+    pub fn dims(&self) -> [usize; N] {
+        self.dims
+    }
+
+    /// Every element, with the last coordinate varying fastest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    /// The coordinates of every element, in the same order as `iter`.
+    pub fn coords(&self) -> impl Iterator<Item = [usize; N]> {
+        let dims = self.dims;
+        (0..self.data.len()).map(move |mut idx| {
+            let mut at = [0; N];
+            for d in (0..N).rev() {
+                at[d] = idx % dims[d];
+                idx /= dims[d];
+            }
+            at
+        })
+    }
+
+    /// The up to `3^N - 1` elements around `at`, differing by at most one in
+    /// every coordinate, diagonals included.
+    pub fn neighbors(&self, at: [usize; N]) -> impl Iterator<Item = [usize; N]> {
+        let dims = self.dims;
+        // each offset is `N` digits in base 3, standing for -1, 0 and +1
+        (0..3usize.pow(N as u32)).filter_map(move |mut offset| {
+            let mut next = at;
+            let mut moved = false;
+            for d in (0..N).rev() {
+                match offset % 3 {
+                    0 if at[d] > 0 => next[d] -= 1,
+                    2 if at[d] + 1 < dims[d] => next[d] += 1,
+                    1 => {}
+                    _ => return None,
+                }
+                moved |= offset % 3 != 1;
+                offset /= 3;
+            }
+            if moved { Some(next) } else { None }
+        })
+    }
+}
+
+impl<T, const N: usize> Index<[usize; N]> for VecN<T, N> {
+    type Output = T;
+
+    fn index(&self, at: [usize; N]) -> &T {
+        &self.data[self.idx(at).expect("index out of bounds")]
+    }
+}
+
+impl<T, const N: usize> IndexMut<[usize; N]> for VecN<T, N> {
+    fn index_mut(&mut self, at: [usize; N]) -> &mut T {
+        let idx = self.idx(at).expect("index out of bounds");
+        &mut self.data[idx]
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for VecN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VecN{:?}:", self.dims)?;
+        for (at, value) in self.coords().zip(self.iter()) {
+            write!(f, "\n\t{:?}: {:?}", at, value)?;
+        }
+        Ok(())
+    }
+}
+
 #[test]
 fn smoke() {
     let mut v = Vec3::fill(3, 4, 4, false);
@@ -132,4 +240,39 @@ fn smoke() {
     }
     println!("{:?}", v);
     assert!(v[(1, 2, 0)]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn spilled() {
+    // a 16x16 board and a 30 letter word on it, both past what's stored inline
+    let mut v = Vec2::fill(16, 16, 0);
+    v[(15, 15)] = 1;
+    v[(0, 15)] = 2;
+    assert_eq!((v[(15, 15)], v[(0, 15)], v[(15, 0)]), (1, 2, 0));
+    assert!(format!("{:?}", v).starts_with("Vec2:"));
+
+    let mut v = Vec3::fill(30, 16, 16, false);
+    v[(29, 15, 15)] = true;
+    assert!(v[(29, 15, 15)] && !v[(29, 15, 14)]);
+}
+
+#[test]
+fn n_dimensional() {
+    let mut v = VecN::fill([2, 3, 4], 0);
+    v[[1, 2, 3]] = 7;
+    assert_eq!(v[[1, 2, 3]], 7);
+    assert_eq!(v.iter().position(|&x| x == 7), Some(23));
+    assert_eq!(v.coords().nth(23), Some([1, 2, 3]));
+    assert_eq!(v.coords().count(), 24);
+    assert!(format!("{:?}", v).starts_with("VecN[2, 3, 4]:"));
+
+    // a cube's middle has all 26 neighbors, a corner 7 and an edge 11
+    let cube = VecN::fill([4, 4, 4], ());
+    assert_eq!(cube.neighbors([1, 2, 1]).count(), 26);
+    assert_eq!(cube.neighbors([0, 0, 0]).collect::<Vec<_>>(),
+               [[0, 0, 1], [0, 1, 0], [0, 1, 1], [1, 0, 0], [1, 0, 1], [1, 1, 0], [1, 1, 1]]);
+    assert_eq!(cube.neighbors([0, 0, 2]).count(), 11);
+    // and in two dimensions they're the board's eight
+    assert_eq!(VecN::fill([4, 4], ()).neighbors([1, 1]).count(), 8);
+    assert_eq!(VecN::fill([1], ()).neighbors([0]).count(), 0);
+}
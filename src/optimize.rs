@@ -0,0 +1,147 @@
+//! Searching for high-scoring boards by simulated annealing: roll the dice,
+//! then keep rerolling or swapping them, solving each new board, and keep
+//! changes that raise the score. Changes that lower it are kept too, less and
+//! less often as the search cools, so it doesn't get stuck on the first peak.
+//! Every board tried is a legal roll of the dice.
+
+use crate::board::{Board, Goal, SolveOptions};
+use crate::dice::Dice;
+use crate::rng::RngSource;
+use crate::trie::Trie;
+
+/// How long and how adventurously [`optimize`] searches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Annealing {
+    /// Boards to try after the first roll; defaults to 2000.
+    pub steps: usize,
+    /// How many points a change may lose and still usually be kept, at the
+    /// start of the search and at the end; the search cools geometrically from
+    /// one to the other. They default to 10 and 0.1.
+    pub start_temperature: f64,
+    pub end_temperature: f64,
+}
+
+impl Default for Annealing {
+    fn default() -> Self {
+        Annealing { steps: 2000, start_temperature: 10.0, end_temperature: 0.1 }
+    }
+}
+
+/// The best board [`optimize`] found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Optimized {
+    /// The board as text for [`Board::parse`].
+    pub board: String,
+    /// Its total score, classic scoring.
+    pub score: u32,
+}
+
+// which die sits in each cell, and which face it shows
+#[derive(Clone)]
+struct Layout(Vec<(usize, usize)>);
+
+impl Layout {
+    fn text(&self, dice: &Dice) -> String {
+        let size = (1..=dice.len()).find(|n| n * n >= dice.len()).unwrap_or(0);
+        let mut board = String::with_capacity(dice.len() * 2);
+        for row in self.0.chunks(size) {
+            for &(die, face) in row {
+                match dice[die].as_bytes()[face] {
+                    b'q' => board.push_str("qu"),
+                    c => board.push(c as char),
+                }
+            }
+            board.push('\n');
+        }
+        board
+    }
+}
+
+/// Searches for the highest-scoring roll of `dice` against `trie`. The same
+/// `rng` state always finds the same board.
+///
+/// # Panics
+///
+/// If the number of dice isn't a square.
+pub fn optimize<R: RngSource>(dice: &Dice, trie: &Trie, annealing: &Annealing, rng: &mut R) -> Optimized {
+    let size = (1..=dice.len()).find(|n| n * n >= dice.len()).unwrap_or(0);
+    assert_eq!(size * size, dice.len(), "{} dice don't make a square board", dice.len());
+
+    // each step solves one small board, so threads would cost more than they save
+    let options = SolveOptions::low_power();
+    let score_until = |layout: &Layout, goal| {
+        let board = Board::parse(&layout.text(dice)).expect("dice faces are letters");
+        board.solve_until(trie, goal, &options).iter().map(|s| s.score).sum::<u32>()
+    };
+    let score = |layout: &Layout| score_until(layout, Goal::StopAtScore(u32::MAX));
+
+    let mut order: Vec<_> = (0..dice.len()).collect();
+    rng.shuffle(&mut order);
+    let mut current = Layout(order.into_iter().map(|die| (die, rng.below(dice[die].len()))).collect());
+    let mut current_score = score(&current);
+    let mut best = (current.clone(), current_score);
+
+    let cooling = (annealing.end_temperature / annealing.start_temperature).powf(1.0 / annealing.steps.max(1) as f64);
+    let mut temperature = annealing.start_temperature;
+    for step in 0..annealing.steps {
+        if step % (annealing.steps / 10).max(1) == 0 {
+            debug!("step {} of {}: best score {}, current {}, temperature {:.2}", step, annealing.steps, best.1, current_score, temperature);
+        }
+        let mut next = current.clone();
+        let cell = rng.below(next.0.len());
+        if rng.below(2) == 0 {
+            let die = next.0[cell].0;
+            next.0[cell].1 = rng.below(dice[die].len());
+        } else {
+            let other = rng.below(next.0.len());
+            next.0.swap(cell, other);
+        }
+
+        // a change that gains points is always kept, so the search can stop once it has, and only then does
+        // the board need solving in full
+        let next_score = match score_until(&next, Goal::StopAtScore(current_score + 1)) {
+            gained if gained > current_score => score(&next),
+            lost => lost,
+        };
+        let loss = current_score as f64 - next_score as f64;
+        // uniform in [0, 1)
+        let roll = (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        if loss <= 0.0 || roll < (-loss / temperature).exp() {
+            current = next;
+            current_score = next_score;
+            if current_score > best.1 {
+                best = (current.clone(), current_score);
+            }
+        }
+        temperature *= cooling;
+    }
+
+    Optimized { board: best.0.text(dice), score: best.1 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dice::{self, CLASSIC};
+    use crate::rng::SplitMix64;
+
+    const DICTIONARY: &str = include_str!("../test/dictionary");
+
+    #[test]
+    fn beats_a_roll() {
+        let trie = Trie::from_dictionary(DICTIONARY);
+        let annealing = Annealing { steps: 200, ..Annealing::default() };
+        let best = optimize(&CLASSIC, &trie, &annealing, &mut SplitMix64::new(1));
+        assert_eq!(best, optimize(&CLASSIC, &trie, &annealing, &mut SplitMix64::new(1)));
+
+        let board = Board::parse(&best.board).unwrap();
+        assert_eq!(board.len(), 4);
+        assert_eq!(board.solve_with_trie(&trie, &SolveOptions::default()).iter().map(|s| s.score).sum::<u32>(), best.score);
+        let raw = dice::roll(&CLASSIC, 1);
+        let rolled = Board::parse(&raw).unwrap();
+        assert!(best.score > rolled.solve_with_trie(&trie, &SolveOptions::default()).iter().map(|s| s.score).sum::<u32>());
+
+        let none = optimize(&CLASSIC, &trie, &Annealing { steps: 0, ..Annealing::default() }, &mut SplitMix64::new(1));
+        assert!(none.score <= best.score);
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::compiled;
+
+/// Identifies the exact solver and inputs behind a set of results, so bug
+/// reports and disputes can be traced back to them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Provenance {
+    pub version: &'static str,
+    /// A hash of the dictionary's words as searched, one per line, so the
+    /// same list hashes the same whether it was read as text or compiled.
+    pub dictionary_hash: u64,
+    /// The [`compiled`] format this build reads and writes.
+    pub index_version: u8,
+    /// The rules and scoring the words were found under, e.g. a preset's
+    /// name; empty for the defaults.
+    pub rules: String,
+}
+
+impl Provenance {
+    /// Results from `dictionary`, e.g. [`Dictionary::as_str`](crate::Dictionary::as_str),
+    /// under the default rules.
+    pub fn new(dictionary: &str) -> Provenance {
+        Provenance {
+            version: env!("CARGO_PKG_VERSION"),
+            dictionary_hash: fnv1a(dictionary.as_bytes()),
+            index_version: compiled::VERSION,
+            rules: String::new(),
+        }
+    }
+
+    /// Records the rules the results were found under.
+    pub fn with_rules<S: Into<String>>(self, rules: S) -> Provenance {
+        Provenance { rules: rules.into(), ..self }
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "boggle {} dictionary {:016x} index v{}", self.version, self.dictionary_hash, self.index_version)?;
+        if !self.rules.is_empty() {
+            write!(f, " rules {}", self.rules)?;
+        }
+        Ok(())
+    }
+}
+
+// 64-bit FNV-1a; unlike std's hashers its output is fixed across releases and platforms
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[test]
+fn smoke() {
+    assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(Provenance::new("foo\nbar"), Provenance::new("foo\nbar"));
+    assert_ne!(Provenance::new("foo\nbar"), Provenance::new("foo\nbaz"));
+    assert_ne!(Provenance::new("foo\nbar"), Provenance::new("foo\nbar").with_rules("big"));
+    let shown = Provenance::new("foo\nbar").to_string();
+    assert!(shown.starts_with("boggle ") && shown.ends_with(&format!(" index v{}", compiled::VERSION)), "{}", shown);
+    assert!(Provenance::new("foo\nbar").with_rules("min-length 4").to_string().ends_with(" rules min-length 4"));
+}
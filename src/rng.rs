@@ -0,0 +1,105 @@
+//! Every source of randomness in the crate, from rolling dice to shuffling the
+//! search order, goes through [`RngSource`], so callers can plug in their own
+//! entropy or a fixed sequence for tests.
+
+/// A stream of random numbers.
+pub trait RngSource {
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniform in `0..n`; the modulo bias is negligible for the small `n` we
+    /// pick from.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, items: &mut [T]) where Self: Sized {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// SplitMix64: a tiny seedable generator, plenty for shuffling search order.
+/// Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    /// Seeded from [`entropy`].
+    pub fn from_entropy() -> SplitMix64 {
+        SplitMix64::new(entropy())
+    }
+}
+
+impl RngSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Adapts any `rand` generator, e.g. `Rand(rand::thread_rng())`.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct Rand<R>(pub R);
+
+#[cfg(feature = "rand")]
+impl<R: rand::RngCore> RngSource for Rand<R> {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/// A seed nobody chose: from the OS with the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn entropy() -> u64 {
+    rand::random()
+}
+
+/// A seed nobody chose: the current time, as there is no OS entropy without
+/// the `rand` feature.
+#[cfg(not(feature = "rand"))]
+pub fn entropy() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64)
+}
+
+#[test]
+fn smoke() {
+    let mut a = SplitMix64::new(7);
+    let mut b = SplitMix64::new(7);
+    assert_eq!(a.next_u64(), b.next_u64());
+
+    let mut items: Vec<_> = (0..20).collect();
+    a.shuffle(&mut items);
+    assert_ne!(items, (0..20).collect::<Vec<_>>());
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+
+    struct Counter(u64);
+    impl RngSource for Counter {
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+    }
+    let mut counter = Counter(0);
+    let mut items = vec![1, 2, 3];
+    counter.shuffle(&mut items);
+    assert_eq!(items, vec![3, 1, 2]);
+    assert_eq!(counter.0, 2);
+}
@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use crate::board::Board;
+use crate::solution::Solution;
+
+/// A house rule deciding whether a word counts, independent of where it lies
+/// on the board.
+pub trait GuessRule {
+    fn allows(&self, word: &[u8], board: &Board) -> bool;
+}
+
+/// Words shorter than the given number of letters don't count.
+#[derive(Debug, Clone, Copy)]
+pub struct MinLength(pub usize);
+
+impl GuessRule for MinLength {
+    fn allows(&self, word: &[u8], _board: &Board) -> bool {
+        word.len() >= self.0
+    }
+}
+
+/// Rejects words ending in a single `s`, e.g. "cats" but not "glass".
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoPlurals;
+
+impl GuessRule for NoPlurals {
+    fn allows(&self, word: &[u8], _board: &Board) -> bool {
+        !word.ends_with(b"s") || word.ends_with(b"ss")
+    }
+}
+
+/// Rejects words from a list of proper nouns, for dictionaries that mix them
+/// in with common words.
+#[derive(Debug, Default, Clone)]
+pub struct NoProperNouns {
+    nouns: HashSet<Vec<u8>>,
+}
+
+impl NoProperNouns {
+    pub fn new<'a, I>(nouns: I) -> NoProperNouns
+        where I: IntoIterator<Item = &'a str>
+    {
+        let nouns = nouns.into_iter().map(|n| n.to_ascii_lowercase().into_bytes()).collect();
+        NoProperNouns { nouns }
+    }
+}
+
+impl GuessRule for NoProperNouns {
+    fn allows(&self, word: &[u8], _board: &Board) -> bool {
+        !self.nouns.contains(word)
+    }
+}
+
+/// Only the `n` most common words count, for handicapping stronger players.
+///
+/// Built from a word list ordered from most to least frequent, one word per
+/// line, ignoring blank lines and surrounding whitespace; each player can get
+/// their own view of a shared frequency list.
+#[derive(Debug, Default, Clone)]
+pub struct CommonWords {
+    words: HashSet<Vec<u8>>,
+}
+
+impl CommonWords {
+    pub fn new(ranked: &str, n: usize) -> CommonWords {
+        let words = ranked_words(ranked).take(n).map(|w| w.as_bytes().to_vec()).collect();
+        CommonWords { words }
+    }
+}
+
+// the words of a frequency list, most frequent first, as both `CommonWords` and `Rarity` read it
+pub(crate) fn ranked_words(ranked: &str) -> impl Iterator<Item = &str> {
+    ranked.lines().map(str::trim).filter(|w| !w.is_empty())
+}
+
+impl GuessRule for CommonWords {
+    fn allows(&self, word: &[u8], _board: &Board) -> bool {
+        self.words.contains(word)
+    }
+}
+
+/// A chain of rules; a word is allowed only if every rule allows it. An empty
+/// chain allows everything.
+#[derive(Default)]
+pub struct Rules {
+    rules: Vec<Box<dyn GuessRule>>,
+}
+
+impl Rules {
+    pub fn new() -> Rules {
+        Rules::default()
+    }
+
+    pub fn push<R: GuessRule + 'static>(&mut self, rule: R) {
+        self.rules.push(Box::new(rule));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl GuessRule for Rules {
+    fn allows(&self, word: &[u8], board: &Board) -> bool {
+        self.rules.iter().all(|r| r.allows(word, board))
+    }
+}
+
+/// Drops plurals whose singular was also found on the same board, for games
+/// where "cat" and "cats" only count once.
+///
+/// A word is treated as a plural of `w` if it is `w` + "s", `w` + "es", or `w`
+/// with a trailing "y" replaced by "ies". Words ending in "ss" are never
+/// plurals, and words in the exception list are always kept.
+#[derive(Debug, Clone)]
+pub struct PluralFilter {
+    exceptions: HashSet<Vec<u8>>,
+}
+
+impl Default for PluralFilter {
+    fn default() -> Self {
+        PluralFilter::new(vec!["series", "species"])
+    }
+}
+
+impl PluralFilter {
+    pub fn new<'a, I>(exceptions: I) -> PluralFilter
+        where I: IntoIterator<Item = &'a str>
+    {
+        let exceptions = exceptions.into_iter().map(|w| w.as_bytes().to_vec()).collect();
+        PluralFilter { exceptions }
+    }
+
+    pub fn except(&mut self, word: &str) {
+        self.exceptions.insert(word.as_bytes().to_vec());
+    }
+
+    /// Returns the possible singulars of `word`, empty if it isn't a plural.
+    pub fn singulars(&self, word: &[u8]) -> Vec<Vec<u8>> {
+        if !word.ends_with(b"s") || word.ends_with(b"ss") || self.exceptions.contains(word) {
+            return Vec::new();
+        }
+
+        let stem = &word[..word.len() - 1];
+        let mut singulars = vec![stem.to_vec()];
+        if stem.ends_with(b"e") {
+            singulars.push(stem[..stem.len() - 1].to_vec());
+        }
+        if stem.ends_with(b"ie") {
+            let mut singular = stem[..stem.len() - 2].to_vec();
+            singular.push(b'y');
+            singulars.push(singular);
+        }
+        singulars
+    }
+
+    pub fn apply(&self, solutions: &mut Vec<Solution>) {
+        let found: HashSet<&[u8]> = solutions.iter().map(|s| s.word.as_bytes()).collect();
+        let plurals: HashSet<&str> = solutions.iter()
+            .filter(|s| self.singulars(s.word.as_bytes()).iter().any(|w| found.contains(&w[..])))
+            .map(|s| s.word)
+            .collect();
+        solutions.retain(|s| !plurals.contains(s.word));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    const BOARD: &str = "abcd\nefgh\nijkl\nmnop";
+
+    #[test]
+    fn chain() {
+        let board = Board::parse(BOARD).unwrap();
+        let mut rules = Rules::new();
+        assert!(rules.allows(b"cats", &board));
+
+        rules.push(MinLength(4));
+        rules.push(NoPlurals);
+        rules.push(NoProperNouns::new(vec!["Paris"]));
+        assert!(!rules.allows(b"cat", &board));
+        assert!(!rules.allows(b"cats", &board));
+        assert!(!rules.allows(b"paris", &board));
+        assert!(rules.allows(b"glass", &board));
+        assert!(rules.allows(b"dogma", &board));
+    }
+
+    #[test]
+    fn common_words() {
+        let board = Board::parse(BOARD).unwrap();
+        let ranked = "the\nand\nfor\nzax";
+        let handicap = CommonWords::new(ranked, 3);
+        assert!(handicap.allows(b"and", &board));
+        assert!(!handicap.allows(b"zax", &board));
+        assert!(CommonWords::new(ranked, 20_000).allows(b"zax", &board));
+
+        // blank lines don't take a place, and padding doesn't stop a word matching
+        let padded = CommonWords::new("the\r\n\n  and \n\nfor\nzax", 3);
+        assert!(padded.allows(b"the", &board) && padded.allows(b"and", &board) && padded.allows(b"for", &board));
+        assert!(!padded.allows(b"zax", &board));
+    }
+
+    #[test]
+    fn plurals() {
+        let solutions = |words: &[&'static str]| -> Vec<Solution> {
+            words.iter().map(|&word| Solution { word, score: 1, path: Vec::new() }).collect()
+        };
+
+        let mut filter = PluralFilter::default();
+        let mut found = solutions(&[
+            "cat", "cats", "box", "boxes", "berry", "berries", "glass", "glas", "specie", "species", "dogs", "bus",
+        ]);
+        filter.apply(&mut found);
+        assert_eq!(found, solutions(&["cat", "box", "berry", "glass", "glas", "specie", "species", "dogs", "bus"]));
+
+        filter.except("cats");
+        let mut found = solutions(&["cat", "cats"]);
+        filter.apply(&mut found);
+        assert_eq!(found.len(), 2);
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::board::Board;
+use crate::multivec::Vec2;
+use crate::rules::ranked_words;
+use crate::solution::Solutions;
+
+/// Assigns points to a word found on a board.
+///
+/// `path` is the sequence of `(row, col)` cells the word was traced through,
+/// starting with the cell holding its first letter.
+pub trait Scorer {
+    fn score(&self, word: &[u8], path: &[(usize, usize)], board: &Board) -> u32;
+
+    /// The most any word of at most `len` letters can score, along any path,
+    /// if the scorer can say; [`Board::solve_until`] skips branches that
+    /// can't beat the words it has. `None` by default, which skips none.
+    fn max_points(&self, _len: usize) -> Option<u32> {
+        None
+    }
+}
+
+/// Official Boggle scoring: 3-4 letters are worth 1, 5 is 2, 6 is 3, 7 is 5
+/// and 8 or more is 11.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Classic;
+
+impl Scorer for Classic {
+    fn score(&self, word: &[u8], _path: &[(usize, usize)], _board: &Board) -> u32 {
+        classic_points(word.len())
+    }
+
+    fn max_points(&self, len: usize) -> Option<u32> {
+        Some(classic_points(len))
+    }
+}
+
+/// [`Classic`]'s points for a word of `len` letters, for scoring words found
+/// off a [`Board`].
+pub fn classic_points(len: usize) -> u32 {
+    match len {
+        0..=2 => 0,
+        3 | 4 => 1,
+        5 => 2,
+        6 => 3,
+        7 => 5,
+        _ => 11,
+    }
+}
+
+/// Word Hunt scoring: 100 for 3 letters, 400 for 4, 800 for 5, 1400 for 6 and
+/// 400 more for every letter after that.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WordHunt;
+
+impl Scorer for WordHunt {
+    fn score(&self, word: &[u8], _path: &[(usize, usize)], _board: &Board) -> u32 {
+        word_hunt_points(word.len())
+    }
+
+    fn max_points(&self, len: usize) -> Option<u32> {
+        Some(word_hunt_points(len))
+    }
+}
+
+fn word_hunt_points(len: usize) -> u32 {
+    match len {
+        0..=2 => 0,
+        3 => 100,
+        4 => 400,
+        5 => 800,
+        n => 1400 + 400 * (n as u32 - 6),
+    }
+}
+
+/// Wraps another scorer and multiplies its points by the multiplier of every
+/// cell the word passes through, e.g. a "double word" tile.
+#[derive(Debug, Clone)]
+pub struct Multiplier<S> {
+    base: S,
+    multipliers: Vec2<u32>,
+    // the same multipliers, largest first
+    largest: Vec<u32>,
+}
+
+impl<S: Scorer> Multiplier<S> {
+    /// `multipliers` is indexed by `(row, col)` like the board itself; cells
+    /// without a bonus should hold 1.
+    pub fn new(base: S, multipliers: Vec2<u32>) -> Multiplier<S> {
+        let mut largest: Vec<_> = multipliers.iter().copied().collect();
+        largest.sort_unstable_by(|a, b| b.cmp(a));
+        Multiplier { base, multipliers, largest }
+    }
+}
+
+impl<S: Scorer> Scorer for Multiplier<S> {
+    fn score(&self, word: &[u8], path: &[(usize, usize)], board: &Board) -> u32 {
+        path.iter().fold(self.base.score(word, path, board), |score, &cell| score * self.multipliers[cell])
+    }
+
+    /// The base scorer's bound times the `len` largest multipliers, as a
+    /// word of `len` letters passes through at most `len` cells; with one
+    /// bonus cell, that's the base bound times the largest multiplier.
+    fn max_points(&self, len: usize) -> Option<u32> {
+        Some(self.largest.iter().take(len).fold(self.base.max_points(len)?, |points, &m| points.saturating_mul(m)))
+    }
+}
+
+impl<S: Scorer + ?Sized> Scorer for Arc<S> {
+    fn score(&self, word: &[u8], path: &[(usize, usize)], board: &Board) -> u32 {
+        (**self).score(word, path, board)
+    }
+
+    fn max_points(&self, len: usize) -> Option<u32> {
+        (**self).max_points(len)
+    }
+}
+
+/// How common a word is, from a frequency list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tier {
+    Common,
+    Uncommon,
+    /// Ranked low, or not in the frequency list at all.
+    Rare,
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Tier::Common => "common",
+            Tier::Uncommon => "uncommon",
+            Tier::Rare => "rare",
+        })
+    }
+}
+
+/// Wraps another scorer and multiplies its points by a bonus for rarer words.
+///
+/// Built from a word list ordered from most to least frequent, one word per
+/// line, like [`CommonWords`](crate::rules::CommonWords). The first `common`
+/// words are common, those up to `uncommon` are uncommon and everything else
+/// is rare.
+#[derive(Debug, Clone)]
+pub struct Rarity<S> {
+    base: S,
+    ranks: HashMap<Vec<u8>, usize>,
+    pub common: usize,
+    pub uncommon: usize,
+    /// Multipliers for common, uncommon and rare words, in that order.
+    pub multipliers: [u32; 3],
+}
+
+impl<S: Scorer> Rarity<S> {
+    /// Starts with the top 5,000 words common, the next 15,000 uncommon, and
+    /// rare words worth triple.
+    pub fn new(base: S, ranked: &str) -> Rarity<S> {
+        let mut ranks = HashMap::new();
+        for (rank, word) in ranked_words(ranked).enumerate() {
+            ranks.entry(word.as_bytes().to_vec()).or_insert(rank);
+        }
+        Rarity { base, ranks, common: 5_000, uncommon: 20_000, multipliers: [1, 2, 3] }
+    }
+
+    pub fn tier(&self, word: &[u8]) -> Tier {
+        match self.ranks.get(word) {
+            Some(&rank) if rank < self.common => Tier::Common,
+            Some(&rank) if rank < self.uncommon => Tier::Uncommon,
+            _ => Tier::Rare,
+        }
+    }
+}
+
+impl<'a> Solutions<'a> {
+    /// The words no rarer than `max` by `rarity`'s reckoning, in the same
+    /// order, e.g. to hide obscure words from casual players.
+    pub fn no_rarer_than<S: Scorer>(&self, rarity: &Rarity<S>, max: Tier) -> Solutions<'a> {
+        Solutions(self.iter().filter(|s| rarity.tier(s.word.as_bytes()) <= max).cloned().collect())
+    }
+}
+
+impl<S: Scorer> Scorer for Rarity<S> {
+    fn score(&self, word: &[u8], path: &[(usize, usize)], board: &Board) -> u32 {
+        self.base.score(word, path, board) * self.multipliers[self.tier(word) as usize]
+    }
+
+    fn max_points(&self, len: usize) -> Option<u32> {
+        Some(self.base.max_points(len)? * self.multipliers.iter().max().copied().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::Solution;
+    const BOARD: &str = "abcd\nefgh\nijkl\nmnop";
+
+    #[test]
+    fn classic() {
+        let board = Board::parse(BOARD).unwrap();
+        let scores: Vec<_> = ["ab", "abc", "abcd", "abcde", "abcdef", "abcdefg", "abcdefgh", "abcdefghijk"]
+            .iter()
+            .map(|w| Classic.score(w.as_bytes(), &[], &board))
+            .collect();
+        assert_eq!(scores, vec![0, 1, 1, 2, 3, 5, 11, 11]);
+    }
+
+    #[test]
+    fn word_hunt() {
+        let board = Board::parse(BOARD).unwrap();
+        let scores: Vec<_> = ["abc", "abcd", "abcde", "abcdef", "abcdefg", "abcdefgh"]
+            .iter()
+            .map(|w| WordHunt.score(w.as_bytes(), &[], &board))
+            .collect();
+        assert_eq!(scores, vec![100, 400, 800, 1400, 1800, 2200]);
+    }
+
+    #[test]
+    fn multiplier() {
+        let board = Board::parse(BOARD).unwrap();
+        let mut multipliers = Vec2::fill(4, 4, 1);
+        multipliers[(0, 1)] = 2;
+        multipliers[(1, 1)] = 3;
+        let scorer = Multiplier::new(Classic, multipliers);
+        assert_eq!(scorer.score(b"abcd", &[(0, 0), (0, 1), (0, 2), (0, 3)], &board), 2);
+        assert_eq!(scorer.score(b"abfe", &[(0, 0), (0, 1), (1, 1), (1, 0)], &board), 6);
+        assert_eq!(scorer.score(b"aei", &[(0, 0), (1, 0), (2, 0)], &board), 1);
+        // both bonuses on one path, so the bound has both too
+        assert_eq!((scorer.max_points(4), scorer.max_points(1)), (Some(6), Some(0)));
+        assert_eq!(Multiplier::new(Classic, Vec2::fill(4, 4, 1)).max_points(8), Some(11));
+        assert_eq!(Multiplier::new(Rarity::new(Classic, ""), Vec2::fill(4, 4, 1)).max_points(3), Some(3));
+
+        // the bound lets a top-k search prune, with the same words as a full solve
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let dictionary = include_str!("../test/dictionary");
+        let mut multipliers = Vec2::fill(4, 4, 1);
+        multipliers[(1, 2)] = 2;
+        multipliers[(2, 3)] = 3;
+        let options = crate::SolveOptions { scorer: Box::new(Multiplier::new(Classic, multipliers)), ..crate::SolveOptions::default() };
+        let mut solved = Solutions(board.solve(dictionary, &options));
+        solved.sort_by_score();
+        let scores = |solutions: &[Solution]| solutions.iter().map(|s| s.score).collect::<Vec<_>>();
+        let mut best = Solutions(board.solve_until(&crate::Trie::from_dictionary(dictionary), crate::Goal::TopKByScore(5), &options));
+        best.sort_by_score();
+        assert_eq!(scores(&best), scores(&solved[..5]));
+    }
+
+    #[test]
+    fn rarity() {
+        let board = Board::parse(BOARD).unwrap();
+        let mut scorer = Rarity::new(Classic, "the\nand\nfor\nzax\n");
+        scorer.common = 2;
+        scorer.uncommon = 3;
+        assert_eq!(scorer.tier(b"and"), Tier::Common);
+        assert_eq!(scorer.tier(b"for"), Tier::Uncommon);
+        assert_eq!(scorer.tier(b"zax"), Tier::Rare);
+        assert_eq!(scorer.tier(b"qoph"), Tier::Rare);
+        let scores: Vec<_> = ["the", "for", "zax", "bijou"].iter().map(|w| scorer.score(w.as_bytes(), &[], &board)).collect();
+        assert_eq!(scores, vec![1, 2, 3, 6]);
+
+        let shared = Arc::new(scorer);
+        assert_eq!(shared.score(b"bijou", &[], &board), 6);
+        assert_eq!(Tier::Uncommon.to_string(), "uncommon");
+
+        let solutions = Solutions(["zax", "the", "for"].iter().map(|&word| Solution { word, score: 1, path: Vec::new() }).collect());
+        let words = |s: Solutions<'static>| s.iter().map(|s| s.word).collect::<Vec<_>>();
+        assert_eq!(words(solutions.no_rarer_than(&shared, Tier::Uncommon)), ["the", "for"]);
+        assert_eq!(words(solutions.no_rarer_than(&shared, Tier::Common)), ["the"]);
+        assert_eq!(words(solutions.no_rarer_than(&shared, Tier::Rare)), ["zax", "the", "for"]);
+    }
+}
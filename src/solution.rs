@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::ops::{Deref, DerefMut};
+
+use crate::board::Board;
+use crate::provenance::fnv1a;
+
+/// A word found on a board, with where it was found and what it's worth.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Solution<'a> {
+    pub word: &'a str,
+    pub score: u32,
+    /// The `(row, col)` cells spelling out `word`, in order. When a word can
+    /// be traced more than one way this is whichever path the solver found
+    /// first.
+    pub path: Vec<(usize, usize)>,
+}
+
+impl<'a> Solution<'a> {
+    /// The number of letters in the word.
+    pub fn len(&self) -> usize {
+        self.word.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.word.is_empty()
+    }
+
+    /// The cell holding the first letter.
+    pub fn start(&self) -> (usize, usize) {
+        self.path[0]
+    }
+
+    /// A hash of the word and its [`Board::canonical_path`] on `board`, the
+    /// same in every process and from every solver, for clients to refer to
+    /// it by. A word `board` can't trace is hashed with `path` instead.
+    ///
+    /// The bytes hashed are the word, a space and each cell as `row,col;`,
+    /// so "cat" at the top left is `cat 0,0;0,1;0,2;`.
+    pub fn id(&self, board: &Board) -> u64 {
+        let path = board.canonical_path(self.word);
+        let mut key = format!("{} ", self.word);
+        for &(row, col) in path.as_ref().unwrap_or(&self.path) {
+            let _ = write!(key, "{},{};", row, col);
+        }
+        fnv1a(key.as_bytes())
+    }
+}
+
+/// A solution with its [`Solution::id`], worked out once by
+/// [`Solutions::identify`]. Serialized, it's the solution's fields and `id`
+/// as 16 hex digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Identified<'a> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub solution: Solution<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "hex"))]
+    pub id: u64,
+}
+
+#[cfg(feature = "serde")]
+fn hex<S: serde::Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{:016x}", id))
+}
+
+/// One step of a hint towards a word the player hasn't found yet, from
+/// [`Solutions::hints`]. Each gives away more than the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Hint<'s> {
+    /// How many letters the word has.
+    Length(usize),
+    FirstLetter(char),
+    /// The cells spelling it out, which all but gives it away.
+    Path(&'s [(usize, usize)]),
+}
+
+/// Every word found on a board, with helpers for presenting them. Derefs to
+/// the underlying `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Solutions<'a>(pub Vec<Solution<'a>>);
+
+impl<'a> Solutions<'a> {
+    /// Each solution with its [`Solution::id`] on `board`, in the same
+    /// order, so ids are worked out once per solve rather than each time
+    /// they're shown.
+    pub fn identify(self, board: &Board) -> Vec<Identified<'a>> {
+        self.0.into_iter().map(|solution| Identified { id: solution.id(board), solution }).collect()
+    }
+
+    pub fn total_score(&self) -> u32 {
+        self.iter().map(|s| s.score).sum()
+    }
+
+    pub fn sort_alphabetically(&mut self) {
+        self.sort_by(|a, b| a.word.cmp(b.word));
+    }
+
+    /// Longest first, ties alphabetically.
+    pub fn sort_by_length(&mut self) {
+        self.sort_by(|a, b| b.len().cmp(&a.len()).then(a.word.cmp(b.word)));
+    }
+
+    /// Highest first, ties alphabetically.
+    pub fn sort_by_score(&mut self) {
+        self.sort_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(b.word)));
+    }
+
+    /// Groups the words by their first letter, keeping their current order
+    /// within each group.
+    pub fn by_first_letter(&self) -> BTreeMap<char, Vec<&Solution<'a>>> {
+        let mut groups = BTreeMap::new();
+        for solution in self.iter() {
+            if let Some(c) = solution.word.chars().next() {
+                groups.entry(c).or_insert_with(Vec::new).push(solution);
+            }
+        }
+        groups
+    }
+
+    /// Groups the words by the cell their path starts at, keeping their
+    /// current order within each group. Words without a path are left out.
+    pub fn by_start_cell(&self) -> BTreeMap<(usize, usize), Vec<&Solution<'a>>> {
+        let mut groups = BTreeMap::new();
+        for solution in self.iter().filter(|s| !s.path.is_empty()) {
+            groups.entry(solution.start()).or_insert_with(Vec::new).push(solution);
+        }
+        groups
+    }
+
+    /// The words not in `other`, in their current order, e.g. what a board
+    /// loses when one of its tiles is changed to give `other`.
+    pub fn difference(&self, other: &Solutions) -> Solutions<'a> {
+        let words: HashSet<_> = other.iter().map(|s| s.word).collect();
+        Solutions(self.iter().filter(|s| !words.contains(s.word)).cloned().collect())
+    }
+
+    /// The words also in `other`, in their current order, with their scores
+    /// and paths from `self`.
+    pub fn intersection(&self, other: &Solutions) -> Solutions<'a> {
+        let words: HashSet<_> = other.iter().map(|s| s.word).collect();
+        Solutions(self.iter().filter(|s| words.contains(s.word)).cloned().collect())
+    }
+
+    /// `by_length()[n]`: how many of the words have `n` letters.
+    pub fn by_length(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.iter().map(Solution::len).max().map_or(0, |n| n + 1)];
+        for solution in self.iter() {
+            counts[solution.len()] += 1;
+        }
+        counts
+    }
+
+    /// How many of the words score each number of points.
+    pub fn by_score(&self) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for solution in self.iter() {
+            *counts.entry(solution.score).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Hints towards the best word not in `found`: the highest-scoring, ties
+    /// alphabetically. They're graded, so show them one at a time: its
+    /// length, then its first letter, then its path. Empty once every word
+    /// has been found.
+    pub fn hints(&self, found: &[&str]) -> Vec<Hint<'_>> {
+        let next = self.iter()
+            .filter(|s| !found.contains(&s.word))
+            .min_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(b.word)));
+        match next {
+            Some(solution) => {
+                let mut hints = vec![Hint::Length(solution.len())];
+                hints.extend(solution.word.chars().next().map(Hint::FirstLetter));
+                if !solution.path.is_empty() {
+                    hints.push(Hint::Path(&solution.path));
+                }
+                hints
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Totals over every word a search found along with the best of them, for
+/// boards with more words than are worth keeping; see
+/// [`Board::summarize`](crate::Board::summarize).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Summary<'a> {
+    pub found: usize,
+    pub score: u32,
+    /// `by_length[n]`: how many of the words have `n` letters.
+    pub by_length: Vec<usize>,
+    /// The highest-scoring words, highest first, ties alphabetically: all of
+    /// them unless [`SolveOptions::keep`](crate::SolveOptions::keep) limits
+    /// how many.
+    pub best: Solutions<'a>,
+}
+
+impl<'a> From<Vec<Solution<'a>>> for Solutions<'a> {
+    fn from(solutions: Vec<Solution<'a>>) -> Self {
+        Solutions(solutions)
+    }
+}
+
+impl<'a> Deref for Solutions<'a> {
+    type Target = Vec<Solution<'a>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> DerefMut for Solutions<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a> IntoIterator for Solutions<'a> {
+    type Item = Solution<'a>;
+    type IntoIter = std::vec::IntoIter<Solution<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smoke() {
+        let solution = Solution { word: "abc", score: 1, path: vec![(0, 0), (0, 1), (1, 1)] };
+        assert_eq!(solution.len(), 3);
+        assert_eq!(solution.start(), (0, 0));
+    }
+
+    #[test]
+    fn id() {
+        // either way of tracing "aba" is the same finding
+        let board = Board::parse("ab\nba").unwrap();
+        let ids: HashSet<_> = [vec![(0, 0), (0, 1), (1, 1)], vec![(1, 1), (1, 0), (0, 0)]].iter()
+            .map(|path| Solution { word: "aba", score: 0, path: path.clone() }.id(&board))
+            .collect();
+        assert_eq!(ids.len(), 1);
+        assert!(ids.contains(&fnv1a(b"aba 0,0;0,1;1,1;")));
+        let bab = Solution { word: "bab", score: 0, path: vec![(0, 1), (0, 0), (1, 0)] };
+        assert!(!ids.contains(&bab.id(&board)));
+        // the same word traced elsewhere is another finding
+        assert_ne!(bab.id(&Board::parse("ba\nab").unwrap()), bab.id(&board));
+
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let dictionary = crate::Dictionary::parse(include_str!("../test/dictionary"));
+        let ids = |strategy: &dyn crate::strategy::SolveStrategy| {
+            let mut solutions = strategy.solve(&board, &dictionary);
+            solutions.sort_alphabetically();
+            solutions.iter().map(|s| s.id(&board)).collect::<Vec<_>>()
+        };
+        let expected = ids(crate::strategy::ALL[0]);
+        let mut solutions = crate::strategy::ALL[0].solve(&board, &dictionary);
+        solutions.sort_alphabetically();
+        assert_eq!(solutions.identify(&board).iter().map(|found| found.id).collect::<Vec<_>>(), expected);
+        assert_eq!(expected.iter().collect::<HashSet<_>>().len(), 126);
+        for &strategy in crate::strategy::ALL {
+            assert_eq!(ids(strategy), expected, "{}", strategy.name());
+        }
+    }
+
+    #[test]
+    fn solutions() {
+        let mut solutions: Solutions = [("bead", 1), ("ab", 0), ("abode", 2), ("bed", 1)]
+            .iter()
+            .map(|&(word, score)| Solution { word, score, path: Vec::new() })
+            .collect::<Vec<_>>()
+            .into();
+        let words = |s: &Solutions<'static>| s.iter().map(|s| s.word).collect::<Vec<_>>();
+        assert_eq!(solutions.total_score(), 4);
+
+        solutions.sort_alphabetically();
+        assert_eq!(words(&solutions), vec!["ab", "abode", "bead", "bed"]);
+        solutions.sort_by_length();
+        assert_eq!(words(&solutions), vec!["abode", "bead", "bed", "ab"]);
+        solutions.sort_by_score();
+        assert_eq!(words(&solutions), vec!["abode", "bead", "bed", "ab"]);
+
+        let groups = solutions.by_first_letter();
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec![&'a', &'b']);
+        assert_eq!(groups[&'b'].iter().map(|s| s.word).collect::<Vec<_>>(), vec!["bead", "bed"]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let solutions = |words: &[(&'static str, u32)]| Solutions(words.iter().map(|&(word, score)| Solution { word, score, path: Vec::new() }).collect());
+        let words = |s: Solutions<'static>| s.iter().map(|s| s.word).collect::<Vec<_>>();
+        let before = solutions(&[("cats", 1), ("cat", 1), ("tax", 1), ("act", 1)]);
+        let after = solutions(&[("act", 1), ("acts", 1), ("cat", 1), ("cats", 1)]);
+        assert_eq!(words(before.difference(&after)), ["tax"]);
+        assert_eq!(words(after.difference(&before)), ["acts"]);
+        assert_eq!(words(before.intersection(&after)), ["cats", "cat", "act"]);
+        assert!(before.difference(&before).is_empty());
+        assert_eq!(before.intersection(&Solutions::default()), Solutions::default());
+
+        let scored = solutions(&[("cat", 1), ("cats", 1), ("beacon", 3), ("abode", 2)]);
+        assert_eq!(scored.by_length(), [0, 0, 0, 1, 1, 1, 1]);
+        assert_eq!(scored.by_score().into_iter().collect::<Vec<_>>(), [(1, 2), (2, 1), (3, 1)]);
+        assert!(Solutions::default().by_length().is_empty());
+    }
+
+    #[test]
+    fn by_start_cell() {
+        let solutions = Solutions(vec![
+            Solution { word: "cats", score: 1, path: vec![(0, 0), (0, 1), (0, 2), (0, 3)] },
+            Solution { word: "tax", score: 1, path: vec![(0, 2), (0, 1), (1, 1)] },
+            Solution { word: "cat", score: 1, path: vec![(0, 0), (0, 1), (0, 2)] },
+            Solution { word: "dog", score: 1, path: Vec::new() },
+        ]);
+        let groups = solutions.by_start_cell();
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec![&(0, 0), &(0, 2)]);
+        assert_eq!(groups[&(0, 0)].iter().map(|s| s.word).collect::<Vec<_>>(), vec!["cats", "cat"]);
+        assert_eq!(groups[&(0, 2)][0].word, "tax");
+    }
+
+    #[test]
+    fn hints() {
+        let solutions = Solutions(vec![
+            Solution { word: "cat", score: 1, path: vec![(0, 0), (0, 1), (0, 2)] },
+            Solution { word: "cats", score: 1, path: vec![(0, 0), (0, 1), (0, 2), (0, 3)] },
+            Solution { word: "acts", score: 1, path: Vec::new() },
+            Solution { word: "sect", score: 2, path: vec![(0, 3), (1, 3), (0, 0), (0, 2)] },
+        ]);
+        assert_eq!(solutions.hints(&["cat"]), [
+            Hint::Length(4),
+            Hint::FirstLetter('s'),
+            Hint::Path(&[(0, 3), (1, 3), (0, 0), (0, 2)]),
+        ]);
+        // ties go alphabetically, and words without a path get no path hint
+        assert_eq!(solutions.hints(&["sect"]), [Hint::Length(4), Hint::FirstLetter('a')]);
+        assert_eq!(solutions.hints(&["sect", "acts", "cat"])[1], Hint::FirstLetter('c'));
+        assert!(solutions.hints(&["sect", "acts", "cat", "cats"]).is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! A dictionary prepared once and owned, for programs that keep one around
+//! between boards, e.g. a server or a batch job. [`Trie`](crate::Trie) and
+//! [`CompiledDictionary`] borrow their words, so holding one in a struct
+//! means holding the words alongside it; a [`Solver`] owns both.
+
+use crate::board::{Board, SolveOptions};
+use crate::compiled::{self, CompiledDictionary};
+use crate::error::Error;
+use crate::solution::Solution;
+
+/// Owns a compiled dictionary and solves any number of boards with it, from
+/// any number of threads at once.
+///
+/// ```
+/// use boggle::{Board, SolveOptions, Solver};
+///
+/// let solver = Solver::new("cat\ncats\ndog");
+/// for raw in &["cats\nxxxx\nxxxx\nxxxx", "dogx\nxxxx\nxxxx\nxxxx"] {
+///     let board = Board::parse(raw).unwrap();
+///     assert!(!solver.solve(&board, &SolveOptions::default()).is_empty());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Solver {
+    index: Vec<u8>,
+}
+
+impl Solver {
+    /// Compiles `words`, one per line, as [`compiled::compile`] does.
+    pub fn new(words: &str) -> Solver {
+        Solver { index: compiled::compile(words) }
+    }
+
+    /// Like [`Solver::new`], compiling on every core with
+    /// [`compiled::compile_parallel`], which is faster for big lists.
+    /// Without the `parallel` feature it's the same as `new`.
+    pub fn build_parallel(words: &str) -> Solver {
+        #[cfg(feature = "parallel")]
+        let index = compiled::compile_parallel(words);
+        #[cfg(not(feature = "parallel"))]
+        let index = compiled::compile(words);
+        Solver { index }
+    }
+
+    /// Takes a dictionary compiled ahead of time, e.g. read from a file made
+    /// with `boggle compile-dict`.
+    pub fn from_compiled(index: Vec<u8>) -> Result<Solver, Error> {
+        CompiledDictionary::from_bytes(&index)?;
+        Ok(Solver { index })
+    }
+
+    /// The dictionary, borrowed from the solver.
+    pub fn dictionary(&self) -> CompiledDictionary<'_> {
+        CompiledDictionary::from_bytes(&self.index).expect("checked when the solver was made")
+    }
+
+    /// Finds the words on `board`, as
+    /// [`Board::solve_compiled`] does; they borrow from the solver.
+    pub fn solve(&self, board: &Board, options: &SolveOptions) -> Vec<Solution<'_>> {
+        board.solve_compiled(&self.dictionary(), options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    // what a long-running program does: keep the solver in a struct and hand it to threads
+    struct Game {
+        solver: Arc<Solver>,
+    }
+
+    #[test]
+    fn solver() {
+        let game = Game { solver: Arc::new(Solver::new(include_str!("../test/dictionary"))) };
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let solver = game.solver.clone();
+                thread::spawn(move || {
+                    let board = Board::parse(include_str!("../test/board1")).unwrap();
+                    solver.solve(&board, &SolveOptions::default()).len()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 126);
+        }
+
+        let solver = Solver::from_compiled(compiled::compile("cat\ndog")).unwrap();
+        assert_eq!(solver.dictionary().words().collect::<Vec<_>>(), ["cat", "dog"]);
+        assert!(Solver::from_compiled(b"cat\n".to_vec()).is_err());
+        let words = include_str!("../test/dictionary");
+        assert_eq!(Solver::build_parallel(words).index, Solver::new(words).index);
+    }
+}
@@ -0,0 +1,162 @@
+//! Key-value persistence for features that keep data between runs, such as
+//! saved puzzles, stats and replays, so they don't depend on where the data
+//! lives. Keys are `/`-separated paths like `puzzles/2024-01-31`.
+
+use std::collections::BTreeMap;
+#[cfg(feature = "fs")]
+use std::fs;
+#[cfg(feature = "fs")]
+use std::io;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::Error;
+
+/// Somewhere to keep values by key. Implementations can be shared between
+/// threads, e.g. by a server's request handlers.
+pub trait Storage: Send + Sync {
+    /// The value stored under `key`, or `None` if there isn't one.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error>;
+
+    /// Stores `value` under `key`, replacing any earlier value.
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), Error>;
+
+    /// Every key starting with `prefix`, sorted.
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error>;
+}
+
+/// Keeps each value in a file under a root directory, with the key as its
+/// path.
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+#[cfg(feature = "fs")]
+impl FileStorage {
+    /// Storage under `root`, which is created on the first `put`.
+    pub fn new<P: Into<PathBuf>>(root: P) -> FileStorage {
+        FileStorage { root: root.into() }
+    }
+
+    // keys can't climb out of the root or name it
+    fn path(&self, key: &str) -> Result<PathBuf, Error> {
+        if key.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("invalid storage key {:?}", key)).into());
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[cfg(feature = "fs")]
+impl Storage for FileStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        match fs::read(self.path(key)?) {
+            Ok(value) => Ok(Some(value)),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), Error> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // written aside and renamed into place, so a reader never sees half a value
+        let partial = path.with_extension("partial");
+        fs::write(&partial, value)?;
+        fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        fn walk(dir: &Path, key: &str, keys: &mut Vec<String>) -> io::Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let child = if key.is_empty() { name } else { format!("{}/{}", key, name) };
+                if entry.file_type()?.is_dir() {
+                    walk(&entry.path(), &child, keys)?;
+                } else if !child.ends_with(".partial") {
+                    keys.push(child);
+                }
+            }
+            Ok(())
+        }
+
+        let mut keys = Vec::new();
+        match walk(&self.root, "", &mut keys) {
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => {}
+            result => result?,
+        }
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// Keeps values in memory, for tests and one-off runs.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    values: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> MemoryStorage {
+        MemoryStorage::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.values.lock().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), Error> {
+        self.values.lock().unwrap().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, Error> {
+        let values = self.values.lock().unwrap();
+        Ok(values.range(prefix.to_string()..).map(|(key, _)| key).take_while(|key| key.starts_with(prefix)).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(storage: &dyn Storage) {
+        assert_eq!(storage.get("puzzles/1").unwrap(), None);
+        storage.put("puzzles/1", b"cats").unwrap();
+        storage.put("puzzles/2", b"dogs").unwrap();
+        storage.put("stats", b"{}").unwrap();
+        storage.put("puzzles/1", b"tacs").unwrap();
+        assert_eq!(storage.get("puzzles/1").unwrap(), Some(b"tacs".to_vec()));
+        assert_eq!(storage.list("puzzles/").unwrap(), vec!["puzzles/1", "puzzles/2"]);
+        assert_eq!(storage.list("").unwrap(), vec!["puzzles/1", "puzzles/2", "stats"]);
+        assert!(storage.list("replays/").unwrap().is_empty());
+    }
+
+    #[test]
+    fn memory() {
+        round_trip(&MemoryStorage::new());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn files() {
+        let root = std::env::temp_dir().join(format!("boggle-storage-{}", std::process::id()));
+        let storage = FileStorage::new(&root);
+        assert!(storage.list("").unwrap().is_empty());
+        round_trip(&storage);
+        for bad in &["../escape", "/absolute", "a//b", "a/./b", ""] {
+            assert!(storage.put(bad, b"x").is_err(), "{:?}", bad);
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+}
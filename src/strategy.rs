@@ -0,0 +1,135 @@
+//! The plain solvers behind one trait, so a program can pick one by name and
+//! benchmarks and self-checks can run every one of them. Each finds every
+//! word of at least three letters and scores it with
+//! [`Classic`](crate::score::Classic); for rules, scorers and length limits
+//! use [`Board::solve`].
+
+use crate::board::Board;
+use crate::dawg::Dawg;
+use crate::dictionary::Dictionary;
+use crate::solution::Solutions;
+
+/// A way of finding the words of a dictionary on a board.
+///
+/// ```
+/// use boggle::strategy::{self, SolveStrategy};
+/// use boggle::{Board, Dictionary};
+///
+/// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+/// let dictionary = Dictionary::parse("cat\ncats\ndog");
+/// for strategy in strategy::ALL {
+///     assert_eq!(strategy.solve(&board, &dictionary).len(), 2, "{}", strategy.name());
+/// }
+/// ```
+pub trait SolveStrategy: Sync {
+    /// What it's called on the command line and in reports.
+    fn name(&self) -> &'static str;
+
+    /// Every word from `dict` on `board`, each once.
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a>;
+}
+
+/// [`Board::solve_single_threaded`]: each word searched for on its own.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SingleThreaded;
+
+/// [`Board::solve_trie`]: one search over a trie of the dictionary.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrieSearch;
+
+/// [`Board::solve_parallel`]: the trie search on every core.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParallelSearch;
+
+/// [`Board::solve_recursive`]: the trie search, backtracking rather than
+/// copying state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecursiveSearch;
+
+/// [`Board::solve_dawg`], building the [`Dawg`] for each board.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DawgSearch;
+
+impl SolveStrategy for SingleThreaded {
+    fn name(&self) -> &'static str {
+        "single-threaded"
+    }
+
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a> {
+        Solutions(board.solve_single_threaded(dict.as_str()))
+    }
+}
+
+impl SolveStrategy for TrieSearch {
+    fn name(&self) -> &'static str {
+        "trie"
+    }
+
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a> {
+        Solutions(board.solve_trie(dict.as_str()))
+    }
+}
+
+impl SolveStrategy for ParallelSearch {
+    fn name(&self) -> &'static str {
+        "parallel"
+    }
+
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a> {
+        Solutions(board.solve_parallel(dict.as_str()))
+    }
+}
+
+impl SolveStrategy for RecursiveSearch {
+    fn name(&self) -> &'static str {
+        "recursive"
+    }
+
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a> {
+        Solutions(board.solve_recursive(dict.as_str()))
+    }
+}
+
+impl SolveStrategy for DawgSearch {
+    fn name(&self) -> &'static str {
+        "dawg"
+    }
+
+    fn solve<'a>(&self, board: &Board, dict: &'a Dictionary) -> Solutions<'a> {
+        Solutions(board.solve_dawg(&Dawg::new(dict.as_str())))
+    }
+}
+
+/// Every strategy, the simplest first. New solvers are added here to be
+/// benchmarked and checked along with the rest.
+pub static ALL: &[&dyn SolveStrategy] = &[&SingleThreaded, &TrieSearch, &ParallelSearch, &RecursiveSearch, &DawgSearch];
+
+/// The strategy called `name`.
+pub fn find(name: &str) -> Option<&'static dyn SolveStrategy> {
+    ALL.iter().copied().find(|strategy| strategy.name() == name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn strategies() {
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let dictionary = Dictionary::parse(include_str!("../test/dictionary"));
+        let expected: BTreeSet<_> = crate::verify::oracle(&board, dictionary.as_str()).into_iter().collect();
+        for strategy in ALL {
+            let solutions = strategy.solve(&board, &dictionary);
+            let words: BTreeSet<_> = solutions.iter().map(|s| s.word).collect();
+            assert_eq!((&words, solutions.len()), (&expected, 126), "{}", strategy.name());
+            assert_eq!(solutions.total_score(), 165, "{}", strategy.name());
+            assert!(solutions.iter().all(|s| crate::validate_path(&board, s.word, &s.path).is_ok()), "{}", strategy.name());
+        }
+
+        let names: BTreeSet<_> = ALL.iter().map(|strategy| strategy.name()).collect();
+        assert_eq!(names.len(), ALL.len());
+        assert_eq!(find("trie").map(|strategy| strategy.name()), Some("trie"));
+        assert!(find("Trie").is_none() && find("").is_none());
+    }
+}
@@ -0,0 +1,176 @@
+//! The "word strings" puzzle: pick words from the board that share no
+//! tiles, scoring as many points as possible. A word may be traced along any
+//! of its paths, not just the one the solver found it on.
+
+use crate::board::Board;
+use crate::solution::Solution;
+
+/// Boards with at most this many cells are searched exactly, the rest
+/// greedily.
+pub const EXACT_CELLS: usize = 25;
+
+// ways of picking words an exact search tries before settling for the best so far
+const BUDGET: usize = 1_000_000;
+
+// the cells one way of tracing a word covers, as a bit per cell and as its path
+type Traced = (u64, Vec<(usize, usize)>);
+
+/// Words from [`word_strings`], no two sharing a cell, highest scoring first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordStrings<'a> {
+    /// Each word's path is the one it takes in this set.
+    pub words: Vec<Solution<'a>>,
+    pub score: u32,
+    /// Whether no other set scores more. False when the board was too big to
+    /// search exactly, or the search ran too long.
+    pub exact: bool,
+}
+
+/// The highest scoring set of `solutions` whose paths share no cells.
+pub fn word_strings<'a>(board: &Board, solutions: &[Solution<'a>]) -> WordStrings<'a> {
+    let mut candidates: Vec<&Solution<'a>> = solutions.iter().collect();
+    // most points per tile first, which is what greedy picks and makes a good first guess for the exact search
+    candidates.sort_by(|a, b| (b.score * a.len() as u32).cmp(&(a.score * b.len() as u32)).then(a.word.cmp(b.word)));
+    let greedy = greedy(board, &candidates);
+    if board.cells() > EXACT_CELLS {
+        return greedy;
+    }
+
+    // every way of tracing each word, as the cells it covers
+    let index = |&(x, y): &(usize, usize)| x * board.len() + y;
+    let paths: Vec<Vec<Traced>> = candidates.iter()
+        .map(|solution| {
+            let mut paths: Vec<Traced> = Vec::new();
+            board.find_path(solution.word.as_bytes(), |path| {
+                let mask = path.iter().fold(0, |mask, cell| mask | 1 << index(cell));
+                if paths.iter().all(|&(other, _)| other != mask) {
+                    paths.push((mask, path.to_vec()));
+                }
+                false
+            });
+            paths
+        })
+        .collect();
+
+    // sorted by score, the candidates left can't add more than their scores' sum
+    let mut by_score: Vec<usize> = (0..candidates.len()).collect();
+    by_score.sort_by_key(|&i| std::cmp::Reverse(candidates[i].score));
+    let mut rest = vec![0; by_score.len() + 1];
+    for i in (0..by_score.len()).rev() {
+        rest[i] = rest[i + 1] + candidates[by_score[i]].score;
+    }
+    let mut search = Search { paths: &paths, candidates: &candidates, by_score: &by_score, rest: &rest, best: (greedy.score, Vec::new()), budget: BUDGET };
+    let mut chosen = Vec::new();
+    search.pick(0, 0, 0, &mut chosen);
+    if search.best.1.is_empty() {
+        // nothing beat greedy
+        return WordStrings { exact: search.budget > 0, ..greedy };
+    }
+    let mut words: Vec<_> = search.best.1.iter()
+        .map(|&(i, path)| Solution { path: paths[i][path].1.clone(), ..candidates[i].clone() })
+        .collect();
+    words.sort_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(b.word)));
+    WordStrings { words, score: search.best.0, exact: search.budget > 0 }
+}
+
+// takes each word, in order, that still fits on the cells left
+fn greedy<'a>(board: &Board, candidates: &[&Solution<'a>]) -> WordStrings<'a> {
+    let mut used = vec![vec![false; board.len()]; board.len()];
+    let mut words = Vec::new();
+    for solution in candidates {
+        if let Some(path) = board.find_path(solution.word.as_bytes(), |path| path.iter().all(|&(x, y)| !used[x][y])) {
+            for &(x, y) in &path {
+                used[x][y] = true;
+            }
+            words.push(Solution { path: path.to_vec(), ..(*solution).clone() });
+        }
+    }
+    words.sort_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(b.word)));
+    WordStrings { score: words.iter().map(|s| s.score).sum(), words, exact: false }
+}
+
+struct Search<'s, 'a> {
+    paths: &'s [Vec<Traced>],
+    candidates: &'s [&'s Solution<'a>],
+    by_score: &'s [usize],
+    rest: &'s [u32],
+    // the best score found and the (candidate, path) pairs making it up; empty until something beats greedy
+    best: (u32, Vec<(usize, usize)>),
+    budget: usize,
+}
+
+impl<'s, 'a> Search<'s, 'a> {
+    // tries adding each candidate from `from` on, in score order, to the `chosen` ones covering `used`
+    fn pick(&mut self, from: usize, used: u64, score: u32, chosen: &mut Vec<(usize, usize)>) {
+        if score > self.best.0 {
+            self.best = (score, chosen.clone());
+        }
+        for next in from..self.by_score.len() {
+            if score + self.rest[next] <= self.best.0 || self.budget == 0 {
+                return;
+            }
+            let i = self.by_score[next];
+            for (path, &(mask, _)) in self.paths[i].iter().enumerate() {
+                if mask & used != 0 {
+                    continue;
+                }
+                if self.budget == 0 {
+                    return;
+                }
+                self.budget -= 1;
+                chosen.push((i, path));
+                self.pick(next + 1, used | mask, score + self.candidates[i].score, chosen);
+                chosen.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::SolveOptions;
+
+    #[test]
+    fn strings() {
+        // "cats" scores no more than "cat", so the extra tile isn't worth spending
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let solutions = board.solve("cat\ncats\nact\nat", &SolveOptions::default());
+        let strings = word_strings(&board, &solutions);
+        assert!(strings.exact);
+        assert_eq!(strings.words.iter().map(|s| s.word).collect::<Vec<_>>(), ["cat"]);
+        assert_eq!(strings.score, 1);
+
+        // greedy takes "seat" first, for the most points per tile, but "sea" and "tab" score more together
+        let board = Board::parse("seax\nxxtx\nxxab\nxxxx").unwrap();
+        let mut solutions = board.solve("seat\nsea\ntab", &SolveOptions::default());
+        for solution in &mut solutions {
+            solution.score = if solution.word == "seat" { 3 } else { 2 };
+        }
+        assert_eq!(greedy_score(&board, &solutions), 3);
+        let strings = word_strings(&board, &solutions);
+        assert_eq!(strings.words.iter().map(|s| s.word).collect::<Vec<_>>(), ["sea", "tab"]);
+        assert_eq!(strings.score, 4);
+        assert!(strings.exact);
+
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        let solutions = board.solve(include_str!("../test/dictionary"), &SolveOptions::default());
+        let strings = word_strings(&board, &solutions);
+        let mut cells: Vec<_> = strings.words.iter().flat_map(|s| s.path.iter().copied()).collect();
+        let len = cells.len();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), len);
+        assert!(strings.score >= greedy_score(&board, &solutions));
+        for solution in &strings.words {
+            assert_eq!(crate::board::validate_path(&board, solution.word, &solution.path), Ok(()));
+        }
+        assert_eq!(word_strings(&board, &[]), WordStrings { exact: true, ..WordStrings::default() });
+    }
+
+    fn greedy_score(board: &Board, solutions: &[Solution]) -> u32 {
+        let mut candidates: Vec<_> = solutions.iter().collect();
+        candidates.sort_by(|a, b| (b.score * a.len() as u32).cmp(&(a.score * b.len() as u32)).then(a.word.cmp(b.word)));
+        greedy(board, &candidates).score
+    }
+}
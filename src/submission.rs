@@ -0,0 +1,118 @@
+//! Checks a community-submitted board goes through before it's accepted:
+//! that real dice could roll it, that no banned word reads along a straight
+//! line, and that it isn't a near copy of a board already stored.
+
+use crate::board::Board;
+use crate::dice::{self, Dice};
+
+/// What to check a submission against.
+#[derive(Debug, Clone, Copy)]
+pub struct Checks<'c> {
+    /// The dice the board must be a roll of, or `None` to skip the check.
+    pub dice: Option<&'c Dice>,
+    /// Words, one per line, that mustn't read forwards or backwards along a
+    /// row, column or diagonal.
+    pub banned: &'c str,
+    /// Boards already accepted.
+    pub existing: &'c [Board],
+    /// Boards differing from an existing one in fewer cells than this, after
+    /// rotating and reflecting, are near duplicates; defaults to 3.
+    pub min_distance: usize,
+}
+
+impl Default for Checks<'_> {
+    fn default() -> Self {
+        Checks { dice: None, banned: "", existing: &[], min_distance: 3 }
+    }
+}
+
+/// How a submission fared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Report<'c> {
+    /// Whether the dice can roll the board, if they were checked.
+    pub legal_roll: Option<bool>,
+    /// Banned words found along straight lines.
+    pub banned: Vec<&'c str>,
+    /// The closest existing board, by index into `Checks::existing`, and how
+    /// many cells it differs in.
+    pub nearest: Option<(usize, usize)>,
+    pub accepted: bool,
+}
+
+/// Runs every check on `board`.
+pub fn review<'c>(board: &Board, checks: &Checks<'c>) -> Report<'c> {
+    let legal_roll = checks.dice.map(|dice| dice::can_roll(dice, board));
+
+    let lines = straight_lines(board);
+    let banned: Vec<_> = checks.banned.lines()
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .filter(|w| {
+            let reversed: String = w.chars().rev().collect();
+            lines.iter().any(|line| line.contains(&w.to_ascii_lowercase()) || line.contains(&reversed.to_ascii_lowercase()))
+        })
+        .collect();
+
+    let nearest = checks.existing.iter()
+        .enumerate()
+        .map(|(i, other)| (i, board.distance(other)))
+        .min_by_key(|&(_, distance)| distance);
+
+    let accepted = legal_roll != Some(false)
+        && banned.is_empty()
+        && nearest.is_none_or(|(_, distance)| distance >= checks.min_distance);
+    Report { legal_roll, banned, nearest, accepted }
+}
+
+// every row, column and diagonal as text, a "Qu" tile spelling both letters
+fn straight_lines(board: &Board) -> Vec<String> {
+    let n = board.len() as isize;
+    let mut starts = Vec::new();
+    for i in 0..n {
+        starts.push(((i, 0), (0, 1)));
+        starts.push(((0, i), (1, 0)));
+        starts.push(((i, 0), (1, 1)));
+        starts.push(((0, i), (1, -1)));
+        if i > 0 {
+            starts.push(((0, i), (1, 1)));
+            starts.push(((i, n - 1), (1, -1)));
+        }
+    }
+    starts.into_iter()
+        .map(|((mut x, mut y), (dx, dy))| {
+            let mut line = Vec::new();
+            while board.get((x, y)).is_some() {
+                line.extend_from_slice(board.tile((x as usize, y as usize)));
+                x += dx;
+                y += dy;
+            }
+            String::from_utf8(line).expect("tiles are ascii")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dice::CLASSIC;
+
+    #[test]
+    fn review() {
+        let board = Board::parse("zoon\ntaxe\nrbsi\nvycm").unwrap();
+        let lines = straight_lines(&board);
+        assert_eq!(lines.len(), 4 + 4 + 7 + 7);
+        assert!(lines.contains(&"zasm".to_string()) && lines.contains(&"nxbv".to_string()));
+
+        let existing = [Board::parse("zoon\ntaxe\nrbsi\nvyce").unwrap(), Board::parse("abcd\nefgh\nijkl\nmnop").unwrap()];
+        let checks = Checks { dice: Some(&CLASSIC), banned: "BAT\nzoo\nsax\nMSAZ", existing: &existing, ..Checks::default() };
+        let report = super::review(&board, &checks);
+        assert_eq!(report, Report { legal_roll: Some(true), banned: vec!["zoo", "MSAZ"], nearest: Some((0, 1)), accepted: false });
+
+        let report = super::review(&board, &Checks { dice: Some(&CLASSIC), ..Checks::default() });
+        assert!(report.accepted);
+        let report = super::review(&Board::parse("qoon\ntaxe\nrbsi\nvycm").unwrap(), &Checks { dice: Some(&CLASSIC), ..Checks::default() });
+        assert_eq!(report.legal_roll, Some(false));
+        assert!(!report.accepted);
+    }
+}
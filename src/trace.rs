@@ -0,0 +1,115 @@
+//! A record of how a search explored the board, for debugging pruning and
+//! for showing how the algorithm works. See [`Board::trace`](crate::Board::trace).
+
+use std::fmt;
+
+/// What happened when the search reached a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "snake_case"))]
+pub enum Step {
+    /// A prefix of some word, explored further.
+    Prefix,
+    /// A word, found for the first time, and explored further.
+    Word,
+    /// A word already found along another path, explored further.
+    Repeat,
+    /// A prefix none of whose next letters are next to the cell, so it
+    /// wasn't explored.
+    DeadEnd,
+    /// Longer than the maximum word length, so it wasn't explored.
+    TooLong,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Step::*;
+        f.write_str(match *self {
+            Prefix => "prefix",
+            Word => "word",
+            Repeat => "repeat",
+            DeadEnd => "dead_end",
+            TooLong => "too_long",
+        })
+    }
+}
+
+/// One cell reached by the search. Steps into cells that spell no prefix of
+/// any word aren't recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TraceNode<'a> {
+    /// The index in [`Trace::nodes`] of the node this one was reached from,
+    /// `None` for a starting cell.
+    pub parent: Option<usize>,
+    pub cell: (usize, usize),
+    /// The letters spelled on the way here, this cell's included.
+    pub prefix: &'a str,
+    pub step: Step,
+}
+
+/// The cells a search reached, in the order it reached them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Trace<'a> {
+    pub nodes: Vec<TraceNode<'a>>,
+    /// The search went on past the node limit; later nodes weren't recorded.
+    pub truncated: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    limit: usize,
+}
+
+impl<'a> Trace<'a> {
+    /// An empty trace that stops recording after `limit` nodes.
+    pub fn new(limit: usize) -> Trace<'a> {
+        Trace { nodes: Vec::new(), truncated: false, limit }
+    }
+
+    /// A Graphviz graph of the search tree. Found words have a double
+    /// border, and pruned steps are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph search {\n");
+        for (id, node) in self.nodes.iter().enumerate() {
+            let attributes = match node.step {
+                Step::Prefix => "",
+                Step::Word => ", peripheries=2",
+                Step::Repeat => ", peripheries=2, color=gray",
+                Step::DeadEnd | Step::TooLong => ", style=dashed",
+            };
+            dot.push_str(&format!("    n{} [label=\"{} {:?}\"{}];\n", id, node.prefix, node.cell, attributes));
+            if let Some(parent) = node.parent {
+                dot.push_str(&format!("    n{} -> n{};\n", parent, id));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// the search's hook for recording what it does; `()` records nothing, so untraced searches pay nothing
+pub(crate) trait Tracer<'a> {
+    // records a step, returning its id to pass as the parent of steps from it
+    fn step(&mut self, parent: Option<usize>, cell: (usize, usize), prefix: &'a str, step: Step) -> usize;
+
+    // asked every so often, stopping the search for good once it says yes
+    fn cancelled(&mut self) -> bool {
+        false
+    }
+}
+
+impl<'a> Tracer<'a> for () {
+    fn step(&mut self, _: Option<usize>, _: (usize, usize), _: &'a str, _: Step) -> usize {
+        0
+    }
+}
+
+impl<'a> Tracer<'a> for Trace<'a> {
+    fn step(&mut self, parent: Option<usize>, cell: (usize, usize), prefix: &'a str, step: Step) -> usize {
+        if self.nodes.len() >= self.limit {
+            // nothing after this gets recorded, so the id is never looked up
+            self.truncated = true;
+            return usize::MAX;
+        }
+        self.nodes.push(TraceNode { parent, cell, prefix, step });
+        self.nodes.len() - 1
+    }
+}
@@ -1,95 +1,478 @@
-use std::cell::Cell;
-use std::ops::Index;
+use std::str;
 
-use typed_arena::Arena;
+use crate::alphabet::{is_letter, Alphabet, LETTERS};
 
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
-pub struct TrieNode<'trie, 'word: 'trie> {
+/// A trie over lowercase words, built with [`Trie::insert`] and then
+/// only read, so one trie can be searched from many threads at once.
+///
+/// Nodes live in one `Vec` and are addressed by index, the root being
+/// [`Trie::ROOT`]. Which words a search has found is up to the search to
+/// track, e.g. with a [`Seen`] set.
+///
+/// With the `serde` feature a trie serializes as its nodes in index order,
+/// each with its prefix, `sources` and the indices of its children in letter
+/// order, so a snapshot shows its exact shape. Deserializing borrows the
+/// prefixes from the input, as the trie borrows them from its word list, and
+/// checks the nodes still form a trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trie<'word> {
+    nodes: Vec<TrieNode<'word>>,
+    words: usize,
+}
+
+/// A node of a [`Trie`], standing for the prefix in `word`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieNode<'word> {
     pub word: &'word [u8],
-    pub word_end: bool,
-    pub seen: Cell<bool>,
-    pub roots: [Cell<Option<&'trie TrieNode<'trie, 'word>>>; 26]
+    /// Bit `i` is set when dictionary `i` has `word` as a whole word; see
+    /// [`Trie::from_dictionaries`]. Zero for prefixes that aren't words.
+    pub sources: u8,
+    /// Bit `c - b'a'` is set when the node has a child for letter `c`.
+    pub children: u32,
+    // indices into `Trie::nodes`; 0 is the root, which is never a child, so it means no child
+    roots: [u32; LETTERS],
+    // the length of the longest word through the node, saturating, so searches can go for long words first
+    longest: u16,
+}
+
+impl<'word> Default for Trie<'word> {
+    fn default() -> Self {
+        Trie::new()
+    }
 }
 
-impl<'trie, 'word> TrieNode<'trie, 'word> {
-    pub fn root(arena: &'trie Arena<TrieNode<'trie, 'word>>) -> &'trie TrieNode<'trie, 'word> {
-        TrieNode::new(false, &[], arena)
-    }
-
-    pub fn new(word_end: bool, word: &'word [u8], arena: &'trie Arena<TrieNode<'trie, 'word>>) -> &'trie TrieNode<'trie, 'word> {
-        arena.alloc(TrieNode {
-            word_end,
-            word,
-            seen: Cell::new(false),
-            roots: [
-                Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None),
-                Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None),
-                Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None),
-                Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None),
-                Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None), Cell::new(None),
-                Cell::new(None),
-            ]
-        })
-    }
-
-    pub fn insert(&'trie self, word: &'word [u8], arena: &'trie Arena<TrieNode<'trie, 'word>>) {
-        let mut last = self;
+impl<'word> Trie<'word> {
+    /// The index of the root node.
+    pub const ROOT: usize = 0;
+
+    /// The most dictionaries one trie can tell apart.
+    pub const MAX_SOURCES: usize = 8;
+
+    /// An empty trie.
+    pub fn new() -> Trie<'word> {
+        Trie { nodes: vec![TrieNode::new(&[])], words: 0 }
+    }
+
+    /// A trie of `words`, one per line, to search any number of boards with
+    /// [`Board::solve_with_trie`](crate::Board::solve_with_trie).
+    /// Surrounding whitespace and blank lines are ignored, as are words
+    /// shorter than 3 letters, which no solver reports, and words with
+    /// characters other than `a..=z`; see [`Trie::from_dictionary_in`] for
+    /// words with an alphabet's extra letters.
+    ///
+    /// ```
+    /// use boggle::{Board, SolveOptions, Trie};
+    ///
+    /// let trie = Trie::from_dictionary("cat\ncats\ndog");
+    /// for raw in &["cats\nxxxx\nxxxx\nxxxx", "dogx\nxxxx\nxxxx\nxxxx"] {
+    ///     let board = Board::parse(raw).unwrap();
+    ///     assert!(!board.solve_with_trie(&trie, &SolveOptions::default()).is_empty());
+    /// }
+    /// ```
+    pub fn from_dictionary(words: &'word str) -> Trie<'word> {
+        Trie::from_dictionary_in(words, &Alphabet::english())
+    }
+
+    /// Like `from_dictionary`, but words may also have `alphabet`'s extra
+    /// letters, encoded as [`Dictionary::parse_in`](crate::Dictionary::parse_in)
+    /// leaves them.
+    ///
+    /// ```
+    /// use boggle::alphabet::Alphabet;
+    /// use boggle::{Dictionary, Trie};
+    ///
+    /// let dictionary = Dictionary::parse_in("año\ncat\n", &Alphabet::spanish());
+    /// assert_eq!(Trie::from_dictionary_in(dictionary.as_str(), &Alphabet::spanish()).len(), 2);
+    /// assert_eq!(Trie::from_dictionary(dictionary.as_str()).len(), 1);
+    /// ```
+    pub fn from_dictionary_in(words: &'word str, alphabet: &Alphabet) -> Trie<'word> {
+        let mut trie = Trie::new();
+        for word in words.lines().map(str::trim) {
+            if word.len() >= 3 && word.bytes().all(|c| alphabet.is_letter(c)) {
+                trie.insert(word.as_bytes());
+            }
+        }
+        debug!("built a trie of {} words in {} nodes", trie.len(), trie.node_count());
+        trie
+    }
+
+    /// A trie of every word in each of `dictionaries`, read as
+    /// `from_dictionary` reads one, recording which of them has each word:
+    /// bit `i` of [`Trie::sources`] stands for `dictionaries[i]`. A word in
+    /// several is found once.
+    ///
+    /// ```
+    /// use boggle::Trie;
+    ///
+    /// let trie = Trie::from_dictionaries(&["cat\ndog", "cat\ncats"]);
+    /// assert_eq!(trie.sources(b"cat"), 0b11);
+    /// assert_eq!(trie.sources(b"cats"), 0b10);
+    /// assert_eq!(trie.sources(b"ca"), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If there are more than [`Trie::MAX_SOURCES`] dictionaries.
+    pub fn from_dictionaries(dictionaries: &[&'word str]) -> Trie<'word> {
+        let mut trie = Trie::new();
+        for (source, words) in dictionaries.iter().enumerate() {
+            for word in words.lines().map(str::trim) {
+                if word.len() >= 3 && word.bytes().all(|c| c.is_ascii_lowercase()) {
+                    trie.insert_from(word.as_bytes(), source);
+                }
+            }
+        }
+        debug!("built a trie of {} words from {} dictionaries in {} nodes", trie.len(), dictionaries.len(), trie.node_count());
+        trie
+    }
+
+    /// Adds `word`, which must be all letters as [`is_letter`] sees them, as a
+    /// word of dictionary 0.
+    pub fn insert(&mut self, word: &'word [u8]) {
+        self.insert_from(word, 0);
+    }
+
+    /// Adds `word`, which must be all letters as [`is_letter`] sees them, as a
+    /// word of dictionary `source`.
+    ///
+    /// # Panics
+    ///
+    /// If `source` isn't below [`Trie::MAX_SOURCES`].
+    pub fn insert_from(&mut self, word: &'word [u8], source: usize) {
+        assert!(source < Trie::MAX_SOURCES, "a trie tells apart at most {} dictionaries", Trie::MAX_SOURCES);
+        let mut last = Trie::ROOT;
+        self.nodes[last].lengthen(word.len());
         for l in 0..word.len() {
-            let c = word[l];
-            let root = last[c].take();
-            let child = if let Some(root) = root {
-                root
-            } else {
-                TrieNode::new(l == word.len() - 1, &word[..l+1], arena)
+            let idx = (word[l] - b'a') as usize;
+            let child = match self.nodes[last].roots[idx] {
+                0 => {
+                    self.nodes.push(TrieNode::new(&word[..l + 1]));
+                    let child = self.nodes.len() - 1;
+                    self.nodes[last].roots[idx] = child as u32;
+                    self.nodes[last].children |= letter_bit(word[l]);
+                    child
+                }
+                child => child as usize,
             };
-            last[c].set(Some(child));
+            self.nodes[child].lengthen(word.len());
             last = child;
         }
-        
+        if self.nodes[last].sources == 0 {
+            self.words += 1;
+        }
+        self.nodes[last].sources |= 1 << source;
+    }
+
+    /// The number of distinct words inserted.
+    pub fn len(&self) -> usize {
+        self.words
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.words == 0
+    }
+
+    /// The number of nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The node at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` isn't a node of this trie.
+    pub fn node(&self, index: usize) -> &TrieNode<'word> {
+        &self.nodes[index]
+    }
+
+    /// Whether `word` is a prefix of some inserted word.
     pub fn contains(&self, word: &[u8]) -> bool {
-        let mut last = self;
-        for &c in word {
-            if let Some(root) = last.get(c) {
-                last = root;
-            } else {
-                return false;
+        self.step(Trie::ROOT, word).is_some()
+    }
+
+    /// Which dictionaries have `word`, a bit per dictionary as in
+    /// [`TrieNode::sources`]; zero if none do.
+    pub fn sources(&self, word: &[u8]) -> u8 {
+        self.step(Trie::ROOT, word).map_or(0, |node| self.nodes[node].sources)
+    }
+
+    /// Follows `letters` down from node `index`, e.g. both letters of a "Qu"
+    /// tile.
+    pub fn step(&self, index: usize, letters: &[u8]) -> Option<usize> {
+        letters.iter().try_fold(index, |node, &c| self.get(node, c))
+    }
+
+    /// A Graphviz graph of the trie down to `max_depth` letters, for
+    /// debugging: each node is labelled with its prefix, and whole words are
+    /// drawn with a double border. Nodes with children past the cutoff are
+    /// dashed.
+    pub fn to_dot(&self, max_depth: usize) -> String {
+        self.to_dot_from(Trie::ROOT, max_depth)
+    }
+
+    /// Like `to_dot`, but only the part of the trie under node `index`, down
+    /// to `max_depth` letters below it.
+    pub fn to_dot_from(&self, index: usize, max_depth: usize) -> String {
+        let mut dot = String::from("digraph trie {\n");
+        let mut stack = vec![(index, 0)];
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            let mut attributes = format!("label={:?}", String::from_utf8_lossy(node.word));
+            if node.is_word() {
+                attributes.push_str(", peripheries=2");
+            }
+            if depth == max_depth && node.children != 0 {
+                attributes.push_str(", style=dashed");
+            }
+            dot.push_str(&format!("    n{} [{}];\n", index, attributes));
+            if depth == max_depth {
+                continue;
+            }
+            let children: Vec<_> = (b'a'..b'a' + LETTERS as u8).filter_map(|c| Some((c, self.get(index, c)?))).collect();
+            for &(c, child) in &children {
+                dot.push_str(&format!("    n{} -> n{} [label=\"{}\"];\n", index, child, c as char));
+            }
+            // so the first letter's subtree comes out first
+            stack.extend(children.iter().rev().map(|&(_, child)| (child, depth + 1)));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The words that can be spelled with some or all of the letters in
+    /// `rack`, each used at most as often as it's there, in alphabetical
+    /// order. A `?` in the rack stands for any one letter; anything else
+    /// that isn't a letter is ignored.
+    ///
+    /// ```
+    /// use boggle::Trie;
+    ///
+    /// let trie = Trie::from_dictionary("act\ncat\ncats\ntact\ndog");
+    /// assert_eq!(trie.anagrams(b"tac"), ["act", "cat"]);
+    /// assert_eq!(trie.anagrams(b"tac?"), ["act", "cat", "cats", "tact"]);
+    /// ```
+    pub fn anagrams(&self, rack: &[u8]) -> Vec<&'word str> {
+        let mut counts = [0usize; LETTERS];
+        let mut blanks = 0;
+        for &c in rack {
+            if c == b'?' {
+                blanks += 1;
+            } else if is_letter(c) {
+                counts[(c - b'a') as usize] += 1;
             }
         }
-        true
+        let mut words = Vec::new();
+        self.anagrams_from(Trie::ROOT, &mut counts, blanks, &mut words);
+        words
     }
 
-    pub fn get(&self, c: u8) -> Option<&'trie TrieNode<'trie, 'word>> {
-        if c < b'a' || c > b'z' {
-            None
-        } else {
-            let idx = (c - b'a') as usize;
-            let child = self.roots[idx].take();
-            self.roots[idx].set(child);
-            child
+    // the words under node `index` spelled with what's left of the rack
+    fn anagrams_from(&self, index: usize, counts: &mut [usize; LETTERS], blanks: usize, words: &mut Vec<&'word str>) {
+        let node = &self.nodes[index];
+        if node.is_word() {
+            words.push(as_str(node.word));
+        }
+        for (i, &child) in node.roots.iter().enumerate().filter(|&(_, &child)| child != 0) {
+            if counts[i] > 0 {
+                counts[i] -= 1;
+                self.anagrams_from(child as usize, counts, blanks, words);
+                counts[i] += 1;
+            } else if blanks > 0 {
+                self.anagrams_from(child as usize, counts, blanks - 1, words);
+            }
+        }
+    }
+
+    /// The words matching `pattern`, in alphabetical order: `?` matches any
+    /// one letter, `*` any run of letters, including none, and anything else
+    /// only itself.
+    ///
+    /// ```
+    /// use boggle::Trie;
+    ///
+    /// let trie = Trie::from_dictionary("cat\ncats\ncot\ncourt\ndog");
+    /// assert_eq!(trie.matching(b"c?t"), ["cat", "cot"]);
+    /// assert_eq!(trie.matching(b"c*t*"), ["cat", "cats", "cot", "court"]);
+    /// ```
+    pub fn matching(&self, pattern: &[u8]) -> Vec<&'word str> {
+        // a node may be reached at the same place in the pattern along several paths through a `*`
+        let mut visited = vec![Seen::new(self); pattern.len() + 1];
+        let mut words = Vec::new();
+        let mut stack = vec![(Trie::ROOT, 0)];
+        while let Some((index, i)) = stack.pop() {
+            if !visited[i].insert(index) {
+                continue;
+            }
+            let children = self.nodes[index].roots.iter().filter(|&&child| child != 0).map(|&child| child as usize);
+            match pattern.get(i) {
+                None if self.nodes[index].is_word() => words.push(as_str(self.nodes[index].word)),
+                None => {}
+                Some(b'*') => {
+                    stack.push((index, i + 1));
+                    stack.extend(children.map(|child| (child, i)));
+                }
+                Some(b'?') => stack.extend(children.map(|child| (child, i + 1))),
+                Some(&c) => stack.extend(self.get(index, c).map(|child| (child, i + 1))),
+            }
+        }
+        words.sort_unstable();
+        words
+    }
+
+    /// The child of node `index` for letter `c`, if any.
+    pub fn get(&self, index: usize, c: u8) -> Option<usize> {
+        if !is_letter(c) {
+            return None;
+        }
+        match self.nodes[index].roots[(c - b'a') as usize] {
+            0 => None,
+            child => Some(child as usize),
         }
     }
 }
 
-impl<'trie, 'word> Index<u8> for TrieNode<'trie, 'word> {
-    type Output = Cell<Option<&'trie TrieNode<'trie, 'word>>>;
+impl<'word> TrieNode<'word> {
+    fn new(word: &'word [u8]) -> TrieNode<'word> {
+        TrieNode { word, sources: 0, children: 0, roots: [0; LETTERS], longest: 0 }
+    }
+
+    /// Whether the node's prefix is a whole word.
+    pub fn is_word(&self) -> bool {
+        self.sources != 0
+    }
+
+    /// The length of the longest word starting with the node's prefix, or 0
+    /// if there's none. Lengths past `u16::MAX` read as `u16::MAX`.
+    pub fn longest(&self) -> usize {
+        self.longest as usize
+    }
 
-    fn index(&self, c: u8) -> &Self::Output {
-        assert!(c >= b'a');
-        assert!(c <= b'z');
-        let idx = (c - b'a') as usize;
-        &self.roots[idx]
+    fn lengthen(&mut self, len: usize) {
+        self.longest = self.longest.max(len.min(u16::MAX as usize) as u16);
     }
 }
+
+#[cfg(feature = "serde")]
+mod serialize {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{as_str, Trie, TrieNode};
+    use crate::alphabet::is_letter;
+
+    #[derive(Serialize, Deserialize)]
+    struct RawTrie<'a> {
+        #[serde(borrow)]
+        nodes: Vec<RawNode<'a>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct RawNode<'a> {
+        word: &'a str,
+        sources: u8,
+        children: Vec<u32>,
+    }
+
+    impl<'word> Serialize for Trie<'word> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let nodes = self.nodes.iter()
+                .map(|node| RawNode {
+                    word: as_str(node.word),
+                    sources: node.sources,
+                    children: node.roots.iter().copied().filter(|&child| child != 0).collect(),
+                })
+                .collect();
+            RawTrie { nodes }.serialize(serializer)
+        }
+    }
+
+    impl<'de: 'word, 'word> Deserialize<'de> for Trie<'word> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let raw = RawTrie::deserialize(deserializer)?;
+            match raw.nodes.first() {
+                Some(root) if root.word.is_empty() => {}
+                _ => return Err(D::Error::custom("a trie starts with its root, whose prefix is empty")),
+            }
+            let mut nodes: Vec<_> = raw.nodes.iter().map(|node| TrieNode::new(node.word.as_bytes())).collect();
+            let mut parents = vec![0; nodes.len()];
+            for (index, node) in raw.nodes.iter().enumerate() {
+                if !node.word.bytes().all(is_letter) {
+                    return Err(D::Error::custom(format!("node {} has a prefix that isn't all letters", index)));
+                }
+                nodes[index].sources = node.sources;
+                for &child in &node.children {
+                    // each child is one letter longer than its parent, so with one parent apiece the
+                    // nodes can't loop back on themselves
+                    let word = match raw.nodes.get(child as usize) {
+                        Some(other) if child != 0 => other.word.as_bytes(),
+                        _ => return Err(D::Error::custom(format!("node {} has a child {} that isn't a node", index, child))),
+                    };
+                    let c = *word.last().unwrap_or(&0);
+                    if word.len() != node.word.len() + 1 || !word.starts_with(node.word.as_bytes()) || !is_letter(c)
+                        || nodes[index].roots[(c - b'a') as usize] != 0 {
+                        return Err(D::Error::custom(format!("node {} isn't a child of node {}", child, index)));
+                    }
+                    nodes[index].roots[(c - b'a') as usize] = child;
+                    nodes[index].children |= super::letter_bit(c);
+                    parents[child as usize] += 1;
+                }
+            }
+            if let Some(orphan) = (1..nodes.len()).find(|&index| parents[index] != 1) {
+                return Err(D::Error::custom(format!("node {} isn't the child of exactly one node", orphan)));
+            }
+            let mut trie = Trie { nodes, words: 0 };
+            for node in raw.nodes.iter().filter(|node| node.sources != 0) {
+                // every node now hangs off the root, along the letters of its prefix
+                trie.words += 1;
+                let mut at = Trie::ROOT;
+                trie.nodes[at].lengthen(node.word.len());
+                for &c in node.word.as_bytes() {
+                    at = trie.get(at, c).expect("nodes form a trie");
+                    trie.nodes[at].lengthen(node.word.len());
+                }
+            }
+            Ok(trie)
+        }
+    }
+}
+
+// a word or prefix, which is all letters and so ascii
+fn as_str(word: &[u8]) -> &str {
+    str::from_utf8(word).expect("letters are ascii")
+}
+
+/// The bit for `c` in a `TrieNode::children`-style letter mask.
+pub fn letter_bit(c: u8) -> u32 {
+    1 << (c - b'a')
+}
+
+/// One bit per trie node, for a search to note the words it has found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seen(Vec<u64>);
+
+impl Seen {
+    /// Nothing seen yet in `trie`.
+    pub fn new(trie: &Trie) -> Seen {
+        Seen(vec![0; trie.node_count().div_ceil(64)])
+    }
+
+    /// Marks node `index`, returning whether it wasn't marked before.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let fresh = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        fresh
+    }
+}
+
 #[test]
 fn smoke() {
-    let arena = Arena::new();
-    let trie = TrieNode::root(&arena);
-    let words: &[&[u8]] = &[b"test", b"foo", b"bar", b"baz"];
+    let mut trie = Trie::new();
+    let words: &[&[u8]] = &[b"test", b"foo", b"bar", b"baz", b"ba", b"foo"];
 
     for word in words {
-        trie.insert(word, &arena);
+        trie.insert(word);
     }
 
     assert!(trie.contains(b"test"));
@@ -97,4 +480,57 @@ fn smoke() {
     assert!(trie.contains(b"bar"));
     assert!(trie.contains(b"baz"));
     assert!(!trie.contains(b"dne"));
-}
\ No newline at end of file
+    assert_eq!(trie.len(), 5);
+    assert_eq!(trie.node(Trie::ROOT).children, letter_bit(b't') | letter_bit(b'f') | letter_bit(b'b'));
+    let ba = trie.step(Trie::ROOT, b"ba").unwrap();
+    assert_eq!(trie.node(ba).children, letter_bit(b'r') | letter_bit(b'z'));
+    // a word that's a prefix of one inserted earlier still ends a word
+    assert!(trie.node(ba).is_word());
+    assert!(!trie.node(trie.step(Trie::ROOT, b"b").unwrap()).is_word());
+    assert_eq!(trie.sources(b"foo"), 1);
+    assert_eq!(trie.node(Trie::ROOT).longest(), 4);
+    assert_eq!(trie.node(ba).longest(), 3);
+
+    let mut seen = Seen::new(&trie);
+    assert!(seen.insert(ba));
+    assert!(!seen.insert(ba));
+    assert!(seen.insert(trie.node_count() - 1));
+
+    let mut trie = Trie::new();
+    trie.insert(b"qua");
+    trie.insert(b"quay");
+    trie.insert(b"ox");
+    let qu = trie.step(Trie::ROOT, b"qu").unwrap();
+    let dot = trie.to_dot_from(qu, 1);
+    assert!(dot.starts_with("digraph trie {\n"));
+    assert!(dot.contains(&format!("n{} [label=\"qu\"];", qu)));
+    assert!(dot.contains("[label=\"qua\", peripheries=2, style=dashed];"));
+    assert!(!dot.contains("quay") && !dot.contains("ox"));
+    assert_eq!(trie.to_dot(9).matches(" -> ").count(), trie.node_count() - 1);
+
+    let trie = Trie::from_dictionaries(&["cat\ndog\n", "cats\ncat\n", "", "dog\nox\n"]);
+    assert_eq!(trie.len(), 3);
+    assert_eq!(trie.sources(b"cat"), 0b0011);
+    assert_eq!(trie.sources(b"dog"), 0b1001);
+    assert_eq!(trie.sources(b"cats"), 0b0010);
+    assert_eq!(trie.sources(b"ox"), 0);
+    // bytes past `z` aren't letters in a raw list, only once an alphabet encodes letters as them
+    assert_eq!(Trie::from_dictionary("ca{\ncat\ndo~g\n").len(), 1);
+    assert_eq!(Trie::from_dictionaries(&["ca{\ncat\n"]).len(), 1);
+    assert_eq!(Trie::from_dictionary_in("ca{\ncat\ndo~g\n", &Alphabet::spanish()).len(), 2);
+
+    let trie = Trie::from_dictionary("act\ncat\ncats\ntact\nattack\nquat");
+    assert_eq!(trie.anagrams(b"tact"), ["act", "cat", "tact"]);
+    assert_eq!(trie.anagrams(b"T-A-C"), Vec::<&str>::new());
+    assert_eq!(trie.anagrams(b"??????"), ["act", "attack", "cat", "cats", "quat", "tact"]);
+    // far more of a letter than a word could use
+    let rack = format!("{}ct{}", "a".repeat(300), "?".repeat(300));
+    assert_eq!(trie.anagrams(rack.as_bytes()), trie.anagrams(b"??????"));
+    assert_eq!(trie.matching(b"*"), trie.anagrams(b"??????"));
+    assert_eq!(trie.matching(b"?**a**t"), ["cat", "quat", "tact"]);
+    assert_eq!(trie.matching(b"?a??"), ["cats", "tact"]);
+    assert!(trie.matching(b"").is_empty() && trie.matching(b"c?").is_empty());
+
+    fn sync<T: Sync>() {}
+    sync::<Trie>();
+}
@@ -0,0 +1,176 @@
+//! A slow, simple search to check the solvers against. Each dictionary word
+//! is looked for on its own along every path on the board, without the
+//! solvers' pruning, shared prefixes or record of words already found. See
+//! [`verify`].
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::board::Board;
+use crate::solution::Solution;
+
+/// A word a solver and the oracle disagree on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy<'a> {
+    /// On the board, but the solver didn't report it.
+    Missed(&'a str),
+    /// Reported, but not a dictionary word that can be traced on the board.
+    Spurious(&'a str),
+    /// Reported with a path that doesn't spell it.
+    BadPath(Solution<'a>),
+    /// Reported more than once.
+    Duplicate(&'a str),
+}
+
+impl<'a> fmt::Display for Discrepancy<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Discrepancy::*;
+        match *self {
+            Missed(word) => write!(f, "{} is on the board but wasn't found", word),
+            Spurious(word) => write!(f, "{} was found but isn't on the board", word),
+            BadPath(ref solution) => write!(f, "{} was found along {:?}, which doesn't spell it", solution.word, solution.path),
+            Duplicate(word) => write!(f, "{} was found more than once", word),
+        }
+    }
+}
+
+/// Every word from `words` that can be traced on `board`, found the slow
+/// way, in the order they're listed and each once. Words are read as
+/// [`Trie::from_dictionary`](crate::Trie::from_dictionary) reads them.
+pub fn oracle<'a>(board: &Board, words: &'a str) -> Vec<&'a str> {
+    let mut seen = HashSet::new();
+    words.lines()
+        .map(str::trim)
+        .filter(|word| word.len() >= 3 && word.bytes().all(|c| c.is_ascii_lowercase()))
+        .filter(|&word| seen.insert(word))
+        .filter(|word| on_board(board, word.as_bytes()))
+        .collect()
+}
+
+/// Checks `solutions`, as some solver found them on `board` from `words`,
+/// against the [`oracle`], returning where they disagree: first what's wrong
+/// with the solutions, in their order, then the words missed, in dictionary
+/// order. Only meaningful for solvers that report every word, so without
+/// rules or length limits.
+pub fn check<'a>(board: &Board, words: &'a str, solutions: &[Solution<'a>]) -> Vec<Discrepancy<'a>> {
+    let expected: HashSet<_> = oracle(board, words).into_iter().collect();
+    let mut discrepancies = Vec::new();
+    let mut reported = HashSet::new();
+    for solution in solutions {
+        if !reported.insert(solution.word) {
+            discrepancies.push(Discrepancy::Duplicate(solution.word));
+        } else if !expected.contains(solution.word) {
+            discrepancies.push(Discrepancy::Spurious(solution.word));
+        } else if !spells(board, solution.word.as_bytes(), &solution.path) {
+            discrepancies.push(Discrepancy::BadPath(solution.clone()));
+        }
+    }
+    // in dictionary order rather than the set's
+    discrepancies.extend(oracle(board, words).into_iter().filter(|word| !reported.contains(word)).map(Discrepancy::Missed));
+    discrepancies
+}
+
+/// Checks what [`Board::solve_trie`] finds on `board` against the
+/// [`oracle`]; empty when they agree.
+///
+/// ```
+/// use boggle::{verify, Board};
+///
+/// let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+/// assert!(verify::verify(&board, "cat\ncats\ndog").is_empty());
+/// ```
+pub fn verify<'a>(board: &Board, words: &'a str) -> Vec<Discrepancy<'a>> {
+    check(board, words, &board.solve_trie(words))
+}
+
+// whether some path of distinct, adjacent cells spells `word`, trying them all
+fn on_board(board: &Board, word: &[u8]) -> bool {
+    let mut path = Vec::new();
+    (0..board.len()).any(|x| (0..board.len()).any(|y| extend(board, word, (x, y), &mut path)))
+}
+
+// whether `word` can be spelled starting at `cell` after `path`
+fn extend(board: &Board, word: &[u8], cell: (usize, usize), path: &mut Vec<(usize, usize)>) -> bool {
+    let tile = board.tile(cell);
+    if !word.starts_with(tile) || path.contains(&cell) || path.last().is_some_and(|&last| !adjacent(board, last, cell)) {
+        return false;
+    }
+    if word.len() == tile.len() {
+        return true;
+    }
+    path.push(cell);
+    let found = (0..board.len()).any(|x| (0..board.len()).any(|y| extend(board, &word[tile.len()..], (x, y), path)));
+    path.pop();
+    found
+}
+
+fn spells(board: &Board, word: &[u8], path: &[(usize, usize)]) -> bool {
+    let n = board.len();
+    let mut letters = Vec::new();
+    for (i, &cell) in path.iter().enumerate() {
+        if cell.0 >= n || cell.1 >= n || path[..i].contains(&cell) || (i > 0 && !adjacent(board, path[i - 1], cell)) {
+            return false;
+        }
+        letters.extend_from_slice(board.tile(cell));
+    }
+    letters == word
+}
+
+// worked out from the topology directly rather than with the board's own neighbor lists
+fn adjacent(board: &Board, a: (usize, usize), b: (usize, usize)) -> bool {
+    let topology = board.topology();
+    let distance = |i: usize, j: usize| {
+        let d = i.abs_diff(j);
+        if topology.wrap { d.min(board.len() - d) } else { d }
+    };
+    let (rows, cols) = (distance(a.0, b.0), distance(a.1, b.1));
+    a != b && rows <= 1 && cols <= 1 && (topology.diagonals || rows + cols == 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::Topology;
+
+    const DICTIONARY: &str = include_str!("../test/dictionary");
+
+    #[test]
+    fn oracle() {
+        let board = Board::parse("quatx\nxxxx\nxxxx\nxxxs").unwrap();
+        assert_eq!(super::oracle(&board, "qua\nquat\nat\ntas\nquat\nQUA\nquats"), ["qua", "quat"]);
+        let board = board.with_topology(Topology { diagonals: true, wrap: true });
+        assert_eq!(super::oracle(&board, "quat\nquats\nsqua"), ["quat", "quats", "squa"]);
+
+        let board = Board::parse(include_str!("../test/board1")).unwrap();
+        assert_eq!(super::oracle(&board, DICTIONARY).len(), 126);
+    }
+
+    #[test]
+    fn check() {
+        let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+        let words = "cat\ncats\ndog";
+        let solution = |word, path: &[(usize, usize)]| Solution { word, score: 1, path: path.to_vec() };
+        let cat = solution("cat", &[(0, 0), (0, 1), (0, 2)]);
+        let cats = solution("cats", &[(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert!(super::check(&board, words, &[cats.clone(), cat.clone()]).is_empty());
+
+        let backwards = solution("cats", &[(0, 3), (0, 2), (0, 1), (0, 0)]);
+        assert_eq!(super::check(&board, words, &[cat.clone(), cat.clone(), solution("dog", &[]), backwards.clone()]), [
+            Discrepancy::Duplicate("cat"),
+            Discrepancy::Spurious("dog"),
+            Discrepancy::BadPath(backwards),
+        ]);
+        assert_eq!(super::check(&board, words, &[cats]), [Discrepancy::Missed("cat")]);
+    }
+
+    #[test]
+    fn verify() {
+        for seed in 0..2 {
+            let raw = crate::dice::roll(&crate::dice::CLASSIC, seed);
+            for &topology in &[Topology::default(), Topology { diagonals: false, wrap: true }] {
+                let board = Board::parse(&raw).unwrap().with_topology(topology);
+                assert_eq!(super::verify(&board, DICTIONARY), [], "{}", raw);
+            }
+        }
+    }
+}
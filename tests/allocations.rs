@@ -0,0 +1,35 @@
+// Allocation budgets for solving a standard 4x4 board. Run with
+// `cargo test --features alloc-audit --test allocations`; a failure means a
+// change added allocations to the search, so either find them or raise the
+// budget on purpose. Everything runs in one test so no other test's
+// allocations are counted.
+#![cfg(feature = "alloc-audit")]
+
+use boggle::alloc_audit::{self, CountingAllocator};
+use boggle::{Board, SolveOptions, Trie};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const DICTIONARY: &str = include_str!("../test/dictionary");
+const BOARD: &str = include_str!("../test/board1");
+
+#[test]
+fn budget() {
+    let board = Board::parse(BOARD).unwrap();
+    let trie = Trie::from_dictionary(DICTIONARY);
+    let options = SolveOptions::low_power();
+    // warm up lazily initialized statics
+    board.solve_with_trie(&trie, &options);
+
+    let (found, with_trie) = alloc_audit::count(|| board.solve_with_trie(&trie, &options));
+    let (_, single) = alloc_audit::count(|| board.solve_single_threaded(DICTIONARY));
+    let (_, loading) = alloc_audit::count(|| board.solve(DICTIONARY, &options));
+
+    // every word owns its path, so the budgets are what's spent beyond that
+    let words = found.len();
+    assert_eq!(words, 126);
+    assert!(with_trie <= words + 16, "{} allocations searching a shared trie", with_trie);
+    assert!(single <= words + 32, "{} allocations solving single-threaded", single);
+    assert!(loading <= words + 32, "{} allocations loading the trie and solving", loading);
+}
@@ -0,0 +1,73 @@
+// Pins the signatures of the stable API: if any of these stop compiling, the
+// change is breaking and needs a major version bump. Compile-fail cases for
+// guarantees that can only be checked by rejecting code live in tests/ui.
+
+use boggle::rules::{CommonWords, MinLength, NoPlurals, NoProperNouns, PluralFilter};
+use boggle::score::{Classic, Multiplier, Rarity, Tier, WordHunt};
+use boggle::{Board, Dictionary, Error, GuessRule, LoadStats, PathError, Rules, Scorer, Solution, SolveOptions, Solutions, Trie};
+
+#[test]
+fn signatures() {
+    // each wrapper only compiles while the method keeps this signature
+    fn parse(raw: &str) -> Result<Board, Error> { Board::parse(raw) }
+    fn solve<'a>(b: &Board, words: &'a str, o: &SolveOptions) -> Vec<Solution<'a>> { b.solve(words, o) }
+    fn solve_with_stats<'a>(b: &Board, words: &'a str, o: &SolveOptions) -> (Vec<Solution<'a>>, LoadStats) {
+        b.solve_with_stats(words, o)
+    }
+    fn solve_trie<'a>(b: &Board, words: &'a str) -> Vec<Solution<'a>> { b.solve_trie(words) }
+    fn solve_single_threaded<'a>(b: &Board, words: &'a str) -> Vec<Solution<'a>> { b.solve_single_threaded(words) }
+    fn solve_parallel<'a>(b: &Board, words: &'a str) -> Vec<Solution<'a>> { b.solve_parallel(words) }
+    fn score(b: &Board, words: &str) -> u32 { b.score(words) }
+    fn len(b: &Board) -> usize { b.len() }
+    fn distance(b: &Board, other: &Board) -> usize { b.distance(other) }
+    fn tile(b: &Board, cell: (usize, usize)) -> &'static [u8] { b.tile(cell) }
+    fn dictionary(raw: &str) -> Dictionary { Dictionary::parse(raw) }
+    fn as_str(d: &Dictionary) -> &str { d.as_str() }
+    fn total_score(s: &Solutions) -> u32 { s.total_score() }
+    fn difference<'a>(s: &Solutions<'a>, other: &Solutions) -> Solutions<'a> { s.difference(other) }
+    fn intersection<'a>(s: &Solutions<'a>, other: &Solutions) -> Solutions<'a> { s.intersection(other) }
+    fn low_power() -> SolveOptions { SolveOptions::low_power() }
+    fn validate_path(b: &Board, word: &str, path: &[(usize, usize)]) -> Result<(), PathError> { boggle::validate_path(b, word, path) }
+    fn rules() -> Rules { Rules::new() }
+    fn common_words(ranked: &str, n: usize) -> CommonWords { CommonWords::new(ranked, n) }
+    fn rarity(ranked: &str) -> Rarity<Classic> { Rarity::new(Classic, ranked) }
+    let _ = (parse, solve, solve_with_stats, solve_trie, solve_single_threaded, solve_parallel, score, len, distance, tile);
+    let _ = (dictionary, as_str, total_score, difference, intersection, low_power, validate_path, rules, common_words, rarity);
+
+    let options = SolveOptions::default();
+    let SolveOptions { scorer: _, rules: _, plurals: _, min_len: _, max_len: _, threads: _, seed: _, region: _, keep: _, max_depth: _ } = options;
+    let LoadStats { loaded: _, too_long: _ } = LoadStats::default();
+    let Solution { word: _, score: _, path: _ } = Solution { word: "cat", score: 1, path: Vec::new() };
+
+    fn scorer<S: Scorer>() {}
+    scorer::<Classic>();
+    scorer::<WordHunt>();
+    scorer::<Multiplier<Classic>>();
+    scorer::<Rarity<WordHunt>>();
+    fn rule<R: GuessRule>() {}
+    rule::<MinLength>();
+    rule::<NoPlurals>();
+    rule::<NoProperNouns>();
+    rule::<CommonWords>();
+    rule::<Rules>();
+    let _ = PluralFilter::default();
+    let _ = [Tier::Common, Tier::Uncommon, Tier::Rare];
+
+    fn error<E: std::error::Error + Send + Sync + 'static>() {}
+    error::<Error>();
+    error::<PathError>();
+    fn send<T: Send>() {}
+    send::<Board>();
+    send::<Dictionary>();
+    fn sync<T: Sync>() {}
+    sync::<Board>();
+    sync::<Trie>();
+    // a board owns its cells, so it can outlive the text it was parsed from
+    fn owned<T: 'static>() {}
+    owned::<Board>();
+}
+
+#[test]
+fn compile_fail() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,117 @@
+// One test per Cargo feature, so each can be checked building alone, e.g.
+// `cargo test --no-default-features --features rand --test features_smoke`.
+
+use boggle::{dice, rng, Board, SolveOptions};
+
+#[test]
+fn default() {
+    let raw = dice::roll(&dice::CLASSIC, 1);
+    let board = Board::parse(&raw).unwrap();
+    board.solve("cat\ndog", &SolveOptions::default());
+    let _ = rng::entropy();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+    let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    let options = SolveOptions { threads: Some(4), ..SolveOptions::default() };
+    assert_eq!(board.solve("cat\ncats\ndog", &options).len(), 2);
+    assert_eq!(board.solve_parallel("cat\ncats\ndog").len(), 2);
+}
+
+#[cfg(feature = "fs")]
+#[test]
+fn fs() {
+    use boggle::storage::{FileStorage, Storage};
+
+    let root = format!("{}/features-smoke", env!("CARGO_TARGET_TMPDIR"));
+    let storage = FileStorage::new(&root);
+    storage.put("board", b"cats").unwrap();
+    assert_eq!(storage.get("board").unwrap(), Some(b"cats".to_vec()));
+}
+
+#[cfg(all(feature = "mmap", unix))]
+#[test]
+fn mmap() {
+    let path = format!("{}/mmap-words", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, "cat\ncats\ndog\n").unwrap();
+    let dictionary = boggle::Dictionary::open_mmap(&path).unwrap();
+    let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    assert_eq!(board.solve(dictionary.as_str(), &SolveOptions::default()).len(), 2);
+}
+
+#[cfg(feature = "alloc-audit")]
+#[global_allocator]
+static ALLOCATOR: boggle::alloc_audit::CountingAllocator = boggle::alloc_audit::CountingAllocator;
+
+#[cfg(feature = "alloc-audit")]
+#[test]
+fn alloc_audit() {
+    let (solutions, allocations) = boggle::alloc_audit::count(|| Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap().solve("cat", &SolveOptions::low_power()));
+    assert_eq!(solutions.len(), 1);
+    assert!(allocations > 0);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LOGGED: AtomicUsize = AtomicUsize::new(0);
+    struct Counter;
+    impl log::Log for Counter {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+        fn log(&self, _: &log::Record) {
+            LOGGED.fetch_add(1, Ordering::Relaxed);
+        }
+        fn flush(&self) {}
+    }
+
+    log::set_logger(&Counter).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+    Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap().solve("cat", &SolveOptions::default());
+    assert!(LOGGED.load(Ordering::Relaxed) > 0);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rand() {
+    use rand::SeedableRng;
+
+    let mut source = rng::Rand(rand::rngs::StdRng::seed_from_u64(1));
+    let raw = dice::roll_with(&dice::BIG, &mut source);
+    assert_eq!(Board::parse(&raw).unwrap().len(), 5);
+    assert_ne!(rng::entropy(), rng::entropy());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    let (solutions, stats) = board.solve_with_stats("cat", &SolveOptions::default());
+    let json = serde_json::to_string(&solutions).unwrap();
+    assert_eq!(json, r#"[{"word":"cat","score":1,"path":[[0,0],[0,1],[0,2]]}]"#);
+    let identified = boggle::Solutions(solutions).identify(&board);
+    assert_eq!(serde_json::to_string(&identified).unwrap(),
+               format!(r#"[{{"word":"cat","score":1,"path":[[0,0],[0,1],[0,2]],"id":"{:016x}"}}]"#, identified[0].id));
+    assert_eq!(serde_json::to_string(&stats).unwrap(), r#"{"loaded":1,"too_long":0}"#);
+    let provenance = boggle::provenance::Provenance::new("cat");
+    assert!(serde_json::to_string(&provenance).unwrap().starts_with(r#"{"version":""#));
+
+    let trie = boggle::Trie::from_dictionary("cat\ncab\n");
+    let json = serde_json::to_string(&trie).unwrap();
+    assert_eq!(json, concat!(r#"{"nodes":[{"word":"","sources":0,"children":[1]},"#,
+                             r#"{"word":"c","sources":0,"children":[2]},{"word":"ca","sources":0,"children":[4,3]},"#,
+                             r#"{"word":"cat","sources":1,"children":[]},{"word":"cab","sources":1,"children":[]}]}"#));
+    let copy: boggle::Trie = serde_json::from_str(&json).unwrap();
+    assert_eq!(copy, trie);
+    assert_eq!(copy.len(), 2);
+    // a child that isn't one letter on from its parent
+    let bad = json.replace(r#""children":[4,3]"#, r#""children":[4,1]"#);
+    assert!(serde_json::from_str::<boggle::Trie>(&bad).is_err());
+    assert!(serde_json::from_str::<boggle::Trie>(&json.replace(r#""cab""#, r#""ca!""#)).is_err());
+    assert!(serde_json::from_str::<boggle::Trie>(r#"{"nodes":[]}"#).is_err());
+}
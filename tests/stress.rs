@@ -0,0 +1,157 @@
+// Runs every solver over thousands of random boards and checks that each word
+// they return can really be traced on the board, then feeds the parser
+// thousands of strings that are mostly not boards.
+
+use std::collections::{BTreeSet, HashSet};
+
+use boggle::multivec::Vec2;
+use boggle::score::{Classic, Scorer};
+use boggle::{Board, Solution, SolveOptions};
+
+const DICTIONARY: &str = include_str!("../test/dictionary");
+const BOARDS: usize = 2000;
+
+// weighted roughly like the real dice so boards hold some words
+const LETTERS: &[u8] = b"aaaaabbccddeeeeeeffgghhiiiiijkllmmnnnoooooppqrrrsssttttuuvwxyyz";
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn random_board(rng: &mut XorShift, size: usize) -> String {
+    let mut raw = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            // a lone `q` followed by a `u` would read as a Qu tile, so like the
+            // real dice every `q` is one
+            match LETTERS[rng.below(LETTERS.len())] {
+                b'q' => raw.push_str("qu"),
+                c => raw.push(c as char),
+            }
+        }
+        raw.push('\n');
+    }
+    raw
+}
+
+fn check(board: &Board, words: &HashSet<&str>, region: Option<&Vec2<bool>>, solutions: &[Solution]) {
+    let mut seen = HashSet::new();
+    for solution in solutions {
+        assert!(seen.insert(solution.word), "{} found twice", solution.word);
+        assert!(words.contains(solution.word), "{} isn't in the dictionary", solution.word);
+        assert!(solution.len() >= 3, "{} is too short", solution.word);
+
+        let path = &solution.path;
+        let cells: HashSet<_> = path.iter().collect();
+        assert_eq!(cells.len(), path.len(), "{} reuses a cell", solution.word);
+        assert!(path.iter().all(|&(x, y)| x < board.len() && y < board.len()));
+        assert!(path.iter().all(|&cell| region.is_none_or(|r| r[cell])), "{} leaves the region", solution.word);
+        assert!(path.windows(2).all(|w| {
+            let (a, b) = (w[0], w[1]);
+            a != b && a.0.max(b.0) - a.0.min(b.0) <= 1 && a.1.max(b.1) - a.1.min(b.1) <= 1
+        }), "{} jumps between cells", solution.word);
+
+        let letters: Vec<_> = path.iter().flat_map(|&cell| board.tile(cell)).cloned().collect();
+        assert_eq!(letters, solution.word.as_bytes());
+        assert_eq!(solution.score, Classic.score(solution.word.as_bytes(), path, board));
+    }
+}
+
+fn words<'a>(solutions: &[Solution<'a>]) -> BTreeSet<&'a str> {
+    solutions.iter().map(|s| s.word).collect()
+}
+
+#[test]
+fn random_boards() {
+    // a slice of the dictionary keeps the slower solvers quick
+    let dictionary: String = DICTIONARY.lines()
+        .enumerate()
+        .filter(|&(i, w)| i % 100 == 0 || w.len() == 3)
+        .map(|(_, w)| format!("{}\n", w))
+        .collect();
+    let known: HashSet<_> = dictionary.lines().collect();
+
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    for n in 0..BOARDS {
+        let size = 1 + n % 10;
+        let raw = random_board(&mut rng, size);
+        let board = Board::parse(&raw).unwrap();
+        assert_eq!(board.len(), size);
+
+        let trie = board.solve_trie(&dictionary);
+        check(&board, &known, None, &trie);
+        let expected = words(&trie);
+
+        let recursive = board.solve_recursive(&dictionary);
+        check(&board, &known, None, &recursive);
+        assert_eq!(words(&recursive), expected, "{}", raw);
+
+        // the single threaded solver is much slower on big boards
+        if size <= 5 || n % 10 == 0 {
+            let single = board.solve_single_threaded(&dictionary);
+            check(&board, &known, None, &single);
+            assert_eq!(words(&single), expected, "{}", raw);
+        }
+
+        let mut options = SolveOptions { seed: Some(n as u64), ..SolveOptions::default() };
+        if n % 50 == 0 {
+            options.threads = Some(2);
+            let parallel = board.solve_parallel(&dictionary);
+            check(&board, &known, None, &parallel);
+            assert_eq!(words(&parallel), expected, "{}", raw);
+        } else {
+            options.threads = Some(1);
+        }
+        let seeded = board.solve(&dictionary, &options);
+        check(&board, &known, None, &seeded);
+        assert_eq!(words(&seeded), expected, "{}", raw);
+
+        // block a few cells; every word left must avoid them
+        let mut region = Vec2::fill(size, size, true);
+        for _ in 0..size {
+            region[(rng.below(size), rng.below(size))] = false;
+        }
+        options.region = Some(region.clone());
+        options.max_len = Some(3 + rng.below(6));
+        let blocked = board.solve(&dictionary, &options);
+        check(&board, &known, Some(&region), &blocked);
+        assert!(blocked.iter().all(|s| s.len() <= options.max_len.unwrap()));
+        assert!(words(&blocked).is_subset(&expected), "{}", raw);
+    }
+}
+
+#[test]
+fn hostile_input() {
+    // letters, tiles and separators mixed with what a web form might send instead
+    const PIECES: &[&str] = &["a", "e", "Q", "u", "th", "In", " ", "\t", "\n", "\r\n", "", "1", "-", ",", "\"", "[", "é", "ß",
+                              "\u{0}", "\u{202e}", "\u{a0}", "\u{fffd}", "🟩"];
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for _ in 0..20_000 {
+        let raw: String = (0..rng.below(40)).map(|_| PIECES[rng.below(PIECES.len())]).collect();
+        let (board, problems) = Board::parse_lossy(&raw);
+        match Board::parse(&raw) {
+            Ok(parsed) => {
+                assert!(problems.is_empty(), "{:?}", raw);
+                assert_eq!(board.as_ref().map(Board::text), Some(parsed.text()), "{:?}", raw);
+            }
+            Err(err) => assert!(problems.iter().any(|p| p.to_string() == err.to_string()), "{:?}", raw),
+        }
+        // whatever was made of it solves, and reads back the same, but for a lone tile such as `in`, which has
+        // no other cells to be separated from
+        if let Some(board) = board {
+            let words = "aqua\nthe\ninn";
+            check(&board, &words.lines().collect(), None, &board.solve_trie(words));
+            if board.len() == 1 {
+                continue;
+            }
+            assert_eq!(Board::parse(&board.text()).map(|b| b.text()).ok(), Some(board.text()), "{:?} as {:?}", raw, board.text());
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// Solutions borrow their words from the dictionary text.
+use boggle::{Board, Dictionary, SolveOptions};
+
+fn main() {
+    let board = Board::parse("cats\nxxxx\nxxxx\nxxxx").unwrap();
+    let solutions = {
+        let dictionary = Dictionary::parse("cat");
+        board.solve(dictionary.as_str(), &SolveOptions::default())
+    };
+    println!("{}", solutions.len());
+}
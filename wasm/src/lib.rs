@@ -0,0 +1,44 @@
+//! JavaScript bindings for solving boards in the browser. Build with
+//!
+//! ```text
+//! cargo build -p boggle-wasm --release --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/boggle_wasm.wasm
+//! ```
+//!
+//! and from JavaScript:
+//!
+//! ```text
+//! import init, { solve } from "./pkg/boggle_wasm.js";
+//! await init();
+//! for (const { word, score, path } of solve("cats\nxxxx\nxxxx\nxxxx", dictionary)) { ... }
+//! ```
+
+use boggle::{Board, Error, SolveOptions, Solutions};
+use wasm_bindgen::prelude::*;
+
+/// Every word from `dict`, one per line, on `board`, highest scoring first:
+/// an array of `{ word, score, path }` objects, with `path` an array of
+/// `[row, col]` cells. Throws if the board doesn't parse.
+#[wasm_bindgen]
+pub fn solve(board: &str, dict: &str) -> Result<JsValue, JsError> {
+    let solutions = solutions(board, dict).map_err(|err| JsError::new(&err.to_string()))?;
+    Ok(serde_wasm_bindgen::to_value(&solutions)?)
+}
+
+fn solutions<'a>(board: &str, dict: &'a str) -> Result<Solutions<'a>, Error> {
+    let board = Board::parse(board)?;
+    let mut solutions = Solutions(board.solve(dict, &SolveOptions::low_power()));
+    solutions.sort_by_score();
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn solutions() {
+        let found = super::solutions("cats\nxxxx\nxxxx\nxxxx", "act\ncat\ncats\ndog").unwrap();
+        assert_eq!(found.iter().map(|s| s.word).collect::<Vec<_>>(), vec!["cat", "cats"]);
+        assert_eq!(found[1].path, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert!(super::solutions("c4ts\nxxxx\nxxxx\nxxxx", "cat").is_err());
+    }
+}